@@ -158,9 +158,9 @@ vdex_enum!(vdex::versions::VersionGroup, VersionGroupRepr,
 // EFFICACY ///////////////////////////////////////////////////////////////////
 
 #[no_mangle]
-pub static VDEX_INVALID_DAMAGE_TYPE: EfficacyRepr = std::i8::MAX;
+pub static VDEX_INVALID_DAMAGE_TYPE: EfficacyRepr = i8::MAX;
 #[no_mangle]
-pub static VDEX_INVALID_TARGET_TYPE: EfficacyRepr = std::i8::MAX - 1;
+pub static VDEX_INVALID_TARGET_TYPE: EfficacyRepr = i8::MAX - 1;
 
 fn efficacy(damage: TypeRepr, target: TypeRepr) -> Result<EfficacyRepr, EfficacyRepr> {
     let damage_type = vdex::Type::from_repr(damage).ok_or(VDEX_INVALID_DAMAGE_TYPE)?;
@@ -199,7 +199,7 @@ pub unsafe extern "C" fn vdex_item_iter() -> VDexItemIter {
 }
 
 #[no_mangle]
-pub static VDEX_ITEM_ITER_END: ItemIdRepr = std::u16::MAX;
+pub static VDEX_ITEM_ITER_END: ItemIdRepr = u16::MAX;
 
 #[no_mangle]
 pub unsafe extern "C" fn vdex_item_next(iter: VDexItemIter) -> ItemIdRepr {
@@ -239,7 +239,7 @@ pub static VDEX_ITEM_FLAG_HOLDABLE_ACTIVE: ItemFlagsRepr = 0x40;
 pub static VDEX_ITEM_FLAG_UNDERGROUND: ItemFlagsRepr = 0x80;
 
 #[no_mangle]
-pub static VDEX_NO_DOMINANT_FLAVOR: FlavorRepr = std::u8::MAX;
+pub static VDEX_NO_DOMINANT_FLAVOR: FlavorRepr = u8::MAX;
 
 #[repr(C)] pub struct VDexItemDetails {
     pub category: ItemCategoryRepr,
@@ -392,7 +392,7 @@ pub extern "C" fn vdex_move_details(id: MoveIdRepr) -> VDexMoveDetails {
         ailment_chance: mov.meta.ailment_chance,
         flinch_chance: mov.meta.flinch_chance,
         stat_chance: mov.meta.stat_chance,
-        stat_changes: mov.meta.stat_changes,
+        stat_changes: mov.meta.stat_changes.0,
         flags: mov.meta.flags.bits(),
     }
 }
@@ -440,7 +440,10 @@ pub extern "C" fn vdex_species_name(id: SpeciesIdRepr) -> *mut Arch8 {
 // SPECIES DETAILS ////////////////////////////////////////////////////////////
 
 #[no_mangle]
-pub static VDEX_NO_STAT_DEPENDENCE: i8 = std::i8::MAX;
+pub static VDEX_NO_STAT_DEPENDENCE: i8 = i8::MAX;
+
+#[no_mangle]
+pub static VDEX_NO_EVOLUTION_MOVE: MoveIdRepr = u16::MAX;
 
 #[derive(Default)]
 #[repr(C)] pub struct VDexEvolvesFrom {
@@ -475,9 +478,9 @@ pub extern "C" fn vdex_species_details(id: SpeciesIdRepr) -> VDexSpeciesDetails
             Some(e) => VDexEvolvesFrom {
                 from_id: e.from_id.0,
                 trigger: e.trigger.repr(),
-                level: e.level,
-                gender: e.gender.repr(),
-                mov: e.move_id.0,
+                level: e.level.unwrap_or(0),
+                gender: e.gender.unwrap_or(vdex::pokemon::Gender::Genderless).repr(),
+                mov: e.move_id.map_or(VDEX_NO_EVOLUTION_MOVE, |m| m.0),
                 relative_physical_stats:
                     e.relative_physical_stats.unwrap_or(VDEX_NO_STAT_DEPENDENCE),
             },
@@ -489,7 +492,7 @@ pub extern "C" fn vdex_species_details(id: SpeciesIdRepr) -> VDexSpeciesDetails
 
 #[no_mangle]
 pub extern "C" fn vdex_pokemon_count(species: SpeciesIdRepr) -> usize {
-    pokedex().species[SpeciesId(species)].pokemon.len()
+    pokedex().species.pokemon(SpeciesId(species)).len()
 }
 
 #[repr(C)] pub struct VDexPokemon { ptr: *const VDexOpaque }
@@ -511,7 +514,7 @@ pub unsafe extern "C" fn vdex_pokemon(
     species: SpeciesIdRepr, pokemon_index: usize
 ) -> VDexPokemon {
     let mut pokemon: VDexPokemon = VDexPokemon { ptr: null() };
-    pokemon.init(&pokedex().species[SpeciesId(species)].pokemon[pokemon_index]);
+    pokemon.init(&pokedex().species.pokemon(SpeciesId(species))[pokemon_index]);
     pokemon
 }
 
@@ -0,0 +1,83 @@
+//! Time and allocations of constructing a Pokedex from the included data, and
+//! time of iterating over every form.
+//!
+//! Run with and without the `smallvec` feature to compare:
+//!
+//! ```sh
+//! cargo bench --bench construction
+//! cargo bench --bench construction --features smallvec
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(feature = "no-embedded-data"))]
+use std::time::{Duration, Instant};
+#[cfg(not(feature = "no-embedded-data"))]
+use vdex::Pokedex;
+
+/// The system allocator, counting allocations and bytes allocated.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[cfg(not(feature = "no-embedded-data"))]
+const RUNS: u32 = 10;
+
+#[cfg(not(feature = "no-embedded-data"))]
+fn average(mut run: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        run();
+    }
+    start.elapsed() / RUNS
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+fn main() {
+    let storage = if cfg!(feature = "smallvec") { "SmallVec" } else { "Vec" };
+    println!("Forms stored in a {}", storage);
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let dex = Pokedex::new();
+    println!("Pokedex::new: {} allocations, {} bytes",
+             ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+             BYTES.load(Ordering::Relaxed) - bytes);
+    println!("Pokedex::new: {:?} on average", average(|| {
+        Pokedex::new();
+    }));
+
+    let mut forms = 0;
+    let time = average(|| {
+        for _ in 0..100 {
+            for pokemon in dex.species.all_pokemon() {
+                forms += pokemon.forms.iter()
+                    .filter(|f| f.battle_only)
+                    .count();
+            }
+        }
+    });
+    println!("100 passes over every form: {:?} on average ({} battle-only)",
+             time, forms / (100 * RUNS as usize));
+}
+
+#[cfg(feature = "no-embedded-data")]
+fn main() {
+    println!("The construction benchmark needs the included data.");
+}
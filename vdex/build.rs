@@ -0,0 +1,77 @@
+//! Generate the `Ability` enum from Veekun's `abilities.csv`, so the variants
+//! and their IDs always match the data, and the counts of moves, Pokémon,
+//! species, and berries from their CSV files, so that updating the data does
+//! not mean updating the counts by hand.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use veekun::to_pascal_case;
+
+const ABILITIES: &str = "veekun/data/abilities.csv";
+
+/// The generated counts: the name of the constant, its CSV file, and what it
+/// counts.
+const COUNTS: &[(&str, &str, &str)] = &[
+    ("MOVE_COUNT", "veekun/data/moves.csv", "moves"),
+    ("POKEMON_COUNT", "veekun/data/pokemon.csv", "Pokémon"),
+    ("SPECIES_COUNT", "veekun/data/pokemon_species.csv", "Pokémon species"),
+    ("BERRY_COUNT", "veekun/data/berries.csv", "berries"),
+];
+
+/// Count the records of a CSV file, skipping records with IDs of 10000 and
+/// up, which are outside the main series.
+fn count_records(path: &str) -> usize {
+    let data = fs::read_to_string(path).expect("cannot read counted file");
+    data.lines().skip(1)
+        .filter(|line| !line.is_empty())
+        .filter(|line| {
+            let id: u32 = line.split(',').next().and_then(|f| f.parse().ok())
+                .expect("invalid ID");
+            id < 10000
+        })
+        .count()
+}
+
+/// Write each count to its own file, to be included by the module that
+/// defines it.
+fn write_counts(out_dir: &Path) {
+    for &(name, path, what) in COUNTS {
+        println!("cargo:rerun-if-changed={}", path);
+        let file = Path::new(path).file_name().unwrap().to_string_lossy();
+        let source = format!(
+            "/// The total number of {} in pbirch, counted from `{}`.\n\
+             pub const {}: usize = {};\n",
+            what, file, name, count_records(path));
+        fs::write(out_dir.join(format!("{}.rs", name.to_lowercase())), source)
+            .expect("cannot write count");
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", ABILITIES);
+    let data = fs::read_to_string(ABILITIES).expect("cannot read abilities");
+    let mut variants = String::new();
+    for (i, line) in data.lines().skip(1).enumerate() {
+        let mut fields = line.split(',');
+        let id: usize = fields.next().and_then(|f| f.parse().ok())
+            .expect("invalid ability ID");
+        let identifier = fields.next().expect("missing ability identifier");
+        let generation = match fields.next() {
+            Some("3") => "III",
+            Some("4") => "IV",
+            Some("5") => "V",
+            _ => panic!("invalid ability generation"),
+        };
+        // `Ability` is represented as a u8 and indexed by ID.
+        assert_eq!(id, i, "ability IDs must count up from 0");
+        variants += &format!("    {} = {} => {},\n",
+                             to_pascal_case(identifier), id, generation);
+    }
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+    fs::write(out_dir.join("abilities.rs"),
+              format!("ability_enum! {{\n{}}}\n", variants))
+        .expect("cannot write abilities");
+    write_counts(out_dir);
+}
@@ -6,7 +6,7 @@
 
 #![cfg_attr(feature = "nightly", warn(clippy::pedantic))]
 
-#![recursion_limit = "128"]
+#![recursion_limit = "256"]
 
 //! **Do not use this crate directly, and do not push it to upstream.**
 //!
@@ -27,6 +27,15 @@ use syn::*;
 
 type Args = punctuated::Punctuated<NestedMeta, token::Comma>;
 
+/// How `serde = true` (de)serializes an enum.
+#[derive(Copy, Clone, PartialEq)]
+enum SerdeAs {
+    /// As the variant name, e.g. `"InnerFocus"`.
+    Name,
+    /// As the repr.
+    Repr,
+}
+
 struct ArgsWrapper {
     args: Args,
 }
@@ -50,7 +59,8 @@ pub fn EnumRepr(
     //eprintln!("parse input:  {}", time::precise_time_ns() - t0);
     validate(&input.variants);
 
-    let (repr_ty, implicit, derive, enable_fast) = get_repr_type(args);
+    let (repr_ty, implicit, derive, enable_fast, bitset, serde)
+        = get_repr_type(args);
     let (compiler_repr_ty, fast_gen) = match repr_ty.to_string().as_str() {
         "i8" | "i16" | "i32" | "i64" | "isize"
         | "u8" | "u16" | "u32" | "u64" | "usize" => {
@@ -83,6 +93,12 @@ pub fn EnumRepr(
     };
     //eprintln!("genert. code: {}", time::precise_time_ns() - t3);
     ret.extend(gen);
+    if bitset {
+        ret.extend(generate_bitset(&input));
+    }
+    if let Some(serde_as) = serde {
+        ret.extend(generate_serde(&input, &repr_ty, serde_as));
+    }
 
     //let tf = time::precise_time_ns();
     //eprintln!("TOTAL:        {}", tf - t0);
@@ -94,6 +110,7 @@ fn generate_code_fast(input: &ItemEnum, repr_ty1: &Ident) -> TokenStream {
     //let t0 = time::precise_time_ns();
 
     let ty = input.ident.clone();
+    let vis = input.vis.clone();
     let vars_len = input.variants.len();
     let (names1, discrs1) = extract_variants(input, true);
     let (names2, discrs2) = (names1.clone(), discrs1.clone());
@@ -103,31 +120,49 @@ fn generate_code_fast(input: &ItemEnum, repr_ty1: &Ident) -> TokenStream {
     let ty_repeat2 = ty_repeat1.clone();
     let ty_repeat3 = ty_repeat1.clone();
     let generics_tuple = input.generics.split_for_impl();
-    let (impl_generics, ty_generics, where_clause) = generics_tuple;
+    let (impl_generics1, ty_generics1, where_clause1) = generics_tuple.clone();
+    let (impl_generics2, ty_generics2, where_clause2) = generics_tuple;
 
     //let t1 = time::precise_time_ns();
 
     let ret: TokenStream = quote! {
-        impl #impl_generics Enum for #ty #ty_generics #where_clause {
-            type Repr = #repr_ty1;
-
-            const COUNT: usize = #vars_len;
-
-            const VALUES: &'static [Self] = &[ #( #ty_repeat1::#names1, )* ];
-
-            fn repr(self) -> #repr_ty2 {
+        impl #impl_generics1 #ty #ty_generics1 #where_clause1 {
+            /// Returns the underlying representation of the enum value.
+            #[inline]
+            #vis const fn repr(self) -> #repr_ty2 {
                 match self {
                     #( #ty_repeat2::#names2 => #discrs1, )*
                 }
             }
 
-            fn from_repr(x: #repr_ty3) -> Option<#ty> {
+            /// Returns the enum value corresponding to the passed
+            /// representation, or `None` if no such enum value exists.
+            #[inline]
+            #vis const fn from_repr(x: #repr_ty3) -> Option<#ty> {
                 match x {
                     #( #discrs2 => Some(#ty_repeat3::#names3), )*
                     _ => None,
                 }
             }
         }
+
+        impl #impl_generics2 Enum for #ty #ty_generics2 #where_clause2 {
+            type Repr = #repr_ty1;
+
+            const COUNT: usize = #vars_len;
+
+            const VALUES: &'static [Self] = &[ #( #ty_repeat1::#names1, )* ];
+
+            #[inline]
+            fn repr(self) -> #repr_ty1 {
+                #ty::repr(self)
+            }
+
+            #[inline]
+            fn from_repr(x: #repr_ty1) -> Option<#ty> {
+                #ty::from_repr(x)
+            }
+        }
     }.into();
 
     //let t2 = time::precise_time_ns();
@@ -141,7 +176,13 @@ fn generate_code(input: &ItemEnum, repr_ty: &Ident) -> TokenStream {
     //let t0 = time::precise_time_ns();
 
     let ty = input.ident.clone();
+    let vis = input.vis.clone();
     let vars_len = input.variants.len();
+    // Comparing other repr types, like aliases of structs, is not const.
+    let constness = match is_primitive_int(repr_ty) {
+        true => quote!(const),
+        false => quote!(),
+    };
 
     let (names1, discrs1) = extract_variants(input, false);
     let names2 = names1.clone();
@@ -173,22 +214,37 @@ fn generate_code(input: &ItemEnum, repr_ty: &Ident) -> TokenStream {
 
             const VALUES: &'static [Self] = &[ #( #ty_repeat1::#names1, )* ];
 
-            fn repr(self) -> #repr_ty2 {
+            #[inline]
+            fn repr(self) -> #repr_ty1 {
+                #ty::repr(self)
+            }
+
+            #[inline]
+            fn from_repr(x: #repr_ty1) -> Option<#ty> {
+                #ty::from_repr(x)
+            }
+        }
+
+        impl #impl_generics2 #ty #ty_generics2 #where_clause2 {
+            /// Returns the underlying representation of the enum value.
+            #[inline]
+            #vis #constness fn repr(self) -> #repr_ty2 {
                 match self {
                     #( #ty_repeat2::#names2 => #discrs1 as #repr_ty_repeat1, )*
                 }
             }
 
-            fn from_repr(x: #repr_ty3) -> Option<#ty> {
+            /// Returns the enum value corresponding to the passed
+            /// representation, or `None` if no such enum value exists.
+            #[inline]
+            #vis #constness fn from_repr(x: #repr_ty3) -> Option<#ty> {
                 match x {
                     #( x if x == #discrs2 as #repr_ty_repeat2
                         => Some(#ty_repeat3::#names3), )*
                     _ => None,
                 }
             }
-        }
 
-        impl #impl_generics2 #ty #ty_generics2 #where_clause2 {
             #[doc(hidden)]
             #[allow(dead_code)]
             fn _enum_repr_typecheck() {
@@ -205,6 +261,216 @@ fn generate_code(input: &ItemEnum, repr_ty: &Ident) -> TokenStream {
     ret
 }
 
+fn generate_bitset(input: &ItemEnum) -> TokenStream {
+    let ty = input.ident.clone();
+    let vis = input.vis.clone();
+    let set_ty = Ident::new(&format!("{}Set", ty), ty.span());
+    let doc = format!("A set of `{}` values, stored as one bit per variant.",
+        ty);
+    let vars_len = input.variants.len();
+    let words = (vars_len + 63) / 64;
+    let names = input.variants.iter().map(|x| x.ident.clone());
+    let indices = 0..vars_len;
+    let ty_repeat = iter::repeat(ty.clone()).take(vars_len);
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Copy, Clone, Default, Hash, PartialEq, Eq)]
+        #vis struct #set_ty([u64; #words]);
+
+        impl #set_ty {
+            /// Create an empty set.
+            pub const fn new() -> Self {
+                #set_ty([0; #words])
+            }
+
+            /// Create a set of every value.
+            pub fn all() -> Self {
+                <#ty as Enum>::VALUES.iter().cloned().collect()
+            }
+
+            #[inline]
+            fn bit(x: #ty) -> (usize, u64) {
+                let index: usize = match x {
+                    #( #ty_repeat::#names => #indices, )*
+                };
+                (index / 64, 1 << (index % 64))
+            }
+
+            /// Add a value, returning whether it was not already present.
+            pub fn insert(&mut self, x: #ty) -> bool {
+                let (word, mask) = Self::bit(x);
+                let added = self.0[word] & mask == 0;
+                self.0[word] |= mask;
+                added
+            }
+
+            /// Remove a value, returning whether it was present.
+            pub fn remove(&mut self, x: #ty) -> bool {
+                let (word, mask) = Self::bit(x);
+                let removed = self.0[word] & mask != 0;
+                self.0[word] &= !mask;
+                removed
+            }
+
+            /// Whether a value is in the set.
+            pub fn contains(&self, x: #ty) -> bool {
+                let (word, mask) = Self::bit(x);
+                self.0[word] & mask != 0
+            }
+
+            /// The number of values in the set.
+            pub fn len(&self) -> usize {
+                self.0.iter().map(|w| w.count_ones() as usize).sum()
+            }
+
+            /// Whether the set is empty.
+            pub fn is_empty(&self) -> bool {
+                self.0.iter().all(|&w| w == 0)
+            }
+
+            /// Iterate over the values in the set, in declaration order.
+            pub fn iter(&self) -> impl Iterator<Item = #ty> {
+                let set = *self;
+                <#ty as Enum>::VALUES.iter().cloned()
+                    .filter(move |&x| set.contains(x))
+            }
+
+            /// The values in either set.
+            pub fn union(mut self, other: Self) -> Self {
+                for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+                    *a |= *b;
+                }
+                self
+            }
+
+            /// The values in both sets.
+            pub fn intersection(mut self, other: Self) -> Self {
+                for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+                    *a &= *b;
+                }
+                self
+            }
+
+            /// The values in this set but not the other.
+            pub fn difference(mut self, other: Self) -> Self {
+                for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+                    *a &= !*b;
+                }
+                self
+            }
+        }
+
+        impl ::core::fmt::Debug for #set_ty {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl ::core::iter::FromIterator<#ty> for #set_ty {
+            fn from_iter<I: IntoIterator<Item = #ty>>(iter: I) -> Self {
+                let mut set = Self::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl ::core::iter::Extend<#ty> for #set_ty {
+            fn extend<I: IntoIterator<Item = #ty>>(&mut self, iter: I) {
+                for x in iter {
+                    self.insert(x);
+                }
+            }
+        }
+    }.into()
+}
+
+fn generate_serde(
+    input: &ItemEnum, repr_ty: &Ident, serde_as: SerdeAs
+) -> TokenStream {
+    let ty = input.ident.clone();
+    let names: Vec<_> = input.variants.iter()
+        .map(|x| x.ident.clone())
+        .collect();
+    let strs: Vec<_> = names.iter().map(|x| x.to_string()).collect();
+    let (names1, names2) = (names.clone(), names);
+    let (strs1, strs2, strs3) = (strs.clone(), strs.clone(), strs);
+    let vars_len = input.variants.len();
+    let ty_repeat1 = iter::repeat(ty.clone()).take(vars_len);
+    let ty_repeat2 = ty_repeat1.clone();
+    let expecting = format!("a {} name", ty);
+    let unknown = format!("unknown {} representation", ty);
+
+    // The impls are compiled with the `serde` feature of the crate using
+    // the macro, which must depend on serde with that feature.
+    let (ser, de) = match serde_as {
+        SerdeAs::Name => (quote! {
+            serializer.serialize_str(match *self {
+                #( #ty_repeat1::#names1 => #strs1, )*
+            })
+        }, quote! {
+            struct NameVisitor;
+
+            impl<'de> ::serde::de::Visitor<'de> for NameVisitor {
+                type Value = #ty;
+
+                fn expecting(
+                    &self, f: &mut ::core::fmt::Formatter
+                ) -> ::core::fmt::Result {
+                    f.write_str(#expecting)
+                }
+
+                fn visit_str<E: ::serde::de::Error>(
+                    self, name: &str
+                ) -> ::core::result::Result<#ty, E> {
+                    match name {
+                        #( #strs2 => Ok(#ty_repeat2::#names2), )*
+                        _ => Err(E::unknown_variant(name, &[ #( #strs3, )* ])),
+                    }
+                }
+            }
+
+            deserializer.deserialize_str(NameVisitor)
+        }),
+        SerdeAs::Repr => (quote! {
+            ::serde::Serialize::serialize(&#ty::repr(*self), serializer)
+        }, quote! {
+            let repr = <#repr_ty as ::serde::Deserialize>::deserialize(
+                deserializer)?;
+            #ty::from_repr(repr).ok_or_else(
+                || ::serde::de::Error::custom(#unknown))
+        }),
+    };
+
+    quote! {
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for #ty {
+            fn serialize<S: ::serde::Serializer>(
+                &self, serializer: S
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                #ser
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for #ty {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D
+            ) -> ::core::result::Result<Self, D::Error> {
+                #de
+            }
+        }
+    }.into()
+}
+
+fn is_primitive_int(ty: &Ident) -> bool {
+    match ty.to_string().as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize"
+        | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => true,
+        _ => false,
+    }
+}
+
 fn extract_variants(input: &ItemEnum, fast_gen: bool) -> (Vec<Ident>, Vec<Expr>) {
     let mut prev_explicit: Option<Expr> = None;
     let mut implicit_counter = 0;
@@ -277,11 +543,16 @@ fn extract_variants(input: &ItemEnum, fast_gen: bool) -> (Vec<Ident>, Vec<Expr>)
     (names, discrs)
 }
 
-fn get_repr_type(args: TokenStream) -> (Ident, bool, bool, bool) {
+fn get_repr_type(
+    args: TokenStream
+) -> (Ident, bool, bool, bool, bool, Option<SerdeAs>) {
     let mut repr_type = None;
     let mut implicit = true;
     let mut derive = true;
     let mut enable_fast = true;
+    let mut bitset = false;
+    let mut serde = false;
+    let mut serde_as = None;
     let args = syn::parse::<ArgsWrapper>(args)
         .expect("specify repr type in format \"#[EnumRepr]\"").args;
     args.iter().for_each(|arg| {
@@ -313,18 +584,48 @@ fn get_repr_type(args: TokenStream) -> (Ident, bool, bool, bool) {
                             Lit::Bool(fast) => fast.value,
                             _ => panic!("\"fast\" parameter must be bool")
                         }
+                    } else if param == "bitset" {
+                        bitset = match lit {
+                            Lit::Bool(set) => set.value,
+                            _ => panic!("\"bitset\" parameter must be bool")
+                        }
+                    } else if param == "serde" {
+                        serde = match lit {
+                            Lit::Bool(ser) => ser.value,
+                            _ => panic!("\"serde\" parameter must be bool")
+                        }
+                    } else if param == "serde_as" {
+                        serde_as = match lit {
+                            Lit::Str(ser) => match ser.value().as_str() {
+                                "name" => Some(SerdeAs::Name),
+                                "repr" => Some(SerdeAs::Repr),
+                                _ => panic!("\"serde_as\" parameter must be \
+                                    \"name\" or \"repr\"")
+                            },
+                            _ => panic!("\"serde_as\" parameter must be a \
+                                string")
+                        }
                     } else {
                         eprintln!("{}", param);
                         panic!("#[EnumRepr] accepts arguments named \
-                            \"type\", \"implicit\", and \"derive\"")
+                            \"type\", \"implicit\", \"derive\", \"fast\", \
+                            \"bitset\", \"serde\", and \"serde_as\"")
                     }
                 },
                 _ => panic!("specify repr type in format \
                     \"#[EnumRepr(type = \"TYPE\")]\"")
             }
         });
+    if serde_as.is_some() && !serde {
+        panic!("\"serde_as\" requires serde = true");
+    }
+    let serde = match serde {
+        true => Some(serde_as.unwrap_or(SerdeAs::Name)),
+        false => None,
+    };
     match repr_type {
-        Some(repr_ty) => (repr_ty, implicit, derive, enable_fast),
+        Some(repr_ty)
+            => (repr_ty, implicit, derive, enable_fast, bitset, serde),
         None => panic!("\"type \" parameter is required")
     }
 }
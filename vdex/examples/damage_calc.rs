@@ -0,0 +1,55 @@
+//! Calculate the damage range of a move between two stored Pokémon.
+
+use vdex::battle::damage::{calc_damage, MAX_DAMAGE_ROLL, MIN_DAMAGE_ROLL};
+use vdex::battle::{BattleState, Monster, Position, Side};
+use vdex::stored::{StoredMove, StoredPokemon};
+use vdex::versions::Generation;
+use vdex::{pokedex, Nature};
+
+/// A level 100 Pokémon of a species' default form with one move.
+fn stored(species: &str, nature: Nature, evs: [u8; 6], mov: &str)
+        -> StoredPokemon {
+    let dex = pokedex();
+    let species = dex.species.identifiers().id(species).unwrap();
+    let form = dex.species.pokemon(species)[0].forms[0].id;
+    let id = dex.moves.identifiers().id(mov).unwrap();
+    StoredPokemon {
+        species,
+        form,
+        nature,
+        level: 100,
+        ivs: [31; 6],
+        evs,
+        moves: vec![StoredMove { id, pp_ups: 0 }],
+        ..Default::default()
+    }
+}
+
+fn main() {
+    // EVs are in the order of `BaseStats`: HP, Atk, Def, Spe, SpA, SpD.
+    let garchomp = stored(
+        "garchomp", Nature::Jolly, [4, 252, 0, 252, 0, 0], "earthquake");
+    let heatran = stored(
+        "heatran", Nature::Calm, [252, 0, 0, 0, 0, 252], "lava-plume");
+    let attacker = Monster::from_stored(&garchomp).unwrap();
+    let defender = Monster::from_stored(&heatran).unwrap();
+    let max_hp = defender.max_hp();
+    let state = BattleState::new(
+        Generation::V, [Side::new(vec![attacker]), Side::new(vec![defender])]);
+    let earthquake = &pokedex().moves[garchomp.moves[0].id];
+    let from = Position { side: 0, slot: 0 };
+    let to = Position { side: 1, slot: 0 };
+    let low = calc_damage(&state, from, to, earthquake, false, MIN_DAMAGE_ROLL);
+    let high = calc_damage(&state, from, to, earthquake, false, MAX_DAMAGE_ROLL);
+    println!("Earthquake: {}-{} of {} HP ({:.1}%-{:.1}%)", low, high, max_hp,
+             100.0 * low as f64 / max_hp as f64,
+             100.0 * high as f64 / max_hp as f64);
+    // Heatran is weak to Ground four times over, so this is a sure knockout.
+    assert!(low >= max_hp);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn runs() {
+    main();
+}
@@ -0,0 +1,51 @@
+//! Look up a species by its Veekun identifier and print what a battler
+//! wants to know about it, like `cargo run --example dex_browser lucario`.
+
+use vdex::analysis::DefensiveProfile;
+use vdex::veekun::identifiers::to_display_name;
+use vdex::versions::VersionGroup;
+use vdex::pokedex;
+
+fn describe(identifier: &str) -> Option<String> {
+    let dex = pokedex();
+    let species = &dex.species[dex.species.identifiers().id(identifier)?];
+    let pokemon = &dex.species.pokemon(species.id)[0];
+    let mut text = format!("{} (Generation {:?})\n",
+                           to_display_name(&species.name), species.generation);
+    text += &match pokemon.types.second() {
+        Some(second) => {
+            format!("Types: {:?}/{:?}\n", pokemon.types.first(), second)
+        },
+        None => format!("Type: {:?}\n", pokemon.types.first()),
+    };
+    for (stat, base) in pokemon.stats.iter() {
+        text += &format!("{:>14}: {}\n", format!("{:?}", stat), base);
+    }
+    text += &format!("{:>14}: {}\n", "Total", pokemon.stats.total());
+    let profile = DefensiveProfile::new(pokemon);
+    let weaknesses: Vec<_> = profile.weaknesses().iter()
+        .map(|t| format!("{:?}", t)).collect();
+    text += &format!("Weak to: {}\n", weaknesses.join(", "));
+    let learnset = pokemon.learnset(VersionGroup::BlackWhite);
+    text += &format!("Learns {} moves in Black and White", learnset.len());
+    Some(text)
+}
+
+fn main() {
+    let identifier = std::env::args().nth(1)
+        .unwrap_or_else(|| "lucario".to_string());
+    match describe(&identifier) {
+        Some(text) => println!("{}", text),
+        None => println!("No species \"{}\".", identifier),
+    }
+    let lucario = describe("lucario").unwrap();
+    assert!(lucario.starts_with("Lucario"));
+    assert!(lucario.contains("Types: Fighting/Steel"));
+    assert!(describe("missingno").is_none());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn runs() {
+    main();
+}
@@ -0,0 +1,48 @@
+//! Parse a team in the Pokémon Showdown text format and check it against a
+//! ruleset.
+
+use vdex::pokedex;
+use vdex::team::{Error, Ruleset, Team};
+
+const TEAM: &str = "\
+Garchomp @ Choice Scarf
+Ability: Sand Veil
+EVs: 252 Atk / 4 SpD / 252 Spe
+Jolly Nature
+- Outrage
+- Earthquake
+- Stone Edge
+- Fire Fang
+
+Rotom-Wash @ Leftovers
+Ability: Levitate
+EVs: 252 HP / 252 Def / 4 SpA
+Bold Nature
+- Hydro Pump
+- Volt Switch
+- Will-O-Wisp
+- Pain Split
+";
+
+fn main() {
+    let team = Team::from_showdown(TEAM).unwrap();
+    team.validate(&Ruleset::default()).unwrap();
+    println!("Valid team of {} Pokémon.", team.pokemon.len());
+
+    // Ban Sand Veil, as in Smogon's Generation V OU.
+    let mut rules = Ruleset::default();
+    let sand_veil = team.pokemon[0].ability(pokedex()).unwrap();
+    rules.banned_abilities.push(sand_veil);
+    let error = team.validate(&rules).unwrap_err();
+    println!("{}", error);
+    assert_eq!(error, Error::Rule { index: Some(0), rule: "banned ability" });
+
+    // Writing the team back gives text that parses to the same team.
+    assert_eq!(Team::from_showdown(&team.to_showdown()).unwrap(), team);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn runs() {
+    main();
+}
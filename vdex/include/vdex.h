@@ -0,0 +1,160 @@
+#ifndef VDEX_H
+#define VDEX_H
+
+/* Generated with cbindgen from src/capi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The ID returned by failed lookups.
+ */
+#define VDEX_NONE UINT16_MAX
+
+/**
+ * The second type of a Pokémon with only one type.
+ */
+#define VDEX_NO_TYPE UINT8_MAX
+
+/**
+ * The base stats of a Pokémon, in the order of `pokemon::BaseStats`.
+ */
+typedef struct VdexBaseStats {
+  uint8_t hp;
+  uint8_t attack;
+  uint8_t defense;
+  uint8_t speed;
+  uint8_t special_attack;
+  uint8_t special_defense;
+} VdexBaseStats;
+
+/**
+ * The properties of a move.
+ */
+typedef struct VdexMove {
+  /**
+   * The representation of the move's `Type`.
+   */
+  uint8_t typ;
+  /**
+   * The power as in the Veekun data: 0 for moves without power, and 1 for
+   * moves whose power varies.
+   */
+  uint8_t power;
+  /**
+   * The accuracy in percent, or 0 if the move cannot miss.
+   */
+  uint8_t accuracy;
+  uint8_t pp;
+  int8_t priority;
+  /**
+   * The representation of the move's `DamageClass`.
+   */
+  uint8_t damage_class;
+} VdexMove;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Get the ID of a species by Veekun identifier, like `"mr-mime"`.
+ *
+ * # Safety
+ *
+ * `identifier` must be null or a NUL-terminated string.
+ */
+uint16_t vdex_species_id(const char *identifier);
+
+/**
+ * Get the ID of a move by Veekun identifier, like `"karate-chop"`.
+ *
+ * # Safety
+ *
+ * `identifier` must be null or a NUL-terminated string.
+ */
+uint16_t vdex_move_id(const char *identifier);
+
+/**
+ * Get the ID of an item by Veekun identifier, like `"leftovers"`.
+ *
+ * # Safety
+ *
+ * `identifier` must be null or a NUL-terminated string.
+ */
+uint16_t vdex_item_id(const char *identifier);
+
+/**
+ * Copy the name of a species into a buffer, returning the length of the
+ * name, or 0 if there is no such species.
+ *
+ * # Safety
+ *
+ * `buf` must be null or valid for writes of `len` bytes.
+ */
+size_t vdex_copy_species_name(uint16_t id, char *buf, size_t len);
+
+/**
+ * Copy the name of a move into a buffer, returning the length of the name,
+ * or 0 if there is no such move.
+ *
+ * # Safety
+ *
+ * `buf` must be null or valid for writes of `len` bytes.
+ */
+size_t vdex_copy_move_name(uint16_t id, char *buf, size_t len);
+
+/**
+ * Copy the name of an item into a buffer, returning the length of the name,
+ * or 0 if there is no such item.
+ *
+ * # Safety
+ *
+ * `buf` must be null or valid for writes of `len` bytes.
+ */
+size_t vdex_copy_item_name(uint16_t id, char *buf, size_t len);
+
+/**
+ * Get the damage modifier of a type against a type, like 2.0 for super
+ * effective, or -1.0 if either type is invalid.
+ */
+double vdex_type_modifier(uint8_t damage, uint8_t target);
+
+/**
+ * Get the types of a species' default Pokémon, with `VDEX_NO_TYPE` as the
+ * second type if it has one type, returning false if there is no such
+ * species.
+ *
+ * # Safety
+ *
+ * `first` and `second` must be valid for writes.
+ */
+bool vdex_species_types(uint16_t id, uint8_t *first, uint8_t *second);
+
+/**
+ * Get the base stats of a species' default Pokémon, returning false if
+ * there is no such species.
+ *
+ * # Safety
+ *
+ * `stats` must be valid for writes.
+ */
+bool vdex_base_stats(uint16_t id, struct VdexBaseStats *stats);
+
+/**
+ * Get the properties of a move, returning false if there is no such move.
+ *
+ * # Safety
+ *
+ * `mov` must be valid for writes.
+ */
+bool vdex_move(uint16_t id, struct VdexMove *mov);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* VDEX_H */
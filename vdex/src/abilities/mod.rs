@@ -0,0 +1,72 @@
+//! Abilities.
+
+pub(self) mod modifiers;
+
+pub use self::modifiers::{Condition, Modifier, ModifierContext, ModifierKind};
+pub use self::modifiers::Modifiers;
+
+use crate::enums::*;
+use crate::FromVeekun;
+use crate::versions::Generation;
+
+/// Define `Ability` from the variants generated by the build script from
+/// Veekun's `abilities.csv`, with the generation each was introduced in.
+macro_rules! ability_enum {
+    ($($name:ident = $id:expr => $generation:ident,)*) => {
+        /// An ability provides a passive effect in battle or in the overworld.
+        ///
+        /// > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Ability) An
+        /// > Ability (Japanese: 特性 ability) is a game mechanic introduced in
+        /// > Generation III that provides a passive effect in battle or in the
+        /// > overworld. Individual Pokémon may have only one Ability at a time. [An
+        /// > Ability cannot] be changed after a Pokémon was obtained except by
+        /// > Evolution—where the new Ability is determined by the former Ability—and
+        /// > form change. Not every Ability is beneficial; some will hinder the user.
+        ///
+        /// New abilities may be added with new data, so matches on abilities
+        /// outside vdex need a wildcard arm.
+        #[EnumRepr(type = "u8", serde = true)]
+        #[non_exhaustive]
+        pub enum Ability {
+            $($name = $id,)*
+        }
+
+        impl Ability {
+            /// The generation the ability was introduced in.
+            pub fn generation(self) -> Generation {
+                match self {
+                    $(Ability::$name => Generation::$generation,)*
+                }
+            }
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/abilities.rs"));
+
+impl Default for Ability {
+    fn default() -> Self { Ability::Cacophony }
+}
+
+impl Ability {
+    /// True if the ability exists in a generation.
+    pub fn exists_in(self, generation: Generation) -> bool {
+        self.generation() <= generation
+    }
+
+    /// The abilities introduced in a generation, in order of ID.
+    pub fn introduced_in(
+        generation: Generation
+    ) -> impl Iterator<Item = Ability> {
+        Ability::VALUES.iter().cloned()
+            .filter(move |a| a.generation() == generation)
+    }
+}
+
+impl FromVeekun for Ability {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Ability::from_repr(value)
+    }
+}
@@ -0,0 +1,248 @@
+use crate::battle::Monster;
+use crate::moves::{Ailment, DamageClass, Flags, Move, Weather};
+use crate::Stat;
+use crate::Type;
+use super::Ability;
+use self::Condition::*;
+use self::ModifierKind::{Damage, Power, Stab};
+
+/// What an ability modifies.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ModifierKind {
+    /// A stat of the holder, after its stage.
+    Stat(Stat),
+    /// The power of the holder's moves.
+    Power,
+    /// The same-type attack bonus of the holder's moves, on top of the usual
+    /// 1.5.
+    Stab,
+    /// The damage the holder takes from moves. A factor of 0 is an immunity.
+    Damage,
+}
+
+/// A condition for an ability's modifier to apply.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Condition {
+    /// The holder has a non-volatile ailment.
+    Statused,
+    /// The holder's HP is at most a fraction of its maximum, as (numerator,
+    /// denominator).
+    HpAtMost(u16, u16),
+    /// The holder's HP is full.
+    FullHp,
+    /// The weather is in effect.
+    Weather(Weather),
+    /// The move is of a type.
+    MoveType(Type),
+    /// The move has a damage class.
+    MoveClass(DamageClass),
+    /// The move's power is at most a value.
+    PowerAtMost(u16),
+    /// The move has all of some flags.
+    MoveFlags(Flags),
+    /// The move damages its user in recoil.
+    Recoil,
+    /// The move has a chance of a secondary effect.
+    SecondaryEffect,
+    /// The move is super effective against the holder.
+    SuperEffective,
+    /// The move is not super effective against the holder.
+    NotSuperEffective,
+}
+
+/// A passive numeric modifier of an ability, which multiplies something by a
+/// factor when all of its conditions hold.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Modifier {
+    pub ability: Ability,
+    pub kind: ModifierKind,
+    /// The factor, as (numerator, denominator).
+    pub factor: (u16, u16),
+    pub conditions: &'static [Condition],
+}
+
+/// The circumstances in which modifiers apply.
+#[derive(Copy, Clone, Debug)]
+pub struct ModifierContext<'a> {
+    /// The Pokémon with the ability.
+    pub holder: &'a Monster,
+    /// The move used by or against the holder, if any.
+    pub mov: Option<&'a Move>,
+    /// The type of the move, which may differ from its own, like Judgment's.
+    pub move_type: Option<Type>,
+    /// The power of the move, which may differ from its own.
+    pub power: u16,
+    /// The type efficacy of the move against its target, as a factor.
+    pub type_factor: f64,
+    pub weather: Option<Weather>,
+}
+
+impl<'a> ModifierContext<'a> {
+    /// A context with no move, for the holder's stats.
+    pub fn new(holder: &'a Monster, weather: Option<Weather>) -> Self {
+        ModifierContext {
+            holder, mov: None, move_type: None, power: 0, type_factor: 1.0,
+            weather,
+        }
+    }
+
+    /// A context with a move, at its own type and power.
+    pub fn with_move(self, mov: &'a Move) -> Self {
+        ModifierContext {
+            mov: Some(mov), move_type: Some(mov.typ), power: mov.power.into(),
+            .. self
+        }
+    }
+}
+
+impl Condition {
+    /// True if the condition holds in a context.
+    pub fn holds(self, ctx: &ModifierContext) -> bool {
+        let holder = ctx.holder;
+        match self {
+            Statused => holder.ailment != Ailment::None,
+            HpAtMost(numerator, denominator) => {
+                u32::from(holder.hp) * u32::from(denominator)
+                    <= u32::from(holder.stats[0]) * u32::from(numerator)
+            },
+            FullHp => holder.hp == holder.stats[0],
+            Weather(weather) => ctx.weather == Some(weather),
+            MoveType(typ) => ctx.move_type == Some(typ),
+            MoveClass(class) => {
+                ctx.mov.is_some_and(|m| m.damage_class == class)
+            },
+            PowerAtMost(power) => ctx.mov.is_some() && ctx.power <= power,
+            MoveFlags(flags) => {
+                ctx.mov.is_some_and(|m| m.meta.flags.contains(flags))
+            },
+            Recoil => ctx.mov.is_some_and(|m| m.meta.recoil < 0),
+            SecondaryEffect => {
+                ctx.mov.is_some_and(|m| m.effect_chance.is_some())
+            },
+            SuperEffective => ctx.mov.is_some() && ctx.type_factor > 1.0,
+            NotSuperEffective => ctx.mov.is_some() && ctx.type_factor <= 1.0,
+        }
+    }
+}
+
+impl Modifier {
+    /// True if the modifier applies in a context, in which the holder must
+    /// have the modifier's ability.
+    pub fn applies(&self, ctx: &ModifierContext) -> bool {
+        ctx.holder.ability == self.ability
+            && self.conditions.iter().all(|c| c.holds(ctx))
+    }
+}
+
+const fn stat(
+    ability: Ability, stat: Stat, factor: (u16, u16),
+    conditions: &'static [Condition]
+) -> Modifier {
+    modifier(ability, ModifierKind::Stat(stat), factor, conditions)
+}
+
+const fn modifier(
+    ability: Ability, kind: ModifierKind, factor: (u16, u16),
+    conditions: &'static [Condition]
+) -> Modifier {
+    Modifier { ability, kind, factor, conditions }
+}
+
+/// The catalog of passive numeric ability modifiers, as of Generation V.
+///
+/// This covers abilities that multiply stats, move power, the same-type
+/// attack bonus, or damage taken, including immunities to types like
+/// Levitate's. Abilities with other effects are not included.
+pub struct Modifiers;
+
+impl Modifiers {
+    pub const ALL: &'static [Modifier] = &[
+        stat(Ability::HugePower, Stat::Attack, (2, 1), &[]),
+        stat(Ability::PurePower, Stat::Attack, (2, 1), &[]),
+        stat(Ability::Hustle, Stat::Attack, (3, 2), &[]),
+        stat(Ability::Hustle, Stat::Accuracy, (4, 5),
+             &[MoveClass(DamageClass::Physical)]),
+        stat(Ability::Guts, Stat::Attack, (3, 2), &[Statused]),
+        stat(Ability::MarvelScale, Stat::Defense, (3, 2), &[Statused]),
+        stat(Ability::QuickFeet, Stat::Speed, (3, 2), &[Statused]),
+        stat(Ability::SolarPower, Stat::SpecialAttack, (3, 2),
+             &[Weather(Weather::Sun)]),
+        stat(Ability::FlowerGift, Stat::Attack, (3, 2),
+             &[Weather(Weather::Sun)]),
+        stat(Ability::FlowerGift, Stat::SpecialDefense, (3, 2),
+             &[Weather(Weather::Sun)]),
+        stat(Ability::Chlorophyll, Stat::Speed, (2, 1),
+             &[Weather(Weather::Sun)]),
+        stat(Ability::SwiftSwim, Stat::Speed, (2, 1),
+             &[Weather(Weather::Rain)]),
+        stat(Ability::SandRush, Stat::Speed, (2, 1),
+             &[Weather(Weather::Sandstorm)]),
+        stat(Ability::Defeatist, Stat::Attack, (1, 2), &[HpAtMost(1, 2)]),
+        stat(Ability::Defeatist, Stat::SpecialAttack, (1, 2),
+             &[HpAtMost(1, 2)]),
+        stat(Ability::Compoundeyes, Stat::Accuracy, (13, 10), &[]),
+        stat(Ability::SandVeil, Stat::Evasion, (5, 4),
+             &[Weather(Weather::Sandstorm)]),
+        stat(Ability::SnowCloak, Stat::Evasion, (5, 4),
+             &[Weather(Weather::Hail)]),
+        modifier(Ability::Blaze, Power, (3, 2),
+                 &[HpAtMost(1, 3), MoveType(Type::Fire)]),
+        modifier(Ability::Overgrow, Power, (3, 2),
+                 &[HpAtMost(1, 3), MoveType(Type::Grass)]),
+        modifier(Ability::Torrent, Power, (3, 2),
+                 &[HpAtMost(1, 3), MoveType(Type::Water)]),
+        modifier(Ability::Swarm, Power, (3, 2),
+                 &[HpAtMost(1, 3), MoveType(Type::Bug)]),
+        modifier(Ability::Technitian, Power, (3, 2), &[PowerAtMost(60)]),
+        modifier(Ability::IronFist, Power, (6, 5), &[MoveFlags(Flags::PUNCH)]),
+        modifier(Ability::Reckless, Power, (6, 5), &[Recoil]),
+        modifier(Ability::SheerForce, Power, (13, 10), &[SecondaryEffect]),
+        modifier(Ability::SandForce, Power, (13, 10),
+                 &[Weather(Weather::Sandstorm), MoveType(Type::Rock)]),
+        modifier(Ability::SandForce, Power, (13, 10),
+                 &[Weather(Weather::Sandstorm), MoveType(Type::Ground)]),
+        modifier(Ability::SandForce, Power, (13, 10),
+                 &[Weather(Weather::Sandstorm), MoveType(Type::Steel)]),
+        modifier(Ability::Adaptability, Stab, (4, 3), &[]),
+        modifier(Ability::ThickFat, Damage, (1, 2), &[MoveType(Type::Fire)]),
+        modifier(Ability::ThickFat, Damage, (1, 2), &[MoveType(Type::Ice)]),
+        modifier(Ability::Heatproof, Damage, (1, 2), &[MoveType(Type::Fire)]),
+        modifier(Ability::DrySkin, Damage, (5, 4), &[MoveType(Type::Fire)]),
+        modifier(Ability::Filter, Damage, (3, 4), &[SuperEffective]),
+        modifier(Ability::SolidRock, Damage, (3, 4), &[SuperEffective]),
+        modifier(Ability::Multiscale, Damage, (1, 2), &[FullHp]),
+        modifier(Ability::Levitate, Damage, (0, 1), &[MoveType(Type::Ground)]),
+        modifier(Ability::FlashFire, Damage, (0, 1), &[MoveType(Type::Fire)]),
+        modifier(Ability::VoltAbsorb, Damage, (0, 1),
+                 &[MoveType(Type::Electric)]),
+        modifier(Ability::MotorDrive, Damage, (0, 1),
+                 &[MoveType(Type::Electric)]),
+        modifier(Ability::Lightningrod, Damage, (0, 1),
+                 &[MoveType(Type::Electric)]),
+        modifier(Ability::WaterAbsorb, Damage, (0, 1),
+                 &[MoveType(Type::Water)]),
+        modifier(Ability::StormDrain, Damage, (0, 1), &[MoveType(Type::Water)]),
+        modifier(Ability::DrySkin, Damage, (0, 1), &[MoveType(Type::Water)]),
+        modifier(Ability::SapSipper, Damage, (0, 1), &[MoveType(Type::Grass)]),
+        modifier(Ability::WonderGuard, Damage, (0, 1), &[NotSuperEffective]),
+    ];
+
+    /// The modifiers of an ability.
+    pub fn of(ability: Ability) -> impl Iterator<Item = &'static Modifier> {
+        Modifiers::ALL.iter().filter(move |m| m.ability == ability)
+    }
+
+    /// The product of the factors of the holder's modifiers of a kind that
+    /// apply in a context, or 1 if none do.
+    pub fn factor(kind: ModifierKind, ctx: &ModifierContext) -> f64 {
+        Modifiers::of(ctx.holder.ability)
+            .filter(|m| m.kind == kind && m.applies(ctx))
+            .map(|m| f64::from(m.factor.0) / f64::from(m.factor.1))
+            .product()
+    }
+
+    /// Apply the factor of the holder's modifiers of a kind to a value.
+    pub fn apply(kind: ModifierKind, ctx: &ModifierContext, value: u32) -> u32 {
+        (f64::from(value) * Modifiers::factor(kind, ctx)) as u32
+    }
+}
@@ -0,0 +1,151 @@
+//! Opponent behavior for battle simulators, choosing actions from the
+//! battle state.
+
+use crate::battle::damage::{calc_damage, effective_move_type, type_modifier};
+use crate::battle::damage::MAX_DAMAGE_ROLL;
+use crate::battle::restrictions::allowed_moves;
+use crate::battle::{Action, BattleRng, BattleState, Position};
+use crate::moves::{Ailment, Category, MoveId};
+use crate::pokedex;
+
+/// A way of choosing the action of a side in a single battle.
+pub trait Policy {
+    /// Choose the action of a side for the next turn.
+    fn choose(
+        &mut self, state: &BattleState, side: u8, rng: &mut dyn BattleRng
+    ) -> Action;
+}
+
+/// The Struggle move, used when no other move can be selected.
+pub fn struggle() -> MoveId {
+    pokedex().moves.identifiers().id("struggle")
+        .expect("Struggle should be in the moves table.")
+}
+
+/// The moves the active Pokémon of a side can select, as in
+/// `restrictions::allowed_moves`, each targeting the opposing active
+/// Pokémon, or Struggle if it can select none.
+pub fn move_actions(state: &BattleState, side: u8) -> Vec<Action> {
+    let user = state.active(side);
+    let target = state.active(1 - side);
+    let monster = state.monster(user);
+    let mask = allowed_moves(monster, &[state.monster(target)]);
+    let action = |move_id| Action::Move { user, move_id, target };
+    if mask.is_empty() {
+        return vec![action(struggle())];
+    }
+    mask.slots().map(|slot| action(monster.moves[slot].id)).collect()
+}
+
+/// The switches a side can make: to each Pokémon in its party that is not
+/// active or fainted, unless the active Pokémon is trapped.
+pub fn switch_actions(state: &BattleState, side: u8) -> Vec<Action> {
+    if !state.can_switch(side).is_allowed() {
+        return Vec::new();
+    }
+    let team = &state.sides[side as usize];
+    team.party.iter().enumerate()
+        .filter(|&(slot, monster)| {
+            slot != team.active as usize && !monster.is_fainted()
+        })
+        .map(|(slot, _)| Action::Switch { side, slot: slot as u8 })
+        .collect()
+}
+
+/// Every action a side can choose, moves first.
+pub fn legal_actions(state: &BattleState, side: u8) -> Vec<Action> {
+    let mut actions = move_actions(state, side);
+    actions.extend(switch_actions(state, side));
+    actions
+}
+
+/// Choose among every legal action uniformly at random.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RandomPolicy;
+
+impl Policy for RandomPolicy {
+    fn choose(
+        &mut self, state: &BattleState, side: u8, rng: &mut dyn BattleRng
+    ) -> Action {
+        let actions = legal_actions(state, side);
+        actions[rng.below(actions.len() as u32) as usize]
+    }
+}
+
+/// Score moves from a base of 100 like the games' trainer AI, and use the
+/// best, breaking ties at random. It never switches.
+///
+/// See `ScorePolicy::score` for the scoring.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ScorePolicy;
+
+/// The score of a move, before adjustments.
+pub const BASE_SCORE: i32 = 100;
+
+impl ScorePolicy {
+    /// Score a move of the Pokémon at `user` against the opposing active
+    /// Pokémon. Higher is better.
+    ///
+    /// Damaging moves are scored by type efficacy, +20 if they can knock the
+    /// target out, and 0 against an immune target. Moves that only inflict
+    /// an ailment lose 50 against a target with one. Healing moves gain 20
+    /// below half HP and lose 20 otherwise. Moves that only change stats
+    /// gain 10 at full HP and lose 10 otherwise.
+    pub fn score(state: &BattleState, user: Position, move_id: MoveId) -> i32 {
+        let mov = &pokedex().moves[move_id];
+        let target = state.active(1 - user.side);
+        let attacker = state.monster(user);
+        let defender = state.monster(target);
+        let mut score = BASE_SCORE;
+        if mov.power > 0 {
+            let typ = effective_move_type(mov, attacker, state.weather);
+            let modifier = type_modifier(typ, defender.types);
+            if modifier == 0.0 {
+                return 0;
+            }
+            score += ((modifier - 1.0) * 20.0) as i32;
+            let damage
+                = calc_damage(state, user, target, mov, false, MAX_DAMAGE_ROLL);
+            if damage >= defender.hp {
+                score += 20;
+            }
+        }
+        match mov.meta.category {
+            Category::Ailment if defender.ailment != Ailment::None => {
+                score -= 50
+            },
+            Category::Heal if attacker.hp < attacker.max_hp() / 2 => {
+                score += 20
+            },
+            Category::Heal => score -= 20,
+            Category::NetGoodStats if attacker.hp == attacker.max_hp() => {
+                score += 10
+            },
+            Category::NetGoodStats => score -= 10,
+            _ => (),
+        }
+        score
+    }
+}
+
+impl Policy for ScorePolicy {
+    fn choose(
+        &mut self, state: &BattleState, side: u8, rng: &mut dyn BattleRng
+    ) -> Action {
+        let actions = move_actions(state, side);
+        let scores: Vec<_> = actions.iter()
+            .map(|action| match *action {
+                Action::Move { user, move_id, .. } => {
+                    Self::score(state, user, move_id)
+                },
+                Action::Switch { .. } => i32::MIN,
+            })
+            .collect();
+        let best = *scores.iter().max().unwrap();
+        let candidates: Vec<_> = actions.iter().zip(scores)
+            .filter(|&(_, score)| score == best)
+            .map(|(&action, _)| action)
+            .collect();
+        candidates[rng.below(candidates.len() as u32) as usize]
+    }
+}
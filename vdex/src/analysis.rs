@@ -0,0 +1,455 @@
+//! Analyses over every Pokémon in the global pokedex: defensive type
+//! profiles, type coverage, and speed tiers; and damage calculations between
+//! two Pokémon, like `ko_chance`, or between two teams, like
+//! `matchup_matrix`.
+//!
+//! With the `rayon` feature, the `par_` variants compute the same results in
+//! parallel.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use crate::Ability;
+use crate::battle::damage::{calc_damage, effective_move_type};
+use crate::battle::damage::type_modifier;
+use crate::battle::damage::{MAX_DAMAGE_ROLL, MIN_DAMAGE_ROLL};
+use crate::battle::{BattleState, Monster, Position, Side};
+use crate::enums::*;
+use crate::moves::{Ailment, Move, MoveId, PowerKind, Weather};
+use crate::pokedex;
+use crate::pokemon::{OneOrTwo, Pokemon, PokemonId};
+use crate::{Stat, Type, TypeSet};
+
+/// How much damage a Pokémon takes from moves of each type.
+#[derive(Copy, Clone, Debug)]
+pub struct DefensiveProfile {
+    pub pokemon: PokemonId,
+    pub types: OneOrTwo<Type>,
+    /// The type efficacy modifier of each attacking type, indexed by type
+    /// representation.
+    pub modifiers: [f64; Type::COUNT],
+}
+
+impl DefensiveProfile {
+    /// Compute the defensive profile of a Pokémon.
+    pub fn new(pokemon: &Pokemon) -> Self {
+        let mut modifiers = [1.0; Type::COUNT];
+        for &typ in Type::VALUES.iter() {
+            modifiers[typ.repr() as usize] = type_modifier(typ, pokemon.types);
+        }
+        DefensiveProfile { pokemon: pokemon.id, types: pokemon.types, modifiers }
+    }
+
+    /// The modifier of moves of a type against the Pokémon.
+    pub fn modifier(&self, typ: Type) -> f64 {
+        self.modifiers[typ.repr() as usize]
+    }
+
+    /// The types that are super effective against the Pokémon.
+    pub fn weaknesses(&self) -> TypeSet {
+        self.types_where(|m| m > 1.0)
+    }
+
+    /// The types that are not very effective against the Pokémon.
+    pub fn resistances(&self) -> TypeSet {
+        self.types_where(|m| m > 0.0 && m < 1.0)
+    }
+
+    /// The types that do not affect the Pokémon.
+    pub fn immunities(&self) -> TypeSet {
+        self.types_where(|m| m == 0.0)
+    }
+
+    fn types_where(&self, predicate: impl Fn(f64) -> bool) -> TypeSet {
+        Type::VALUES.iter().cloned()
+            .filter(|&t| predicate(self.modifier(t)))
+            .collect()
+    }
+}
+
+/// For each pair of attacking types, the number of Pokémon that at least one
+/// of them hits super effectively.
+///
+/// The matrix is symmetric, and its diagonal holds the counts for single
+/// types.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CoverageMatrix(pub [[u16; Type::COUNT]; Type::COUNT]);
+
+impl CoverageMatrix {
+    /// The pair of types that hits the most Pokémon super effectively, and
+    /// that number. Ties go to the pair that comes first in type order.
+    pub fn best_pair(&self) -> ((Type, Type), u16) {
+        let mut best = ((Type::Normal, Type::Normal), 0);
+        for (i, row) in self.0.iter().enumerate() {
+            for (j, &count) in row.iter().enumerate().skip(i) {
+                if count > best.1 {
+                    let first = Type::from_repr(i as u8).unwrap();
+                    let second = Type::from_repr(j as u8).unwrap();
+                    best = ((first, second), count);
+                }
+            }
+        }
+        best
+    }
+}
+
+impl std::ops::Index<(Type, Type)> for CoverageMatrix {
+    type Output = u16;
+
+    fn index(&self, index: (Type, Type)) -> &u16 {
+        &self.0[index.0.repr() as usize][index.1.repr() as usize]
+    }
+}
+
+/// Every Pokémon in the global pokedex, including alternate Pokémon of a
+/// species, in order of species.
+pub fn all_pokemon() -> Vec<&'static Pokemon> {
+    pokedex().species.all_pokemon().iter().collect()
+}
+
+/// The defensive profile of every Pokémon, in the order of `all_pokemon`.
+pub fn defensive_profiles() -> Vec<DefensiveProfile> {
+    all_pokemon().into_iter().map(DefensiveProfile::new).collect()
+}
+
+/// Compute `defensive_profiles` in parallel.
+#[cfg(feature = "rayon")]
+pub fn par_defensive_profiles() -> Vec<DefensiveProfile> {
+    all_pokemon().into_par_iter().map(DefensiveProfile::new).collect()
+}
+
+/// Compute the coverage of every pair of attacking types against every
+/// Pokémon.
+pub fn coverage_matrix() -> CoverageMatrix {
+    let profiles = defensive_profiles();
+    let mut matrix = [[0; Type::COUNT]; Type::COUNT];
+    for (first, row) in matrix.iter_mut().enumerate() {
+        *row = coverage_row(first, &profiles);
+    }
+    CoverageMatrix(matrix)
+}
+
+/// Compute `coverage_matrix` in parallel.
+#[cfg(feature = "rayon")]
+pub fn par_coverage_matrix() -> CoverageMatrix {
+    let profiles = par_defensive_profiles();
+    let mut matrix = [[0; Type::COUNT]; Type::COUNT];
+    matrix.par_iter_mut().enumerate()
+        .for_each(|(first, row)| *row = coverage_row(first, &profiles));
+    CoverageMatrix(matrix)
+}
+
+fn coverage_row(
+    first: usize, profiles: &[DefensiveProfile]
+) -> [u16; Type::COUNT] {
+    let mut row = [0; Type::COUNT];
+    for (second, count) in row.iter_mut().enumerate() {
+        *count = profiles.iter()
+            .filter(|p| p.modifiers[first] > 1.0 || p.modifiers[second] > 1.0)
+            .count() as u16;
+    }
+    row
+}
+
+/// Every Pokémon grouped by base Speed, from slowest to fastest.
+pub fn speed_tiers() -> BTreeMap<u8, Vec<PokemonId>> {
+    let mut tiers: BTreeMap<u8, Vec<PokemonId>> = BTreeMap::new();
+    for pokemon in all_pokemon() {
+        tiers.entry(pokemon.stats.speed()).or_default().push(pokemon.id);
+    }
+    tiers
+}
+
+/// The number of damage rolls, one for each random factor from
+/// `MIN_DAMAGE_ROLL` to `MAX_DAMAGE_ROLL` percent.
+pub const DAMAGE_ROLLS: usize = (MAX_DAMAGE_ROLL - MIN_DAMAGE_ROLL + 1) as usize;
+
+/// The damage of a move from one Pokémon to another, and the chance that it
+/// knocks the defender out within some hits, as in `ko_chance`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KoReport {
+    /// The damage of each roll of a hit that is not critical, lowest first.
+    pub rolls: [u16; DAMAGE_ROLLS],
+    /// The residual damage to the defender at the end of each turn.
+    pub residual: u16,
+    /// The number of hits, one per turn.
+    pub hits: u8,
+    /// The chance, from 0 to 1, that the defender faints within the hits and
+    /// the residual damage after each.
+    pub chance: f64,
+    /// The defender's current HP.
+    pub hp: u16,
+}
+
+impl KoReport {
+    /// The lowest and highest damage of a hit, in percent of the defender's
+    /// current HP.
+    pub fn percent_range(&self) -> (f64, f64) {
+        let percent = |damage: u16| damage as f64 * 100.0 / self.hp.max(1) as f64;
+        (percent(self.rolls[0]), percent(self.rolls[DAMAGE_ROLLS - 1]))
+    }
+}
+
+/// Formats as, e.g., "37-44 (33.0-39.3%): 43.7% chance to 3HKO".
+impl std::fmt::Display for KoReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (low, high) = self.percent_range();
+        write!(f, "{}-{} ({:.1}-{:.1}%): {:.1}% chance to {}HKO",
+               self.rolls[0], self.rolls[DAMAGE_ROLLS - 1], low, high,
+               self.chance * 100.0, self.hits)?;
+        if self.residual > 0 {
+            write!(f, " after residual damage")?;
+        }
+        Ok(())
+    }
+}
+
+/// The damage a Pokémon takes at the end of each turn from its ailment and
+/// the weather, with Generation V rules: 1/8 of its maximum HP from a burn or
+/// poison, and 1/16 from a sandstorm or hail that it is not immune to.
+pub fn residual_damage(monster: &Monster, weather: Option<Weather>) -> u16 {
+    if monster.ability == Ability::MagicGuard {
+        return 0;
+    }
+    let mut damage = match monster.ailment {
+        Ailment::Burn | Ailment::Poison => (monster.max_hp() / 8).max(1),
+        _ => 0,
+    };
+    let types = monster.types;
+    let immune = match weather {
+        Some(Weather::Sandstorm) => {
+            [Type::Rock, Type::Ground, Type::Steel].iter()
+                .any(|&t| types.contains(t))
+                || [Ability::SandVeil, Ability::SandRush, Ability::SandForce]
+                    .contains(&monster.ability)
+        },
+        Some(Weather::Hail) => {
+            types.contains(Type::Ice)
+                || [Ability::IceBody, Ability::SnowCloak]
+                    .contains(&monster.ability)
+        },
+        _ => true,
+    };
+    if !immune && monster.ability != Ability::Overcoat {
+        damage += (monster.max_hp() / 16).max(1);
+    }
+    damage
+}
+
+/// Calculate the chance that a move knocks a Pokémon out within a number of
+/// hits, one per turn, over every damage roll, without critical hits.
+///
+/// The defender's ailment deals residual damage at the end of each turn, as
+/// in `residual_damage`. The damage is calculated as in
+/// `battle::damage::calc_damage` in Generation V with no screens up.
+pub fn ko_chance(
+    attacker: &Monster, defender: &Monster, mv: &Move, hits: u8
+) -> KoReport {
+    ko_chance_in(attacker, defender, mv, hits, None)
+}
+
+/// Calculate the chance of a knockout like `ko_chance`, in some weather,
+/// which affects both the damage of the move and the residual damage.
+pub fn ko_chance_in(
+    attacker: &Monster, defender: &Monster, mv: &Move, hits: u8,
+    weather: Option<Weather>,
+) -> KoReport {
+    let mut state = duel(attacker, defender);
+    state.weather = weather;
+    let (user, target) = (state.active(0), Position { side: 1, slot: 0 });
+    let mut rolls = [0; DAMAGE_ROLLS];
+    for (i, roll) in (MIN_DAMAGE_ROLL..=MAX_DAMAGE_ROLL).enumerate() {
+        rolls[i] = calc_damage(&state, user, target, mv, false, roll);
+    }
+    let residual = residual_damage(defender, weather);
+    let hp = defender.hp;
+    // The chance of each total damage dealt so far without a knockout.
+    let mut totals = BTreeMap::new();
+    totals.insert(0u32, 1.0);
+    let mut chance = 0.0;
+    for _ in 0..hits {
+        let mut next = BTreeMap::new();
+        for (&total, &p) in totals.iter() {
+            for &damage in rolls.iter() {
+                let p = p / DAMAGE_ROLLS as f64;
+                let total = total + damage as u32 + residual as u32;
+                if total >= hp as u32 {
+                    chance += p;
+                } else {
+                    *next.entry(total).or_insert(0.0) += p;
+                }
+            }
+        }
+        totals = next;
+    }
+    KoReport { rolls, residual, hits, chance, hp }
+}
+
+/// A single battle between an attacker on side 0 and a defender on side 1.
+fn duel(attacker: &Monster, defender: &Monster) -> BattleState {
+    BattleState::new(Default::default(), [
+        Side::new(vec![attacker.clone()]), Side::new(vec![defender.clone()]),
+    ])
+}
+
+/// How one Pokémon fares attacking another, as in `matchup_matrix`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matchup {
+    /// The attacker's damaging move with the highest damage roll, if any.
+    pub best_move: Option<MoveId>,
+    /// The type efficacy modifier of the best move against the defender, or
+    /// 0 without one.
+    pub efficacy: f64,
+    /// The highest damage roll of the best move, without a critical hit.
+    pub damage: u16,
+    /// The attacker's Speed, with its stage applied.
+    pub attacker_speed: u16,
+    /// The defender's Speed, with its stage applied.
+    pub defender_speed: u16,
+}
+
+impl Matchup {
+    /// Whether the attacker is faster than, slower than, or tied with the
+    /// defender.
+    pub fn speed(&self) -> Ordering {
+        self.attacker_speed.cmp(&self.defender_speed)
+    }
+}
+
+/// The matchups of every Pokémon of one team attacking every Pokémon of
+/// another.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchupMatrix {
+    /// The species identifiers of the attacking team.
+    pub attackers: Vec<String>,
+    /// The species identifiers of the defending team.
+    pub defenders: Vec<String>,
+    /// The matchups, one row per attacker and one column per defender.
+    pub matchups: Vec<Vec<Matchup>>,
+}
+
+impl MatchupMatrix {
+    /// Export as CSV, with a header and one line per attacker and defender:
+    /// `attacker,defender,best_move,efficacy,damage,speed`.
+    ///
+    /// Moves are Veekun identifiers, empty without a damaging move, and speed
+    /// is one of "faster", "slower", or "tie" for the attacker.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "attacker,defender,best_move,efficacy,damage,speed\n");
+        for (attacker, defender, matchup) in self.iter() {
+            writeln!(csv, "{},{},{},{},{},{}", attacker, defender,
+                     move_identifier(matchup.best_move), matchup.efficacy,
+                     matchup.damage, speed_name(matchup.speed())).unwrap();
+        }
+        csv
+    }
+
+    /// Export as a JSON array with one object per attacker and defender,
+    /// with the same fields as `to_csv`, plus both Speeds. A missing best move
+    /// is `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, (attacker, defender, matchup)) in self.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let best_move = match matchup.best_move {
+                Some(id) => format!("\"{}\"", move_identifier(Some(id))),
+                None => "null".to_string(),
+            };
+            write!(json, concat!(
+                "{{\"attacker\":\"{}\",\"defender\":\"{}\",",
+                "\"best_move\":{},\"efficacy\":{},\"damage\":{},",
+                "\"attacker_speed\":{},\"defender_speed\":{},",
+                "\"speed\":\"{}\"}}"),
+                attacker, defender, best_move, matchup.efficacy, matchup.damage,
+                matchup.attacker_speed, matchup.defender_speed,
+                speed_name(matchup.speed())).unwrap();
+        }
+        json.push(']');
+        json
+    }
+
+    /// Iterate over the matchups with the attacker's and defender's species
+    /// identifiers, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, &Matchup)> + '_ {
+        self.matchups.iter().zip(&self.attackers)
+            .flat_map(move |(row, attacker)| {
+                row.iter().zip(&self.defenders).map(move |(matchup, defender)| {
+                    (attacker.as_str(), defender.as_str(), matchup)
+                })
+            })
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for MatchupMatrix {
+    type Output = Matchup;
+
+    /// Get the matchup of an attacker and a defender by their team slots.
+    fn index(&self, (attacker, defender): (usize, usize)) -> &Matchup {
+        &self.matchups[attacker][defender]
+    }
+}
+
+fn move_identifier(id: Option<MoveId>) -> &'static str {
+    id.and_then(|id| pokedex().moves.identifiers().identifier(id))
+        .unwrap_or("")
+}
+
+fn speed_name(speed: Ordering) -> &'static str {
+    match speed {
+        Ordering::Greater => "faster",
+        Ordering::Less => "slower",
+        Ordering::Equal => "tie",
+    }
+}
+
+/// Compute how each Pokémon of one team fares attacking each of another.
+///
+/// The best move of each pair is the attacker's damaging move with the
+/// highest damage roll against the defender, calculated as in `ko_chance`.
+pub fn matchup_matrix(team_a: &[Monster], team_b: &[Monster]) -> MatchupMatrix {
+    let identifiers = |team: &[Monster]| team.iter()
+        .map(|m| pokedex().species[m.species].identifier.clone())
+        .collect();
+    let matchups = team_a.iter()
+        .map(|attacker| team_b.iter()
+            .map(|defender| matchup(attacker, defender))
+            .collect())
+        .collect();
+    MatchupMatrix {
+        attackers: identifiers(team_a),
+        defenders: identifiers(team_b),
+        matchups,
+    }
+}
+
+fn matchup(attacker: &Monster, defender: &Monster) -> Matchup {
+    let state = duel(attacker, defender);
+    let (user, target) = (state.active(0), Position { side: 1, slot: 0 });
+    let best = attacker.moves.iter()
+        .map(|slot| &pokedex().moves[slot.id])
+        .filter(|mv| mv.power_kind() != PowerKind::None)
+        .map(|mv| {
+            let damage =
+                calc_damage(&state, user, target, mv, false, MAX_DAMAGE_ROLL);
+            (mv, damage)
+        })
+        .fold(None, |best: Option<(&Move, u16)>, (mv, damage)| match best {
+            Some((_, best_damage)) if best_damage >= damage => best,
+            _ => Some((mv, damage)),
+        });
+    Matchup {
+        best_move: best.map(|(mv, _)| mv.id),
+        efficacy: best.map_or(0.0, |(mv, _)| {
+            let typ = effective_move_type(mv, attacker, state.weather);
+            type_modifier(typ, defender.types)
+        }),
+        damage: best.map_or(0, |(_, damage)| damage),
+        attacker_speed: attacker.staged_stat(Stat::Speed),
+        defender_speed: defender.staged_stat(Stat::Speed),
+    }
+}
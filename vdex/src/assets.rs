@@ -0,0 +1,239 @@
+//! Deterministic keys for front-end assets.
+//!
+//! These are path fragments, not full paths: a front-end builds its own URLs
+//! around them, e.g. `format!("/sprites/{}.png", key)`.
+//!
+//! The footprint and cry keys of species can be overridden with an auxiliary
+//! `species_assets.csv` (see `SpeciesAssetTable`), and the type colors and
+//! item category icon keys with a `Palette`.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use veekun::repr::VeekunString;
+use crate::items::{self, Item};
+use crate::pokedex;
+use crate::pokemon::{Form, Pokemon, Species, SpeciesId};
+use crate::Type;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::VeekunOption;
+
+/// Sprite key of a form of a species, e.g. `"479-wash"`.
+///
+/// The key is the National Pokédex number, followed by the form identifier
+/// unless the form is the species' default form.
+pub fn form_sprite_key(species: &Species, form: &Form) -> String {
+    let number = species.id.0 + 1;
+    let default_form = pokedex().species.pokemon(species.id).first()
+        .and_then(|p| p.forms.first())
+        .map(|f| f.id);
+    match form.name {
+        Some(ref name) if default_form != Some(form.id)
+            => format!("{}-{}", number, name),
+        _ => format!("{}", number),
+    }
+}
+
+/// Sprite key of the default form of a Pokémon of a species.
+pub fn pokemon_sprite_key(species: &Species, pokemon: &Pokemon) -> String {
+    match pokemon.forms.first() {
+        Some(form) => form_sprite_key(species, form),
+        None => format!("{}", species.id.0 + 1),
+    }
+}
+
+/// Icon key of an item: its Veekun identifier, e.g. `"master-ball"`.
+pub fn item_icon_key(item: &Item) -> String {
+    item.identifier.clone()
+}
+
+/// Footprint and cry keys of a species.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SpeciesAssets {
+    /// Key of the species' footprint, or `None` if it has none.
+    pub footprint: Option<String>,
+    /// Key of the species' cry.
+    pub cry: String,
+}
+
+/// Asset keys for species, from the auxiliary `species_assets.csv`, with the
+/// columns `species_id`, `footprint`, and `cry`. An empty footprint means the
+/// species has none.
+///
+/// The file is not included in the binary; provide it through a data source
+/// (see `veekun::data::Overlay`) to override the keys `species_assets`
+/// derives.
+#[derive(Default)]
+pub struct SpeciesAssetTable(pub HashMap<SpeciesId, SpeciesAssets>);
+
+impl SpeciesAssetTable {
+    /// Create an asset table from the CSV data in a source. The table is empty
+    /// if the source does not have the file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        match source.csv(vdata::files::SPECIES_ASSETS) {
+            Some(data) => Self::from_csv_data(data),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Get the overridden asset keys of a species, if any.
+    pub fn get(&self, id: SpeciesId) -> Option<&SpeciesAssets> {
+        self.0.get(&id)
+    }
+}
+
+impl vcsv::FromCsvIncremental for SpeciesAssetTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: SpeciesId = vcsv::from_field(&record, 0)?;
+        let footprint: VeekunOption<VeekunString>
+            = vcsv::from_field(&record, 1)?;
+        let cry: VeekunString = vcsv::from_field(&record, 2)?;
+        self.0.insert(id, SpeciesAssets {
+            footprint: footprint.0.map(Into::into),
+            cry: cry.into(),
+        });
+        Ok(())
+    }
+}
+
+/// Footprint and cry keys of a species, from the global pokedex's
+/// `SpeciesAssetTable` if it has them.
+///
+/// Otherwise, the footprint key is the National Pokédex number, as every
+/// species has a footprint as of Generation V, and the cry key is the Veekun
+/// identifier, e.g. `"mr-mime"`.
+pub fn species_assets(species: &Species) -> SpeciesAssets {
+    match pokedex().assets.get(species.id) {
+        Some(assets) => assets.clone(),
+        None => SpeciesAssets {
+            footprint: Some(format!("{}", species.id.0 + 1)),
+            cry: species.identifier.clone(),
+        },
+    }
+}
+
+/// A color for theming, with 8-bit red, green, and blue channels.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// Formats the color as a CSS hex color, e.g. `"#a8a878"`.
+impl Display for Rgb {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl Type {
+    /// The conventional color of the type, as on type icons.
+    pub fn color(self) -> Rgb {
+        match self {
+            Type::Normal => Rgb(0xa8, 0xa8, 0x78),
+            Type::Fighting => Rgb(0xc0, 0x30, 0x28),
+            Type::Flying => Rgb(0xa8, 0x90, 0xf0),
+            Type::Poison => Rgb(0xa0, 0x40, 0xa0),
+            Type::Ground => Rgb(0xe0, 0xc0, 0x68),
+            Type::Rock => Rgb(0xb8, 0xa0, 0x38),
+            Type::Bug => Rgb(0xa8, 0xb8, 0x20),
+            Type::Ghost => Rgb(0x70, 0x58, 0x98),
+            Type::Steel => Rgb(0xb8, 0xb8, 0xd0),
+            Type::Fire => Rgb(0xf0, 0x80, 0x30),
+            Type::Water => Rgb(0x68, 0x90, 0xf0),
+            Type::Grass => Rgb(0x78, 0xc8, 0x50),
+            Type::Electric => Rgb(0xf8, 0xd0, 0x30),
+            Type::Psychic => Rgb(0xf8, 0x58, 0x88),
+            Type::Ice => Rgb(0x98, 0xd8, 0xd8),
+            Type::Dragon => Rgb(0x70, 0x38, 0xf8),
+            Type::Dark => Rgb(0x70, 0x58, 0x48),
+        }
+    }
+}
+
+impl items::Category {
+    /// Icon key of the category: its Veekun identifier, e.g. `"held-items"`.
+    pub fn icon_key(self) -> &'static str {
+        use crate::items::Category::*;
+        match self {
+            StatBoosts => "stat-boosts",
+            EffortDrop => "effort-drop",
+            Medicine => "medicine",
+            Other => "other",
+            InAPinch => "in-a-pinch",
+            PickyHealing => "picky-healing",
+            TypeProtection => "type-protection",
+            BakingOnly => "baking-only",
+            Collectibles => "collectibles",
+            Evolution => "evolution",
+            Spelunking => "spelunking",
+            HeldItems => "held-items",
+            Choice => "choice",
+            EffortTraining => "effort-training",
+            BadHeldItems => "bad-held-items",
+            Training => "training",
+            Plates => "plates",
+            SpeciesSpecific => "species-specific",
+            TypeEnhancement => "type-enhancement",
+            EventItems => "event-items",
+            Gameplay => "gameplay",
+            PlotAdvancement => "plot-advancement",
+            Unused => "unused",
+            Loot => "loot",
+            Mail => "all-mail",
+            Vitamins => "vitamins",
+            Healing => "healing",
+            PPRecovery => "pp-recovery",
+            Revival => "revival",
+            StatusCures => "status-cures",
+            Mulch => "mulch",
+            SpecialBalls => "special-balls",
+            StandardBalls => "standard-balls",
+            DexCompletion => "dex-completion",
+            Scarves => "scarves",
+            Machines => "all-machines",
+            Flutes => "flutes",
+            ApricornBalls => "apricorn-balls",
+            ApricornBox => "apricorn-box",
+            DataCards => "data-cards",
+            Jewels => "jewels",
+            MiracleShooter => "miracle-shooter",
+        }
+    }
+}
+
+/// Type colors and item category icon keys, with an application's overrides
+/// of the defaults of `Type::color` and `items::Category::icon_key`.
+#[derive(Clone, Debug, Default)]
+pub struct Palette {
+    type_colors: HashMap<Type, Rgb>,
+    icon_keys: HashMap<items::Category, String>,
+}
+
+impl Palette {
+    /// Override the color of a type.
+    pub fn with_type_color(mut self, typ: Type, color: Rgb) -> Self {
+        self.type_colors.insert(typ, color);
+        self
+    }
+
+    /// Override the icon key of an item category.
+    pub fn with_icon_key(
+        mut self, category: items::Category, key: &str
+    ) -> Self {
+        self.icon_keys.insert(category, key.to_string());
+        self
+    }
+
+    /// The color of a type, overridden or default.
+    pub fn type_color(&self, typ: Type) -> Rgb {
+        self.type_colors.get(&typ).copied().unwrap_or_else(|| typ.color())
+    }
+
+    /// The icon key of an item category, overridden or default.
+    pub fn icon_key(&self, category: items::Category) -> &str {
+        self.icon_keys.get(&category).map_or(category.icon_key(), |k| k)
+    }
+}
@@ -0,0 +1,144 @@
+//! Whether an ailment can be applied to a Pokémon, combining the stacking
+//! rules, Safeguard and Substitute, and type and ability immunities.
+
+use crate::Ability;
+use crate::moves::{Ailment, Flags};
+use crate::Type;
+use super::Monster;
+
+/// What protects the target of an ailment, and how the ailment is inflicted.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FieldCtx {
+    /// Whether Safeguard protects the target's side.
+    pub safeguard: bool,
+    /// Whether the target is behind a substitute.
+    pub substitute: bool,
+    /// The flags of the move inflicting the ailment. Moves with
+    /// `Flags::AUTHENTIC` ignore substitutes.
+    pub flags: Flags,
+    /// Whether the Pokémon inflicts the ailment on itself, as with Rest.
+    /// Safeguard and substitutes do not prevent this.
+    pub self_inflicted: bool,
+}
+
+/// The result of `try_apply`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ApplyResult {
+    /// The ailment was applied.
+    Applied,
+    /// The Pokémon already has the ailment or, for a non-volatile ailment,
+    /// another non-volatile ailment.
+    AlreadyAffected(Ailment),
+    /// One of the Pokémon's types is immune to the ailment.
+    TypeImmune(Type),
+    /// The Pokémon's ability prevents the ailment.
+    AbilityImmune(Ability),
+    /// Safeguard protects the Pokémon's side.
+    Safeguard,
+    /// The Pokémon's substitute blocked the ailment.
+    Substitute,
+    /// The Pokémon has fainted, the ailment's conditions are not met (such as
+    /// Nightmare on a Pokémon that is awake), or the ailment is not one that
+    /// can be applied (`None` or `Unknown`).
+    Failed,
+}
+
+impl ApplyResult {
+    pub fn is_applied(self) -> bool {
+        self == ApplyResult::Applied
+    }
+}
+
+/// The types immune to an ailment.
+fn immune_types(ailment: Ailment) -> &'static [Type] {
+    match ailment {
+        Ailment::Burn => &[Type::Fire],
+        Ailment::Freeze => &[Type::Ice],
+        Ailment::Poison => &[Type::Poison, Type::Steel],
+        Ailment::LeechSeed => &[Type::Grass],
+        _ => &[],
+    }
+}
+
+/// True if an ability prevents an ailment.
+fn ability_prevents(ability: Ability, ailment: Ailment) -> bool {
+    match ability {
+        Ability::Limber => ailment == Ailment::Paralysis,
+        Ability::Insomnia | Ability::VitalSpirit => {
+            ailment == Ailment::Sleep || ailment == Ailment::Yawn
+        },
+        Ability::MagmaArmor => ailment == Ailment::Freeze,
+        Ability::WaterVeil => ailment == Ailment::Burn,
+        Ability::Immunity => ailment == Ailment::Poison,
+        Ability::OwnTempo => ailment == Ailment::Confusion,
+        Ability::Oblivious => ailment == Ailment::Infatuation,
+        _ => false,
+    }
+}
+
+/// True if Safeguard prevents an ailment: any non-volatile ailment,
+/// confusion, or Yawn.
+fn safeguard_prevents(ailment: Ailment) -> bool {
+    !ailment.volatile() || ailment == Ailment::Confusion
+        || ailment == Ailment::Yawn
+}
+
+/// Try to apply an ailment to a Pokémon, with Generation V rules.
+///
+/// The checks are made in this order:
+///
+/// 1. A fainted Pokémon cannot be afflicted, and Nightmare only affects a
+///    sleeping Pokémon.
+/// 2. A substitute blocks ailments from moves without `Flags::AUTHENTIC`.
+/// 3. A Pokémon has at most one non-volatile ailment, and each volatile
+///    ailment at most once.
+/// 4. Fire types cannot be burned, Ice types frozen, Poison and Steel types
+///    poisoned, or Grass types seeded.
+/// 5. Limber, Insomnia, Vital Spirit, Magma Armor, Water Veil, Immunity, Own
+///    Tempo, and Oblivious prevent their ailments.
+/// 6. Safeguard prevents non-volatile ailments, confusion, and Yawn.
+///
+/// A non-volatile ailment is stored in `Monster::ailment` and a volatile one
+/// in `Monster::volatile`. Durations, such as `sleep_turns` and
+/// `bound_turns`, are left to the caller.
+pub fn try_apply(
+    target: &mut Monster, ailment: Ailment, ctx: &FieldCtx
+) -> ApplyResult {
+    match ailment {
+        Ailment::None | Ailment::Unknown => return ApplyResult::Failed,
+        Ailment::Nightmare if target.ailment != Ailment::Sleep => {
+            return ApplyResult::Failed
+        },
+        _ if target.is_fainted() => return ApplyResult::Failed,
+        _ => (),
+    }
+    if ctx.substitute && !ctx.self_inflicted
+            && !ctx.flags.contains(Flags::AUTHENTIC) {
+        return ApplyResult::Substitute;
+    }
+    let volatile = ailment.volatile();
+    if !volatile && target.ailment != Ailment::None {
+        return ApplyResult::AlreadyAffected(target.ailment);
+    }
+    if target.volatile.contains(ailment)
+            || (ailment == Ailment::Trap && target.bound_turns > 0) {
+        return ApplyResult::AlreadyAffected(ailment);
+    }
+    let immune = immune_types(ailment).iter().cloned()
+        .find(|&t| target.types.contains(t));
+    if let Some(typ) = immune {
+        return ApplyResult::TypeImmune(typ);
+    }
+    if ability_prevents(target.ability, ailment) {
+        return ApplyResult::AbilityImmune(target.ability);
+    }
+    if ctx.safeguard && !ctx.self_inflicted && safeguard_prevents(ailment) {
+        return ApplyResult::Safeguard;
+    }
+    if volatile {
+        target.volatile.insert(ailment);
+    } else {
+        target.ailment = ailment;
+    }
+    ApplyResult::Applied
+}
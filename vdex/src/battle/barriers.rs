@@ -0,0 +1,141 @@
+//! Barriers between moves and their targets: substitutes, Reflect, and Light
+//! Screen.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::moves::{DamageClass, Effect, Flags, Move};
+use super::Monster;
+
+/// The number of turns Reflect and Light Screen last.
+pub const SCREEN_TURNS: u8 = 5;
+
+/// The number of turns Reflect and Light Screen last when set by a Pokémon
+/// holding Light Clay.
+pub const LIGHT_CLAY_TURNS: u8 = 8;
+
+/// A screen protecting a side from one class of moves.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Screen {
+    /// Halves the damage of physical moves.
+    Reflect,
+    /// Halves the damage of special moves.
+    LightScreen,
+}
+
+impl Screen {
+    pub const VALUES: [Screen; 2] = [Screen::Reflect, Screen::LightScreen];
+
+    /// The screen a move effect sets up, if any.
+    pub fn from_effect(effect: Effect) -> Option<Self> {
+        match effect {
+            Effect::Reflect => Some(Screen::Reflect),
+            Effect::LightScreen => Some(Screen::LightScreen),
+            _ => None,
+        }
+    }
+
+    /// The class of moves the screen weakens.
+    pub fn class(self) -> DamageClass {
+        match self {
+            Screen::Reflect => DamageClass::Physical,
+            Screen::LightScreen => DamageClass::Special,
+        }
+    }
+}
+
+/// The screens protecting a side, with the turns left of each; 0 if the
+/// screen is down.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Screens {
+    pub reflect: u8,
+    pub light_screen: u8,
+}
+
+impl Screens {
+    pub fn turns(&self, screen: Screen) -> u8 {
+        match screen {
+            Screen::Reflect => self.reflect,
+            Screen::LightScreen => self.light_screen,
+        }
+    }
+
+    fn turns_mut(&mut self, screen: Screen) -> &mut u8 {
+        match screen {
+            Screen::Reflect => &mut self.reflect,
+            Screen::LightScreen => &mut self.light_screen,
+        }
+    }
+
+    /// Put up a screen for a number of turns, returning false if it is
+    /// already up.
+    pub fn start(&mut self, screen: Screen, turns: u8) -> bool {
+        let current = self.turns_mut(screen);
+        if *current > 0 {
+            return false;
+        }
+        *current = turns;
+        true
+    }
+
+    /// True if a screen weakens moves of a damage class.
+    pub fn weakens(&self, class: DamageClass) -> bool {
+        Screen::VALUES.iter().any(|&s| s.class() == class && self.turns(s) > 0)
+    }
+
+    /// Count down the screens at the end of a turn, returning the screens
+    /// that came down.
+    pub fn end_turn(&mut self) -> Vec<Screen> {
+        let mut ended = Vec::new();
+        for &screen in Screen::VALUES.iter() {
+            let turns = self.turns_mut(screen);
+            if *turns > 0 {
+                *turns -= 1;
+                if *turns == 0 {
+                    ended.push(screen);
+                }
+            }
+        }
+        ended
+    }
+}
+
+/// The number of turns a screen put up by a Pokémon lasts, which Light Clay
+/// extends.
+pub fn screen_turns(user: &Monster) -> u8 {
+    match user.item_identifier() {
+        Some("light-clay") => LIGHT_CLAY_TURNS,
+        _ => SCREEN_TURNS,
+    }
+}
+
+/// True if a move ignores substitutes, having `Flags::AUTHENTIC`.
+pub fn bypasses_substitute(mov: &Move) -> bool {
+    mov.meta.flags.contains(Flags::AUTHENTIC)
+}
+
+/// The HP a substitute costs and has: a quarter of the maximum HP.
+pub fn substitute_cost(monster: &Monster) -> u16 {
+    monster.max_hp() / 4
+}
+
+/// Pay HP for a substitute, returning the HP paid, or `None` if the
+/// Pokémon already has a substitute or has too little HP.
+pub fn make_substitute(monster: &mut Monster) -> Option<u16> {
+    let cost = substitute_cost(monster);
+    if monster.substitute_hp > 0 || cost == 0 || monster.hp <= cost {
+        return None;
+    }
+    monster.hp -= cost;
+    monster.substitute_hp = cost;
+    Some(cost)
+}
+
+/// Deal damage to a Pokémon's substitute instead of the Pokémon, returning
+/// the damage it absorbed. The substitute breaks when its HP reaches 0.
+pub fn damage_substitute(monster: &mut Monster, amount: u16) -> u16 {
+    let absorbed = amount.min(monster.substitute_hp);
+    monster.substitute_hp -= absorbed;
+    absorbed
+}
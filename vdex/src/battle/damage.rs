@@ -0,0 +1,219 @@
+use crate::Ability;
+use crate::abilities::{ModifierContext, ModifierKind, Modifiers};
+use crate::items::plate_type;
+use crate::moves::{DamageClass, Effect, Move, Weather, WeatherModifier};
+use crate::moves::Ailment;
+use crate::pokedex;
+use crate::pokemon::OneOrTwo;
+use crate::Stat;
+use crate::Type;
+use super::scripts;
+use super::{BattleState, Monster, Position};
+
+/// The lowest and highest random damage factors, in percent.
+pub const MIN_DAMAGE_ROLL: u8 = 85;
+pub const MAX_DAMAGE_ROLL: u8 = 100;
+
+/// The damage before any modifiers, from Generation III on:
+/// ⌊⌊⌊2 × *level* / 5 + 2⌋ × *power* × *attack* / *defense*⌋ / 50⌋ + 2.
+pub fn base_damage(level: u8, power: u16, attack: u16, defense: u16) -> u32 {
+    let level_factor = 2 * level as u32 / 5 + 2;
+    level_factor * power as u32 * attack as u32 / defense.max(1) as u32 / 50
+        + 2
+}
+
+/// The combined type efficacy modifier of a move type against one or two
+/// types.
+pub fn type_modifier(typ: Type, types: OneOrTwo<Type>) -> f64 {
+    let efficacy = &pokedex().efficacy;
+    let first = efficacy[(typ, types.first())].modifier();
+    let second = types.second().map_or(1.0, |t| efficacy[(typ, t)].modifier());
+    first * second
+}
+
+/// The type of a move used by a Pokémon, as in `effective_move_type` without
+/// weather.
+pub fn move_type(user: &Monster, mov: &Move) -> Type {
+    effective_move_type(mov, user, None)
+}
+
+/// The type of a move used by a Pokémon in a weather, for both damage and
+/// type efficacy, as of Generation V.
+///
+/// Normalize makes every move Normal. Otherwise Judgment takes the type of
+/// the user's held plate, and Weather Ball the type of the weather, as in
+/// `WeatherModifier::Type`.
+pub fn effective_move_type(
+    mov: &Move, user: &Monster, weather: Option<Weather>
+) -> Type {
+    if user.ability == Ability::Normalize {
+        return Type::Normal;
+    }
+    if mov.effect == Effect::PlateDriveType {
+        let item = user.item.and_then(|id| pokedex().items.0.get(&id));
+        if let Some(typ) = item.and_then(plate_type) {
+            return typ;
+        }
+    }
+    weather.into_iter()
+        .flat_map(|weather| mov.effect.modifiers_in(weather))
+        .find_map(|modifier| match modifier {
+            WeatherModifier::Type(typ) => Some(typ),
+            _ => None,
+        })
+        .unwrap_or(mov.typ)
+}
+
+/// The factor the weather multiplies the damage of a move of a type by, as
+/// a fraction: 3/2 for Water moves in rain and Fire moves in sun, 1/2 for
+/// Fire moves in rain and Water moves in sun, and 1 otherwise.
+pub fn weather_multiplier(weather: Option<Weather>, typ: Type) -> (u32, u32) {
+    match (weather, typ) {
+        (Some(Weather::Rain), Type::Water) | (Some(Weather::Sun), Type::Fire)
+            => (3, 2),
+        (Some(Weather::Rain), Type::Fire) | (Some(Weather::Sun), Type::Water)
+            => (1, 2),
+        _ => (1, 1),
+    }
+}
+
+/// The same-type attack bonus of a move of a type used by a Pokémon of some
+/// types, as a fraction: 3/2 if the move shares a type with the user, or 2
+/// with Adaptability, and 1 otherwise.
+pub fn stab_multiplier(
+    user_types: OneOrTwo<Type>, move_type: Type, adaptability: bool
+) -> (u32, u32) {
+    if !user_types.contains(move_type) {
+        (1, 1)
+    } else if adaptability {
+        (2, 1)
+    } else {
+        (3, 2)
+    }
+}
+
+/// The chance of a critical hit at a critical hit stage, as a fraction, in
+/// Generations III to V.
+pub fn critical_chance(stage: u8) -> (u32, u32) {
+    match stage {
+        0 => (1, 16),
+        1 => (1, 8),
+        2 => (1, 4),
+        3 => (1, 3),
+        _ => (1, 2),
+    }
+}
+
+/// Calculate the damage of a move from one Pokémon to another, with the
+/// Generation V formula.
+///
+/// `roll` is the random factor in percent, from `MIN_DAMAGE_ROLL` to
+/// `MAX_DAMAGE_ROLL`. A critical hit doubles the damage and ignores the
+/// attacker's negative and the defender's positive stat stages. Reflect and
+/// Light Screen over the defender's side halve the damage of other hits.
+/// Returns 0 if the move does not deal regular damage or the defender is
+/// immune. The power is that of `move_power`.
+pub fn calc_damage(
+    state: &BattleState, attacker: Position, defender: Position, mov: &Move,
+    critical: bool, roll: u8
+) -> u16 {
+    let power = move_power(state, attacker, defender, mov);
+    calc_damage_with_power(
+        state, attacker, defender, mov, power, critical, roll)
+}
+
+/// The power of a move from one Pokémon to another: the move's power, or for
+/// moves whose power depends on HP, like Flail, the power determined by
+/// `Effect::hp_scaling`.
+pub fn move_power(
+    state: &BattleState, attacker: Position, defender: Position, mov: &Move
+) -> u16 {
+    match mov.effect.hp_scaling() {
+        Some(scaling) => {
+            let user = state.monster(attacker);
+            let target = state.monster(defender);
+            scaling.power(mov.power, (user.hp, user.max_hp()),
+                          (target.hp, target.max_hp()))
+        },
+        None => mov.power as u16,
+    }
+}
+
+/// Calculate damage like `calc_damage`, but with a power determined by the
+/// battle state, e.g., for each strike of Beat Up.
+///
+/// The weather changes the power of moves like Weather Ball and SolarBeam as
+/// in `WeatherModifier::Power`, along with their type as in
+/// `effective_move_type`, and boosts or weakens Water and Fire moves as in
+/// `weather_multiplier`.
+pub fn calc_damage_with_power(
+    state: &BattleState, attacker: Position, defender: Position, mov: &Move,
+    power: u16, critical: bool, roll: u8
+) -> u16 {
+    let user = state.monster(attacker);
+    let target = state.monster(defender);
+    let class = mov.damage_class_in(state.generation);
+    let (attack_stat, defense_stat) = match class {
+        DamageClass::Physical => (Stat::Attack, Stat::Defense),
+        DamageClass::Special => (Stat::SpecialAttack, Stat::SpecialDefense),
+        DamageClass::NonDamaging => return 0,
+    };
+    if power == 0 {
+        return 0;
+    }
+    let typ = effective_move_type(mov, user, state.weather);
+    let power = state.weather.into_iter()
+        .flat_map(|weather| mov.effect.modifiers_in(weather))
+        .fold(power, |power, modifier| match modifier {
+            WeatherModifier::Power(numerator, denominator)
+                => power * numerator as u16 / denominator as u16,
+            _ => power,
+        });
+    let type_factor = type_modifier(typ, target.types);
+    if type_factor == 0.0 {
+        return 0;
+    }
+    let user_ctx = ModifierContext {
+        move_type: Some(typ), power, type_factor,
+        .. ModifierContext::new(user, state.weather).with_move(mov)
+    };
+    let target_ctx = ModifierContext { holder: target, .. user_ctx };
+    let damage_factor = Modifiers::factor(ModifierKind::Damage, &target_ctx);
+    if damage_factor == 0.0 {
+        return 0;
+    }
+    let attack = match user.staged_stat(attack_stat) {
+        staged if critical => staged.max(user.stat(attack_stat)),
+        staged => staged,
+    };
+    let attack = Modifiers::apply(
+        ModifierKind::Stat(attack_stat), &user_ctx, attack.into()) as u16;
+    let defense = match target.staged_stat(defense_stat) {
+        staged if critical => staged.min(target.stat(defense_stat)),
+        staged => staged,
+    };
+    let defense = Modifiers::apply(
+        ModifierKind::Stat(defense_stat), &target_ctx, defense.into()) as u16;
+    let power = Modifiers::apply(ModifierKind::Power, &user_ctx, power.into());
+    let power = (power as f64 * scripts::power_factor(mov.effect, &user_ctx)) as u32;
+    let mut damage = base_damage(user.level, power as u16, attack, defense);
+    let (numerator, denominator) = weather_multiplier(state.weather, typ);
+    damage = damage * numerator / denominator;
+    if critical {
+        damage *= 2;
+    }
+    damage = damage * roll as u32 / 100;
+    let adaptability = Modifiers::factor(ModifierKind::Stab, &user_ctx) > 1.0;
+    let (numerator, denominator) =
+        stab_multiplier(user.types, typ, adaptability);
+    damage = damage * numerator / denominator;
+    damage = (damage as f64 * type_factor * damage_factor) as u32;
+    if user.ailment == Ailment::Burn && attack_stat == Stat::Attack
+            && user.ability != Ability::Guts {
+        damage /= 2;
+    }
+    if !critical && state.sides[defender.side as usize].screens.weakens(class) {
+        damage /= 2;
+    }
+    damage.clamp(1, u16::MAX as u32) as u16
+}
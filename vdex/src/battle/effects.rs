@@ -0,0 +1,97 @@
+//! Handlers for move effects whose damage depends on the battle state rather
+//! than on power alone.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::moves::{Ailment, DamageClass, Effect, MoveId};
+use crate::pokedex;
+use crate::pokemon::SPECIES_COUNT;
+use crate::Stat;
+use super::{BattleState, Position};
+
+/// The powers of the three kicks of Triple Kick, each of which checks
+/// accuracy separately.
+pub const TRIPLE_KICK_POWERS: [u16; 3] = [10, 20, 30];
+
+/// The number of turns Bide stores energy before unleashing it.
+pub const BIDE_TURNS: u8 = 2;
+
+/// The last damage a Pokémon took from a move this turn.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DamageTaken {
+    pub amount: u16,
+    /// The class of the move, in the battle's generation.
+    pub class: DamageClass,
+    pub from: Position,
+}
+
+/// A Pokémon storing energy with Bide.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Bide {
+    /// The move that started Bide.
+    pub move_id: MoveId,
+    /// The turns left before the energy is unleashed.
+    pub turns: u8,
+    /// The damage taken since Bide started.
+    pub damage: u16,
+    /// The last Pokémon that damaged the user since Bide started.
+    pub from: Option<Position>,
+}
+
+impl Bide {
+    pub fn new(move_id: MoveId) -> Self {
+        Bide { move_id, turns: BIDE_TURNS, damage: 0, from: None }
+    }
+
+    /// The target and damage of the unleashed energy (twice the damage
+    /// taken), or `None` if Bide fails because no damage was taken.
+    pub fn unleash(&self) -> Option<(Position, u16)> {
+        match self.from {
+            Some(from) if self.damage > 0
+                => Some((from, self.damage.saturating_mul(2))),
+            _ => None,
+        }
+    }
+}
+
+/// The target and damage of Counter, Mirror Coat, or Metal Burst, or `None`
+/// if the move fails.
+///
+/// Counter and Mirror Coat return twice the last physical or special damage
+/// taken from an opponent this turn; Metal Burst returns 1.5 times the last
+/// damage of either class.
+pub fn counter_damage(
+    state: &BattleState, user: Position, effect: Effect
+) -> Option<(Position, u16)> {
+    let taken = state.monster(user).damage_taken?;
+    if taken.from.side == user.side {
+        return None;
+    }
+    let damage = match (effect, taken.class) {
+        (Effect::Counter, DamageClass::Physical)
+        | (Effect::MirrorCoat, DamageClass::Special)
+            => taken.amount as u32 * 2,
+        (Effect::MetalBurst, DamageClass::Physical)
+        | (Effect::MetalBurst, DamageClass::Special)
+            => taken.amount as u32 * 3 / 2,
+        _ => return None,
+    };
+    Some((taken.from, damage.min(u16::MAX as u32) as u16))
+}
+
+/// The powers of the strikes of Beat Up, from Generation V on: one strike
+/// for each party member that is not fainted and has no non-volatile
+/// ailment, with power ⌊*base Attack* / 10⌋ + 5.
+///
+/// The base Attack is that of the party member's species' default Pokémon.
+pub fn beat_up_powers(state: &BattleState, user: Position) -> Vec<u16> {
+    let species = &pokedex().species;
+    state.sides[user.side as usize].party.iter()
+        .filter(|m| !m.is_fainted() && m.ailment == Ailment::None)
+        .filter(|m| (m.species.0 as usize) < SPECIES_COUNT)
+        .filter_map(|m| species.pokemon(m.species).first())
+        .map(|p| p.stats[Stat::Attack] as u16 / 10 + 5)
+        .collect()
+}
@@ -0,0 +1,164 @@
+//! Battle events, for exchanging battle logs between engines and replay or
+//! analysis tools.
+//!
+//! A battle log is a sequence of events in the order they happened. With the
+//! `serde` feature, events serialize as maps with an `"event"` tag naming the
+//! kind of event, e.g. `{"event":"Fainted","target":{"side":1,"slot":0}}`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::items::ItemId;
+use crate::moves::{Ailment, MoveId, Weather};
+use crate::Stat;
+use super::barriers::Screen;
+use super::hazards::Hazard;
+use super::Position;
+
+/// Something that happened in a battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "event"))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Event {
+    /// A Pokémon used a move, before any of its effects.
+    MoveUsed {
+        user: Position,
+        move_id: MoveId,
+        /// The targeted Pokémon, if the move targets a single Pokémon.
+        target: Option<Position>,
+    },
+    /// A Pokémon could not move because it flinched.
+    Flinched {
+        target: Position,
+    },
+    /// A Pokémon could not move because of its ailment.
+    Immobilized {
+        target: Position,
+        ailment: Ailment,
+    },
+    /// A move failed, e.g., Counter without damage to return.
+    MoveFailed {
+        user: Position,
+        move_id: MoveId,
+    },
+    /// A move missed its target.
+    MoveMissed {
+        user: Position,
+        move_id: MoveId,
+        target: Position,
+    },
+    /// A Pokémon lost HP.
+    DamageDealt {
+        target: Position,
+        /// The HP lost, which is no more than the HP the target had.
+        amount: u16,
+        /// The target's remaining HP.
+        hp: u16,
+        critical: bool,
+    },
+    /// A Pokémon recovered HP.
+    Healed {
+        target: Position,
+        amount: u16,
+        /// The target's HP after healing.
+        hp: u16,
+    },
+    /// A Pokémon's stat stage changed.
+    StatStageChanged {
+        target: Position,
+        stat: Stat,
+        /// The actual change, after clamping to the stage limits.
+        change: i8,
+        /// The new stage.
+        stage: i8,
+    },
+    /// A Pokémon was afflicted with an ailment.
+    AilmentInflicted {
+        target: Position,
+        ailment: Ailment,
+    },
+    /// A Pokémon's ailment ended.
+    AilmentCured {
+        target: Position,
+        ailment: Ailment,
+    },
+    /// A held item was used up.
+    ItemConsumed {
+        holder: Position,
+        item: ItemId,
+    },
+    /// A Pokémon fainted.
+    Fainted {
+        target: Position,
+    },
+    /// A Pokémon was switched in.
+    SwitchedIn {
+        target: Position,
+    },
+    /// A Pokémon paid HP to make a substitute.
+    SubstituteMade {
+        target: Position,
+        /// The HP of the substitute.
+        hp: u16,
+    },
+    /// A Pokémon's substitute took damage in its place.
+    SubstituteDamaged {
+        target: Position,
+        amount: u16,
+        /// The substitute's remaining HP; 0 if it broke.
+        hp: u16,
+    },
+    /// A Pokémon put up a screen over its side.
+    ScreenStarted {
+        user: Position,
+        screen: Screen,
+        turns: u8,
+    },
+    /// A Pokémon laid a layer of an entry hazard on the opposing side.
+    HazardSet {
+        user: Position,
+        hazard: Hazard,
+        /// The layers of the hazard after this one.
+        layers: u8,
+    },
+    /// A Pokémon changed the weather.
+    WeatherChanged {
+        user: Position,
+        weather: Weather,
+    },
+    /// A screen over the side of a Pokémon came down.
+    ScreenEnded {
+        /// The active Pokémon of the side.
+        target: Position,
+        screen: Screen,
+    },
+}
+
+impl Event {
+    /// The Pokémon the event mainly concerns: the user of a move, or the
+    /// affected Pokémon otherwise.
+    pub fn subject(&self) -> Position {
+        match *self {
+            Event::MoveUsed { user, .. }
+            | Event::MoveFailed { user, .. }
+            | Event::MoveMissed { user, .. }
+            | Event::ScreenStarted { user, .. }
+            | Event::HazardSet { user, .. }
+            | Event::WeatherChanged { user, .. }
+                => user,
+            Event::Flinched { target }
+            | Event::Immobilized { target, .. }
+            | Event::DamageDealt { target, .. }
+            | Event::Healed { target, .. }
+            | Event::StatStageChanged { target, .. }
+            | Event::AilmentInflicted { target, .. }
+            | Event::AilmentCured { target, .. }
+            | Event::Fainted { target }
+            | Event::SwitchedIn { target }
+            | Event::SubstituteMade { target, .. }
+            | Event::SubstituteDamaged { target, .. }
+            | Event::ScreenEnded { target, .. }
+                => target,
+            Event::ItemConsumed { holder, .. } => holder,
+        }
+    }
+}
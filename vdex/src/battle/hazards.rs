@@ -0,0 +1,150 @@
+//! Entry hazards: Stealth Rock, Spikes, and Toxic Spikes, which affect
+//! Pokémon switching in on a side.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::Ability;
+use crate::moves::{Ailment, Effect};
+use crate::Type;
+use super::ailments::{try_apply, FieldCtx};
+use super::damage::type_modifier;
+use super::trapping::is_grounded;
+use super::Monster;
+
+/// The most layers of Spikes a side can have.
+pub const MAX_SPIKES: u8 = 3;
+
+/// The most layers of Toxic Spikes a side can have.
+pub const MAX_TOXIC_SPIKES: u8 = 2;
+
+/// An entry hazard.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Hazard {
+    /// Damages Pokémon switching in by 1/8 of their max HP, scaled by the
+    /// efficacy of Rock against them.
+    StealthRock,
+    /// Damages grounded Pokémon switching in by 1/8, 1/6, or 1/4 of their max
+    /// HP for one, two, or three layers.
+    Spikes,
+    /// Poisons grounded Pokémon switching in, badly with two layers.
+    ToxicSpikes,
+}
+
+impl Hazard {
+    /// The hazard a move effect lays on the opposing side, if any.
+    pub fn from_effect(effect: Effect) -> Option<Self> {
+        match effect {
+            Effect::StealthRock => Some(Hazard::StealthRock),
+            Effect::Spikes => Some(Hazard::Spikes),
+            Effect::ToxicSpokes => Some(Hazard::ToxicSpikes),
+            _ => None,
+        }
+    }
+}
+
+/// The entry hazards on a side.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct HazardState {
+    pub stealth_rock: bool,
+    /// The layers of Spikes, up to `MAX_SPIKES`.
+    pub spikes: u8,
+    /// The layers of Toxic Spikes, up to `MAX_TOXIC_SPIKES`.
+    pub toxic_spikes: u8,
+}
+
+impl HazardState {
+    /// The layers of a hazard; Stealth Rock has at most one.
+    pub fn layers(&self, hazard: Hazard) -> u8 {
+        match hazard {
+            Hazard::StealthRock => self.stealth_rock as u8,
+            Hazard::Spikes => self.spikes,
+            Hazard::ToxicSpikes => self.toxic_spikes,
+        }
+    }
+
+    /// Add a layer of a hazard, returning false if it has the most layers
+    /// already.
+    pub fn add(&mut self, hazard: Hazard) -> bool {
+        let (layers, max) = match hazard {
+            Hazard::StealthRock => {
+                let added = !self.stealth_rock;
+                self.stealth_rock = true;
+                return added;
+            },
+            Hazard::Spikes => (&mut self.spikes, MAX_SPIKES),
+            Hazard::ToxicSpikes => (&mut self.toxic_spikes, MAX_TOXIC_SPIKES),
+        };
+        if *layers >= max {
+            return false;
+        }
+        *layers += 1;
+        true
+    }
+
+    /// Remove every hazard, as with Rapid Spin.
+    pub fn clear(&mut self) {
+        *self = Default::default();
+    }
+}
+
+/// The damage a Pokémon takes from Stealth Rock and Spikes when it switches
+/// in, with Generation V rules.
+///
+/// Magic Guard prevents the damage, and Spikes only damage grounded Pokémon
+/// (see `trapping::is_grounded`). Each hazard deals at least 1 HP if it
+/// applies.
+pub fn entry_damage(mon: &Monster, hazards: &HazardState) -> u16 {
+    if mon.ability == Ability::MagicGuard {
+        return 0;
+    }
+    let max_hp = mon.max_hp() as u32;
+    let mut damage = 0;
+    if hazards.stealth_rock {
+        let modifier = type_modifier(Type::Rock, mon.types);
+        damage += ((max_hp as f64 * modifier / 8.0) as u32).max(1);
+    }
+    if hazards.spikes > 0 && is_grounded(mon) {
+        let denominator = match hazards.spikes {
+            1 => 8,
+            2 => 6,
+            _ => 4,
+        };
+        damage += (max_hp / denominator).max(1);
+    }
+    damage.min(mon.hp as u32) as u16
+}
+
+/// What Toxic Spikes did to a Pokémon switching in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ToxicSpikesEffect {
+    /// The Pokémon was not affected.
+    None,
+    /// A grounded Poison-type Pokémon removed the Toxic Spikes.
+    Absorbed,
+    /// The Pokémon was poisoned, badly with two layers.
+    Poisoned { badly: bool },
+}
+
+/// Apply Toxic Spikes to a Pokémon switching in, with Generation V rules.
+///
+/// Only grounded Pokémon are affected. Poison types absorb the spikes, and
+/// otherwise the poisoning follows `ailments::try_apply`, so Steel types and
+/// Pokémon already with an ailment are not poisoned.
+pub fn toxic_spikes(
+    mon: &mut Monster, hazards: &mut HazardState
+) -> ToxicSpikesEffect {
+    if hazards.toxic_spikes == 0 || !is_grounded(mon) {
+        return ToxicSpikesEffect::None;
+    }
+    if mon.types.contains(Type::Poison) {
+        hazards.toxic_spikes = 0;
+        return ToxicSpikesEffect::Absorbed;
+    }
+    if try_apply(mon, Ailment::Poison, &FieldCtx::default()).is_applied() {
+        ToxicSpikesEffect::Poisoned { badly: hazards.toxic_spikes > 1 }
+    } else {
+        ToxicSpikesEffect::None
+    }
+}
@@ -0,0 +1,85 @@
+//! The battle effects of held items that restrict or boost moves: Choice
+//! items, which lock their holder into one move; items like the Assault
+//! Vest, which forbid non-damaging moves; and gems, which boost a move of
+//! their type once and are used up.
+//!
+//! `restrictions::allowed_moves` applies the restrictions of held items
+//! together with those of moves, and `turn::resolve_turn` consumes gems as
+//! in `gem_boost`.
+
+use crate::items::Item;
+use crate::moves::{Category, DamageClass, Effect, Move, Weather};
+use crate::pokedex;
+use crate::{Stat, Type};
+use super::damage::effective_move_type;
+use super::Monster;
+
+/// The factor gems multiply the power of moves by in Generation V.
+pub const GEM_BOOST: (u16, u16) = (3, 2);
+
+/// A battle effect of a held item on moves.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HeldEffect {
+    /// The item boosts a stat by half and locks its holder into the first
+    /// move it uses after switching in, as with Choice Band.
+    Choice(Stat),
+    /// The item prevents non-damaging moves from being selected, as with
+    /// the Assault Vest from Generation VI.
+    DamagingOnly,
+    /// The item boosts the power of the first damaging move of its type that
+    /// hits, and is then used up.
+    Gem(Type),
+}
+
+impl HeldEffect {
+    /// The effect of an item on moves, if any.
+    pub fn of_item(item: &Item) -> Option<Self> {
+        match item.identifier.as_str() {
+            "choice-band" => Some(HeldEffect::Choice(Stat::Attack)),
+            "choice-specs" => Some(HeldEffect::Choice(Stat::SpecialAttack)),
+            "choice-scarf" => Some(HeldEffect::Choice(Stat::Speed)),
+            "assault-vest" => Some(HeldEffect::DamagingOnly),
+            _ => item.as_jewel_type().map(HeldEffect::Gem),
+        }
+    }
+
+    /// The effect of a Pokémon's held item on moves, if any.
+    pub fn of(monster: &Monster) -> Option<Self> {
+        monster.item
+            .and_then(|id| pokedex().items.0.get(&id))
+            .and_then(Self::of_item)
+    }
+
+    /// True if the effect allows a move to be selected, before any Choice
+    /// lock, which depends on the moves used (see `restrictions`).
+    pub fn allows(self, mov: &Move) -> bool {
+        self != HeldEffect::DamagingOnly
+            || mov.damage_class != DamageClass::NonDamaging
+    }
+}
+
+/// The type of gem a Pokémon would use up on a move, if any.
+///
+/// A gem boosts a move of its type in the weather, as in
+/// `damage::effective_move_type`, that deals regular damage: not a move with
+/// fixed or variable damage like Seismic Toss, a one-hit KO, Fling, Struggle,
+/// or a Pledge move. It is used up
+/// once the move hits and just before its damage is calculated, so a move
+/// that misses or that the target is immune to keeps it, and only the first
+/// strike of a move that strikes several times is boosted.
+pub fn gem_boost(
+    user: &Monster, mov: &Move, weather: Option<Weather>
+) -> Option<Type> {
+    let typ = match HeldEffect::of(user) {
+        Some(HeldEffect::Gem(typ)) => typ,
+        _ => return None,
+    };
+    let excluded = matches!(mov.effect,
+        Effect::Fling | Effect::Struggle | Effect::WaterPledge
+        | Effect::FirePledge | Effect::GrassPledge);
+    let boosted = typ == effective_move_type(mov, user, weather)
+        && mov.power > 1 && !excluded
+        && mov.damage_class != DamageClass::NonDamaging
+        && mov.meta.category != Category::OneHitKO;
+    boosted.then_some(typ)
+}
@@ -0,0 +1,37 @@
+//! Building blocks for battle engines.
+
+pub mod ailments;
+pub mod barriers;
+pub mod damage;
+pub mod effects;
+pub mod events;
+pub mod hazards;
+pub mod held;
+pub mod restrictions;
+pub mod scripts;
+pub mod trapping;
+pub mod two_turn;
+pub(self) mod rng;
+pub(self) mod state;
+pub(self) mod transform;
+pub(self) mod turn;
+
+pub use self::events::Event;
+pub use self::rng::BattleRng;
+pub use self::state::{BattleState, Monster, MoveSlot, Side, StatStages};
+pub use self::state::{SavedBattle, STATE_SCHEMA_VERSION};
+pub use self::state::{MAX_STAGE, MIN_STAGE};
+pub use self::transform::{transform, TransformSnapshot, TRANSFORM_PP};
+pub use self::turn::{resolve_turn, Action};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The position of a Pokémon in a battle: its side and its slot in that
+/// side's party.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub side: u8,
+    pub slot: u8,
+}
@@ -0,0 +1,273 @@
+//! Which moves a Pokémon may select, combining Taunt, Encore, Disable,
+//! Torment, Imprison, Heal Block, and the held items of `held`, like Choice
+//! items.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::moves::{Ailment, DamageClass, Effect, Flags, MoveId};
+use crate::pokedex;
+use super::held::HeldEffect;
+use super::Monster;
+
+/// The number of turns Disable lasts.
+pub const DISABLE_TURNS: u8 = 4;
+
+/// The number of turns Encore lasts.
+pub const ENCORE_TURNS: u8 = 3;
+
+/// The number of turns Taunt lasts.
+pub const TAUNT_TURNS: u8 = 3;
+
+/// The number of turns Heal Block lasts.
+pub const HEAL_BLOCK_TURNS: u8 = 5;
+
+/// A restriction on which moves a Pokémon may select.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Restriction {
+    /// Non-damaging moves cannot be selected.
+    Taunt,
+    /// Only the last move used can be selected.
+    Encore,
+    /// The last move used cannot be selected.
+    Disable,
+    /// The same move cannot be selected twice in a row.
+    Torment,
+    /// Opponents cannot select moves the Pokémon knows.
+    Imprison,
+    /// Healing moves cannot be selected.
+    HealBlock,
+}
+
+impl Restriction {
+    /// The restriction a move effect imposes on its target, if any.
+    pub fn from_effect(effect: Effect) -> Option<Self> {
+        match effect {
+            Effect::Taunt => Some(Restriction::Taunt),
+            Effect::Encore => Some(Restriction::Encore),
+            Effect::Disable => Some(Restriction::Disable),
+            Effect::Torment => Some(Restriction::Torment),
+            Effect::Imprison => Some(Restriction::Imprison),
+            Effect::HealBlock => Some(Restriction::HealBlock),
+            _ => None,
+        }
+    }
+
+    /// The restriction of an ailment, if any.
+    pub fn from_ailment(ailment: Ailment) -> Option<Self> {
+        match ailment {
+            Ailment::Disable => Some(Restriction::Disable),
+            Ailment::Torment => Some(Restriction::Torment),
+            Ailment::HealBlock => Some(Restriction::HealBlock),
+            _ => None,
+        }
+    }
+
+    /// The ailment of the restriction, if any.
+    pub fn ailment(self) -> Option<Ailment> {
+        match self {
+            Restriction::Disable => Some(Ailment::Disable),
+            Restriction::Torment => Some(Ailment::Torment),
+            Restriction::HealBlock => Some(Ailment::HealBlock),
+            _ => None,
+        }
+    }
+}
+
+/// A move restricted for a number of turns.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TimedMove {
+    pub move_id: MoveId,
+    /// The turns left, counting the current one.
+    pub turns: u8,
+}
+
+/// The restrictions on a Pokémon in battle, all lifted when it leaves the
+/// field.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Restrictions {
+    /// The last move the Pokémon used.
+    pub last_move: Option<MoveId>,
+    /// The turns of Taunt left.
+    pub taunt_turns: u8,
+    pub encore: Option<TimedMove>,
+    pub disabled: Option<TimedMove>,
+    pub tormented: bool,
+    /// Whether the Pokémon used Imprison.
+    pub imprisoning: bool,
+    /// The turns of Heal Block left.
+    pub heal_block_turns: u8,
+    /// The move a Choice item locks the Pokémon into.
+    pub choice_lock: Option<MoveId>,
+}
+
+impl Restrictions {
+    /// True if the Pokémon is under a restriction.
+    pub fn has(&self, restriction: Restriction) -> bool {
+        match restriction {
+            Restriction::Taunt => self.taunt_turns > 0,
+            Restriction::Encore => self.encore.is_some(),
+            Restriction::Disable => self.disabled.is_some(),
+            Restriction::Torment => self.tormented,
+            Restriction::Imprison => self.imprisoning,
+            Restriction::HealBlock => self.heal_block_turns > 0,
+        }
+    }
+}
+
+/// The move slots a Pokémon may select: bit *i* is set if slot *i* is
+/// allowed.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MoveMask(pub u8);
+
+impl MoveMask {
+    pub fn contains(self, slot: usize) -> bool {
+        slot < 8 && self.0 & (1 << slot) != 0
+    }
+
+    /// True if no slot is allowed, in which case the Pokémon uses Struggle.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The allowed slots, in order.
+    pub fn slots(self) -> impl Iterator<Item = usize> {
+        (0..8).filter(move |&slot| self.contains(slot))
+    }
+}
+
+fn holds_choice_item(monster: &Monster) -> bool {
+    matches!(HeldEffect::of(monster), Some(HeldEffect::Choice(_)))
+}
+
+/// Check which moves a Pokémon may select, with Generation V rules, given
+/// the opposing active Pokémon.
+///
+/// A move cannot be selected without PP, while disabled, while another move
+/// is encored, right after using it while tormented, or if an opponent that
+/// used Imprison knows it. Taunt prevents non-damaging moves, Heal Block
+/// prevents healing moves, and a held Choice item prevents every move but the
+/// first one used since switching in. Other held items restrict moves as in
+/// `HeldEffect::allows`.
+pub fn allowed_moves(monster: &Monster, opponents: &[&Monster]) -> MoveMask {
+    let restrictions = &monster.restrictions;
+    let held = HeldEffect::of(monster);
+    let choice_lock = restrictions.choice_lock
+        .filter(|_| matches!(held, Some(HeldEffect::Choice(_))));
+    let mut mask = MoveMask(0);
+    for (i, slot) in monster.moves.iter().enumerate().take(8) {
+        let id = slot.id;
+        let mov = &pokedex().moves[id];
+        let allowed = slot.pp > 0
+            && restrictions.disabled.is_none_or(|d| d.move_id != id)
+            && restrictions.encore.is_none_or(|e| e.move_id == id)
+            && !(restrictions.tormented && restrictions.last_move == Some(id))
+            && !(restrictions.taunt_turns > 0
+                 && mov.damage_class == DamageClass::NonDamaging)
+            && !(restrictions.heal_block_turns > 0
+                 && mov.meta.flags.contains(Flags::HEAL))
+            && choice_lock.is_none_or(|lock| lock == id)
+            && held.is_none_or(|held| held.allows(mov))
+            && !opponents.iter().any(|o| o.restrictions.imprisoning
+                && o.moves.iter().any(|m| m.id == id));
+        if allowed {
+            mask.0 |= 1 << i;
+        }
+    }
+    mask
+}
+
+/// Record that a Pokémon used a move, for Encore, Disable, Torment, and
+/// Choice items.
+pub fn record_move(monster: &mut Monster, move_id: MoveId) {
+    monster.restrictions.last_move = Some(move_id);
+    if monster.restrictions.choice_lock.is_none() && holds_choice_item(monster) {
+        monster.restrictions.choice_lock = Some(move_id);
+    }
+}
+
+/// Impose a restriction on a Pokémon, returning false if the move imposing
+/// it fails.
+///
+/// A restriction fails if it is already imposed. Encore and Disable also fail
+/// if the Pokémon has not used a move with PP left, and Encore fails on
+/// Encore itself. The ailment of the restriction, if any, is added to
+/// `Monster::volatile`.
+pub fn impose(monster: &mut Monster, restriction: Restriction) -> bool {
+    if monster.is_fainted() || monster.restrictions.has(restriction) {
+        return false;
+    }
+    let last_move = monster.restrictions.last_move.filter(|&id| {
+        monster.moves.iter().any(|slot| slot.id == id && slot.pp > 0)
+    });
+    let restrictions = &mut monster.restrictions;
+    match restriction {
+        Restriction::Taunt => restrictions.taunt_turns = TAUNT_TURNS,
+        Restriction::Encore => match last_move {
+            Some(move_id) if pokedex().moves[move_id].effect != Effect::Encore
+                => {
+                restrictions.encore
+                    = Some(TimedMove { move_id, turns: ENCORE_TURNS });
+            },
+            _ => return false,
+        },
+        Restriction::Disable => match last_move {
+            Some(move_id) => {
+                restrictions.disabled
+                    = Some(TimedMove { move_id, turns: DISABLE_TURNS });
+            },
+            None => return false,
+        },
+        Restriction::Torment => restrictions.tormented = true,
+        Restriction::Imprison => restrictions.imprisoning = true,
+        Restriction::HealBlock => {
+            restrictions.heal_block_turns = HEAL_BLOCK_TURNS;
+        },
+    }
+    if let Some(ailment) = restriction.ailment() {
+        monster.volatile.insert(ailment);
+    }
+    true
+}
+
+/// Count down the timed restrictions of a Pokémon at the end of a turn,
+/// returning the restrictions that ended.
+///
+/// Encore also ends when the encored move runs out of PP.
+pub fn end_turn(monster: &mut Monster) -> Vec<Restriction> {
+    fn count_down(turns: &mut u8) -> bool {
+        if *turns == 0 {
+            return false;
+        }
+        *turns -= 1;
+        *turns == 0
+    }
+    let mut ended = Vec::new();
+    let moves = &monster.moves;
+    let restrictions = &mut monster.restrictions;
+    if count_down(&mut restrictions.taunt_turns) {
+        ended.push(Restriction::Taunt);
+    }
+    if count_down(&mut restrictions.heal_block_turns) {
+        ended.push(Restriction::HealBlock);
+    }
+    if let Some(ref mut encore) = restrictions.encore {
+        let pp = moves.iter().find(|slot| slot.id == encore.move_id)
+            .map_or(0, |slot| slot.pp);
+        if count_down(&mut encore.turns) || pp == 0 {
+            restrictions.encore = None;
+            ended.push(Restriction::Encore);
+        }
+    }
+    if let Some(ref mut disabled) = restrictions.disabled {
+        if count_down(&mut disabled.turns) {
+            restrictions.disabled = None;
+            ended.push(Restriction::Disable);
+        }
+    }
+    for ailment in ended.iter().filter_map(|r| r.ailment()) {
+        monster.volatile.remove(ailment);
+    }
+    ended
+}
@@ -0,0 +1,20 @@
+/// The source of randomness for battle mechanics.
+///
+/// It is implemented for all `rand::Rng`s. Implement it directly to script
+/// random outcomes, e.g., for tests or replays.
+pub trait BattleRng {
+    /// Get a uniformly distributed number less than `bound`, which must not be
+    /// 0.
+    fn below(&mut self, bound: u32) -> u32;
+
+    /// Succeed with a chance of `percent` percent.
+    fn chance(&mut self, percent: u8) -> bool {
+        self.below(100) < percent as u32
+    }
+}
+
+impl<R: rand::Rng> BattleRng for R {
+    fn below(&mut self, bound: u32) -> u32 {
+        self.gen_range(0, bound)
+    }
+}
@@ -0,0 +1,214 @@
+//! Declarative scripts for the effects of abilities and moves that are not
+//! purely numeric, built from a small set of primitives.
+//!
+//! An ability or move effect maps onto a list of `Primitive`s, each an `Op`
+//! run when something triggers it or a modifier to move power. Only a subset
+//! of abilities and effects is scripted; the rest have empty scripts.
+
+use crate::Ability;
+use crate::abilities::{Condition, ModifierContext, ModifierKind, Modifiers};
+use crate::moves::{Ailment, ChangeableStat, Effect, Weather};
+use crate::Stat;
+use super::ailments::{try_apply, FieldCtx};
+use super::turn::deal_damage;
+use super::{BattleRng, BattleState, Event, Position};
+use self::Op::*;
+use self::Primitive::*;
+use self::Recipient::*;
+
+/// The Pokémon an operation affects.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Recipient {
+    /// The Pokémon with the ability, or the user of the move.
+    Holder,
+    /// The opposing Pokémon: the attacker on contact, or the active opponent
+    /// otherwise.
+    Other,
+}
+
+/// An operation on the battle state.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Op {
+    /// The weather changes. This crate does not count weather turns, so it
+    /// lasts until it is changed again.
+    SetWeather(Weather),
+    /// A chance in percent to inflict an ailment.
+    ChanceAilment(Recipient, Ailment, u8),
+    /// A stat changes by a number of stages.
+    StatChange(Recipient, Stat, i8),
+    /// A fraction of the recipient's maximum HP is lost.
+    Damage(Recipient, (u16, u16)),
+}
+
+/// A primitive of an ability or move effect script.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Primitive {
+    /// Run when the holder switches in.
+    OnEntry(Op),
+    /// Run when the holder is hit by a move that makes contact.
+    OnContact(Op),
+    /// Run when the move is used, before it deals damage.
+    OnUse(Op),
+    /// Multiply the power of the move when all the conditions hold for its
+    /// user.
+    ModifyMovePower(&'static [Condition], (u16, u16)),
+}
+
+const ABILITY_SCRIPTS: &[(Ability, Primitive)] = &[
+    (Ability::Drizzle, OnEntry(SetWeather(Weather::Rain))),
+    (Ability::Drought, OnEntry(SetWeather(Weather::Sun))),
+    (Ability::SandStream, OnEntry(SetWeather(Weather::Sandstorm))),
+    (Ability::SnowWarning, OnEntry(SetWeather(Weather::Hail))),
+    (Ability::Intimidate, OnEntry(StatChange(Other, Stat::Attack, -1))),
+    (Ability::Static,
+     OnContact(ChanceAilment(Other, Ailment::Paralysis, 30))),
+    (Ability::FlameBody, OnContact(ChanceAilment(Other, Ailment::Burn, 30))),
+    (Ability::PoisonPoint,
+     OnContact(ChanceAilment(Other, Ailment::Poison, 30))),
+    (Ability::CuteCharm,
+     OnContact(ChanceAilment(Other, Ailment::Infatuation, 30))),
+    (Ability::RoughSkin, OnContact(Damage(Other, (1, 8)))),
+    (Ability::IronBarbs, OnContact(Damage(Other, (1, 8)))),
+];
+
+const EFFECT_SCRIPTS: &[(Effect, Primitive)] = &[
+    (Effect::RainDance, OnUse(SetWeather(Weather::Rain))),
+    (Effect::SunnyDay, OnUse(SetWeather(Weather::Sun))),
+    (Effect::Sandstorm, OnUse(SetWeather(Weather::Sandstorm))),
+    (Effect::Hail, OnUse(SetWeather(Weather::Hail))),
+    (Effect::Facade, ModifyMovePower(&[Condition::Statused], (2, 1))),
+];
+
+impl Ability {
+    /// The script of this ability, as of Generation V: its triggered
+    /// operations, followed by its power modifiers from `Modifiers`.
+    pub fn script(self) -> impl Iterator<Item = Primitive> {
+        let triggered = ABILITY_SCRIPTS.iter()
+            .filter(move |&&(ability, _)| ability == self)
+            .map(|&(_, primitive)| primitive);
+        let power = Modifiers::of(self)
+            .filter(|m| m.kind == ModifierKind::Power)
+            .map(|m| ModifyMovePower(m.conditions, m.factor));
+        triggered.chain(power)
+    }
+}
+
+impl Effect {
+    /// The script of this move effect, as of Generation V.
+    pub fn script(self) -> impl Iterator<Item = Primitive> {
+        EFFECT_SCRIPTS.iter()
+            .filter(move |&&(effect, _)| effect == self)
+            .map(|&(_, primitive)| primitive)
+    }
+}
+
+impl Op {
+    /// Run the operation for a holder and its opponent, returning whether it
+    /// had any effect.
+    pub fn run(
+        self, state: &mut BattleState, holder: Position, other: Position,
+        rng: &mut impl BattleRng, events: &mut Vec<Event>
+    ) -> bool {
+        let recipient = |r| if r == Holder { holder } else { other };
+        match self {
+            SetWeather(weather) => {
+                if state.weather == Some(weather) {
+                    return false;
+                }
+                state.weather = Some(weather);
+                events.push(Event::WeatherChanged { user: holder, weather });
+            },
+            ChanceAilment(r, ailment, chance) => {
+                let target = recipient(r);
+                let monster = state.monster_mut(target);
+                if monster.is_fainted() || !rng.chance(chance)
+                        || !try_apply(monster, ailment, &FieldCtx::default())
+                            .is_applied() {
+                    return false;
+                }
+                events.push(Event::AilmentInflicted { target, ailment });
+            },
+            StatChange(r, stat, change) => {
+                let target = recipient(r);
+                let monster = state.monster_mut(target);
+                let stat = match ChangeableStat::new(stat) {
+                    Some(stat) if !monster.is_fainted() => stat,
+                    _ => return false,
+                };
+                let change = monster.stages.change(stat, change);
+                if change == 0 {
+                    return false;
+                }
+                events.push(Event::StatStageChanged {
+                    target,
+                    stat: stat.stat(),
+                    change,
+                    stage: monster.stages[stat],
+                });
+            },
+            Damage(r, (numerator, denominator)) => {
+                let target = recipient(r);
+                let monster = state.monster(target);
+                if monster.is_fainted() {
+                    return false;
+                }
+                let amount = (monster.max_hp() as u32 * numerator as u32
+                    / denominator as u32).max(1) as u16;
+                deal_damage(state, target, amount, false, events);
+            },
+        }
+        true
+    }
+}
+
+/// Run the entry operations of the ability of a Pokémon that switched in.
+pub fn on_entry(
+    state: &mut BattleState, holder: Position, rng: &mut impl BattleRng,
+    events: &mut Vec<Event>
+) {
+    let other = state.active(1 - holder.side);
+    for primitive in state.monster(holder).ability.script() {
+        if let OnEntry(op) = primitive {
+            op.run(state, holder, other, rng, events);
+        }
+    }
+}
+
+/// Run the contact operations of the ability of a Pokémon hit by a move that
+/// makes contact.
+pub fn on_contact(
+    state: &mut BattleState, holder: Position, attacker: Position,
+    rng: &mut impl BattleRng, events: &mut Vec<Event>
+) {
+    for primitive in state.monster(holder).ability.script() {
+        if let OnContact(op) = primitive {
+            op.run(state, holder, attacker, rng, events);
+        }
+    }
+}
+
+/// Run the operations of a move effect when it is used.
+pub fn on_use(
+    state: &mut BattleState, user: Position, effect: Effect,
+    rng: &mut impl BattleRng, events: &mut Vec<Event>
+) {
+    let other = state.active(1 - user.side);
+    for primitive in effect.script() {
+        if let OnUse(op) = primitive {
+            op.run(state, user, other, rng, events);
+        }
+    }
+}
+
+/// The product of the power modifiers of a move effect whose conditions hold
+/// for the user in a context, or 1 if none do.
+pub fn power_factor(effect: Effect, ctx: &ModifierContext) -> f64 {
+    effect.script()
+        .filter_map(|primitive| match primitive {
+            ModifyMovePower(conditions, (numerator, denominator))
+                if conditions.iter().all(|c| c.holds(ctx))
+                => Some(f64::from(numerator) / f64::from(denominator)),
+            _ => None,
+        })
+        .product()
+}
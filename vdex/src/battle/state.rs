@@ -0,0 +1,356 @@
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::Ability;
+use crate::fingerprints::stable_hasher;
+use crate::items::ItemId;
+use crate::pokedex;
+use crate::moves::{Ailment, AilmentSet, ChangeableStat, MoveId};
+use crate::moves::{Weather, CHANGEABLE_STATS};
+use crate::pokemon::{OneOrTwo, SpeciesId, PERMANENT_STATS};
+use crate::Stat;
+use crate::stored::StoredPokemon;
+use crate::Type;
+use crate::versions::Generation;
+use super::effects::{Bide, DamageTaken};
+use super::barriers::Screens;
+use super::hazards::HazardState;
+use super::Position;
+use super::restrictions::Restrictions;
+use super::trapping::{can_switch, SwitchVerdict};
+
+/// The lowest and highest stat stages.
+pub const MIN_STAGE: i8 = -6;
+pub const MAX_STAGE: i8 = 6;
+
+/// The stat stages of a Pokémon in battle, all 0 when it enters battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StatStages(pub [i8; CHANGEABLE_STATS]);
+
+impl StatStages {
+    /// Change a stage, keeping it within the stage limits, and return the
+    /// actual change.
+    pub fn change(&mut self, stat: ChangeableStat, change: i8) -> i8 {
+        let old = self[stat];
+        self[stat] = (old + change).clamp(MIN_STAGE, MAX_STAGE);
+        self[stat] - old
+    }
+
+    /// The multiplier of a stage as a fraction: (2 + *n*) / 2 for stage *n*
+    /// ≥ 0 and 2 / (2 − *n*) otherwise, with 3 in place of 2 for accuracy and
+    /// evasion.
+    pub fn multiplier(stat: ChangeableStat, stage: i8) -> (u32, u32) {
+        let base = match stat.stat() {
+            Stat::Accuracy | Stat::Evasion => 3,
+            _ => 2,
+        };
+        let stage = stage.clamp(MIN_STAGE, MAX_STAGE);
+        if stage >= 0 {
+            (base + stage as u32, base)
+        } else {
+            (base, base + (-stage) as u32)
+        }
+    }
+
+    /// Apply the stage of a stat to a value.
+    pub fn apply(&self, stat: ChangeableStat, value: u16) -> u16 {
+        let (numerator, denominator) = Self::multiplier(stat, self[stat]);
+        (value as u32 * numerator / denominator) as u16
+    }
+}
+
+impl std::ops::Index<ChangeableStat> for StatStages {
+    type Output = i8;
+
+    fn index(&self, index: ChangeableStat) -> &i8 {
+        self.0.index(index.index())
+    }
+}
+
+impl std::ops::IndexMut<ChangeableStat> for StatStages {
+    fn index_mut(&mut self, index: ChangeableStat) -> &mut i8 {
+        self.0.index_mut(index.index())
+    }
+}
+
+/// A move known by a Pokémon in battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MoveSlot {
+    pub id: MoveId,
+    /// The remaining power points.
+    pub pp: u8,
+}
+
+/// A Pokémon in battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Monster {
+    pub species: SpeciesId,
+    pub level: u8,
+    pub types: OneOrTwo<Type>,
+    pub ability: Ability,
+    pub item: Option<ItemId>,
+    /// The calculated permanent stats, ordered like `BaseStats`; the first is
+    /// the maximum HP.
+    pub stats: [u16; PERMANENT_STATS],
+    /// The current HP.
+    pub hp: u16,
+    pub moves: Vec<MoveSlot>,
+    pub stages: StatStages,
+    /// The non-volatile ailment, or `Ailment::None`.
+    pub ailment: Ailment,
+    /// The volatile ailments, which end when the Pokémon leaves the field.
+    pub volatile: AilmentSet,
+    /// The number of turns left to sleep, counting the turn of waking up.
+    pub sleep_turns: u8,
+    /// Whether the Pokémon flinched this turn.
+    pub flinched: bool,
+    /// The last damage taken from a move this turn, for Counter and similar
+    /// moves.
+    pub damage_taken: Option<DamageTaken>,
+    /// Bide progress, if the Pokémon is storing energy.
+    pub bide: Option<Bide>,
+    /// The turns left bound by a move like Wrap (`Ailment::Trap`).
+    pub bound_turns: u8,
+    /// Whether a move like Mean Look prevents the Pokémon from leaving.
+    pub cannot_escape: bool,
+    /// The Pokémon that bound the Pokémon or prevented it from leaving; the
+    /// trapping ends when it leaves the field.
+    pub trapped_by: Option<Position>,
+    /// The restrictions on which moves the Pokémon may select.
+    pub restrictions: Restrictions,
+    /// The HP of the Pokémon's substitute, or 0 if it has none.
+    pub substitute_hp: u16,
+}
+
+impl Monster {
+    /// Create a healthy Pokémon with full HP from its calculated stats.
+    pub fn new(
+        species: SpeciesId, level: u8, types: OneOrTwo<Type>, ability: Ability,
+        stats: [u16; PERMANENT_STATS]
+    ) -> Self {
+        Monster {
+            species,
+            level,
+            types,
+            ability,
+            item: None,
+            stats,
+            hp: stats[0],
+            moves: Vec::new(),
+            stages: Default::default(),
+            ailment: Ailment::None,
+            volatile: AilmentSet::new(),
+            sleep_turns: 0,
+            flinched: false,
+            damage_taken: None,
+            bide: None,
+            bound_turns: 0,
+            cannot_escape: false,
+            trapped_by: None,
+            restrictions: Default::default(),
+            substitute_hp: 0,
+        }
+    }
+
+    /// Create a healthy Pokémon with full HP and PP from a stored Pokémon,
+    /// using the global pokedex, or `None` if it has an unknown form or
+    /// ability.
+    pub fn from_stored(stored: &StoredPokemon) -> Option<Self> {
+        let dex = pokedex();
+        let pokemon = stored.pokemon(dex)?;
+        let mut monster = Monster::new(
+            stored.species, stored.level, pokemon.types, stored.ability(dex)?,
+            stored.stats(dex)?);
+        monster.item = stored.item;
+        monster.moves = stored.moves.iter()
+            .map(|m| {
+                let pp = dex.moves[m.id].pp as u32;
+                let pp = pp + pp * m.pp_ups as u32 / 5;
+                MoveSlot { id: m.id, pp: pp as u8 }
+            })
+            .collect();
+        Some(monster)
+    }
+
+    pub fn max_hp(&self) -> u16 {
+        self.stats[0]
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.hp == 0
+    }
+
+    /// Get a calculated permanent stat, without stages, or 0 for accuracy
+    /// and evasion, which are not permanent stats.
+    pub fn stat(&self, stat: Stat) -> u16 {
+        match stat {
+            Stat::Accuracy | Stat::Evasion => 0,
+            _ => self.stats[(stat.repr() + 1) as usize],
+        }
+    }
+
+    /// Get a calculated stat with its stage applied. HP has no stage.
+    ///
+    /// Accuracy and evasion have no calculated value, so for them this is
+    /// the multiplier of their stage in percent, e.g. 133 at stage +1.
+    pub fn staged_stat(&self, stat: Stat) -> u16 {
+        let changeable = match ChangeableStat::new(stat) {
+            Some(changeable) => changeable,
+            None => return self.stat(stat),
+        };
+        match stat {
+            Stat::Accuracy | Stat::Evasion
+                => self.stages.apply(changeable, 100),
+            _ => self.stages.apply(changeable, self.stat(stat)),
+        }
+    }
+
+    /// The name of the held item, if any.
+    pub fn item_name(&self) -> Option<&'static str> {
+        self.item
+            .and_then(|id| pokedex().items.0.get(&id))
+            .map(|item| item.name.as_str())
+    }
+
+    /// The Veekun identifier of the held item, if any.
+    pub fn item_identifier(&self) -> Option<&'static str> {
+        self.item
+            .and_then(|id| pokedex().items.0.get(&id))
+            .map(|item| item.identifier.as_str())
+    }
+
+    /// Lose up to `amount` HP, returning the HP actually lost.
+    pub fn damage(&mut self, amount: u16) -> u16 {
+        let lost = amount.min(self.hp);
+        self.hp -= lost;
+        lost
+    }
+
+    /// Recover up to `amount` HP, returning the HP actually recovered.
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        let recovered = amount.min(self.max_hp() - self.hp);
+        self.hp += recovered;
+        recovered
+    }
+}
+
+/// One side of a battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Side {
+    pub party: Vec<Monster>,
+    /// The party slot of the active Pokémon.
+    pub active: u8,
+    pub screens: Screens,
+    pub hazards: HazardState,
+}
+
+impl Side {
+    /// Create a side with the first Pokémon of a party active.
+    pub fn new(party: Vec<Monster>) -> Self {
+        Side {
+            party,
+            active: 0,
+            screens: Default::default(),
+            hazards: Default::default(),
+        }
+    }
+}
+
+/// The state of a single battle between two sides.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BattleState {
+    /// The generation whose mechanics apply.
+    pub generation: Generation,
+    pub sides: [Side; 2],
+    /// The number of turns resolved so far.
+    pub turn: u32,
+    pub weather: Option<Weather>,
+}
+
+impl BattleState {
+    pub fn new(generation: Generation, sides: [Side; 2]) -> Self {
+        BattleState { generation, sides, turn: 0, weather: None }
+    }
+
+    /// The position of the active Pokémon of a side.
+    pub fn active(&self, side: u8) -> Position {
+        Position { side, slot: self.sides[side as usize].active }
+    }
+
+    /// True if the Pokémon at a position is active.
+    pub fn is_active(&self, position: Position) -> bool {
+        self.active(position.side) == position
+    }
+
+    pub fn monster(&self, position: Position) -> &Monster {
+        &self.sides[position.side as usize].party[position.slot as usize]
+    }
+
+    pub fn monster_mut(&mut self, position: Position) -> &mut Monster {
+        &mut self.sides[position.side as usize].party[position.slot as usize]
+    }
+
+    /// A hash of the state combined with the fingerprint of the global
+    /// pokedex, for engines on different processes to detect that they have
+    /// fallen out of sync.
+    ///
+    /// Like the fingerprint, it uses SipHash with fixed keys, so it agrees
+    /// between builds with different Rust versions. The state is hashed as
+    /// in its `Hash` impl, which depends on byte order and pointer width, so
+    /// peers must also share those.
+    pub fn hash(&self) -> u64 {
+        let mut hasher = stable_hasher();
+        pokedex().fingerprint().hash(&mut hasher);
+        Hash::hash(self, &mut hasher);
+        hasher.finish()
+    }
+
+    /// Check whether the active Pokémon of a side can switch out.
+    pub fn can_switch(&self, side: u8) -> SwitchVerdict {
+        let opponent = self.monster(self.active(1 - side));
+        can_switch(self.monster(self.active(side)), &[opponent])
+    }
+}
+
+/// The current version of the schema of `SavedBattle`, which changes whenever
+/// the serialized battle state does.
+pub const STATE_SCHEMA_VERSION: u32 = 1;
+
+/// A battle state with the schema version and data fingerprint it was saved
+/// with, for saving and resuming battles or sending them between processes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavedBattle {
+    /// The `STATE_SCHEMA_VERSION` of the saver.
+    pub schema: u32,
+    /// The `Pokedex::fingerprint` of the saver's global pokedex.
+    pub fingerprint: u64,
+    pub state: BattleState,
+}
+
+impl SavedBattle {
+    /// Save a battle state with the current schema and the fingerprint of the
+    /// global pokedex.
+    pub fn new(state: BattleState) -> Self {
+        SavedBattle {
+            schema: STATE_SCHEMA_VERSION,
+            fingerprint: pokedex().fingerprint(),
+            state,
+        }
+    }
+
+    /// Resume the battle state, or `None` if it was saved with another schema
+    /// or with different data than the global pokedex.
+    pub fn restore(self) -> Option<BattleState> {
+        if self.schema != STATE_SCHEMA_VERSION
+                || self.fingerprint != pokedex().fingerprint() {
+            return None;
+        }
+        Some(self.state)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Ability;
+use crate::pokedex;
+use crate::pokemon::{OneOrTwo, SpeciesId, PERMANENT_STATS};
+use crate::Type;
+use crate::versions::Generation;
+use super::{Monster, MoveSlot, StatStages};
+
+/// The PP of each move copied by Transform, or its maximum PP if lower.
+pub const TRANSFORM_PP: u8 = 5;
+
+/// What a Pokémon copies from its target with Transform or Imposter.
+#[derive(Clone, Debug)]
+pub struct TransformSnapshot {
+    pub species: SpeciesId,
+    pub types: OneOrTwo<Type>,
+    /// The target's ability, or `None` before Generation III, which has no
+    /// abilities to copy.
+    pub ability: Option<Ability>,
+    /// The target's calculated stats but HP, ordered like `BaseStats`
+    /// without the first.
+    pub stats: [u16; PERMANENT_STATS - 1],
+    /// The target's moves, each with `TRANSFORM_PP` PP.
+    pub moves: Vec<MoveSlot>,
+    pub stages: StatStages,
+}
+
+impl TransformSnapshot {
+    /// Turn a Pokémon into the copy, keeping its HP, maximum HP, ailments,
+    /// and held item.
+    pub fn apply(&self, user: &mut Monster) {
+        user.species = self.species;
+        user.types = self.types;
+        if let Some(ability) = self.ability {
+            user.ability = ability;
+        }
+        user.stats[1..].copy_from_slice(&self.stats);
+        user.moves = self.moves.clone();
+        user.stages = self.stages;
+    }
+}
+
+/// Copy a Pokémon as Transform and Imposter do in a generation.
+///
+/// The species, types, stats but HP, and stat stages are copied, and the
+/// moves with `TRANSFORM_PP` PP each. The ability is copied from Generation
+/// III on.
+pub fn transform(target: &Monster, generation: Generation) -> TransformSnapshot {
+    let mut stats = [0; PERMANENT_STATS - 1];
+    stats.copy_from_slice(&target.stats[1..]);
+    let moves = target.moves.iter()
+        .map(|slot| MoveSlot {
+            id: slot.id,
+            pp: TRANSFORM_PP.min(pokedex().moves[slot.id].pp),
+        })
+        .collect();
+    TransformSnapshot {
+        species: target.species,
+        types: target.types,
+        ability: Some(target.ability).filter(|_| generation >= Generation::III),
+        stats,
+        moves,
+        stages: target.stages,
+    }
+}
@@ -0,0 +1,72 @@
+//! Whether a Pokémon can switch out, combining trapping moves, abilities, and
+//! items.
+
+use crate::Ability;
+use crate::Type;
+use super::Monster;
+
+/// Why a Pokémon cannot switch out.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TrapReason {
+    /// Bound by a move inflicting `Ailment::Trap`, such as Wrap.
+    Bound,
+    /// Prevented from leaving by a move with the `PreventTargetLeaving`
+    /// effect, such as Mean Look, or by Ingrain.
+    CannotEscape,
+    /// Trapped by an opponent's ability: Shadow Tag, Arena Trap, or Magnet
+    /// Pull.
+    Ability(Ability),
+}
+
+/// The verdict of `can_switch`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SwitchVerdict {
+    Allowed,
+    Trapped(TrapReason),
+}
+
+impl SwitchVerdict {
+    pub fn is_allowed(self) -> bool {
+        self == SwitchVerdict::Allowed
+    }
+}
+
+/// True if a Pokémon is affected by ground-based effects such as Arena Trap:
+/// it is not Flying-type, does not have Levitate, and does not hold an Air
+/// Balloon.
+pub fn is_grounded(monster: &Monster) -> bool {
+    !monster.types.contains(Type::Flying)
+        && monster.ability != Ability::Levitate
+        && monster.item_identifier() != Some("air-balloon")
+}
+
+/// Check whether a Pokémon can switch out, with Generation V rules, given
+/// the opposing active Pokémon.
+///
+/// A held Shed Shell always allows switching. Otherwise, being bound or
+/// prevented from escaping traps the Pokémon, as do opponents with Shadow Tag
+/// (unless the Pokémon also has it), Arena Trap (if the Pokémon is grounded),
+/// and Magnet Pull (if the Pokémon is Steel-type).
+pub fn can_switch(monster: &Monster, opponents: &[&Monster]) -> SwitchVerdict {
+    if monster.item_identifier() == Some("shed-shell") {
+        return SwitchVerdict::Allowed;
+    }
+    if monster.bound_turns > 0 {
+        return SwitchVerdict::Trapped(TrapReason::Bound);
+    }
+    if monster.cannot_escape {
+        return SwitchVerdict::Trapped(TrapReason::CannotEscape);
+    }
+    for opponent in opponents.iter().filter(|o| !o.is_fainted()) {
+        let trapped = match opponent.ability {
+            Ability::ShadowTag => monster.ability != Ability::ShadowTag,
+            Ability::ArenaTrap => is_grounded(monster),
+            Ability::MagnetPull => monster.types.contains(Type::Steel),
+            _ => false,
+        };
+        if trapped {
+            return SwitchVerdict::Trapped(TrapReason::Ability(opponent.ability));
+        }
+    }
+    SwitchVerdict::Allowed
+}
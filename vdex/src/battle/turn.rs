@@ -0,0 +1,719 @@
+use crate::Ability;
+use crate::analysis;
+use crate::abilities::{ModifierContext, ModifierKind, Modifiers};
+use crate::items::HpRestore;
+use crate::moves::{Ailment, Category, ChangeableStat, Effect, Flags, Move};
+use crate::moves::{MoveId, Target};
+use crate::pokedex;
+use crate::Stat;
+use crate::versions::Generation;
+use super::ailments::{try_apply, FieldCtx};
+use super::barriers::{self, Screen};
+use super::damage::{calc_damage_with_power, critical_chance, move_power};
+use super::damage::type_modifier;
+use super::damage::{MAX_DAMAGE_ROLL, MIN_DAMAGE_ROLL};
+use super::effects::{self, Bide, DamageTaken};
+use super::hazards::{self, Hazard, ToxicSpikesEffect};
+use super::held::{self, GEM_BOOST};
+use super::restrictions::{self, Restriction};
+use super::scripts;
+use super::{BattleRng, BattleState, Event, Position, StatStages};
+
+/// An action chosen for one side for a turn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Action {
+    /// Use a move. `target` is ignored by moves that target the user's side.
+    Move {
+        user: Position,
+        move_id: MoveId,
+        target: Position,
+    },
+    /// Switch the active Pokémon of a side with another in its party.
+    Switch {
+        side: u8,
+        slot: u8,
+    },
+}
+
+/// Resolve a turn of a single battle and return what happened.
+///
+/// This is a reference implementation for a subset of the mechanics of
+/// Generations III to V, driven by the generic move data rather than by
+/// `Effect`:
+///
+/// - Switches happen first, then moves by priority and then Speed (with
+///   stages and paralysis), with ties broken randomly.
+/// - Moves check accuracy against the accuracy and evasion stages and the
+///   abilities modifying them, and deal damage with
+///   `damage::calc_damage_with_power` at the power given by
+///   `damage::move_power`, including critical hits.
+/// - The move's meta data provides drain and recoil, healing, one-hit KOs,
+///   flinching, stat changes, and non-volatile ailments (applied with
+///   `ailments::try_apply`). Sleep lasts one to three turns, and frozen
+///   Pokémon thaw with a 20% chance.
+/// - Beat Up, Triple Kick, Counter, Mirror Coat, Metal Burst, and Bide are
+///   handled by `effects`.
+/// - Berries that restore HP or cure ailments, as in `Item::restores`, are
+///   consumed when they apply, and gems are used up as in `held::gem_boost`.
+/// - Moves that `restrictions::allowed_moves` forbids fail, and Taunt,
+///   Encore, Disable, Torment, Imprison, and Heal Block impose their
+///   restrictions.
+/// - Stealth Rock, Spikes, and Toxic Spikes are laid and affect Pokémon
+///   switching in as in `hazards`.
+/// - Substitute, Reflect, and Light Screen work as in `barriers`, and moves
+///   hitting a substitute have no other effect on the target.
+/// - Trapped Pokémon (see `trapping::can_switch`) do not switch out. Bound
+///   Pokémon lose 1/16 of their HP at the end of each turn for four or five
+///   turns, unless they have Magic Guard.
+/// - Burn and poison deal damage at the end of the turn, as in
+///   `analysis::residual_damage` without weather.
+/// - Abilities and move effects with a script run it as in `scripts`, and
+///   passive ability modifiers apply as in `abilities::Modifiers`.
+///
+/// Other effects, including volatile ailments, are not applied.
+pub fn resolve_turn(
+    state: &mut BattleState, actions: &[Action], rng: &mut impl BattleRng
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    for action in order_actions(state, actions, rng) {
+        match action {
+            Action::Move { user, move_id, target }
+                => use_move(state, user, move_id, target, rng, &mut events),
+            Action::Switch { side, slot }
+                => switch(state, side, slot, rng, &mut events),
+        }
+    }
+    end_turn(state, &mut events);
+    state.turn += 1;
+    events
+}
+
+/// The Speed used for turn order.
+fn effective_speed(state: &BattleState, position: Position) -> u16 {
+    let monster = state.monster(position);
+    let context = ModifierContext::new(monster, state.weather);
+    let speed = Modifiers::apply(
+        ModifierKind::Stat(Stat::Speed), &context,
+        monster.staged_stat(Stat::Speed).into()) as u16;
+    if monster.ailment == Ailment::Paralysis
+            && monster.ability != Ability::QuickFeet {
+        speed / 4
+    } else {
+        speed
+    }
+}
+
+fn order_actions(
+    state: &BattleState, actions: &[Action], rng: &mut impl BattleRng
+) -> Vec<Action> {
+    // (is move, -priority, -speed, tiebreaker)
+    let mut keyed: Vec<_> = actions.iter().map(|&action| {
+        let key = match action {
+            Action::Move { user, move_id, .. } => (
+                1, -(pokedex().moves[move_id].priority as i32),
+                -(effective_speed(state, user) as i32)),
+            Action::Switch { .. } => (0, 0, 0),
+        };
+        (key, rng.below(1 << 16), action)
+    }).collect();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    keyed.into_iter().map(|(_, _, action)| action).collect()
+}
+
+fn switch(
+    state: &mut BattleState, side: u8, slot: u8, rng: &mut impl BattleRng,
+    events: &mut Vec<Event>
+) {
+    let outgoing = state.active(side);
+    let incoming = Position { side, slot };
+    let party = &state.sides[side as usize].party;
+    if outgoing == incoming || slot as usize >= party.len()
+            || party[slot as usize].is_fainted()
+            || (!state.monster(outgoing).is_fainted()
+                && !state.can_switch(side).is_allowed()) {
+        return;
+    }
+    let monster = state.monster_mut(outgoing);
+    monster.stages = Default::default();
+    monster.flinched = false;
+    monster.volatile = Default::default();
+    monster.restrictions = Default::default();
+    monster.substitute_hp = 0;
+    monster.bound_turns = 0;
+    monster.cannot_escape = false;
+    monster.trapped_by = None;
+    release_trapped(state, outgoing);
+    state.sides[side as usize].active = slot;
+    events.push(Event::SwitchedIn { target: incoming });
+    enter_hazards(state, incoming, events);
+    if !state.monster(incoming).is_fainted() {
+        scripts::on_entry(state, incoming, rng, events);
+    }
+}
+
+/// Apply the entry hazards of a side to a Pokémon switching in.
+fn enter_hazards(
+    state: &mut BattleState, target: Position, events: &mut Vec<Event>
+) {
+    let hazards = &state.sides[target.side as usize].hazards;
+    let damage = hazards::entry_damage(state.monster(target), hazards);
+    if damage > 0 {
+        deal_damage(state, target, damage, false, events);
+    }
+    let side = &mut state.sides[target.side as usize];
+    let monster = &mut side.party[target.slot as usize];
+    if let ToxicSpikesEffect::Poisoned { .. }
+            = hazards::toxic_spikes(monster, &mut side.hazards) {
+        events.push(Event::AilmentInflicted { target, ailment: Ailment::Poison });
+    }
+}
+
+/// End the trapping of Pokémon trapped by one that leaves the field.
+fn release_trapped(state: &mut BattleState, trapper: Position) {
+    for side in state.sides.iter_mut() {
+        for monster in side.party.iter_mut() {
+            if monster.trapped_by == Some(trapper) {
+                monster.bound_turns = 0;
+                monster.cannot_escape = false;
+                monster.trapped_by = None;
+            }
+        }
+    }
+}
+
+fn is_self_targeting(target: Target) -> bool {
+    matches!(
+        target,
+        Target::User | Target::UserOrAlly | Target::Ally | Target::UsersField)
+}
+
+/// Check whether an ailment stops a Pokémon from moving, waking or thawing
+/// it if its ailment ends.
+fn can_move(
+    state: &mut BattleState, user: Position, rng: &mut impl BattleRng,
+    events: &mut Vec<Event>
+) -> bool {
+    let monster = state.monster_mut(user);
+    if monster.flinched {
+        events.push(Event::Flinched { target: user });
+        return false;
+    }
+    let ailment = monster.ailment;
+    let stopped = match ailment {
+        Ailment::Sleep => {
+            monster.sleep_turns = monster.sleep_turns.saturating_sub(1);
+            monster.sleep_turns > 0
+        },
+        Ailment::Freeze => !rng.chance(20),
+        Ailment::Paralysis => rng.chance(25),
+        _ => false,
+    };
+    if stopped {
+        events.push(Event::Immobilized { target: user, ailment });
+    } else if ailment == Ailment::Sleep || ailment == Ailment::Freeze {
+        monster.ailment = Ailment::None;
+        events.push(Event::AilmentCured { target: user, ailment });
+    }
+    !stopped
+}
+
+/// True if a move hits, given the accuracy and evasion stages and the
+/// accuracy and evasion modifiers of abilities like Compoundeyes and Sand
+/// Veil. One-hit KOs ignore the modifiers.
+fn accuracy_hits(
+    state: &BattleState, user: Position, target: Position, mov: &Move,
+    accuracy: u8, rng: &mut impl BattleRng
+) -> bool {
+    let accuracy_stat = ChangeableStat::new(Stat::Accuracy).unwrap();
+    let evasion_stat = ChangeableStat::new(Stat::Evasion).unwrap();
+    let stage = state.monster(user).stages[accuracy_stat]
+        - state.monster(target).stages[evasion_stat];
+    let (numerator, denominator) = StatStages::multiplier(accuracy_stat, stage);
+    let mut chance = accuracy as u32 * numerator / denominator;
+    if mov.meta.category != Category::OneHitKO {
+        let user_ctx = ModifierContext::new(state.monster(user), state.weather)
+            .with_move(mov);
+        let target_ctx
+            = ModifierContext::new(state.monster(target), state.weather);
+        chance = Modifiers::apply(
+            ModifierKind::Stat(Stat::Accuracy), &user_ctx, chance);
+        let evasion = Modifiers::factor(
+            ModifierKind::Stat(Stat::Evasion), &target_ctx);
+        chance = (f64::from(chance) / evasion) as u32;
+    }
+    rng.below(100) < chance
+}
+
+fn use_move(
+    state: &mut BattleState, user: Position, move_id: MoveId,
+    chosen_target: Position, rng: &mut impl BattleRng, events: &mut Vec<Event>
+) {
+    if !state.is_active(user) || state.monster(user).is_fainted()
+            || !can_move(state, user, rng, events) {
+        return;
+    }
+    if let Some(bide) = state.monster(user).bide {
+        continue_bide(state, user, bide, events);
+        return;
+    }
+    let mov = &pokedex().moves[move_id];
+    let opponent = state.monster(state.active(1 - user.side));
+    let allowed = restrictions::allowed_moves(state.monster(user), &[opponent]);
+    let monster = state.monster_mut(user);
+    if let Some(i) = monster.moves.iter().position(|slot| slot.id == move_id) {
+        if !allowed.contains(i) {
+            events.push(Event::MoveFailed { user, move_id });
+            return;
+        }
+        monster.moves[i].pp = monster.moves[i].pp.saturating_sub(1);
+    }
+    restrictions::record_move(monster, move_id);
+    let self_targeting = is_self_targeting(mov.target);
+    let target = if self_targeting {
+        user
+    } else if chosen_target != user && state.is_active(chosen_target) {
+        chosen_target
+    } else {
+        state.active(1 - user.side)
+    };
+    events.push(Event::MoveUsed { user, move_id, target: Some(target) });
+    if mov.effect == Effect::Bide {
+        state.monster_mut(user).bide = Some(Bide::new(move_id));
+        return;
+    }
+    if state.monster(target).is_fainted() {
+        return;
+    }
+    if !self_targeting {
+        let hit = match mov.accuracy {
+            Some(accuracy)
+                => accuracy_hits(state, user, target, mov, accuracy, rng),
+            None => true,
+        };
+        let level_fails = mov.meta.category == Category::OneHitKO
+            && state.monster(target).level > state.monster(user).level;
+        if !hit || level_fails {
+            events.push(Event::MoveMissed { user, move_id, target });
+            return;
+        }
+    }
+    if let Some(restriction) = Restriction::from_effect(mov.effect) {
+        let monster = state.monster_mut(target);
+        if !restrictions::impose(monster, restriction) {
+            events.push(Event::MoveFailed { user, move_id });
+            return;
+        }
+        if let Some(ailment) = restriction.ailment() {
+            events.push(Event::AilmentInflicted { target, ailment });
+        }
+        return;
+    }
+    if let Some(screen) = Screen::from_effect(mov.effect) {
+        let turns = barriers::screen_turns(state.monster(user));
+        let screens = &mut state.sides[user.side as usize].screens;
+        if screens.start(screen, turns) {
+            events.push(Event::ScreenStarted { user, screen, turns });
+        } else {
+            events.push(Event::MoveFailed { user, move_id });
+        }
+        return;
+    }
+    if let Some(hazard) = Hazard::from_effect(mov.effect) {
+        let hazards = &mut state.sides[target.side as usize].hazards;
+        if hazards.add(hazard) {
+            let layers = hazards.layers(hazard);
+            events.push(Event::HazardSet { user, hazard, layers });
+        } else {
+            events.push(Event::MoveFailed { user, move_id });
+        }
+        return;
+    }
+    if mov.effect == Effect::Substitute {
+        let monster = state.monster_mut(user);
+        match barriers::make_substitute(monster) {
+            Some(hp) => {
+                events.push(Event::DamageDealt {
+                    target: user, amount: hp, hp: monster.hp, critical: false
+                });
+                events.push(Event::SubstituteMade { target: user, hp });
+            },
+            None => events.push(Event::MoveFailed { user, move_id }),
+        }
+        return;
+    }
+    scripts::on_use(state, user, mov.effect, rng, events);
+    // Substitutes block the other effects of moves from opponents.
+    let shielded = target != user && state.monster(target).substitute_hp > 0
+        && !barriers::bypasses_substitute(mov);
+
+    let dealt = match mov.effect {
+        Effect::Counter | Effect::MirrorCoat | Effect::MetalBurst => {
+            let immune = type_modifier(mov.typ, state.monster(target).types)
+                == 0.0;
+            match effects::counter_damage(state, user, mov.effect) {
+                Some((_, damage)) if !immune
+                    => hit(state, user, target, damage, false, mov, events),
+                _ => {
+                    events.push(Event::MoveFailed { user, move_id });
+                    return;
+                },
+            }
+        },
+        Effect::BeatUp => {
+            let mut dealt = 0;
+            for power in effects::beat_up_powers(state, user) {
+                if state.monster(target).is_fainted() {
+                    break;
+                }
+                dealt += strike(state, user, target, mov, power, rng, events);
+            }
+            dealt
+        },
+        Effect::TripleKick => {
+            let mut dealt = 0;
+            for (i, &power) in effects::TRIPLE_KICK_POWERS.iter().enumerate() {
+                let accuracy = mov.accuracy.unwrap_or(100);
+                if state.monster(target).is_fainted() || (i > 0
+                        && !accuracy_hits(
+                            state, user, target, mov, accuracy, rng)) {
+                    break;
+                }
+                dealt += strike(state, user, target, mov, power, rng, events);
+            }
+            dealt
+        },
+        _ if mov.meta.category == Category::OneHitKO => {
+            let hp = state.monster(target).hp;
+            hit(state, user, target, hp, false, mov, events)
+        },
+        _ => {
+            let power = move_power(state, user, target, mov);
+            strike(state, user, target, mov, power, rng, events)
+        },
+    };
+    if mov.power > 0 && dealt == 0 && mov.meta.category != Category::OneHitKO {
+        // The target is immune.
+        return;
+    }
+
+    if dealt > 0 && mov.meta.recoil != 0 {
+        let amount = (dealt as u32 * mov.meta.recoil.unsigned_abs() as u32
+            / 100).max(1) as u16;
+        if mov.meta.recoil > 0 {
+            heal(state, user, amount, events);
+        } else {
+            deal_damage(state, user, amount, false, events);
+        }
+    }
+    if dealt > 0 && !shielded && target != user
+            && mov.meta.flags.contains(Flags::CONTACT) {
+        scripts::on_contact(state, target, user, rng, events);
+    }
+    if mov.meta.healing > 0 {
+        let amount = state.monster(user).max_hp() as u32
+            * mov.meta.healing as u32 / 100;
+        heal(state, user, amount as u16, events);
+    }
+    apply_secondary(state, user, target, mov, shielded, rng, events);
+    consume_berries(state, target, events);
+    consume_berries(state, user, events);
+}
+
+/// Deal one strike of regular damage with a power, rolling for a critical hit
+/// and the random factor.
+fn strike(
+    state: &mut BattleState, user: Position, target: Position, mov: &Move,
+    power: u16, rng: &mut impl BattleRng, events: &mut Vec<Event>
+) -> u16 {
+    let stage = mov.meta.critical_rate.max(0) as u8;
+    let (numerator, denominator) = critical_chance(stage);
+    let critical = rng.below(denominator) < numerator;
+    let roll = MIN_DAMAGE_ROLL
+        + rng.below((MAX_DAMAGE_ROLL - MIN_DAMAGE_ROLL + 1) as u32) as u8;
+    let gem = held::gem_boost(state.monster(user), mov, state.weather);
+    let power = match gem {
+        Some(_) => power * GEM_BOOST.0 / GEM_BOOST.1,
+        None => power,
+    };
+    match calc_damage_with_power(
+            state, user, target, mov, power, critical, roll) {
+        0 => 0,
+        damage => {
+            if gem.is_some() {
+                if let Some(item) = state.monster_mut(user).item.take() {
+                    events.push(Event::ItemConsumed { holder: user, item });
+                }
+            }
+            hit(state, user, target, damage, critical, mov, events)
+        },
+    }
+}
+
+/// Deal damage from a move to the target's substitute, if it has one the
+/// move does not bypass, or else to the target, recording it for Counter and
+/// Bide.
+fn hit(
+    state: &mut BattleState, user: Position, target: Position, amount: u16,
+    critical: bool, mov: &Move, events: &mut Vec<Event>
+) -> u16 {
+    let monster = state.monster_mut(target);
+    if monster.substitute_hp > 0 && !barriers::bypasses_substitute(mov) {
+        let amount = barriers::damage_substitute(monster, amount);
+        let hp = monster.substitute_hp;
+        events.push(Event::SubstituteDamaged { target, amount, hp });
+        return amount;
+    }
+    let class = mov.damage_class_in(state.generation);
+    let amount = deal_damage(state, target, amount, critical, events);
+    let monster = state.monster_mut(target);
+    monster.damage_taken = Some(DamageTaken { amount, class, from: user });
+    if let Some(ref mut bide) = monster.bide {
+        bide.damage = bide.damage.saturating_add(amount);
+        bide.from = Some(user);
+    }
+    amount
+}
+
+/// Store energy for another turn, or unleash it.
+fn continue_bide(
+    state: &mut BattleState, user: Position, mut bide: Bide,
+    events: &mut Vec<Event>
+) {
+    let move_id = bide.move_id;
+    bide.turns -= 1;
+    if bide.turns > 0 {
+        state.monster_mut(user).bide = Some(bide);
+        events.push(Event::MoveUsed { user, move_id, target: None });
+        return;
+    }
+    state.monster_mut(user).bide = None;
+    match bide.unleash() {
+        Some((target, damage)) if state.is_active(target)
+                && !state.monster(target).is_fainted() => {
+            events.push(Event::MoveUsed { user, move_id, target: Some(target) });
+            let mov = &pokedex().moves[move_id];
+            hit(state, user, target, damage, false, mov, events);
+            consume_berries(state, target, events);
+        },
+        _ => {
+            events.push(Event::MoveUsed { user, move_id, target: None });
+            events.push(Event::MoveFailed { user, move_id });
+        },
+    }
+}
+
+/// Apply the ailment, stat change, and flinch effects of a move. Only effects
+/// on the user apply if the target is shielded by a substitute.
+fn apply_secondary(
+    state: &mut BattleState, user: Position, target: Position, mov: &Move,
+    shielded: bool, rng: &mut impl BattleRng, events: &mut Vec<Event>
+) {
+    let meta = &mov.meta;
+    let always = |chance: u8| if chance == 0 { 100 } else { chance };
+    match meta.category {
+        Category::DamageRaise if rng.chance(always(meta.stat_chance)) => {
+            change_stages(state, user, mov, events);
+        },
+        _ if shielded => return,
+        Category::Ailment => {
+            inflict(state, user, target, mov, rng, events);
+        },
+        Category::DamageAilment if rng.chance(always(meta.ailment_chance)) => {
+            inflict(state, user, target, mov, rng, events);
+        },
+        Category::NetGoodStats | Category::Swagger => {
+            change_stages(state, target, mov, events);
+        },
+        Category::DamageLower if rng.chance(always(meta.stat_chance)) => {
+            change_stages(state, target, mov, events);
+        },
+        _ => (),
+    }
+    if mov.effect == Effect::PreventTargetLeaving {
+        let monster = state.monster_mut(target);
+        if !monster.cannot_escape {
+            monster.cannot_escape = true;
+            monster.trapped_by = Some(user);
+        }
+    }
+    if meta.flinch_chance > 0 && rng.chance(meta.flinch_chance) {
+        state.monster_mut(target).flinched = true;
+    }
+}
+
+/// Lose HP, reporting the damage and fainting.
+pub(super) fn deal_damage(
+    state: &mut BattleState, target: Position, amount: u16, critical: bool,
+    events: &mut Vec<Event>
+) -> u16 {
+    let monster = state.monster_mut(target);
+    let amount = monster.damage(amount);
+    events.push(Event::DamageDealt {
+        target, amount, hp: monster.hp, critical
+    });
+    if monster.is_fainted() {
+        events.push(Event::Fainted { target });
+    }
+    amount
+}
+
+fn heal(
+    state: &mut BattleState, target: Position, amount: u16,
+    events: &mut Vec<Event>
+) {
+    let monster = state.monster_mut(target);
+    if monster.is_fainted() {
+        return;
+    }
+    let amount = monster.heal(amount);
+    if amount > 0 {
+        events.push(Event::Healed { target, amount, hp: monster.hp });
+    }
+}
+
+fn change_stages(
+    state: &mut BattleState, target: Position, mov: &Move,
+    events: &mut Vec<Event>
+) {
+    let monster = state.monster_mut(target);
+    if monster.is_fainted() {
+        return;
+    }
+    for (stat, change) in mov.meta.stat_changes.iter() {
+        let change = monster.stages.change(stat, change);
+        if change != 0 {
+            events.push(Event::StatStageChanged {
+                target,
+                stat: stat.stat(),
+                change,
+                stage: monster.stages[stat],
+            });
+        }
+    }
+}
+
+/// Inflict the non-volatile ailment of a move, or bind the target.
+fn inflict(
+    state: &mut BattleState, user: Position, target: Position, mov: &Move,
+    rng: &mut impl BattleRng, events: &mut Vec<Event>
+) {
+    let ailment = mov.meta.ailment;
+    if ailment.volatile() && ailment != Ailment::Trap {
+        return;
+    }
+    let ctx = FieldCtx {
+        flags: mov.meta.flags,
+        self_inflicted: user == target,
+        .. Default::default()
+    };
+    let monster = state.monster_mut(target);
+    if !try_apply(monster, ailment, &ctx).is_applied() {
+        return;
+    }
+    if ailment == Ailment::Trap {
+        return bind(state, user, target, rng, events);
+    }
+    if ailment == Ailment::Sleep {
+        // The turn counter includes the turn the Pokémon wakes up on.
+        monster.sleep_turns = 2 + rng.below(3) as u8;
+    }
+    events.push(Event::AilmentInflicted { target, ailment });
+}
+
+/// Bind a Pokémon for four or five turns, as with Wrap.
+fn bind(
+    state: &mut BattleState, user: Position, target: Position,
+    rng: &mut impl BattleRng, events: &mut Vec<Event>
+) {
+    let monster = state.monster_mut(target);
+    monster.bound_turns = 4 + rng.below(2) as u8;
+    monster.trapped_by = Some(user);
+    events.push(Event::AilmentInflicted { target, ailment: Ailment::Trap });
+}
+
+/// Consume the held berry of a Pokémon if it applies.
+fn consume_berries(
+    state: &mut BattleState, holder: Position, events: &mut Vec<Event>
+) {
+    let generation = state.generation;
+    let monster = state.monster_mut(holder);
+    let item = match monster.item {
+        Some(item) if !monster.is_fainted() => item,
+        _ => return,
+    };
+    let restore = match pokedex().items.0.get(&item) {
+        Some(held) if held.berry.is_some() => held.restores(),
+        _ => None,
+    };
+    let restore = match restore {
+        Some(restore) => restore,
+        None => return,
+    };
+    let low_hp = monster.hp as u32 * 2 <= monster.max_hp() as u32;
+    let healing = match restore.hp {
+        Some(_) if !low_hp => 0,
+        // The Sitrus Berry restored a fixed 30 HP before Generation IV.
+        Some(HpRestore::Fraction(..)) if generation < Generation::IV => 30,
+        Some(hp) => hp.amount(monster.max_hp()),
+        None => 0,
+    };
+    let ailment = monster.ailment;
+    let cures = ailment != Ailment::None && restore.cures(ailment);
+    if healing == 0 && !cures {
+        return;
+    }
+    monster.item = None;
+    events.push(Event::ItemConsumed { holder, item });
+    if cures {
+        monster.ailment = Ailment::None;
+        events.push(Event::AilmentCured { target: holder, ailment });
+    }
+    heal(state, holder, healing, events);
+}
+
+fn end_turn(state: &mut BattleState, events: &mut Vec<Event>) {
+    for side in 0..2 {
+        let position = state.active(side);
+        let monster = state.monster(position);
+        let residual = analysis::residual_damage(monster, None);
+        if residual > 0 && !monster.is_fainted() {
+            deal_damage(state, position, residual, false, events);
+            consume_berries(state, position, events);
+        }
+        let monster = state.monster(position);
+        if monster.bound_turns > 0 && !monster.is_fainted() {
+            if monster.ability != Ability::MagicGuard {
+                let damage = (monster.max_hp() / 16).max(1);
+                deal_damage(state, position, damage, false, events);
+            }
+            let monster = state.monster_mut(position);
+            monster.bound_turns -= 1;
+            if monster.bound_turns == 0 {
+                monster.volatile.remove(Ailment::Trap);
+                if !monster.cannot_escape {
+                    monster.trapped_by = None;
+                }
+                events.push(Event::AilmentCured {
+                    target: position, ailment: Ailment::Trap
+                });
+            }
+        }
+    }
+    for side in 0..2 {
+        let position = state.active(side);
+        for screen in state.sides[side as usize].screens.end_turn() {
+            events.push(Event::ScreenEnded { target: position, screen });
+        }
+        for ended in restrictions::end_turn(state.monster_mut(position)) {
+            if let Some(ailment) = ended.ailment() {
+                events.push(Event::AilmentCured { target: position, ailment });
+            }
+        }
+    }
+    for side in state.sides.iter_mut() {
+        for monster in side.party.iter_mut() {
+            monster.flinched = false;
+            monster.damage_taken = None;
+        }
+    }
+}
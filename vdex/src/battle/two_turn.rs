@@ -0,0 +1,167 @@
+//! Moves that take two turns: moves with `Flags::CHARGE`, which charge on the
+//! first turn and some of which leave the user semi-invulnerable meanwhile,
+//! like Fly; and moves with `Flags::RECHARGE`, like Hyper Beam, after which
+//! the user must recharge.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::moves::{Effect, Flags, Move, MoveId, Weather, WeatherModifier};
+use super::Monster;
+
+/// Where a Pokémon charging a move has vanished to, out of reach of most
+/// moves.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Vanished {
+    /// In the air, with Fly, Bounce, or Sky Drop.
+    Air,
+    /// Underground, with Dig.
+    Underground,
+    /// Underwater, with Dive.
+    Underwater,
+    /// Out of sight, with Shadow Force.
+    Phantom,
+}
+
+impl Vanished {
+    /// Where the user of a move with an effect vanishes to while charging,
+    /// if anywhere.
+    pub fn from_effect(effect: Effect) -> Option<Self> {
+        match effect {
+            Effect::Fly | Effect::Bounce | Effect::SkyDrop
+                => Some(Vanished::Air),
+            Effect::Dig => Some(Vanished::Underground),
+            Effect::Dive => Some(Vanished::Underwater),
+            Effect::ShadowForce => Some(Vanished::Phantom),
+            _ => None,
+        }
+    }
+
+    /// True if a move can still hit a Pokémon that has vanished here, as of
+    /// Generation V.
+    pub fn hittable_by(self, mov: &Move) -> bool {
+        match self {
+            Vanished::Air => matches!(mov.effect,
+                Effect::Gust | Effect::Twister | Effect::Thunder
+                | Effect::SkyUppercut | Effect::Hurricane | Effect::SmackDown),
+            Vanished::Underground => matches!(mov.effect,
+                Effect::Earthquake | Effect::Magnitude)
+                || mov.identifier == "fissure",
+            Vanished::Underwater => matches!(mov.effect,
+                Effect::Surf | Effect::Whirlpool),
+            Vanished::Phantom => false,
+        }
+    }
+
+    /// The factor a move's damage is multiplied by against a Pokémon that
+    /// has vanished here: doubled for Gust and Twister in the air,
+    /// Earthquake and Magnitude underground, and Surf and Whirlpool
+    /// underwater.
+    pub fn damage_factor(self, mov: &Move) -> (u16, u16) {
+        let doubled = match self {
+            Vanished::Air => matches!(mov.effect,
+                Effect::Gust | Effect::Twister),
+            Vanished::Underground => matches!(mov.effect,
+                Effect::Earthquake | Effect::Magnitude),
+            Vanished::Underwater => matches!(mov.effect,
+                Effect::Surf | Effect::Whirlpool),
+            Vanished::Phantom => false,
+        };
+        if doubled { (2, 1) } else { (1, 1) }
+    }
+}
+
+/// True if a Pokémon uses a charging move at once: SolarBeam in sun, or any
+/// charging move with a Power Herb, which the engine should consume if the
+/// weather does not already skip the charge.
+pub fn skips_charge(
+    user: &Monster, mov: &Move, weather: Option<Weather>
+) -> bool {
+    weather.is_some_and(|weather| mov.effect.modifiers_in(weather)
+        .any(|m| m == WeatherModifier::SkipCharge))
+        || user.item_identifier() == Some("power-herb")
+}
+
+/// What a Pokémon does on a turn it would use a move, as decided by `State`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Step {
+    /// The move is used as usual.
+    Execute,
+    /// The turn is spent charging the move, which is used on the next turn.
+    Charge,
+    /// The move charged on the last turn is used, whatever was chosen.
+    Release(MoveId),
+    /// The turn is spent recharging, and no move is used.
+    Recharge,
+}
+
+/// The two-turn state of a Pokémon.
+///
+/// An engine calls `use_move` each turn the Pokémon would use a move, and
+/// `interrupt` when the Pokémon cannot move or switches out, which cancels a
+/// charge, or when a recharging move fails to hit, which needs no recharge.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum State {
+    /// Not in the middle of a two-turn move.
+    #[default]
+    Ready,
+    /// Charging a move to use on the next turn, and vanished while doing so
+    /// if the move is like Fly.
+    Charging { move_id: MoveId, vanished: Option<Vanished> },
+    /// Must spend the next turn recharging.
+    Recharging,
+}
+
+impl State {
+    /// Decide what a Pokémon does on a turn it would use a move, and advance
+    /// the state. `skip_charge` is whether the move skips its charging turn,
+    /// as in `skips_charge`.
+    pub fn use_move(&mut self, mov: &Move, skip_charge: bool) -> Step {
+        let step = match *self {
+            State::Recharging => Step::Recharge,
+            State::Charging { move_id, .. } => Step::Release(move_id),
+            State::Ready if mov.meta.flags.contains(Flags::CHARGE)
+                    && !skip_charge => {
+                *self = State::Charging {
+                    move_id: mov.id,
+                    vanished: Vanished::from_effect(mov.effect),
+                };
+                return Step::Charge;
+            },
+            State::Ready if mov.meta.flags.contains(Flags::RECHARGE) => {
+                *self = State::Recharging;
+                return Step::Execute;
+            },
+            State::Ready => Step::Execute,
+        };
+        *self = State::Ready;
+        step
+    }
+
+    /// Cancel a charge or a pending recharge.
+    pub fn interrupt(&mut self) {
+        *self = State::Ready;
+    }
+
+    /// The move the Pokémon must use on its next turn, if it is charging.
+    pub fn locked_move(&self) -> Option<MoveId> {
+        match *self {
+            State::Charging { move_id, .. } => Some(move_id),
+            _ => None,
+        }
+    }
+
+    /// Where the Pokémon has vanished to, if it is semi-invulnerable.
+    pub fn vanished(&self) -> Option<Vanished> {
+        match *self {
+            State::Charging { vanished, .. } => vanished,
+            _ => None,
+        }
+    }
+
+    /// True if a move can hit the Pokémon, as in `Vanished::hittable_by`.
+    pub fn can_be_hit_by(&self, mov: &Move) -> bool {
+        self.vanished().is_none_or(|vanished| vanished.hittable_by(mov))
+    }
+}
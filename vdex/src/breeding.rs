@@ -0,0 +1,292 @@
+//! Breeding, with Generation V rules.
+
+use crate::battle::BattleRng;
+use crate::items::ItemId;
+use crate::pokedex;
+use crate::pokemon::{EggGroup, EggGroupSet, Species, SpeciesId};
+use crate::pokemon::PERMANENT_STATS;
+use crate::stored::StoredPokemon;
+use crate::versions::Generation;
+use crate::{Enum, Nature, Stat};
+
+/// The number of IVs inherited from the parents.
+pub const INHERITED_IVS: usize = 3;
+
+/// The number of steps in an egg cycle.
+pub const STEPS_PER_CYCLE: u32 = 257;
+
+/// The number of possible values that decide whether a Pokémon is shiny,
+/// one of which is shiny.
+pub const SHINY_ODDS: u32 = 8192;
+
+/// A probability, as (numerator, denominator).
+pub type Fraction = (u32, u32);
+
+/// The baby species that hatch only if a parent holds an incense, by Veekun
+/// identifier; otherwise, the species they evolve into hatches.
+pub const INCENSE_BABIES: &[(&str, &str)] = &[
+    ("azurill", "sea-incense"),
+    ("wynaut", "lax-incense"),
+    ("budew", "rose-incense"),
+    ("chingling", "pure-incense"),
+    ("bonsly", "rock-incense"),
+    ("mime-jr", "odd-incense"),
+    ("happiny", "luck-incense"),
+    ("munchlax", "full-incense"),
+    ("mantyke", "wave-incense"),
+];
+
+/// The Power items, which pass down a stat's IV, by Veekun identifier.
+const POWER_ITEMS: &[(&str, Stat)] = &[
+    ("power-weight", Stat::HP),
+    ("power-bracer", Stat::Attack),
+    ("power-belt", Stat::Defense),
+    ("power-anklet", Stat::Speed),
+    ("power-lens", Stat::SpecialAttack),
+    ("power-band", Stat::SpecialDefense),
+];
+
+/// Whether Pokémon of two egg groups can breed, indexed by the egg groups'
+/// IDs minus one.
+///
+/// Pokémon of the same group can breed, and Ditto can breed with any group
+/// but itself. Pokémon of the `NoEggs` group cannot breed at all.
+pub const EGG_GROUP_COMPATIBILITY: [[bool; EggGroup::COUNT]; EggGroup::COUNT]
+    = egg_group_compatibility();
+
+const fn egg_group_compatibility(
+) -> [[bool; EggGroup::COUNT]; EggGroup::COUNT] {
+    let ditto = EggGroup::Ditto as usize - 1;
+    let no_eggs = EggGroup::NoEggs as usize - 1;
+    let mut matrix = [[false; EggGroup::COUNT]; EggGroup::COUNT];
+    let mut a = 0;
+    while a < EggGroup::COUNT {
+        let mut b = 0;
+        while b < EggGroup::COUNT {
+            matrix[a][b] = a != no_eggs && b != no_eggs
+                && ((a == ditto) != (b == ditto) || (a == b && a != ditto));
+            b += 1;
+        }
+        a += 1;
+    }
+    matrix
+}
+
+impl EggGroup {
+    /// True if Pokémon of this egg group can breed with Pokémon of another,
+    /// as in `EGG_GROUP_COMPATIBILITY`.
+    pub fn compatible(self, other: EggGroup) -> bool {
+        EGG_GROUP_COMPATIBILITY[self as usize - 1][other as usize - 1]
+    }
+
+    /// The egg groups Pokémon of this egg group can breed with.
+    pub fn compatible_groups(self) -> EggGroupSet {
+        EggGroup::VALUES.iter().cloned()
+            .filter(|&other| self.compatible(other))
+            .collect()
+    }
+}
+
+impl Species {
+    /// The species' egg groups, as a set.
+    pub fn egg_group_set(&self) -> EggGroupSet {
+        let groups = self.egg_groups;
+        groups.second().into_iter().chain(Some(groups.first())).collect()
+    }
+
+    /// True if Pokémon of two species have compatible egg groups, as in
+    /// `EggGroup::compatible`. Genders are not checked.
+    pub fn shares_egg_group(a: &Species, b: &Species) -> bool {
+        let groups = b.egg_group_set();
+        a.egg_group_set().iter()
+            .any(|group| !group.compatible_groups().intersection(groups)
+                 .is_empty())
+    }
+}
+
+/// The Pokémon hatched from an egg, as far as its parents determine it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OffspringSpec {
+    pub species: SpeciesId,
+    pub nature: Nature,
+    /// IVs, in the order of `BaseStats`.
+    pub ivs: [u8; PERMANENT_STATS],
+    /// Whether each IV was inherited from a parent rather than random.
+    pub inherited: [bool; PERMANENT_STATS],
+}
+
+/// The stat whose IV a held item passes down, if it is a Power item.
+pub fn power_item_stat(item: ItemId) -> Option<Stat> {
+    let identifier = pokedex().items.identifiers().identifier(item)?;
+    POWER_ITEMS.iter()
+        .find(|&&(power, _)| power == identifier)
+        .map(|&(_, stat)| stat)
+}
+
+fn is_ditto(species: &Species) -> bool {
+    species.egg_groups.contains(EggGroup::Ditto)
+}
+
+/// True if Pokémon of two species can breed, by their egg groups. Genders
+/// are not checked.
+pub fn compatible(mother: &Species, father: &Species) -> bool {
+    let no_eggs = |species: &Species| {
+        species.egg_groups.contains(EggGroup::NoEggs)
+    };
+    !no_eggs(mother) && !no_eggs(father)
+        && Species::shares_egg_group(mother, father)
+}
+
+/// The first species in the evolution line of a species.
+pub fn base_species(id: SpeciesId) -> SpeciesId {
+    let species = &pokedex().species;
+    let mut id = id;
+    while let Some(evolves_from) = species[id].evolves_from {
+        id = evolves_from.from_id;
+    }
+    id
+}
+
+fn species_named(identifier: &str) -> SpeciesId {
+    pokedex().species.identifiers().id(identifier)
+        .expect("Species should be in the species table.")
+}
+
+/// The species of the offspring of a parent of a species, given the items
+/// held by both parents.
+fn offspring_species(
+    rng: &mut impl BattleRng, parent: SpeciesId, items: [Option<ItemId>; 2]
+) -> SpeciesId {
+    let dex = pokedex();
+    let base = base_species(parent);
+    let identifier = dex.species[base].identifier.as_str();
+    let incense = INCENSE_BABIES.iter()
+        .find(|&&(baby, _)| baby == identifier)
+        .map(|&(_, incense)| dex.items.identifiers().id(incense));
+    if let Some(incense) = incense {
+        if !items.contains(&incense) {
+            return dex.species.evolves_into(base)[0];
+        }
+    }
+    match identifier {
+        "nidoran-f" if rng.chance(50) => species_named("nidoran-m"),
+        "illumise" if rng.chance(50) => species_named("volbeat"),
+        "manaphy" => species_named("phione"),
+        _ => base,
+    }
+}
+
+/// Breed two Pokémon, or get `None` if they cannot breed, as in
+/// `compatible`.
+///
+/// The offspring is of the first species in the mother's evolution line, or
+/// the father's if the mother is a Ditto, with `INCENSE_BABIES` hatching only
+/// if a parent holds the incense. `INHERITED_IVS` IVs are inherited, each
+/// from a random parent; `power_items` are the stats of the Power items held
+/// by the mother and the father, of which one, chosen at random, is always
+/// inherited. If `everstone` is true, the mother, or the father if it is a
+/// Ditto, holds an Everstone and passes down its nature half of the time.
+pub fn offspring(
+    rng: &mut impl BattleRng, mother: &StoredPokemon, father: &StoredPokemon,
+    power_items: (Option<Stat>, Option<Stat>), everstone: bool,
+) -> Option<OffspringSpec> {
+    let species = &pokedex().species;
+    let (mother_species, father_species)
+        = (&species[mother.species], &species[father.species]);
+    if !compatible(mother_species, father_species) {
+        return None;
+    }
+    let (parent, nature_parent) = if is_ditto(mother_species) {
+        (father, mother)
+    } else if is_ditto(father_species) {
+        (mother, father)
+    } else {
+        (mother, mother)
+    };
+    let offspring_species
+        = offspring_species(rng, parent.species, [mother.item, father.item]);
+    let nature = if everstone && rng.chance(50) {
+        nature_parent.nature
+    } else {
+        Nature::VALUES[rng.below(Nature::COUNT as u32) as usize]
+    };
+    let parents = [mother, father];
+    let mut ivs = [0; PERMANENT_STATS];
+    for iv in ivs.iter_mut() {
+        *iv = rng.below(32) as u8;
+    }
+    let mut inherited = [false; PERMANENT_STATS];
+    let powers: Vec<_> = [power_items.0, power_items.1].iter().enumerate()
+        .filter_map(|(i, stat)| stat.map(|stat| (i, stat)))
+        .collect();
+    if !powers.is_empty() {
+        let (i, stat) = powers[rng.below(powers.len() as u32) as usize];
+        let index = (stat.repr() + 1) as usize;
+        ivs[index] = parents[i].ivs[index];
+        inherited[index] = true;
+    }
+    while inherited.iter().filter(|&&i| i).count() < INHERITED_IVS {
+        let index = rng.below(PERMANENT_STATS as u32) as usize;
+        if !inherited[index] {
+            ivs[index] = parents[rng.below(2) as usize].ivs[index];
+            inherited[index] = true;
+        }
+    }
+    Some(OffspringSpec { species: offspring_species, nature, ivs, inherited })
+}
+
+/// The number of egg cycles a species' eggs need to hatch, or half as many,
+/// rounding up, if a Pokémon with Magma Armor or Flame Body is in the party.
+pub fn egg_cycles(species: SpeciesId, magma_armor_or_flame_body: bool) -> u8 {
+    let cycles = pokedex().species[species].hatch_counter;
+    if magma_armor_or_flame_body {
+        cycles.div_ceil(2)
+    } else {
+        cycles
+    }
+}
+
+/// The number of steps for a species' eggs to hatch, as in `egg_cycles`.
+pub fn hatch_steps(species: SpeciesId, magma_armor_or_flame_body: bool) -> u32 {
+    egg_cycles(species, magma_armor_or_flame_body) as u32 * STEPS_PER_CYCLE
+}
+
+/// The number of egg cycles left after walking a number of steps with an egg
+/// that needed some cycles to hatch.
+pub fn cycles_remaining(cycles: u8, steps: u32) -> u8 {
+    let walked = (steps / STEPS_PER_CYCLE).min(u8::MAX as u32) as u8;
+    cycles.saturating_sub(walked)
+}
+
+/// The chance that an egg hatches shiny, or that a wild Pokémon is shiny if
+/// `masuda` is false.
+///
+/// The Masuda method, breeding parents from games of different languages,
+/// rolls the PID 4 more times from Generation IV, and 5 from Generation V;
+/// the Shiny Charm, from Generation V, rolls it 2 more times. As usual, the
+/// chance is approximated by the number of rolls times the chance of each.
+/// There are no shiny Pokémon in Generation I.
+pub fn shiny_chance(masuda: bool, charm: bool, generation: Generation) -> Fraction {
+    let masuda_rolls = match generation {
+        Generation::IV if masuda => 4,
+        Generation::V if masuda => 5,
+        _ => 0,
+    };
+    let charm_rolls = if charm && generation >= Generation::V { 2 } else { 0 };
+    match generation {
+        Generation::I => (0, 1),
+        _ => (1 + masuda_rolls + charm_rolls, SHINY_ODDS),
+    }
+}
+
+/// The average number of eggs hatched until one is shiny, with the chance of
+/// `shiny_chance`, or `None` if none can be.
+pub fn expected_eggs_until_shiny(
+    masuda: bool, charm: bool, generation: Generation
+) -> Option<f64> {
+    let (numerator, denominator) = shiny_chance(masuda, charm, generation);
+    if numerator == 0 {
+        return None;
+    }
+    Some(denominator as f64 / numerator as f64)
+}
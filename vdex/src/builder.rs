@@ -0,0 +1,31 @@
+//! Validation shared by the builders of custom data: `MoveBuilder`,
+//! `ItemBuilder`, and `PokemonBuilder`.
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+
+/// Error building a move, item, or Pokémon with a field out of range or
+/// inconsistent with another.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    /// The name of the field.
+    pub field: &'static str,
+    pub reason: &'static str,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Invalid {}: {}", self.field, self.reason)
+    }
+}
+
+impl StdError for Error { }
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Fail with an error for a field unless a condition holds.
+pub(crate) fn check(
+    condition: bool, field: &'static str, reason: &'static str
+) -> Result<()> {
+    if condition { Ok(()) } else { Err(Error { field, reason }) }
+}
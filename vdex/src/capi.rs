@@ -0,0 +1,246 @@
+//! A C API over the global pokedex, with the `capi` feature, for engines in
+//! other languages to use vdex's data from a shared library.
+//!
+//! Build the library with
+//! `cargo rustc --release --features capi --crate-type cdylib`, and include
+//! `include/vdex.h`, which is generated from this module with
+//! `cbindgen --config cbindgen.toml --output include/vdex.h`.
+//!
+//! IDs and enums cross the API as their representations, as in `Enum::repr`
+//! and the `.0` of the ID types. Lookups that fail return `VDEX_NONE` or
+//! false. Names are copied into buffers of the caller's, like `snprintf`.
+
+use std::ffi::{c_char, CStr};
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokedex;
+use crate::pokemon::{Pokemon, SpeciesId};
+use crate::Type;
+
+/// The ID returned by failed lookups.
+pub const VDEX_NONE: u16 = u16::MAX;
+
+/// The second type of a Pokémon with only one type.
+pub const VDEX_NO_TYPE: u8 = u8::MAX;
+
+/// The base stats of a Pokémon, in the order of `pokemon::BaseStats`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct VdexBaseStats {
+    pub hp: u8,
+    pub attack: u8,
+    pub defense: u8,
+    pub speed: u8,
+    pub special_attack: u8,
+    pub special_defense: u8,
+}
+
+/// The properties of a move.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct VdexMove {
+    /// The representation of the move's `Type`.
+    pub typ: u8,
+    /// The power as in the Veekun data: 0 for moves without power, and 1 for
+    /// moves whose power varies.
+    pub power: u8,
+    /// The accuracy in percent, or 0 if the move cannot miss.
+    pub accuracy: u8,
+    pub pp: u8,
+    pub priority: i8,
+    /// The representation of the move's `DamageClass`.
+    pub damage_class: u8,
+}
+
+/// Read a C string, if it is not null and is UTF-8.
+unsafe fn read_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Copy a name into a buffer of a length including the terminating NUL,
+/// truncating it to fit, and return the length of the whole name.
+unsafe fn write_name(name: &str, buf: *mut c_char, len: usize) -> usize {
+    if !buf.is_null() && len > 0 {
+        let n = name.len().min(len - 1);
+        std::ptr::copy_nonoverlapping(name.as_ptr() as *const c_char, buf, n);
+        *buf.add(n) = 0;
+    }
+    name.len()
+}
+
+/// Get the default Pokémon of a species, if there is such a species.
+fn default_pokemon(id: u16) -> Option<&'static Pokemon> {
+    let species = &pokedex().species;
+    if !species.contains(SpeciesId(id)) {
+        return None;
+    }
+    species.pokemon(SpeciesId(id)).first()
+}
+
+/// Get the ID of a species by Veekun identifier, like `"mr-mime"`.
+///
+/// # Safety
+///
+/// `identifier` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_species_id(identifier: *const c_char) -> u16 {
+    read_str(identifier)
+        .and_then(|s| pokedex().species.identifiers().id(s))
+        .map_or(VDEX_NONE, |id| id.0)
+}
+
+/// Get the ID of a move by Veekun identifier, like `"karate-chop"`.
+///
+/// # Safety
+///
+/// `identifier` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_move_id(identifier: *const c_char) -> u16 {
+    read_str(identifier)
+        .and_then(|s| pokedex().moves.identifiers().id(s))
+        .map_or(VDEX_NONE, |id| id.0)
+}
+
+/// Get the ID of an item by Veekun identifier, like `"leftovers"`.
+///
+/// # Safety
+///
+/// `identifier` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_item_id(identifier: *const c_char) -> u16 {
+    read_str(identifier)
+        .and_then(|s| pokedex().items.identifiers().id(s))
+        .map_or(VDEX_NONE, |id| id.0)
+}
+
+/// Copy the name of a species into a buffer, returning the length of the
+/// name, or 0 if there is no such species.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_copy_species_name(
+    id: u16, buf: *mut c_char, len: usize
+) -> usize {
+    let species = &pokedex().species;
+    if !species.contains(SpeciesId(id)) {
+        return 0;
+    }
+    write_name(&species[SpeciesId(id)].name, buf, len)
+}
+
+/// Copy the name of a move into a buffer, returning the length of the name,
+/// or 0 if there is no such move.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_copy_move_name(
+    id: u16, buf: *mut c_char, len: usize
+) -> usize {
+    let moves = &pokedex().moves;
+    if !moves.contains(MoveId(id)) {
+        return 0;
+    }
+    write_name(&moves[MoveId(id)].name, buf, len)
+}
+
+/// Copy the name of an item into a buffer, returning the length of the name,
+/// or 0 if there is no such item.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_copy_item_name(
+    id: u16, buf: *mut c_char, len: usize
+) -> usize {
+    match pokedex().items.0.get(&ItemId(id)) {
+        Some(item) => write_name(&item.name, buf, len),
+        None => 0,
+    }
+}
+
+/// Get the damage modifier of a type against a type, like 2.0 for super
+/// effective, or -1.0 if either type is invalid.
+#[no_mangle]
+pub extern "C" fn vdex_type_modifier(damage: u8, target: u8) -> f64 {
+    match (Type::from_repr(damage), Type::from_repr(target)) {
+        (Some(damage), Some(target)) => {
+            pokedex().efficacy[(damage, target)].modifier()
+        },
+        _ => -1.0,
+    }
+}
+
+/// Get the types of a species' default Pokémon, with `VDEX_NO_TYPE` as the
+/// second type if it has one type, returning false if there is no such
+/// species.
+///
+/// # Safety
+///
+/// `first` and `second` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_species_types(
+    id: u16, first: *mut u8, second: *mut u8
+) -> bool {
+    let Some(pokemon) = default_pokemon(id) else {
+        return false;
+    };
+    *first = pokemon.types.first().repr();
+    *second = pokemon.types.second().map_or(VDEX_NO_TYPE, Type::repr);
+    true
+}
+
+/// Get the base stats of a species' default Pokémon, returning false if
+/// there is no such species.
+///
+/// # Safety
+///
+/// `stats` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_base_stats(
+    id: u16, stats: *mut VdexBaseStats
+) -> bool {
+    let Some(pokemon) = default_pokemon(id) else {
+        return false;
+    };
+    let base = &pokemon.stats;
+    *stats = VdexBaseStats {
+        hp: base.hp(),
+        attack: base.attack(),
+        defense: base.defense(),
+        speed: base.speed(),
+        special_attack: base.special_attack(),
+        special_defense: base.special_defense(),
+    };
+    true
+}
+
+/// Get the properties of a move, returning false if there is no such move.
+///
+/// # Safety
+///
+/// `mov` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn vdex_move(id: u16, mov: *mut VdexMove) -> bool {
+    let moves = &pokedex().moves;
+    if !moves.contains(MoveId(id)) {
+        return false;
+    }
+    let m = &moves[MoveId(id)];
+    *mov = VdexMove {
+        typ: m.typ.repr(),
+        power: m.power,
+        accuracy: m.accuracy.unwrap_or(0),
+        pp: m.pp,
+        priority: m.priority,
+        damage_class: m.damage_class.repr(),
+    };
+    true
+}
@@ -0,0 +1,122 @@
+//! Differences between a data source and the data included in the binary.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use crate::fingerprints::fingerprint;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata::{files, Embedded, Source};
+
+/// The differences between two versions of a CSV file, by entity: the first
+/// field of each record, e.g. a species ID. An entity changes if any of its
+/// records do.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TableDiff {
+    pub file: &'static str,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl TableDiff {
+    /// Compare two versions of a file record by record.
+    pub fn new(
+        file: &'static str, base: &[u8], other: &[u8]
+    ) -> vcsv::Result<Self> {
+        let Entities(base) = Entities::from_csv_data(base)
+            .map_err(|e| e.in_file(file))?;
+        let Entities(other) = Entities::from_csv_data(other)
+            .map_err(|e| e.in_file(file))?;
+        let mut diff = TableDiff { file, .. Default::default() };
+        for (key, records) in other.iter() {
+            match base.get(key) {
+                None => diff.added.push(key.clone()),
+                Some(base_records) if base_records != records => {
+                    diff.changed.push(key.clone())
+                },
+                Some(_) => (),
+            }
+        }
+        diff.removed = base.keys()
+            .filter(|key| !other.contains_key(*key))
+            .cloned()
+            .collect();
+        Ok(diff)
+    }
+
+    /// True if the versions have the same entities with the same records.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// The records of a file grouped by their first field, in order.
+struct Entities(BTreeMap<String, Vec<Vec<String>>>);
+
+impl vcsv::FromCsvIncremental for Entities {
+    fn from_empty_csv() -> Self { Entities(BTreeMap::new()) }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let key = record.get(0).unwrap_or_default().to_string();
+        let fields = record.iter().map(String::from).collect();
+        self.0.entry(key).or_default().push(fields);
+        Ok(())
+    }
+}
+
+/// The differences between the standard files (see `files::ALL`) of a source
+/// and those included in the binary. Files the source does not have are
+/// skipped.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DataDiff {
+    /// The files that differ, in the order of `files::ALL`.
+    pub tables: Vec<TableDiff>,
+}
+
+impl DataDiff {
+    /// Compare a source to the data included in the binary.
+    ///
+    /// Only files whose fingerprint differs from the included file's are
+    /// parsed and compared record by record.
+    pub fn from_embedded(source: &dyn Source) -> vcsv::Result<Self> {
+        let mut tables = Vec::new();
+        for &file in files::ALL.iter() {
+            let (data, base) = match (source.csv(file), Embedded.csv(file)) {
+                (Some(data), Some(base)) => (data, base),
+                _ => continue,
+            };
+            if embedded_fingerprints().get(file) == Some(&fingerprint(data)) {
+                continue;
+            }
+            let diff = TableDiff::new(file, base, data)?;
+            if !diff.is_empty() {
+                tables.push(diff);
+            }
+        }
+        Ok(DataDiff { tables })
+    }
+
+    /// True if no entity differs.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// The differences in a file, if any.
+    pub fn table(&self, file: &str) -> Option<&TableDiff> {
+        self.tables.iter().find(|t| t.file == file)
+    }
+}
+
+/// The fingerprints of the files included in the binary, computed once.
+fn embedded_fingerprints() -> &'static HashMap<&'static str, u64> {
+    static FINGERPRINTS: OnceLock<HashMap<&str, u64>> = OnceLock::new();
+    FINGERPRINTS.get_or_init(|| {
+        files::ALL.iter()
+            .filter_map(|&file| Some((file, Embedded.csv(file)?)))
+            .map(|(file, data)| (file, fingerprint(data)))
+            .collect()
+    })
+}
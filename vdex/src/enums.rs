@@ -16,6 +16,11 @@
 //! >
 //! > `fn from_repr(x: EnumReprType) -> Option<Self>`
 //!
+//! They implement the `Enum` trait, and are also inherent `const fn`s when
+//! `EnumReprType` is one of the integer types listed below, so const tables
+//! can be built on top of enums. Other types, like aliases, only get plain
+//! inherent functions.
+//!
 //! The real enum discriminant is usually forced to be `#[repr(isize)]`.
 //! If `u*` or `i*` types are used for the discriminant, the actual enum
 //! representation is made to be `#[repr(that_type_specified)]`.
@@ -31,9 +36,6 @@
 //!
 //! Discriminants can be implicit if `implicit = true` (default):
 //! ```
-//! # extern crate vdex;
-//! # extern crate enum_repr;
-//! #
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
 //! #
@@ -54,9 +56,6 @@
 //!
 //! Using implicit discriminants with the flag false is an error:
 //! ```compile_fail
-//! # extern crate vdex;
-//! # extern crate enum_repr;
-//! #
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
 //! #
@@ -72,8 +71,6 @@
 //! Take extra care to avoid collisions when using implicit discriminants:
 //! ```compile_fail
 //! # #![deny(overflowing_literals)]
-//! # extern crate vdex;
-//! # extern crate enum_repr;
 //! #
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
@@ -92,8 +89,6 @@
 //! Out of bound discriminants fail to compile:
 //! ```compile_fail
 //! # #![deny(overflowing_literals)]
-//! # extern crate vdex;
-//! # extern crate enum_repr;
 //! #
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
@@ -109,8 +104,6 @@
 //! Even if they are implicit:
 //! ```compile_fail
 //! # #![deny(overflowing_literals)]
-//! # extern crate vdex;
-//! # extern crate enum_repr;
 //! #
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
@@ -127,8 +120,6 @@
 //! Discriminants of a wrong type fail to compile as well:
 //! ```compile_fail
 //! # #![deny(overflowing_literals)]
-//! # extern crate vdex;
-//! # extern crate enum_repr;
 //! #
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
@@ -145,9 +136,6 @@
 //!
 //! Using the actual enum discriminant representation:
 //! ```
-//! # extern crate vdex;
-//! # extern crate enum_repr;
-//! #
 //! # use std::mem::size_of;
 //! #
 //! # use vdex::Enum;
@@ -165,9 +153,6 @@
 //!
 //! Prevent automatic derive with `derive = false`:
 //! ```
-//! # extern crate vdex;
-//! # extern crate enum_repr;
-//! #
 //! # use std::fmt;
 //! # use vdex::Enum;
 //! # use enum_repr::EnumRepr;
@@ -189,6 +174,56 @@
 //!     assert_eq!(format!("{:?}", Test::B), "Test(1)");
 //! }
 //! ```
+//!
+//! Building const tables:
+//! ```
+//! # use vdex::Enum;
+//! # use enum_repr::EnumRepr;
+//! #
+//! #[EnumRepr(type = "u8")]
+//! pub enum Test {
+//!     A = 1,
+//!     B,
+//! }
+//!
+//! const WEIGHTS: [u8; 3] = [0, Test::A.repr() * 10, Test::B.repr() * 10];
+//! const LAST: Option<Test> = Test::from_repr(WEIGHTS.len() as u8 - 1);
+//!
+//! fn main() {
+//!     assert_eq!(WEIGHTS[Test::B.repr() as usize], 20);
+//!     assert_eq!(LAST, Some(Test::B));
+//! }
+//! ```
+//!
+//! Generate `serde::Serialize` and `serde::Deserialize` with `serde = true`,
+//! which (de)serialize values as their variant names, e.g. `"InnerFocus"`,
+//! or as their reprs with `serde_as = "repr"` (`serde_as = "name"` is the
+//! default). The impls are only compiled with the `serde` feature of the
+//! crate using the macro, which must depend on `serde` under that feature.
+//!
+//! Generate a companion set type with `bitset = true`. `TestSet` stores one bit
+//! per variant, indexed by declaration order, and is `Copy`:
+//! ```
+//! # use vdex::Enum;
+//! # use enum_repr::EnumRepr;
+//! #
+//! #[EnumRepr(type = "i8", bitset = true)]
+//! pub enum Test {
+//!     A = -1,
+//!     B,
+//!     C,
+//! }
+//!
+//! fn main() {
+//!     let mut set = TestSet::new();
+//!     assert!(set.insert(Test::C));
+//!     assert!(!set.insert(Test::C));
+//!     let other: TestSet = vec![Test::A, Test::C].into_iter().collect();
+//!     assert!(set.union(other).contains(Test::A));
+//!     assert_eq!(other.difference(set).iter().collect::<Vec<_>>(), [Test::A]);
+//!     assert_eq!(format!("{:?}", TestSet::all()), "{A, B, C}");
+//! }
+//! ```
 
 pub use enum_repr::EnumRepr;
 
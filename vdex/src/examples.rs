@@ -0,0 +1,27 @@
+//! Worked examples that wire the major subsystems together.
+//!
+//! Each example is also in the crate's `examples/` directory, where it can be
+//! run with `cargo run --example`, and each is tested with `cargo test`, so
+//! they keep working as the API changes.
+//!
+//! # Damage calculation
+//!
+//! Stored Pokémon become battle `Monster`s with [`Monster::from_stored`],
+//! whose stats come from [`StoredPokemon::stats`], and `battle::damage`
+//! calculates the damage between them.
+//!
+//! [`Monster::from_stored`]: crate::battle::Monster::from_stored
+//! [`StoredPokemon::stats`]: crate::stored::StoredPokemon::stats
+#![doc = concat!("```\n", include_str!("../examples/damage_calc.rs"), "```")]
+//!
+//! # Team validation
+//!
+//! A `team::Team` parses from and writes to the Pokémon Showdown text format,
+//! and validates against a `team::Ruleset`.
+#![doc = concat!("```\n", include_str!("../examples/team_validation.rs"), "```")]
+//!
+//! # Dex browser
+//!
+//! Species are looked up by Veekun identifier, and `analysis` computes their
+//! defensive profiles.
+#![doc = concat!("```\n", include_str!("../examples/dex_browser.rs"), "```")]
@@ -0,0 +1,88 @@
+//! Experience and leveling, with Generation V rules.
+
+use crate::items::ItemId;
+use crate::pokedex;
+use crate::pokemon::{GrowthRate, Pokemon};
+
+/// The highest level a Pokémon can reach.
+pub const MAX_LEVEL: u8 = 100;
+
+/// The total experience a Pokémon with a growth rate needs to reach a level.
+///
+/// Level 1 needs no experience, and levels above `MAX_LEVEL` need as much as
+/// `MAX_LEVEL`.
+pub fn exp_for_level(rate: GrowthRate, level: u8) -> u32 {
+    let n = level.min(MAX_LEVEL) as i64;
+    if n <= 1 {
+        return 0;
+    }
+    let cube = n * n * n;
+    let exp = match rate {
+        GrowthRate::Slow => cube * 5 / 4,
+        GrowthRate::Medium => cube,
+        GrowthRate::Fast => cube * 4 / 5,
+        GrowthRate::MediumSlow => cube * 6 / 5 - 15 * n * n + 100 * n - 140,
+        GrowthRate::SlowThenVeryFast => match n {
+            0..=49 => cube * (100 - n) / 50,
+            50..=67 => cube * (150 - n) / 100,
+            68..=97 => cube * ((1911 - 10 * n) / 3) / 500,
+            _ => cube * (160 - n) / 100,
+        },
+        GrowthRate::FastThenVerySlow => match n {
+            0..=14 => cube * ((n + 1) / 3 + 24) / 50,
+            15..=35 => cube * (n + 14) / 50,
+            _ => cube * (n / 2 + 32) / 50,
+        },
+    };
+    exp.max(0) as u32
+}
+
+/// The level of a Pokémon with a growth rate and a total experience.
+pub fn level_for_exp(rate: GrowthRate, exp: u32) -> u8 {
+    (2..=MAX_LEVEL)
+        .take_while(|&level| exp_for_level(rate, level) <= exp)
+        .last()
+        .unwrap_or(1)
+}
+
+/// The Lucky Egg, which boosts the experience gained by its holder.
+pub fn lucky_egg() -> ItemId {
+    pokedex().items.identifiers().id("lucky-egg")
+        .expect("Lucky Egg should be in the items table.")
+}
+
+/// Whether a held item boosts the experience gained by its holder.
+pub fn boosts_exp(item: Option<ItemId>) -> bool {
+    item == Some(lucky_egg())
+}
+
+/// Scale a level for the Generation V formula, as the games do: the 2.5th
+/// power, rounded down.
+fn scale(level: u64) -> u64 {
+    (level as f64).powf(2.5) as u64
+}
+
+/// The experience a Pokémon at `recipient_level` gains for defeating a wild
+/// Pokémon at `level`, shared between a number of `participants`.
+///
+/// The experience is multiplied by 1.5 if the recipient was traded, and again
+/// if it holds a Lucky Egg, as in `boosts_exp`. Defeating a trainer's Pokémon
+/// gives 1.5 times as much, and a Pokémon traded from a game of another
+/// language gains 1.7 times as much instead of 1.5; callers apply these.
+pub fn exp_gain(
+    defeated: &Pokemon, level: u8, recipient_level: u8, participants: u8,
+    traded: bool, lucky_egg: bool,
+) -> u32 {
+    let level = level as u64;
+    let participants = participants.max(1) as u64;
+    let base = defeated.base_exp as u64 * level / (5 * participants);
+    let mut exp = base * scale(2 * level + 10)
+        / scale(level + recipient_level as u64 + 10) + 1;
+    if traded {
+        exp = exp * 3 / 2;
+    }
+    if lucky_egg {
+        exp = exp * 3 / 2;
+    }
+    exp as u32
+}
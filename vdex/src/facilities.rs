@@ -0,0 +1,112 @@
+//! The opponents of battle facilities like the Battle Subway, from the
+//! auxiliary `facility_sets.csv` (see `FacilityTable`).
+
+use std::ops::RangeInclusive;
+use crate::enums::*;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokemon::SpeciesId;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::{FromVeekun, Nature, VeekunOption};
+
+/// A battle facility.
+#[EnumRepr(type = "u8")]
+pub enum Facility {
+    /// The Battle Tower of Generations III and IV.
+    BattleTower = 1,
+    /// The other facilities of the Battle Frontier, in Emerald and
+    /// HeartGold and SoulSilver.
+    BattleFrontier,
+    /// The Battle Subway of Generation V.
+    BattleSubway,
+}
+
+impl FromVeekun for Facility {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// A Pokémon that trainers at a facility can use.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FacilitySet {
+    pub facility: Facility,
+    /// The battles of a streak, counting from 1, in which trainers can use
+    /// the Pokémon.
+    pub battles: RangeInclusive<u16>,
+    pub species: SpeciesId,
+    pub nature: Nature,
+    pub item: Option<ItemId>,
+    pub moves: Vec<MoveId>,
+}
+
+/// The Pokémon of facility trainers, from the auxiliary `facility_sets.csv`,
+/// with the columns `id`, `facility`, `min_battle`, `max_battle`,
+/// `species_id`, `nature_id`, `item_id`, and `move1_id` to `move4_id`, by
+/// Veekun ID. Empty items and moves are omitted.
+///
+/// The file is not included in the binary; provide it through a data source
+/// (see `veekun::data::Overlay`).
+#[derive(Default)]
+pub struct FacilityTable(pub Vec<FacilitySet>);
+
+impl FacilityTable {
+    /// Create a facility table from the CSV data in a source. The table is
+    /// empty if the source does not have the file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        match source.csv(vdata::files::FACILITY_SETS) {
+            Some(data) => Self::from_csv_data(data),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// The Pokémon trainers at a facility can use in a battle of a streak.
+    pub fn opponents(
+        &self, facility: Facility, battle: u16
+    ) -> impl Iterator<Item = &FacilitySet> {
+        self.in_streak(facility, battle..=battle)
+    }
+
+    /// The Pokémon trainers at a facility can use in any battle of a range of
+    /// a streak.
+    pub fn in_streak(
+        &self, facility: Facility, streak: RangeInclusive<u16>
+    ) -> impl Iterator<Item = &FacilitySet> {
+        self.0.iter().filter(move |set| {
+            set.facility == facility
+                && set.battles.start() <= streak.end()
+                && streak.start() <= set.battles.end()
+        })
+    }
+}
+
+impl vcsv::FromCsvIncremental for FacilityTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let min_battle = vcsv::from_field(&record, 2)?;
+        let max_battle = vcsv::from_field(&record, 3)?;
+        let item: VeekunOption<_> = vcsv::from_field(&record, 6)?;
+        let mut moves = Vec::new();
+        for i in 7..11 {
+            if let VeekunOption(Some(id)) = vcsv::from_field(&record, i)? {
+                moves.push(id);
+            }
+        }
+        self.0.push(FacilitySet {
+            facility: vcsv::from_field(&record, 1)?,
+            battles: min_battle..=max_battle,
+            species: vcsv::from_field(&record, 4)?,
+            nature: vcsv::from_field(&record, 5)?,
+            item: item.into(),
+            moves,
+        });
+        Ok(())
+    }
+}
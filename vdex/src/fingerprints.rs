@@ -0,0 +1,36 @@
+//! Hashes of data files and sources, to tell whether two differ.
+//!
+//! The hashes use SipHash-1-3 with fixed keys, so unlike `DefaultHasher`
+//! they agree between builds with different Rust versions.
+
+use std::hash::Hasher;
+use siphasher::sip::SipHasher13;
+use crate::vdata::{files, Source};
+
+/// The hasher for fingerprints and battle state hashes.
+pub(crate) fn stable_hasher() -> SipHasher13 {
+    SipHasher13::new_with_keys(0, 0)
+}
+
+/// A hash of the contents of a file, to tell whether two files differ.
+pub fn fingerprint(data: &[u8]) -> u64 {
+    let mut hasher = stable_hasher();
+    hasher.write(data);
+    hasher.finish()
+}
+
+/// A hash of the contents of the standard files of a source (see
+/// `veekun::data::files::ALL`), to tell whether two processes use the same
+/// data.
+pub fn source_fingerprint(source: &dyn Source) -> u64 {
+    let mut hasher = stable_hasher();
+    for &file in files::ALL.iter() {
+        if let Some(data) = source.csv(file) {
+            hasher.write(&(file.len() as u64).to_le_bytes());
+            hasher.write(file.as_bytes());
+            hasher.write(&(data.len() as u64).to_le_bytes());
+            hasher.write(data);
+        }
+    }
+    hasher.finish()
+}
@@ -0,0 +1,87 @@
+//! Happiness, also known as friendship, with Generation V rules.
+
+use crate::items::ItemId;
+use crate::pokedex;
+use crate::pokemon::SpeciesId;
+
+/// The highest happiness a Pokémon can have.
+pub const MAX_HAPPINESS: u8 = 255;
+
+/// The happiness most species need to evolve by happiness.
+pub const EVOLUTION_HAPPINESS: u8 = 220;
+
+/// Something that changes a Pokémon's happiness.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HappinessEvent {
+    /// Walking with the Pokémon in the party, each time the steps are counted.
+    Walk,
+    LevelUp,
+    /// Using a vitamin, like HP Up, on the Pokémon.
+    Vitamin,
+    Faint,
+}
+
+impl HappinessEvent {
+    /// The change in happiness from the event for a Pokémon with a happiness
+    /// below 100, below 200, and otherwise.
+    pub fn deltas(self) -> [i8; 3] {
+        match self {
+            HappinessEvent::Walk => [1, 1, 1],
+            HappinessEvent::LevelUp => [5, 3, 2],
+            HappinessEvent::Vitamin => [5, 3, 2],
+            HappinessEvent::Faint => [-1, -1, -1],
+        }
+    }
+
+    /// The change in happiness from the event for a Pokémon with a happiness,
+    /// holding an item or not.
+    ///
+    /// Increases are multiplied by 1.5, rounding down, if the item boosts
+    /// happiness, as in `boosts_happiness`.
+    pub fn delta(self, happiness: u8, item: Option<ItemId>) -> i8 {
+        let delta = self.deltas()[(happiness / 100).min(2) as usize];
+        if delta > 0 && boosts_happiness(item) {
+            delta * 3 / 2
+        } else {
+            delta
+        }
+    }
+
+    /// Apply the event to a Pokémon with a happiness, holding an item or not,
+    /// to get its new happiness.
+    pub fn apply(self, happiness: u8, item: Option<ItemId>) -> u8 {
+        let delta = self.delta(happiness, item);
+        if delta < 0 {
+            happiness.saturating_sub(delta.unsigned_abs())
+        } else {
+            happiness.saturating_add(delta as u8)
+        }
+    }
+}
+
+/// The Soothe Bell, which boosts the happiness gained by its holder.
+pub fn soothe_bell() -> ItemId {
+    pokedex().items.identifiers().id("soothe-bell")
+        .expect("Soothe Bell should be in the items table.")
+}
+
+/// Whether a held item boosts the happiness gained by its holder.
+pub fn boosts_happiness(item: Option<ItemId>) -> bool {
+    item == Some(soothe_bell())
+}
+
+/// The happiness a Pokémon of a species starts with when caught or hatched.
+pub fn base_happiness(species: SpeciesId) -> u8 {
+    pokedex().species[species].base_happiness
+}
+
+/// The lowest happiness at which a species can evolve, or `None` if it does
+/// not evolve by happiness.
+///
+/// Other conditions may apply, like the level up being at night for Umbreon.
+pub fn evolves_by_happiness(species: SpeciesId) -> Option<u8> {
+    let table = &pokedex().species;
+    table.evolves_into(species).iter()
+        .filter_map(|&id| table[id].evolves_from?.happiness)
+        .min()
+}
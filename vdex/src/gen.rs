@@ -0,0 +1,63 @@
+//! Random generation of Pokémon, for randomizers and challenge-run tools.
+
+use std::ops::RangeInclusive;
+use crate::pokedex;
+use crate::pokemon::{EggGroup, Species, SpeciesId, SPECIES_COUNT};
+use crate::Type;
+use crate::versions::Generation;
+
+/// Constraints on a randomly chosen species. The default allows every
+/// species.
+///
+/// Types and base stats are those of the species' default Pokémon.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Constraints {
+    /// The generation the species was introduced in.
+    pub generation: Option<Generation>,
+    /// A type the species must have.
+    pub typ: Option<Type>,
+    /// An egg group the species must be in.
+    pub egg_group: Option<EggGroup>,
+    /// Whether the species must not evolve any further.
+    pub fully_evolved: bool,
+    /// The range the species' base stat total must be in.
+    pub stat_total: Option<RangeInclusive<u16>>,
+}
+
+impl Constraints {
+    /// True if a species satisfies the constraints.
+    pub fn allows(&self, species: &Species) -> bool {
+        let pokemon = match pokedex().species.pokemon(species.id).first() {
+            Some(pokemon) => pokemon,
+            None => return false,
+        };
+        self.generation.is_none_or(|g| species.generation == g)
+            && self.typ.is_none_or(|t| pokemon.types.contains(t))
+            && self.egg_group.is_none_or(|e| species.egg_groups.contains(e))
+            && (!self.fully_evolved
+                || pokedex().species.is_fully_evolved(species.id))
+            && self.stat_total.as_ref()
+                .is_none_or(|range| range.contains(&pokemon.stats.total()))
+    }
+}
+
+/// Every species that satisfies some constraints, in order of id.
+pub fn candidates(constraints: &Constraints) -> Vec<SpeciesId> {
+    let species = &pokedex().species;
+    (0..SPECIES_COUNT)
+        .map(|i| SpeciesId(i as u16))
+        .filter(|&id| constraints.allows(&species[id]))
+        .collect()
+}
+
+/// Choose a random species that satisfies some constraints, or `None` if no
+/// species does.
+pub fn random_pokemon<R: rand::Rng>(
+    rng: &mut R, constraints: &Constraints
+) -> Option<SpeciesId> {
+    let candidates = candidates(constraints);
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(candidates[rng.gen_range(0, candidates.len())])
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+/// A bidirectional map between Veekun `kebab-case` identifiers and IDs, for
+/// interoperating with other Veekun-derived tools.
+#[derive(Clone, Debug)]
+pub struct IdentifierMap<Id: Copy + Eq + Hash> {
+    ids: HashMap<String, Id>,
+    identifiers: HashMap<Id, String>,
+}
+
+impl<Id: Copy + Eq + Hash> IdentifierMap<Id> {
+    /// Get the ID with an identifier, e.g. `"karate-chop"`.
+    pub fn id(&self, identifier: &str) -> Option<Id> {
+        self.ids.get(identifier).cloned()
+    }
+
+    /// Get the identifier of an ID.
+    pub fn identifier(&self, id: Id) -> Option<&str> {
+        self.identifiers.get(&id).map(|s| s.as_str())
+    }
+
+    /// The number of identifiers, which is also the number of IDs.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// True if the map has no identifiers.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Map an identifier to an ID, replacing any ID it mapped to and any
+    /// identifier the ID had.
+    pub(crate) fn insert(&mut self, identifier: &str, id: Id) {
+        if let Some(old) = self.ids.insert(identifier.to_string(), id) {
+            self.identifiers.remove(&old);
+        }
+        let previous = self.identifiers.insert(id, identifier.to_string());
+        if let Some(previous) = previous.filter(|p| p != identifier) {
+            self.ids.remove(&previous);
+        }
+    }
+
+    /// Iterate over the identifiers and IDs, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Id)> {
+        self.ids.iter().map(|(identifier, &id)| (identifier.as_str(), id))
+    }
+}
+
+impl<Id: Copy + Eq + Hash> Default for IdentifierMap<Id> {
+    fn default() -> Self {
+        IdentifierMap { ids: HashMap::new(), identifiers: HashMap::new() }
+    }
+}
+
+impl<'a, Id: Copy + Eq + Hash> FromIterator<(&'a str, Id)>
+        for IdentifierMap<Id> {
+    /// Collect pairs of identifiers and IDs, skipping empty identifiers.
+    fn from_iter<I: IntoIterator<Item = (&'a str, Id)>>(iter: I) -> Self {
+        let mut map = IdentifierMap::default();
+        let pairs = iter.into_iter().filter(|(s, _)| !s.is_empty());
+        for (identifier, id) in pairs {
+            map.insert(identifier, id);
+        }
+        map
+    }
+}
@@ -0,0 +1,5 @@
+//! Loaders for data from other Pokémon tools, matched against vdex IDs.
+//!
+//! For the Pokémon Showdown team format, see `team::Team::from_showdown`.
+
+pub mod smogon_usage;
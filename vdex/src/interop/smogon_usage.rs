@@ -0,0 +1,308 @@
+//! Smogon usage statistics, for weighting decisions by what players actually
+//! use.
+//!
+//! Smogon publishes monthly statistics for each format in several files:
+//!
+//! - The usage file (e.g. `gen5ou-1500.txt`) ranks Pokémon by usage.
+//! - The moveset file (e.g. `moveset/gen5ou-1500.txt`) lists the abilities,
+//!   items, and moves of each Pokémon.
+//! - The chaos file (e.g. `chaos/gen5ou-1500.json`) has both in JSON. It is
+//!   loaded with the `smogon-json` feature.
+//!
+//! Names are matched against the global pokedex ignoring case, spaces, and
+//! punctuation. Names that match nothing in vdex, like Smogon's "Other" and
+//! "Nothing" or things from later generations, are skipped.
+
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use crate::Ability;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokemon::SpeciesId;
+use crate::team::{find_ability, find_item, find_move, find_species};
+
+/// Error loading usage statistics.
+#[derive(Debug)]
+pub enum Error {
+    /// A line of a text file could not be parsed.
+    Parse {
+        /// The line number, starting at 1.
+        line: usize,
+        reason: &'static str,
+    },
+    /// A chaos file is not valid JSON.
+    #[cfg(feature = "smogon-json")]
+    Json(serde_json::Error),
+    /// A chaos file lacks a required field, like `data`.
+    #[cfg(feature = "smogon-json")]
+    MissingField(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Error::Parse { line, reason } => {
+                write!(f, "Error on line {}: {}", line, reason)
+            },
+            #[cfg(feature = "smogon-json")]
+            Error::Json(error) => write!(f, "{}", error),
+            #[cfg(feature = "smogon-json")]
+            Error::MissingField(field) => write!(f, "Missing field {}", field),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            #[cfg(feature = "smogon-json")]
+            Error::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "smogon-json")]
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Json(error)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The usage statistics of one form of a species.
+///
+/// Percentages are out of 100. The ability, item, and move lists are sorted
+/// from most to least common.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UsageStats {
+    /// The Veekun ID of the form.
+    pub form: u16,
+    /// The weighted percentage of teams with the form.
+    pub usage: f64,
+    /// The number of times the form was used, unweighted.
+    pub raw_count: u64,
+    /// The percentage of the form's sets with each ability.
+    pub abilities: Vec<(Ability, f64)>,
+    /// The percentage of the form's sets with each item.
+    pub items: Vec<(ItemId, f64)>,
+    /// The percentage of the form's sets with each move.
+    pub moves: Vec<(MoveId, f64)>,
+}
+
+/// Usage statistics by species.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UsageTable(pub HashMap<SpeciesId, Vec<UsageStats>>);
+
+impl UsageTable {
+    /// Get the statistics of a species' most used form.
+    pub fn get(&self, species: SpeciesId) -> Option<&UsageStats> {
+        self.forms(species).first()
+    }
+
+    /// Get the statistics of each form of a species with any, from most to
+    /// least used.
+    pub fn forms(&self, species: SpeciesId) -> &[UsageStats] {
+        self.0.get(&species).map_or(&[], |forms| &forms[..])
+    }
+
+    /// Get the species with statistics, from most to least used.
+    pub fn ranked(&self) -> Vec<SpeciesId> {
+        let mut species: Vec<_> = self.0.keys().cloned().collect();
+        species.sort_by(|a, b| {
+            let usage = |s| self.get(s).map_or(0.0, |stats| stats.usage);
+            usage(*b).total_cmp(&usage(*a)).then(a.cmp(b))
+        });
+        species
+    }
+
+    /// Get the statistics of a form, adding them if they are missing.
+    fn entry(&mut self, species: SpeciesId, form: u16) -> &mut UsageStats {
+        let forms = self.0.entry(species).or_default();
+        let index = match forms.iter().position(|s| s.form == form) {
+            Some(index) => index,
+            None => {
+                forms.push(UsageStats { form, .. Default::default() });
+                forms.len() - 1
+            },
+        };
+        &mut forms[index]
+    }
+
+    /// Sort each species' forms and lists from most to least used.
+    fn sort(&mut self) {
+        fn by_percent<T>(list: &mut [(T, f64)]) {
+            list.sort_by(|a, b| b.1.total_cmp(&a.1));
+        }
+        for forms in self.0.values_mut() {
+            forms.sort_by(|a, b| b.usage.total_cmp(&a.usage));
+            for stats in forms {
+                by_percent(&mut stats.abilities);
+                by_percent(&mut stats.items);
+                by_percent(&mut stats.moves);
+            }
+        }
+    }
+
+    /// Add the usage percentages and raw counts of a usage file.
+    pub fn add_usage_text(&mut self, text: &str) -> Result<()> {
+        for (i, line) in text.lines().enumerate() {
+            let parse_error = |reason| Error::Parse { line: i + 1, reason };
+            let cells = match table_cells(line) {
+                Some(cells) => cells,
+                None => continue,
+            };
+            // Skip the header, "| Rank | Pokemon | Usage % | Raw | ...".
+            if cells.len() < 4 || cells[0].parse::<u32>().is_err() {
+                continue;
+            }
+            let (species, form) = match find_species(cells[1]) {
+                Some(found) => found,
+                None => continue,
+            };
+            let usage = parse_percent(cells[2])
+                .ok_or_else(|| parse_error("invalid usage percentage"))?;
+            let raw_count = cells[3].parse()
+                .map_err(|_| parse_error("invalid raw count"))?;
+            let stats = self.entry(species, form);
+            stats.usage = usage;
+            stats.raw_count = raw_count;
+        }
+        self.sort();
+        Ok(())
+    }
+
+    /// Add the abilities, items, moves, and raw counts of a moveset file.
+    pub fn add_moveset_text(&mut self, text: &str) -> Result<()> {
+        let mut current: Option<(SpeciesId, u16)> = None;
+        let mut section = Section::Other;
+        let mut after_border = false;
+        for (i, line) in text.lines().enumerate() {
+            let parse_error = |reason| Error::Parse { line: i + 1, reason };
+            let cell = match table_cells(line) {
+                Some(cells) => cells.join(" "),
+                None => {
+                    after_border = line.trim_start().starts_with('+');
+                    continue;
+                },
+            };
+            let heading = after_border;
+            after_border = false;
+            if let Some(count) = cell.strip_prefix("Raw count:") {
+                if let Some((species, form)) = current {
+                    self.entry(species, form).raw_count = count.trim().parse()
+                        .map_err(|_| parse_error("invalid raw count"))?;
+                }
+                continue;
+            }
+            if heading {
+                section = match &cell[..] {
+                    "Abilities" => Section::Abilities,
+                    "Items" => Section::Items,
+                    "Moves" => Section::Moves,
+                    "Spreads" | "Teammates" | "Checks and Counters" => {
+                        Section::Other
+                    },
+                    _ if cell.contains(':') => continue,
+                    name => {
+                        current = find_species(name);
+                        Section::Other
+                    },
+                };
+                continue;
+            }
+            let (species, form) = match current {
+                Some(current) if section != Section::Other => current,
+                _ => continue,
+            };
+            let (name, percent) = match cell.rsplit_once(' ') {
+                Some((name, percent)) => (name, parse_percent(percent)
+                    .ok_or_else(|| parse_error("invalid percentage"))?),
+                None => return Err(parse_error("missing percentage")),
+            };
+            let stats = self.entry(species, form);
+            match section {
+                Section::Abilities => if let Some(a) = find_ability(name) {
+                    stats.abilities.push((a, percent));
+                },
+                Section::Items => if let Some(i) = find_item(name) {
+                    stats.items.push((i, percent));
+                },
+                Section::Moves => if let Some(m) = find_move(name) {
+                    stats.moves.push((m, percent));
+                },
+                Section::Other => (),
+            }
+        }
+        self.sort();
+        Ok(())
+    }
+
+    /// Load a chaos file.
+    ///
+    /// Chaos files give weighted counts rather than percentages; they are
+    /// converted to percentages of each form's total ability count.
+    #[cfg(feature = "smogon-json")]
+    pub fn from_chaos_json(json: &str) -> Result<Self> {
+        use serde_json::Value;
+        let root: Value = serde_json::from_str(json)?;
+        let data = root.get("data").and_then(Value::as_object)
+            .ok_or(Error::MissingField("data"))?;
+        let mut table = UsageTable::default();
+        for (name, entry) in data {
+            let (species, form) = match find_species(name) {
+                Some(found) => found,
+                None => continue,
+            };
+            let counts = |key| entry.get(key).and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .filter_map(|(name, count)| Some((name, count.as_f64()?)));
+            let total: f64 = counts("Abilities").map(|(_, count)| count).sum();
+            let percent = |count: f64| if total > 0.0 {
+                count / total * 100.0
+            } else {
+                0.0
+            };
+            let stats = table.entry(species, form);
+            stats.usage = entry.get("usage").and_then(Value::as_f64)
+                .map_or(0.0, |usage| usage * 100.0);
+            stats.raw_count = entry.get("Raw count").and_then(Value::as_u64)
+                .unwrap_or(0);
+            stats.abilities = counts("Abilities")
+                .filter_map(|(n, c)| Some((find_ability(n)?, percent(c))))
+                .collect();
+            stats.items = counts("Items")
+                .filter_map(|(n, c)| Some((find_item(n)?, percent(c))))
+                .collect();
+            stats.moves = counts("Moves")
+                .filter_map(|(n, c)| Some((find_move(n)?, percent(c))))
+                .collect();
+        }
+        table.sort();
+        Ok(table)
+    }
+}
+
+/// The section of a moveset file entry being read.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Section {
+    Abilities,
+    Items,
+    Moves,
+    Other,
+}
+
+/// Split a table row like "| 1 | Garchomp | 25.123% |" into its trimmed
+/// cells, or return `None` if the line is not a row.
+fn table_cells(line: &str) -> Option<Vec<&str>> {
+    let row = line.trim().strip_prefix('|')?.strip_suffix('|')?;
+    Some(row.split('|').map(str::trim).collect())
+}
+
+/// Parse a percentage like "25.123%".
+fn parse_percent(s: &str) -> Option<f64> {
+    s.trim().strip_suffix('%')?.trim().parse().ok()
+}
@@ -2,7 +2,7 @@ use crate::enums::*;
 use crate::FromVeekun;
 
 /// Broad item category; not used for anything other than organization.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Category {
     /// X *Stat*, Dire Hit, and Guard Spec.
     StatBoosts = 1,
@@ -133,7 +133,7 @@ impl FromVeekun for Category {
 }
 
 /// Bag pocket in which items are stored.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Pocket {
     Misc = 0,
     Medicine,
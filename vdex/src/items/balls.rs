@@ -0,0 +1,80 @@
+use crate::pokedex;
+use crate::pokemon::{Gender, SpeciesId};
+use crate::Type;
+use super::Item;
+
+/// The modifier of a Poké Ball that always catches, like the Master Ball.
+pub const GUARANTEED_CATCH: f64 = 255.0;
+
+/// The circumstances of a catch that conditional Poké Balls depend on.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CatchContext {
+    /// The species of the Pokémon being caught.
+    pub species: SpeciesId,
+    pub level: u8,
+    pub gender: Gender,
+    /// The turns that have passed in the battle, 0 on the first turn.
+    pub turns: u16,
+    /// Whether it is night, or the battle is in a cave.
+    pub night_or_cave: bool,
+    /// Whether the battle is underwater or on water.
+    pub underwater: bool,
+    /// Whether the player has caught the species before.
+    pub caught_before: bool,
+    /// The species of the player's active Pokémon.
+    pub user_species: SpeciesId,
+    /// The level of the player's active Pokémon.
+    pub user_level: u8,
+    /// The gender of the player's active Pokémon.
+    pub user_gender: Gender,
+}
+
+/// The catch rate modifier of a Poké Ball in some circumstances, as of
+/// Generation V, or as of HeartGold and SoulSilver for the Apricorn balls.
+///
+/// Balls that always catch have `GUARANTEED_CATCH`. Items that are not
+/// Poké Balls, balls with no effect on the catch rate, and balls whose
+/// condition depends on an unknown species have 1.
+pub fn ball_modifier(item: &Item, ctx: &CatchContext) -> f64 {
+    let condition = |met: bool, modifier: f64| {
+        if met { modifier } else { 1.0 }
+    };
+    match item.identifier.as_str() {
+        "master-ball" | "park-ball" => GUARANTEED_CATCH,
+        "ultra-ball" => 2.0,
+        "great-ball" | "safari-ball" | "sport-ball" => 1.5,
+        "net-ball" => {
+            let species = &pokedex().species;
+            let water_or_bug = species.contains(ctx.species)
+                && species.pokemon(ctx.species).first().is_some_and(|p| {
+                    p.types.contains(Type::Water) || p.types.contains(Type::Bug)
+                });
+            condition(water_or_bug, 3.0)
+        },
+        "dive-ball" => condition(ctx.underwater, 3.5),
+        "dusk-ball" => condition(ctx.night_or_cave, 3.5),
+        "quick-ball" => condition(ctx.turns == 0, 5.0),
+        "timer-ball" => {
+            (1.0 + f64::from(ctx.turns) * 1229.0 / 4096.0).min(4.0)
+        },
+        "nest-ball" => (f64::from(41 - ctx.level.min(40)) / 10.0).max(1.0),
+        "repeat-ball" => condition(ctx.caught_before, 3.0),
+        "level-ball" => {
+            let (user, target) = (ctx.user_level, ctx.level);
+            if user / 4 >= target {
+                8.0
+            } else if user / 2 >= target {
+                4.0
+            } else {
+                condition(user > target, 2.0)
+            }
+        },
+        "love-ball" => {
+            let opposite = matches!(
+                (ctx.gender, ctx.user_gender),
+                (Gender::Female, Gender::Male) | (Gender::Male, Gender::Female));
+            condition(opposite && ctx.species == ctx.user_species, 8.0)
+        },
+        _ => 1.0,
+    }
+}
@@ -44,7 +44,7 @@ pub enum ContestType {
 /// > example, all Pokémon who like spicy flavors have a Nature that raises
 /// > their Attack, while those that dislike spicy flavors have a Nature that
 /// > lowers it.  Pokémon who have neutral Natures have no likes or dislikes.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Flavor {
     Spicy = 0,
     Sour,
@@ -88,14 +88,21 @@ impl FromVeekun for Flavor {
     }
 }
 
-/// The total number of berries in pbirch.
-pub const BERRY_COUNT: usize = 64;
+include!(concat!(env!("OUT_DIR"), "/berry_count.rs"));
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct BerryId(pub u8);
 
 impl Default for BerryId {
-    fn default() -> Self { BerryId(std::u8::MAX) }
+    fn default() -> Self { BerryId(u8::MAX) }
+}
+
+impl BerryId {
+    /// True if the ID is that of a berry, rather than a placeholder like the
+    /// default.
+    pub fn is_valid(self) -> bool {
+        (self.0 as usize) < BERRY_COUNT
+    }
 }
 
 impl FromVeekun for BerryId {
@@ -128,13 +135,30 @@ pub struct Berry {
     pub flavor: Option<Flavor>,
 }
 
-pub struct BerryTable(pub [Berry; BERRY_COUNT]);
+/// Wrapper of an array of all berries, indexed by `BerryId`.
+///
+/// Use `table.0` to access the array.
+pub struct BerryTable(pub [Berry; BERRY_COUNT], [Vec<ItemId>; Flavor::COUNT]);
 
 impl BerryTable {
+    /// Create a berry table from the included CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
     pub fn new() -> Self {
-        let mut table = BerryTable::from_csv_data(vdata::BERRIES).unwrap();
-        table.set_flavors(&BerryFlavorTable::new());
-        table
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Create a berry table from the CSV data in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut table
+            = BerryTable::from_csv_source(source, vdata::files::BERRIES)?;
+        table.set_flavors(&BerryFlavorTable::load(source)?);
+        Ok(table)
+    }
+
+    /// The items of the berries whose dominant flavor is `flavor`, sorted by
+    /// ID.
+    pub fn by_flavor(&self, flavor: Flavor) -> &[ItemId] {
+        &self.1[flavor.repr() as usize]
     }
 
     fn set_flavors(&mut self, flavors: &BerryFlavorTable) {
@@ -151,13 +175,19 @@ impl BerryTable {
                 }
             }
             self.0[id].flavor = max_flavor;
+            if let Some(flavor) = max_flavor {
+                self.1[flavor.repr() as usize].push(self.0[id].item);
+            }
+        }
+        for items in self.1.iter_mut() {
+            items.sort();
         }
     }
 }
 
 impl Default for BerryTable {
     fn default() -> Self {
-        BerryTable([Default::default(); BERRY_COUNT])
+        BerryTable([Default::default(); BERRY_COUNT], Default::default())
     }
 }
 
@@ -165,7 +195,7 @@ impl vcsv::FromCsvIncremental for BerryTable {
     fn from_empty_csv() -> Self { Default::default() }
     
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id: BerryId = vcsv::from_field(&record, 0)?;
         self[id] = Berry {
@@ -201,8 +231,8 @@ pub struct BerryFlavorTable {
 }
 
 impl BerryFlavorTable {
-    pub fn new() -> Self {
-        BerryFlavorTable::from_csv_data(vdata::BERRY_FLAVORS).unwrap()
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        BerryFlavorTable::from_csv_source(source, vdata::files::BERRY_FLAVORS)
     }
 }
 
@@ -222,7 +252,7 @@ impl vcsv::FromCsvIncremental for BerryFlavorTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id: BerryId = vcsv::from_field(&record, 0)?;
         let contest_type: ContestType = vcsv::from_field(&record, 1)?;
@@ -0,0 +1,48 @@
+use crate::moves::ChangeableStat;
+use crate::Stat;
+use super::{Category, Item};
+
+/// The stages an X item raises its stat by, as of Generation V.
+pub const X_ITEM_STAGES: i8 = 1;
+
+/// The critical hit stages Dire Hit raises, as of Generation V.
+pub const DIRE_HIT_STAGES: i8 = 1;
+
+/// The turns Guard Spec. protects stats for.
+pub const GUARD_SPEC_TURNS: u8 = 5;
+
+/// The effect of using an item of the `StatBoosts` category on the active
+/// Pokémon in battle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BattleStatBoost {
+    /// Raise a stat of the Pokémon by a number of stages, like X Attack.
+    Stat(ChangeableStat, i8),
+    /// Raise the Pokémon's critical hit stage, like Dire Hit.
+    Critical(i8),
+    /// Keep the stats of the user's side from being lowered by the opponents
+    /// for a number of turns, like Guard Spec. and Mist.
+    Mist(u8),
+}
+
+impl Item {
+    /// The effect of the item as a battle item like X Attack, or `None` if it
+    /// is not in the `StatBoosts` category.
+    pub fn battle_stat_boost(&self) -> Option<BattleStatBoost> {
+        if self.category != Category::StatBoosts {
+            return None;
+        }
+        let stat = match self.identifier.as_str() {
+            "guard-spec" => return Some(BattleStatBoost::Mist(GUARD_SPEC_TURNS)),
+            "dire-hit" => return Some(BattleStatBoost::Critical(DIRE_HIT_STAGES)),
+            "x-attack" => Stat::Attack,
+            "x-defend" => Stat::Defense,
+            "x-speed" => Stat::Speed,
+            "x-accuracy" => Stat::Accuracy,
+            "x-special" => Stat::SpecialAttack,
+            "x-sp-def" => Stat::SpecialDefense,
+            _ => return None,
+        };
+        let stat = ChangeableStat::new(stat)?;
+        Some(BattleStatBoost::Stat(stat, X_ITEM_STAGES))
+    }
+}
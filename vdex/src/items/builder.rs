@@ -0,0 +1,88 @@
+use crate::builder::{check, Result};
+use crate::to_pascal_case;
+use crate::versions::Generation;
+use super::{plate_type, Berry, Category, FlingEffect, Flags, Item, ItemId};
+use super::Pocket;
+
+/// An item of a program's own, to add with `ItemTable::insert_custom` or to
+/// use as a fixture.
+///
+/// Fields not set keep the defaults of `Item`. A builder can also start from
+/// an existing item, to tweak it.
+#[derive(Clone, Debug)]
+pub struct ItemBuilder(Item);
+
+impl ItemBuilder {
+    /// Start an item with a Veekun-style identifier, e.g. `"shadow-orb"`,
+    /// from which its name is derived.
+    pub fn new(identifier: &str) -> Self {
+        ItemBuilder(Item {
+            name: to_pascal_case(identifier),
+            identifier: identifier.to_string(),
+            .. Default::default()
+        })
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.0.name = name.to_string();
+        self
+    }
+
+    pub fn category(mut self, category: Category) -> Self {
+        self.0.category = category;
+        self
+    }
+
+    pub fn cost(mut self, cost: u16) -> Self {
+        self.0.cost = cost;
+        self
+    }
+
+    /// The power and effect of Fling with the item.
+    pub fn fling(mut self, power: u8, effect: FlingEffect) -> Self {
+        self.0.fling_power = Some(power);
+        self.0.fling_effect = effect;
+        self
+    }
+
+    pub fn flags(mut self, flags: Flags) -> Self {
+        self.0.flags = flags;
+        self
+    }
+
+    pub fn generation(mut self, generation: Generation) -> Self {
+        self.0.generation = Some(generation);
+        self
+    }
+
+    pub fn berry(mut self, berry: Berry) -> Self {
+        self.0.berry = Some(berry);
+        self
+    }
+
+    /// Check the item and finish it with an ID.
+    ///
+    /// Only items in the berries pocket can have berry properties, only items
+    /// that can be flung can have a Fling effect, and plates must be named
+    /// after a type, like `"fist-plate"`.
+    pub fn build(self, id: ItemId) -> Result<Item> {
+        let item = &self.0;
+        check(!item.identifier.is_empty(), "identifier", "empty")?;
+        check(item.berry.is_none()
+              || item.category.pocket() == Pocket::Berries,
+              "berry", "not in the berries pocket")?;
+        check(item.fling_power.is_some()
+              || item.fling_effect == FlingEffect::None,
+              "fling_effect", "cannot be flung")?;
+        check(item.category != Category::Plates || plate_type(item).is_some(),
+              "identifier", "plate not named after a type")?;
+        let berry = self.0.berry.map(|berry| Berry { item: id, .. berry });
+        Ok(Item { id, berry, .. self.0 })
+    }
+}
+
+impl From<Item> for ItemBuilder {
+    fn from(item: Item) -> Self {
+        ItemBuilder(item)
+    }
+}
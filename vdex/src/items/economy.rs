@@ -0,0 +1,64 @@
+use crate::pokedex;
+use super::{Item, ItemId, ItemTable, MachineKind, Pocket};
+
+/// The most money a player can carry.
+pub const MAX_MONEY: u32 = 999_999;
+
+/// The number of Poké Balls that must be bought at once to get a free
+/// Premier Ball.
+pub const PREMIER_BALL_THRESHOLD: u32 = 10;
+
+impl Item {
+    /// The money a mart pays for the item, which is half its cost, or 0 if it
+    /// cannot be sold, like key items and HMs.
+    pub fn sell_price(&self) -> u16 {
+        let hm = self.as_machine()
+            .is_some_and(|machine| machine.kind == MachineKind::Hidden);
+        if hm || self.category.pocket() == Pocket::Key {
+            0
+        } else {
+            self.cost / 2
+        }
+    }
+}
+
+/// Items to buy at a mart, with their quantities, each bought at once.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ShoppingList(pub Vec<(ItemId, u32)>);
+
+impl ShoppingList {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a purchase of some quantity of an item.
+    pub fn add(&mut self, item: ItemId, quantity: u32) -> &mut Self {
+        self.0.push((item, quantity));
+        self
+    }
+
+    /// The total cost of the purchases.
+    pub fn cost(&self, items: &ItemTable) -> u32 {
+        self.0.iter()
+            .map(|&(item, quantity)| u32::from(items[item].cost) * quantity)
+            .sum()
+    }
+
+    /// The money left after making the purchases with some money, or `None`
+    /// if the money does not cover them.
+    pub fn change(&self, money: u32, items: &ItemTable) -> Option<u32> {
+        money.checked_sub(self.cost(items))
+    }
+
+    /// The Premier Balls given for free with the purchases: one for each
+    /// purchase of at least `PREMIER_BALL_THRESHOLD` Poké Balls, as of
+    /// Generations III–V.
+    pub fn premier_balls(&self) -> u32 {
+        let poke_ball = pokedex().items.identifiers().id("poke-ball");
+        self.0.iter()
+            .filter(|&&(item, quantity)| {
+                Some(item) == poke_ball && quantity >= PREMIER_BALL_THRESHOLD
+            })
+            .count() as u32
+    }
+}
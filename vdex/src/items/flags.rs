@@ -1,3 +1,4 @@
+use bitflags::bitflags;
 use crate::FromVeekun;
 use std::collections::HashMap;
 use super::ItemId;
@@ -46,8 +47,8 @@ impl FromVeekun for Flags {
 pub struct FlagTable(pub HashMap<ItemId, Flags>);
 
 impl FlagTable {
-    pub fn new() -> Self {
-        FlagTable::from_csv_data(vdata::ITEM_FLAGS).unwrap()
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        FlagTable::from_csv_source(source, vdata::files::ITEM_FLAGS)
     }
 }
 
@@ -55,7 +56,7 @@ impl vcsv::FromCsvIncremental for FlagTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id = vcsv::from_field(&record, 0)?;
         let flag = vcsv::from_field(&record, 1)?;
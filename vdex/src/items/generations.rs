@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use super::ItemId;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::versions::Generation;
+
+/// The earliest generation each item has a game index in, from Veekun's
+/// `item_game_indices.csv`, which is not included in the binary.
+#[derive(Default)]
+pub struct GenerationTable(pub HashMap<ItemId, Generation>);
+
+impl GenerationTable {
+    /// Load the table from a source, or an empty table if the source does not
+    /// have the file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        match source.csv(vdata::files::ITEM_GAME_INDICES) {
+            Some(data) => Self::from_csv_data(data),
+            None => Ok(Default::default()),
+        }
+    }
+}
+
+impl vcsv::FromCsvIncremental for GenerationTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id = vcsv::from_field(&record, 0)?;
+        let generation = vcsv::from_field(&record, 1)?;
+        let earliest = self.0.entry(id).or_insert(generation);
+        *earliest = (*earliest).min(generation);
+        Ok(())
+    }
+}
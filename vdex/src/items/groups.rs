@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use crate::enums::*;
+use crate::pokedex;
+use super::{Category, Item, ItemId};
+
+/// A curated group of related items, as of Generation V.
+#[EnumRepr(type = "u8", serde = true)]
+pub enum ItemGroup {
+    /// Choice Band, Scarf, and Specs.
+    Choice = 0,
+    /// Gems, which boost the power of a move of their type once.
+    TypeGems,
+    /// Arceus type plates.
+    Plates,
+    /// Incenses, which can be held or used to breed baby Pokémon.
+    Incenses,
+    /// Stones used to evolve Pokémon, such as the Fire Stone; not the Oval
+    /// Stone, which is held.
+    EvolutionStones,
+    /// Berries that lower a stat's EVs and raise happiness.
+    EvBerries,
+    /// Flame and Toxic Orbs, which inflict an ailment on their holder.
+    StatusOrbs,
+}
+
+impl ItemGroup {
+    /// True if an item belongs in this group.
+    pub fn contains(self, item: &Item) -> bool {
+        let name = item.name.as_str();
+        match self {
+            ItemGroup::Choice => item.category == Category::Choice,
+            ItemGroup::TypeGems => item.category == Category::Jewels,
+            ItemGroup::Plates => item.category == Category::Plates,
+            ItemGroup::Incenses => name.ends_with("Incense"),
+            ItemGroup::EvolutionStones => item.category == Category::Evolution
+                && (name.ends_with("Stone") && name != "OvalStone"
+                    || name == "Thunderstone"),
+            ItemGroup::EvBerries => item.category == Category::EffortDrop,
+            ItemGroup::StatusOrbs => item.category == Category::BadHeldItems
+                && name.ends_with("Orb"),
+        }
+    }
+
+    /// The items in this group, sorted by ID, from the global pokedex.
+    pub fn members(self) -> &'static [ItemId] {
+        pokedex().items.group(self)
+    }
+}
+
+/// The members of every group, and the berries in each category, sorted by
+/// ID.
+#[derive(Default)]
+pub struct GroupTable {
+    groups: HashMap<ItemGroup, Vec<ItemId>>,
+    berries: HashMap<Category, Vec<ItemId>>,
+}
+
+impl GroupTable {
+    /// Sort the items of a table into groups.
+    pub fn new(items: &HashMap<ItemId, Item>) -> Self {
+        let mut table = GroupTable::default();
+        for item in items.values() {
+            for &group in ItemGroup::VALUES.iter() {
+                if group.contains(item) {
+                    table.groups.entry(group).or_default().push(item.id);
+                }
+            }
+            if item.berry.is_some() {
+                table.berries.entry(item.category).or_default().push(item.id);
+            }
+        }
+        for members in table.groups.values_mut()
+                .chain(table.berries.values_mut()) {
+            members.sort();
+        }
+        table
+    }
+
+    /// The items in a group, sorted by ID.
+    pub fn get(&self, group: ItemGroup) -> &[ItemId] {
+        self.groups.get(&group).map_or(&[], |members| members.as_slice())
+    }
+
+    /// The berries in a category, sorted by ID.
+    pub fn berries_in(&self, category: Category) -> &[ItemId] {
+        self.berries.get(&category).map_or(&[], |members| members.as_slice())
+    }
+}
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use super::ItemId;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::versions::VersionGroup;
+
+/// The version groups whose marts sell each item, from an `item_marts.csv`
+/// of `item_id,version_group_id` rows, which is not included in the binary.
+#[derive(Default)]
+pub struct MartTable(pub HashMap<ItemId, Vec<VersionGroup>>);
+
+impl MartTable {
+    /// Load the table from a source, or an empty table if the source does not
+    /// have the file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        match source.csv(vdata::files::ITEM_MARTS) {
+            Some(data) => Self::from_csv_data(data),
+            None => Ok(Default::default()),
+        }
+    }
+}
+
+impl vcsv::FromCsvIncremental for MartTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id = vcsv::from_field(&record, 0)?;
+        let version_group = vcsv::from_field(&record, 1)?;
+        let version_groups = self.0.entry(id).or_default();
+        if !version_groups.contains(&version_group) {
+            version_groups.push(version_group);
+            version_groups.sort();
+        }
+        Ok(())
+    }
+}
@@ -1,27 +1,51 @@
 //! Items and related data.
 
 pub(self) mod bag;
+pub(self) mod balls;
 pub(self) mod berries;
+pub(self) mod boosts;
+pub(self) mod builder;
+pub(self) mod economy;
 pub(self) mod flags;
+pub(self) mod generations;
+pub(self) mod groups;
+pub(self) mod marts;
+pub(self) mod relations;
+pub(self) mod restores;
 
 pub use self::bag::Category;
+pub use self::balls::{ball_modifier, CatchContext, GUARANTEED_CATCH};
 pub use self::bag::Pocket;
 pub use self::berries::Berry;
+pub use self::berries::BerryTable;
 pub use self::berries::BERRY_COUNT;
 pub use self::berries::Flavor;
+pub use self::boosts::{BattleStatBoost, DIRE_HIT_STAGES, GUARD_SPEC_TURNS};
+pub use self::boosts::X_ITEM_STAGES;
+pub use self::builder::ItemBuilder;
+pub use self::economy::{ShoppingList, MAX_MONEY, PREMIER_BALL_THRESHOLD};
 pub use self::flags::Flags;
+pub use self::groups::ItemGroup;
+pub use self::relations::Machine;
+pub use self::relations::MachineKind;
+pub use self::restores::{HpRestore, PpRestore, Restore};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::IdentifierMap;
 use crate::to_pascal_case;
+use crate::Type;
 use crate::vcsv;
 use crate::vcsv::FromCsv;
 use crate::vdata;
 use crate::VeekunOption;
+use crate::versions::{Generation, VersionGroup};
 
 /// Extra effect when thrown using Fling.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum FlingEffect {
     None = 0,
     BadlyPoison,
@@ -45,6 +69,7 @@ impl FromVeekun for FlingEffect {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct ItemId(pub u16);
 
@@ -77,6 +102,8 @@ pub struct Item {
     pub id: ItemId,
     /// The pbirch name for the item.
     pub name: String,
+    /// The Veekun identifier for the item, e.g. `"master-ball"`.
+    pub identifier: String,
     /// The item's category.
     ///
     /// The category can be used to derive the unused and pocket properties.
@@ -91,21 +118,139 @@ pub struct Item {
     pub flags: Flags,
     /// Berry properties, or `None` if the item is not a berry.
     pub berry: Option<Berry>,
+    /// The generation the item was introduced in, or `None` if unknown
+    /// because the source lacks `item_game_indices.csv`.
+    pub generation: Option<Generation>,
+}
+
+impl Item {
+    /// True if the item exists in a generation. Items whose generation is
+    /// unknown are taken to exist in every generation.
+    pub fn exists_in(&self, generation: Generation) -> bool {
+        self.generation.is_none_or(|g| g <= generation)
+    }
+}
+
+/// Get the type of a plate, which sets the type of Arceus with Multitype and
+/// of Judgment, or `None` if the item is not a plate.
+pub fn plate_type(item: &Item) -> Option<Type> {
+    if item.category != Category::Plates {
+        return None;
+    }
+    let typ = match item.identifier.strip_suffix("-plate")? {
+        "fist" => Type::Fighting,
+        "sky" => Type::Flying,
+        "toxic" => Type::Poison,
+        "earth" => Type::Ground,
+        "stone" => Type::Rock,
+        "insect" => Type::Bug,
+        "spooky" => Type::Ghost,
+        "iron" => Type::Steel,
+        "flame" => Type::Fire,
+        "splash" => Type::Water,
+        "meadow" => Type::Grass,
+        "zap" => Type::Electric,
+        "mind" => Type::Psychic,
+        "icicle" => Type::Ice,
+        "draco" => Type::Dragon,
+        "dread" => Type::Dark,
+        _ => return None,
+    };
+    Some(typ)
 }
 
 /// Wrapper of a `HashMap` mapping IDs to items.
 ///
 /// Use `table.0` to access `HashMap` members.
+///
+/// The relations of items to species, as in `Item::evolves_species`, are
+/// empty until `relate_species` is called, which `Pokedex` does when it is
+/// loaded. The marts selling items are only known if the source has an
+/// `item_marts.csv`.
 #[derive(Default)]
-pub struct ItemTable(pub HashMap<ItemId, Item>);
+pub struct ItemTable(
+    pub HashMap<ItemId, Item>, groups::GroupTable, IdentifierMap<ItemId>,
+    relations::Relations, marts::MartTable);
 
 impl ItemTable {
     /// Create an item table from the included CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
     pub fn new() -> Self {
-        let mut items_table = ItemTable::from_csv_data(vdata::ITEMS).unwrap();
-        items_table.set_berries(&berries::BerryTable::new());
-        items_table.set_flags(&flags::FlagTable::new());
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Create an item table from the CSV data in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut items_table
+            = ItemTable::from_csv_source(source, vdata::files::ITEMS)?;
+        items_table.set_berries(&berries::BerryTable::load(source)?);
+        items_table.set_flags(&flags::FlagTable::load(source)?);
         items_table
+            .set_generations(&generations::GenerationTable::load(source)?);
+        items_table.4 = marts::MartTable::load(source)?;
+        items_table.1 = groups::GroupTable::new(&items_table.0);
+        items_table.2 = items_table.0.values()
+            .map(|item| (item.identifier.as_str(), item.id))
+            .collect();
+        Ok(items_table)
+    }
+
+    /// Get the map between the items' Veekun identifiers and IDs.
+    pub fn identifiers(&self) -> &IdentifierMap<ItemId> {
+        &self.2
+    }
+
+    /// Add an item of a program's own and get its ID, which follows every
+    /// existing item, or an error if the item is invalid.
+    ///
+    /// The identifiers and groups are updated. The item's identifier should
+    /// be unique, as the identifier map only keeps the newest item with an
+    /// identifier.
+    pub fn insert_custom(
+        &mut self, builder: ItemBuilder
+    ) -> crate::builder::Result<ItemId> {
+        let id = self.0.keys().max().map_or(ItemId(1), |id| ItemId(id.0 + 1));
+        let item = builder.build(id)?;
+        self.2.insert(&item.identifier, id);
+        self.0.insert(id, item);
+        self.1 = groups::GroupTable::new(&self.0);
+        Ok(id)
+    }
+
+    /// The items introduced in a generation, sorted by ID. This is empty if
+    /// the generations of items are unknown.
+    pub fn introduced_in(&self, generation: Generation) -> Vec<&Item> {
+        let mut items: Vec<_> = self.0.values()
+            .filter(|item| item.generation == Some(generation))
+            .collect();
+        items.sort_by_key(|item| item.id);
+        items
+    }
+
+    /// The version groups whose marts sell an item, in order.
+    pub fn marts_selling(&self, item: ItemId) -> &[VersionGroup] {
+        self.4.0.get(&item).map_or(&[], |version_groups| &version_groups[..])
+    }
+
+    /// The items sold at marts in a version group, sorted by ID.
+    pub fn sold_in(&self, version_group: VersionGroup) -> Vec<ItemId> {
+        let mut items: Vec<_> = self.4.0.iter()
+            .filter(|(_, groups)| groups.contains(&version_group))
+            .map(|(&item, _)| item)
+            .collect();
+        items.sort();
+        items
+    }
+
+    /// The items in a group, sorted by ID.
+    pub fn group(&self, group: ItemGroup) -> &[ItemId] {
+        self.1.get(group)
+    }
+
+    /// The berries in a category, such as `Category::TypeProtection`, sorted
+    /// by ID.
+    pub fn berries_in_category(&self, category: Category) -> &[ItemId] {
+        self.1.berries_in(category)
     }
 
     fn set_berries(&mut self, berry_table: &berries::BerryTable) {
@@ -116,6 +261,12 @@ impl ItemTable {
         }
     }
 
+    fn set_generations(&mut self, table: &generations::GenerationTable) {
+        for (id, item) in self.0.iter_mut() {
+            item.generation = table.0.get(id).cloned();
+        }
+    }
+
     fn set_flags(&mut self, flag_table: &flags::FlagTable) {
         for (id, item) in self.0.iter_mut() {
             item.flags = flag_table.0.get(id)
@@ -128,13 +279,15 @@ impl vcsv::FromCsvIncremental for ItemTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id = vcsv::from_field(&record, 0)?;
         let fling_power: VeekunOption<_> = vcsv::from_field(&record, 4)?;
+        let identifier = vcsv::get_field(&record, 1)?;
         self.0.insert(id, Item {
             id,
-            name: to_pascal_case(vcsv::get_field(&record, 1)?),
+            name: to_pascal_case(identifier),
+            identifier: identifier.to_string(),
             category: vcsv::from_field(&record, 2)?,
             cost: vcsv::from_field(&record, 3)?,
             fling_power: fling_power.into(),
@@ -142,6 +295,7 @@ impl vcsv::FromCsvIncremental for ItemTable {
                 vcsv::from_option_field(&record, 5, FlingEffect::None)?,
             flags: flags::Flags::empty(),
             berry: None,
+            generation: None,
         });
         Ok(())
     }
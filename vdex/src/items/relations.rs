@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use crate::enums::*;
+use crate::pokedex;
+use crate::pokemon::{SpeciesId, SpeciesTable};
+use crate::Type;
+use super::{plate_type, Berry, Category, Item, ItemId, ItemTable};
+
+/// Whether a machine is a TM or an HM.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MachineKind {
+    /// A technical machine, which was single-use before Generation V.
+    Technical,
+    /// A hidden machine, which teaches a move usable outside of battle.
+    Hidden,
+}
+
+/// A TM or HM, e.g. TM01 or HM06.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Machine {
+    pub kind: MachineKind,
+    pub number: u8,
+}
+
+/// The items related to species, computed when the Pokedex is loaded.
+#[derive(Default)]
+pub struct Relations {
+    evolutions: HashMap<ItemId, Vec<SpeciesId>>,
+}
+
+impl Relations {
+    /// Find the items used to evolve each species.
+    pub fn new(species: &SpeciesTable) -> Self {
+        let mut evolutions: HashMap<_, Vec<_>> = HashMap::new();
+        for id in (0..species.len() as u16).map(SpeciesId) {
+            let item = species[id].evolves_from.and_then(|e| e.trigger_item);
+            if let Some(item) = item {
+                evolutions.entry(item).or_default().push(id);
+            }
+        }
+        Relations { evolutions }
+    }
+}
+
+/// The type named at the start of an identifier like `"fire-gem"`.
+fn type_named(identifier: &str, suffix: &str) -> Option<Type> {
+    let name = identifier.strip_suffix(suffix)?;
+    Type::VALUES.iter().cloned()
+        .find(|t| format!("{:?}", t).eq_ignore_ascii_case(name))
+}
+
+impl Item {
+    /// The TM or HM this item is, if it is a machine.
+    pub fn as_machine(&self) -> Option<Machine> {
+        if self.category != Category::Machines {
+            return None;
+        }
+        let identifier = self.identifier.as_str();
+        let (kind, number) = match identifier.strip_prefix("tm") {
+            Some(number) => (MachineKind::Technical, number),
+            None => (MachineKind::Hidden, identifier.strip_prefix("hm")?),
+        };
+        Some(Machine { kind, number: number.parse().ok()? })
+    }
+
+    /// The berry properties of this item, if it is a berry.
+    pub fn as_berry(&self) -> Option<&Berry> {
+        self.berry.as_ref()
+    }
+
+    /// The type of move this item boosts, if it is a gem.
+    pub fn as_jewel_type(&self) -> Option<Type> {
+        if self.category != Category::Jewels {
+            return None;
+        }
+        type_named(&self.identifier, "-gem")
+    }
+
+    /// The type of this item as a plate, as in `plate_type`.
+    pub fn as_plate_type(&self) -> Option<Type> {
+        plate_type(self)
+    }
+
+    /// The species that evolve when this item is used on them, like with a
+    /// Fire Stone, in order of ID, from the global pokedex.
+    pub fn evolves_species(&self) -> &'static [SpeciesId] {
+        pokedex().items.evolves_species(self.id)
+    }
+}
+
+impl ItemTable {
+    /// The species that evolve when an item is used on them, in order of ID.
+    pub fn evolves_species(&self, item: ItemId) -> &[SpeciesId] {
+        self.3.evolutions.get(&item).map_or(&[], |species| &species[..])
+    }
+
+    /// Compute the relations of the items to the species.
+    pub fn relate_species(&mut self, species: &SpeciesTable) {
+        self.3 = Relations::new(species);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::moves::Ailment;
+use super::{Flags, Item};
+
+/// How much HP an item restores.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HpRestore {
+    /// A fixed amount of HP.
+    Amount(u16),
+    /// A fraction of the maximum HP, as (numerator, denominator).
+    Fraction(u16, u16),
+}
+
+impl HpRestore {
+    /// The HP restored to a Pokémon with some maximum HP, before capping at
+    /// the HP it is missing.
+    pub fn amount(self, max_hp: u16) -> u16 {
+        match self {
+            HpRestore::Amount(amount) => amount,
+            HpRestore::Fraction(numerator, denominator) => {
+                let amount = u32::from(max_hp) * u32::from(numerator)
+                    / u32::from(denominator);
+                (amount as u16).max(1)
+            },
+        }
+    }
+}
+
+/// How much PP an item restores.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PpRestore {
+    /// The PP restored to each move, or `None` if all of its PP is.
+    pub amount: Option<u8>,
+    /// Whether the PP of every move is restored, rather than of one chosen
+    /// move.
+    pub all_moves: bool,
+}
+
+/// What a medicine restores when used on a Pokémon, as of Generation V.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Restore {
+    pub hp: Option<HpRestore>,
+    pub pp: Option<PpRestore>,
+    /// The ailments cured.
+    pub cures: &'static [Ailment],
+    /// Whether the item revives a fainted Pokémon, which is then the only
+    /// kind of Pokémon it can be used on.
+    pub revives: bool,
+    /// Whether the item applies to every Pokémon in the party at once.
+    pub whole_party: bool,
+}
+
+impl Restore {
+    /// True if the item cures an ailment.
+    pub fn cures(&self, ailment: Ailment) -> bool {
+        self.cures.contains(&ailment)
+    }
+}
+
+/// The ailments cured by Full Heal and the like.
+const ALL_AILMENTS: &[Ailment] = &[
+    Ailment::Paralysis, Ailment::Sleep, Ailment::Freeze, Ailment::Burn,
+    Ailment::Poison, Ailment::Confusion,
+];
+
+const NOTHING: Restore = Restore {
+    hp: None, pp: None, cures: &[], revives: false, whole_party: false,
+};
+
+const fn hp(amount: u16) -> Restore {
+    Restore { hp: Some(HpRestore::Amount(amount)), .. NOTHING }
+}
+
+const fn hp_fraction(numerator: u16, denominator: u16) -> Restore {
+    Restore {
+        hp: Some(HpRestore::Fraction(numerator, denominator)), .. NOTHING
+    }
+}
+
+const fn cure(ailments: &'static [Ailment]) -> Restore {
+    Restore { cures: ailments, .. NOTHING }
+}
+
+const fn pp(amount: Option<u8>, all_moves: bool) -> Restore {
+    Restore { pp: Some(PpRestore { amount, all_moves }), .. NOTHING }
+}
+
+const fn revive(numerator: u16, denominator: u16) -> Restore {
+    Restore { revives: true, .. hp_fraction(numerator, denominator) }
+}
+
+/// What each medicine restores, by Veekun identifier.
+const RESTORES: &[(&str, Restore)] = &[
+    ("potion", hp(20)),
+    ("super-potion", hp(50)),
+    ("hyper-potion", hp(200)),
+    ("max-potion", hp_fraction(1, 1)),
+    ("full-restore", Restore { cures: ALL_AILMENTS, .. hp_fraction(1, 1) }),
+    ("fresh-water", hp(50)),
+    ("soda-pop", hp(60)),
+    ("lemonade", hp(80)),
+    ("moomoo-milk", hp(100)),
+    ("energypowder", hp(50)),
+    ("energy-root", hp(200)),
+    ("berry-juice", hp(20)),
+    ("sweet-heart", hp(20)),
+    ("oran-berry", hp(10)),
+    ("sitrus-berry", hp_fraction(1, 4)),
+    ("antidote", cure(&[Ailment::Poison])),
+    ("burn-heal", cure(&[Ailment::Burn])),
+    ("ice-heal", cure(&[Ailment::Freeze])),
+    ("awakening", cure(&[Ailment::Sleep])),
+    ("parlyz-heal", cure(&[Ailment::Paralysis])),
+    ("full-heal", cure(ALL_AILMENTS)),
+    ("heal-powder", cure(ALL_AILMENTS)),
+    ("lava-cookie", cure(ALL_AILMENTS)),
+    ("old-gateau", cure(ALL_AILMENTS)),
+    ("casteliacone", cure(ALL_AILMENTS)),
+    ("cheri-berry", cure(&[Ailment::Paralysis])),
+    ("chesto-berry", cure(&[Ailment::Sleep])),
+    ("pecha-berry", cure(&[Ailment::Poison])),
+    ("rawst-berry", cure(&[Ailment::Burn])),
+    ("aspear-berry", cure(&[Ailment::Freeze])),
+    ("persim-berry", cure(&[Ailment::Confusion])),
+    ("lum-berry", cure(ALL_AILMENTS)),
+    ("ether", pp(Some(10), false)),
+    ("max-ether", pp(None, false)),
+    ("elixir", pp(Some(10), true)),
+    ("max-elixir", pp(None, true)),
+    ("leppa-berry", pp(Some(10), false)),
+    ("revive", revive(1, 2)),
+    ("max-revive", revive(1, 1)),
+    ("revival-herb", revive(1, 1)),
+    ("sacred-ash", Restore { whole_party: true, .. revive(1, 1) }),
+];
+
+impl Item {
+    /// True if the item is used up when used from the bag in battle, like a
+    /// Potion or a Poké Ball.
+    pub fn consumed_on_use_in_battle(&self) -> bool {
+        self.flags.contains(Flags::USABLE_IN_BATTLE | Flags::CONSUMABLE)
+    }
+
+    /// What the item restores when used on a Pokémon, if it is a medicine
+    /// that restores HP or PP, cures ailments, or revives.
+    pub fn restores(&self) -> Option<Restore> {
+        RESTORES.iter()
+            .find(|&&(identifier, _)| identifier == self.identifier)
+            .map(|&(_, restore)| restore)
+    }
+}
@@ -0,0 +1,4 @@
+//! Checks that a Pokémon's attributes are consistent with how the games
+//! generate them, for save editors and legality tools.
+
+pub mod pid;
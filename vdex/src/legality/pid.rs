@@ -0,0 +1,128 @@
+//! The attributes derived from a Pokémon's personality value (PID) in
+//! Generations III to V.
+//!
+//! In these generations, the PID determines the Pokémon's gender and ability
+//! slot and, with its original trainer's IDs, whether it is shiny. Before
+//! Generation V, it also determines the nature.
+
+use crate::pokedex;
+use crate::pokemon::{Gender, GenderRatio, SpeciesId};
+use crate::stored::{AbilitySlot, Error, Result, StoredPokemon};
+use crate::versions::Generation;
+use crate::{Enum, Nature};
+
+/// The public trainer ID and secret ID of a Pokémon's original trainer.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TrainerId {
+    pub tid: u16,
+    pub sid: u16,
+}
+
+/// The nature determined by a PID, or `None` outside of Generations III and
+/// IV, where the nature is independent of the PID.
+pub fn nature(pid: u32, generation: Generation) -> Option<Nature> {
+    if (Generation::III..=Generation::IV).contains(&generation) {
+        Some(Nature::VALUES[(pid % Nature::COUNT as u32) as usize])
+    } else {
+        None
+    }
+}
+
+/// The lowest value of a PID's lowest byte that makes a Pokémon of a gender
+/// ratio male, or `None` if the ratio does not depend on the PID.
+pub fn gender_threshold(ratio: GenderRatio) -> Option<u8> {
+    match ratio {
+        GenderRatio::FemaleEighths(1) => Some(31),
+        GenderRatio::FemaleEighths(2) => Some(63),
+        GenderRatio::FemaleEighths(4) => Some(127),
+        GenderRatio::FemaleEighths(6) => Some(191),
+        GenderRatio::FemaleEighths(7) => Some(225),
+        _ => None,
+    }
+}
+
+/// The gender determined by a PID for a Pokémon of a gender ratio.
+pub fn gender(pid: u32, ratio: GenderRatio) -> Gender {
+    match (ratio, gender_threshold(ratio)) {
+        (GenderRatio::Genderless, _) => Gender::Genderless,
+        (GenderRatio::FemaleEighths(0), _) => Gender::Male,
+        (_, Some(threshold)) if (pid & 0xff) as u8 >= threshold => Gender::Male,
+        _ => Gender::Female,
+    }
+}
+
+/// The ability slot determined by a PID, which is never `Hidden`: the lowest
+/// bit before Generation V, and the lowest bit of the upper half from then.
+pub fn ability_slot(pid: u32, generation: Generation) -> AbilitySlot {
+    let bit = if generation >= Generation::V { pid >> 16 } else { pid };
+    if bit & 1 == 0 { AbilitySlot::First } else { AbilitySlot::Second }
+}
+
+/// True if a PID makes a Pokémon of a trainer shiny.
+pub fn is_shiny(pid: u32, trainer: TrainerId) -> bool {
+    let xor = trainer.tid ^ trainer.sid ^ (pid >> 16) as u16 ^ pid as u16;
+    xor < 8
+}
+
+/// The attributes determined by a PID.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PidAttributes {
+    pub nature: Option<Nature>,
+    pub gender: Gender,
+    pub ability_slot: AbilitySlot,
+    pub shiny: bool,
+}
+
+impl PidAttributes {
+    /// The attributes a PID determines for a Pokémon of a species caught by a
+    /// trainer in a generation, or an error if the species is unknown.
+    pub fn new(
+        pid: u32, trainer: TrainerId, species: SpeciesId,
+        generation: Generation,
+    ) -> Result<Self> {
+        let species_table = &pokedex().species;
+        if !species_table.contains(species) {
+            return Err(Error::Invalid {
+                field: "species", reason: "unknown species"
+            });
+        }
+        let ratio = species_table[species].gender_ratio();
+        Ok(PidAttributes {
+            nature: nature(pid, generation),
+            gender: gender(pid, ratio),
+            ability_slot: ability_slot(pid, generation),
+            shiny: is_shiny(pid, trainer),
+        })
+    }
+}
+
+/// Check that a Pokémon's nature, gender, ability slot, and shininess are
+/// consistent with its PID and original trainer, or that its species is
+/// known.
+///
+/// Hidden abilities and species with only one ability are not checked
+/// against the PID.
+pub fn check(
+    pid: u32, trainer: TrainerId, pokemon: &StoredPokemon, gender: Gender,
+    shiny: bool, generation: Generation,
+) -> Result<()> {
+    let invalid = |field, reason| Err(Error::Invalid { field, reason });
+    let expected
+        = PidAttributes::new(pid, trainer, pokemon.species, generation)?;
+    if expected.nature.is_some_and(|nature| nature != pokemon.nature) {
+        return invalid("nature", "does not match the PID");
+    }
+    if expected.gender != gender {
+        return invalid("gender", "does not match the PID");
+    }
+    let two_abilities = pokedex().species.pokemon(pokemon.species).first()
+        .is_some_and(|p| p.abilities.second().is_some());
+    if two_abilities && pokemon.ability != AbilitySlot::Hidden
+        && pokemon.ability != expected.ability_slot {
+        return invalid("ability", "does not match the PID");
+    }
+    if expected.shiny != shiny {
+        return invalid("shiny", "does not match the PID and trainer IDs");
+    }
+    Ok(())
+}
@@ -1,22 +1,109 @@
-#[macro_use]
-extern crate bitflags;
-extern crate enum_repr;
-extern crate veekun;
+/// The Veekun CSV tools vdex is built on, for loading supplemental tables.
+///
+/// To load a table of your own, implement `veekun::csv::FromCsvIncremental`
+/// for it, reading fields with `veekun::csv::from_field`, then load it with
+/// `veekun::csv::FromCsv::from_csv_source` from the same source as the
+/// Pokedex. Fields convert to vdex's types through `veekun::repr::FromVeekun`;
+/// use `veekun::repr::VeekunOption` for fields that may be empty. To share the
+/// table along with the Pokedex, attach it with `Pokedex::register_extension`
+/// before installing the Pokedex with `install`.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use vdex::pokemon::SpeciesId;
+/// use vdex::veekun::csv::{self as vcsv, FromCsv, FromCsvIncremental};
+///
+/// /// Usage percentages by species, from a `usage.csv` of our own.
+/// #[derive(Default)]
+/// struct Usage(HashMap<SpeciesId, f64>);
+///
+/// impl FromCsvIncremental for Usage {
+///     fn from_empty_csv() -> Self { Default::default() }
+///
+///     fn load_csv_record(
+///         &mut self, record: vcsv::Record
+///     ) -> vcsv::Result<()> {
+///         let species: SpeciesId = vcsv::from_field(&record, 0)?;
+///         let percent = vcsv::from_field(&record, 1)?;
+///         self.0.insert(species, percent);
+///         Ok(())
+///     }
+/// }
+///
+/// let data = b"species_id,percent\n445,21.5\n";
+/// let mut source = HashMap::new();
+/// source.insert("usage.csv".to_string(), data.to_vec());
+/// let usage = Usage::from_csv_source(&source, "usage.csv").unwrap();
+/// assert_eq!(usage.0[&SpeciesId(444)], 21.5);
+/// ```
+pub use veekun;
 
-pub(self) mod abilities;
+pub mod abilities;
+pub mod ai;
+pub mod analysis;
+pub mod assets;
+pub mod battle;
+pub mod breeding;
+pub mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(not(feature = "no-embedded-data"))]
+pub(self) mod diff;
 pub(self) mod enums;
+#[cfg(not(feature = "no-embedded-data"))]
+pub mod examples;
+pub mod experience;
+pub mod facilities;
+pub(self) mod fingerprints;
+pub mod friendship;
+pub mod gen;
+pub(self) mod identifiers;
+pub mod interop;
 pub mod items;
+pub mod legality;
+pub mod locations;
 pub mod moves;
-pub(self) mod natures;
+pub mod natures;
 pub mod pokemon;
-pub(self) mod types;
+pub mod prelude;
+pub mod prose;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod query;
+pub(self) mod provenance;
+pub mod randomizer;
+pub(self) mod refs;
+pub mod retro;
+pub mod rng;
+#[cfg(feature = "sandbox")]
+pub(self) mod sandbox;
+pub mod stored;
+pub mod summary;
+pub mod team;
+pub mod trainers;
+#[cfg(feature = "serde")]
+pub(self) mod serde_impls;
+pub mod types;
 pub mod versions;
+pub mod world;
 
 pub use self::abilities::Ability;
+#[cfg(not(feature = "no-embedded-data"))]
+pub use self::diff::{DataDiff, TableDiff};
 pub use self::enums::Enum;
+pub use self::fingerprints::{fingerprint, source_fingerprint};
+pub use self::identifiers::IdentifierMap;
 pub use self::natures::*;
+#[cfg(not(feature = "no-embedded-data"))]
+pub use self::provenance::about;
+pub use self::provenance::{DataInfo, TableInfo};
+pub use self::refs::{MoveRef, SpeciesRef};
 pub use self::types::*;
 
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+#[cfg(feature = "external-data")]
+use std::path::Path;
 use veekun::csv as vcsv;
 use veekun::data as vdata;
 use veekun::repr::{FromVeekun, VeekunOption};
@@ -25,36 +112,181 @@ use veekun::to_pascal_case;
 #[cfg(test)]
 mod tests;
 
+/// A table attached to a Pokedex with `Pokedex::register_extension`.
+type Extension = Box<dyn Any + Send + Sync>;
+
 /// All the data in vdex.
 pub struct Pokedex {
+    pub assets: assets::SpeciesAssetTable,
     pub efficacy: EfficacyTable,
+    pub facilities: facilities::FacilityTable,
     pub items: items::ItemTable,
+    pub locations: locations::LocationTable,
     pub moves: moves::MoveTable,
     pub palace: PalaceTable,
+    pub presentation: moves::PresentationTable,
+    pub prose: prose::ProseTable,
     pub species: pokemon::SpeciesTable,
+    pub trainers: trainers::TrainerTable,
+    /// The fingerprint of the data source, as in `source_fingerprint`.
+    fingerprint: u64,
+    /// Extension tables by type, then key.
+    extensions: HashMap<TypeId, HashMap<&'static str, Extension>>,
 }
 
 impl Pokedex {
+    /// Load all the tables from the Veekun CSV data included in the binary.
+    #[cfg(not(feature = "no-embedded-data"))]
     pub fn new() -> Self {
-        Pokedex {
+        let mut dex = Pokedex {
+            assets: Default::default(),
             efficacy: EfficacyTable::new(),
+            facilities: Default::default(),
             items: items::ItemTable::new(),
+            locations: Default::default(),
             moves: moves::MoveTable::new(),
             palace: PalaceTable::new(),
+            presentation: moves::PresentationTable::new(),
+            prose: prose::ProseTable::load(&vdata::Embedded).unwrap(),
             species: pokemon::SpeciesTable::new(),
+            trainers: Default::default(),
+            fingerprint: source_fingerprint(&vdata::Embedded),
+            extensions: HashMap::new(),
+        };
+        dex.items.relate_species(&dex.species);
+        dex
+    }
+
+    /// Load all the tables from the CSV files in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut dex = Pokedex {
+            assets: assets::SpeciesAssetTable::load(source)?,
+            efficacy: EfficacyTable::load(source)?,
+            facilities: facilities::FacilityTable::load(source)?,
+            items: items::ItemTable::load(source)?,
+            locations: locations::LocationTable::load(source)?,
+            moves: moves::MoveTable::load(source)?,
+            palace: PalaceTable::load(source)?,
+            presentation: moves::PresentationTable::load(source)?,
+            prose: prose::ProseTable::load(source)?,
+            species: pokemon::SpeciesTable::load(source)?,
+            trainers: trainers::TrainerTable::load(source)?,
+            fingerprint: source_fingerprint(source),
+            extensions: HashMap::new(),
+        };
+        dex.items.relate_species(&dex.species);
+        Ok(dex)
+    }
+
+    /// Load all the tables from a directory of Veekun CSV files, which are
+    /// memory-mapped rather than read into memory up front.
+    #[cfg(feature = "external-data")]
+    pub fn open(path: &Path) -> vcsv::Result<Self> {
+        Self::load(&vdata::Directory::open(path)?)
+    }
+
+    /// Attach a table of the application's own, such as usage statistics, under
+    /// a key, returning the table of the same type previously under the key.
+    ///
+    /// Extensions must be registered before the Pokedex is installed as the
+    /// global instance, which cannot be modified.
+    pub fn register_extension<T: Any + Send + Sync>(
+        &mut self, key: &'static str, table: T
+    ) -> Option<T> {
+        self.extensions.entry(TypeId::of::<T>()).or_default()
+            .insert(key, Box::new(table))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Get the table of a type registered under a key.
+    pub fn extension<T: Any + Send + Sync>(&self, key: &str) -> Option<&T> {
+        self.extensions.get(&TypeId::of::<T>())
+            .and_then(|tables| tables.get(key))
+            .and_then(|table| table.downcast_ref())
+    }
+
+    /// Report which entities of the standard files of a source, such as an
+    /// overlay the Pokedex was loaded from, differ from the data included in
+    /// the binary. See `DataDiff::from_embedded`.
+    #[cfg(not(feature = "no-embedded-data"))]
+    pub fn diff_from_embedded(
+        source: &dyn vdata::Source
+    ) -> vcsv::Result<DataDiff> {
+        DataDiff::from_embedded(source)
+    }
+
+    /// Get a Pokémon by its Veekun ID, numbered like pbirch or like upstream
+    /// Veekun, as in `PokemonId::from_veekun_id`, so that data from other
+    /// Veekun-based tools maps onto the same Pokémon.
+    pub fn by_veekun_pokemon_id(&self, id: u16) -> Option<&pokemon::Pokemon> {
+        self.species.pokemon_by_id(pokemon::PokemonId::from_veekun_id(id)?)
+    }
+
+    /// The fingerprint of the data the Pokedex was loaded from, as in
+    /// `source_fingerprint`. Tables changed after loading do not change it.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Compute summary statistics of the tables.
+    pub fn stats(&self) -> summary::DexStats {
+        summary::DexStats::new(self)
+    }
+
+    /// Find the abilities and moves whose prose contains every word of the
+    /// query, e.g. "flinch". Abilities come first, then moves in order of id.
+    ///
+    /// Prose is only available if the data source provided it (see `prose`).
+    pub fn search_text(&self, query: &str) -> Vec<prose::SearchHit> {
+        let mut hits = Vec::new();
+        let mut moves = Vec::new();
+        for subject in self.prose.search(query) {
+            match subject {
+                prose::Subject::Ability(ability)
+                    => hits.push(prose::SearchHit::Ability(ability)),
+                prose::Subject::Effect(effect)
+                    => moves.extend_from_slice(self.moves.with_effect(effect)),
+            }
         }
+        moves.sort();
+        hits.extend(moves.into_iter().map(prose::SearchHit::Move));
+        hits
     }
 }
 
 static mut POKEDEX: Option<Pokedex> = None;
 static POKEDEX_ONCE: std::sync::Once = std::sync::Once::new();
 
+/// Install `dex` as the global Pokedex instance returned by `pokedex`.
+///
+/// This must happen before the first call to `pokedex`; otherwise the global
+/// instance already exists and `dex` is handed back as the error.
+#[allow(clippy::result_large_err)]
+pub fn install(dex: Pokedex) -> Result<(), Pokedex> {
+    let mut dex = Some(dex);
+    unsafe {
+        POKEDEX_ONCE.call_once(|| {
+            POKEDEX = dex.take();
+        });
+    }
+    match dex {
+        Some(dex) => Err(dex),
+        None => Ok(()),
+    }
+}
+
 /// START HERE: Load (if not loaded) and return the global Pokedex instance.
+///
+/// With the `no-embedded-data` feature there is nothing to load by default,
+/// so a Pokedex must first be supplied with `install`.
 pub fn pokedex() -> &'static Pokedex {
     unsafe {
         POKEDEX_ONCE.call_once(|| {
-            POKEDEX = Some(Pokedex::new());
+            #[cfg(not(feature = "no-embedded-data"))] {
+                POKEDEX = Some(Pokedex::new());
+            }
         });
-        POKEDEX.as_ref().unwrap()
+        POKEDEX.as_ref().expect("no Pokedex installed")
     }
 }
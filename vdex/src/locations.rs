@@ -0,0 +1,195 @@
+//! Location areas, the regions they are in, and which areas lead to which,
+//! from the auxiliary `location_area_regions.csv` and
+//! `location_area_adjacency.csv` (see `LocationTable`).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::enums::*;
+use crate::FromVeekun;
+use crate::IdentifierMap;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::VeekunOption;
+use veekun::repr::VeekunString;
+
+/// A region of the Pokémon world.
+#[EnumRepr(type = "u8")]
+pub enum Region {
+    Kanto = 1,
+    Johto,
+    Hoenn,
+    Sinnoh,
+    Unova,
+}
+
+impl FromVeekun for Region {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The Veekun ID of a location area, like a floor of a cave or a route.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AreaId(pub u16);
+
+impl FromVeekun for AreaId {
+    type Intermediate = u16;
+
+    fn from_veekun(value: u16) -> Option<Self> {
+        Some(AreaId(value))
+    }
+}
+
+/// A location area.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LocationArea {
+    pub id: AreaId,
+    /// The identifier of the area, e.g. `"unova-route-1-area"`.
+    pub identifier: String,
+    /// The region the area is in, if it is known.
+    pub region: Option<Region>,
+}
+
+/// The location areas with their regions, from the auxiliary
+/// `location_area_regions.csv`, with the columns `location_area_id`,
+/// `identifier`, and `region_id`; and the paths between them, from the
+/// auxiliary `location_area_adjacency.csv`, with the columns
+/// `location_area_id` and `adjacent_area_id`. Paths go both ways.
+///
+/// The files are not included in the binary; provide them through a data
+/// source (see `veekun::data::Overlay`), and override them the same way or
+/// with `insert`, `connect`, and `disconnect`.
+#[derive(Clone, Debug, Default)]
+pub struct LocationTable(
+    pub HashMap<AreaId, LocationArea>, IdentifierMap<AreaId>,
+    HashMap<AreaId, Vec<AreaId>>);
+
+impl LocationTable {
+    /// Create a location table from the CSV data in a source. The table is
+    /// empty of whichever file the source does not have.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut table: LocationTable =
+            match source.csv(vdata::files::LOCATION_AREA_REGIONS) {
+                Some(data) => Self::from_csv_data(data)?,
+                None => Default::default(),
+            };
+        if let Some(data) = source.csv(vdata::files::LOCATION_AREA_ADJACENCY) {
+            for (from, to) in AdjacencyTable::from_csv_data(data)?.0 {
+                table.connect(from, to);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Get the map between the areas' identifiers and IDs.
+    pub fn identifiers(&self) -> &IdentifierMap<AreaId> {
+        &self.1
+    }
+
+    /// Add or replace an area.
+    pub fn insert(&mut self, area: LocationArea) {
+        self.1.insert(&area.identifier, area.id);
+        self.0.insert(area.id, area);
+    }
+
+    /// The areas in a region, in order of ID.
+    pub fn in_region(&self, region: Region) -> Vec<AreaId> {
+        let mut areas: Vec<_> = self.0.values()
+            .filter(|area| area.region == Some(region))
+            .map(|area| area.id)
+            .collect();
+        areas.sort();
+        areas
+    }
+
+    /// The areas with a path to an area, in order of ID.
+    pub fn adjacent(&self, id: AreaId) -> &[AreaId] {
+        self.2.get(&id).map_or(&[], |areas| &areas[..])
+    }
+
+    /// Add a path between two areas, both ways.
+    pub fn connect(&mut self, a: AreaId, b: AreaId) {
+        for (from, to) in [(a, b), (b, a)] {
+            let adjacent = self.2.entry(from).or_default();
+            if let Err(i) = adjacent.binary_search(&to) {
+                adjacent.insert(i, to);
+            }
+        }
+    }
+
+    /// Remove the path between two areas, both ways.
+    pub fn disconnect(&mut self, a: AreaId, b: AreaId) {
+        for (from, to) in [(a, b), (b, a)] {
+            if let Some(adjacent) = self.2.get_mut(&from) {
+                adjacent.retain(|&id| id != to);
+            }
+        }
+    }
+
+    /// Find the nearest area to a starting area that satisfies a condition,
+    /// like having an encounter with a species, and get the path to it,
+    /// starting with the starting area, or `None` if no reachable area does.
+    ///
+    /// Nearer means fewer areas away; ties go to the area first reached in
+    /// order of ID.
+    pub fn earliest_where(
+        &self, start: AreaId, mut condition: impl FnMut(AreaId) -> bool
+    ) -> Option<Vec<AreaId>> {
+        let mut previous = HashMap::new();
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            if condition(id) {
+                let mut path = vec![id];
+                while let Some(&prev) = previous.get(path.last()?) {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &next in self.adjacent(id) {
+                if seen.insert(next) {
+                    previous.insert(next, id);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl vcsv::FromCsvIncremental for LocationTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let identifier: VeekunString = vcsv::from_field(&record, 1)?;
+        let region: VeekunOption<_> = vcsv::from_field(&record, 2)?;
+        self.insert(LocationArea {
+            id: vcsv::from_field(&record, 0)?,
+            identifier: identifier.into(),
+            region: region.into(),
+        });
+        Ok(())
+    }
+}
+
+/// The paths of `location_area_adjacency.csv`.
+#[derive(Default)]
+struct AdjacencyTable(Vec<(AreaId, AreaId)>);
+
+impl vcsv::FromCsvIncremental for AdjacencyTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let from = vcsv::from_field(&record, 0)?;
+        let to = vcsv::from_field(&record, 1)?;
+        self.0.push((from, to));
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt::{Display, Formatter};
+use crate::pokedex;
+use super::{Ailment, Category, DamageClass, Effect, Move, MoveId, MoveTable};
+
+/// A way a move's meta data disagrees with its effect, or with itself.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Inconsistency {
+    /// The category heals the user, but the move heals no HP.
+    HealWithoutHealing,
+    /// The category heals the user by the damage inflicted, but the move
+    /// absorbs none.
+    DrainWithoutDrain,
+    /// The category inflicts an ailment, but the move has none.
+    AilmentCategoryWithoutAilment,
+    /// The move has a chance of inflicting an ailment, but has none.
+    AilmentChanceWithoutAilment,
+    /// The category changes stats, but the move changes none.
+    StatCategoryWithoutChanges,
+    /// The move has a chance of changing stats, but changes none.
+    StatChanceWithoutChanges,
+    /// The category is damaging and the move is not, or the other way round.
+    DamageClassMismatch,
+    /// The effect inflicts an ailment other than the move's.
+    EffectAilmentMismatch(Ailment),
+    /// The effect may make the target flinch, but the move has no chance of
+    /// flinching.
+    EffectWithoutFlinchChance,
+    /// The move has an effect chance, but no chance of an ailment, flinching,
+    /// or a stat change.
+    EffectChanceWithoutMetaChance,
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Inconsistency::HealWithoutHealing
+                => write!(f, "heal category without healing"),
+            Inconsistency::DrainWithoutDrain
+                => write!(f, "damage-heal category without drain"),
+            Inconsistency::AilmentCategoryWithoutAilment
+                => write!(f, "ailment category without an ailment"),
+            Inconsistency::AilmentChanceWithoutAilment
+                => write!(f, "ailment chance without an ailment"),
+            Inconsistency::StatCategoryWithoutChanges
+                => write!(f, "stat category without stat changes"),
+            Inconsistency::StatChanceWithoutChanges
+                => write!(f, "stat chance without stat changes"),
+            Inconsistency::DamageClassMismatch
+                => write!(f, "category disagrees with damage class"),
+            Inconsistency::EffectAilmentMismatch(ailment)
+                => write!(f, "effect inflicts {:?}", ailment),
+            Inconsistency::EffectWithoutFlinchChance
+                => write!(f, "flinch effect without flinch chance"),
+            Inconsistency::EffectChanceWithoutMetaChance
+                => write!(f, "effect chance without a meta chance"),
+        }
+    }
+}
+
+/// An inconsistency found in a move by `audit`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AuditWarning {
+    pub move_id: MoveId,
+    /// The Veekun identifier of the move, e.g. `"karate-chop"`.
+    pub identifier: String,
+    pub inconsistency: Inconsistency,
+}
+
+impl Display for AuditWarning {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.identifier, self.inconsistency)
+    }
+}
+
+/// The ailment an effect inflicts, for effects that only inflict one.
+fn effect_ailment(effect: Effect) -> Option<Ailment> {
+    Some(match effect {
+        Effect::ChanceParalyzeTarget | Effect::ParalyzeTarget
+            => Ailment::Paralysis,
+        Effect::SleepTarget => Ailment::Sleep,
+        Effect::ChanceFreezeTarget => Ailment::Freeze,
+        Effect::ChanceBurnTarget => Ailment::Burn,
+        Effect::ChancePoisonTarget | Effect::PoisonTarget => Ailment::Poison,
+        Effect::ConfuseTarget | Effect::ChanceConfuseTarget
+            => Ailment::Confusion,
+        _ => return None,
+    })
+}
+
+/// Find the inconsistencies in a move.
+fn audit_move(mov: &Move) -> Vec<Inconsistency> {
+    let meta = &mov.meta;
+    let changes_stats = meta.stat_changes.iter().next().is_some();
+    let damaging = mov.damage_class != DamageClass::NonDamaging;
+    let checks = [
+        (matches!(meta.category, Category::Heal) && meta.healing <= 0,
+         Inconsistency::HealWithoutHealing),
+        (matches!(meta.category, Category::DamageHeal) && meta.recoil <= 0,
+         Inconsistency::DrainWithoutDrain),
+        (matches!(meta.category, Category::Ailment | Category::DamageAilment)
+            && meta.ailment == Ailment::None,
+         Inconsistency::AilmentCategoryWithoutAilment),
+        (meta.ailment_chance > 0 && meta.ailment == Ailment::None,
+         Inconsistency::AilmentChanceWithoutAilment),
+        (matches!(meta.category, Category::NetGoodStats
+                  | Category::DamageLower | Category::DamageRaise)
+            && !changes_stats,
+         Inconsistency::StatCategoryWithoutChanges),
+        (meta.stat_chance > 0 && !changes_stats,
+         Inconsistency::StatChanceWithoutChanges),
+        (match meta.category {
+            Category::Damage | Category::DamageAilment
+                | Category::DamageLower | Category::DamageRaise
+                | Category::DamageHeal => !damaging,
+            Category::Ailment | Category::NetGoodStats | Category::Heal
+                | Category::Swagger => damaging,
+            _ => false,
+        }, Inconsistency::DamageClassMismatch),
+        (matches!(mov.effect, Effect::ChanceFlinchTarget)
+            && meta.flinch_chance == 0,
+         Inconsistency::EffectWithoutFlinchChance),
+        (mov.effect_chance.is_some_and(|chance| chance > 0)
+            && meta.ailment_chance == 0 && meta.flinch_chance == 0
+            && meta.stat_chance == 0,
+         Inconsistency::EffectChanceWithoutMetaChance),
+    ];
+    let mut found: Vec<_> = checks.iter()
+        .filter(|&&(failed, _)| failed)
+        .map(|&(_, inconsistency)| inconsistency)
+        .collect();
+    if let Some(ailment) = effect_ailment(mov.effect) {
+        if ailment != meta.ailment {
+            found.push(Inconsistency::EffectAilmentMismatch(ailment));
+        }
+    }
+    found
+}
+
+impl MoveTable {
+    /// Check every move's meta data against its effect and itself, for
+    /// validating the Veekun data or an overlay of it.
+    pub fn audit(&self) -> Vec<AuditWarning> {
+        self.0.iter()
+            .flat_map(|mov| audit_move(mov).into_iter().map(|inconsistency| {
+                AuditWarning {
+                    move_id: mov.id,
+                    identifier: mov.identifier.clone(),
+                    inconsistency,
+                }
+            }))
+            .collect()
+    }
+}
+
+/// Check the moves of the global pokedex, as in `MoveTable::audit`.
+pub fn audit() -> Vec<AuditWarning> {
+    pokedex().moves.audit()
+}
@@ -0,0 +1,130 @@
+use crate::builder::{check, Result};
+use crate::Type;
+use crate::to_pascal_case;
+use crate::versions::Generation;
+use super::{DamageClass, Effect, Flags, Meta, Move, MoveId, Target};
+
+/// The highest power of a move, that of Explosion.
+pub const MAX_POWER: u8 = 250;
+
+/// The most PP of a move before PP Ups.
+pub const MAX_PP: u8 = 40;
+
+/// A move of a program's own, to add with `MoveTable::insert_custom` or to
+/// use as a fixture.
+///
+/// Fields not set keep the defaults of `Move`, but the generation is the
+/// latest and the move cannot miss. A builder can also start
+/// from an existing move, to tweak it.
+#[derive(Clone, Debug)]
+pub struct MoveBuilder(Move);
+
+impl MoveBuilder {
+    /// Start a move with a Veekun-style identifier, e.g. `"shadow-rush"`,
+    /// from which its name is derived.
+    pub fn new(identifier: &str) -> Self {
+        MoveBuilder(Move {
+            name: to_pascal_case(identifier),
+            identifier: identifier.to_string(),
+            generation: Generation::V,
+            .. Default::default()
+        })
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.0.name = name.to_string();
+        self
+    }
+
+    pub fn generation(mut self, generation: Generation) -> Self {
+        self.0.generation = generation;
+        self
+    }
+
+    pub fn typ(mut self, typ: Type) -> Self {
+        self.0.typ = typ;
+        self
+    }
+
+    pub fn power(mut self, power: u8) -> Self {
+        self.0.power = power;
+        self
+    }
+
+    pub fn pp(mut self, pp: u8) -> Self {
+        self.0.pp = pp;
+        self
+    }
+
+    /// The accuracy in percent, or `None` if the move cannot miss.
+    pub fn accuracy(mut self, accuracy: Option<u8>) -> Self {
+        self.0.accuracy = accuracy;
+        self
+    }
+
+    pub fn priority(mut self, priority: i8) -> Self {
+        self.0.priority = priority;
+        self
+    }
+
+    pub fn target(mut self, target: Target) -> Self {
+        self.0.target = target;
+        self
+    }
+
+    pub fn damage_class(mut self, damage_class: DamageClass) -> Self {
+        self.0.damage_class = damage_class;
+        self
+    }
+
+    /// The effect, with its chance in percent if relevant.
+    pub fn effect(mut self, effect: Effect, chance: Option<u8>) -> Self {
+        self.0.effect = effect;
+        self.0.effect_chance = chance;
+        self
+    }
+
+    pub fn meta(mut self, meta: Meta) -> Self {
+        self.0.meta = meta;
+        self
+    }
+
+    pub fn flags(mut self, flags: Flags) -> Self {
+        self.0.meta.flags = flags;
+        self
+    }
+
+    /// Check the move and finish it with an ID.
+    ///
+    /// The power must be at most `MAX_POWER`; the PP at most `MAX_PP`, or 0
+    /// for moves that cannot be chosen, like Struggle; percentages between 1
+    /// and 100; and the priority between -7 and +5. A move cannot both
+    /// charge and recharge, and punching moves make contact.
+    pub fn build(self, id: MoveId) -> Result<Move> {
+        let mov = &self.0;
+        let percent = |p: Option<u8>| p.is_none_or(|p| (1..=100).contains(&p));
+        check(!mov.identifier.is_empty(), "identifier", "empty")?;
+        check(mov.power <= MAX_POWER, "power", "above 250")?;
+        check(mov.pp <= MAX_PP, "pp", "above 40")?;
+        check(percent(mov.accuracy), "accuracy", "not between 1 and 100")?;
+        check(percent(mov.effect_chance), "effect_chance",
+              "not between 1 and 100")?;
+        check((-7..=5).contains(&mov.priority), "priority",
+              "not between -7 and +5")?;
+        let flags = mov.meta.flags;
+        check(!flags.contains(Flags::CHARGE | Flags::RECHARGE), "flags",
+              "both charge and recharge")?;
+        check(!flags.contains(Flags::PUNCH) || flags.contains(Flags::CONTACT),
+              "flags", "punch without contact")?;
+        for range in [mov.meta.hits, mov.meta.turns].iter().flatten() {
+            check(range.0 <= range.1, "meta", "range minimum above maximum")?;
+        }
+        Ok(Move { id, .. self.0 })
+    }
+}
+
+impl From<Move> for MoveBuilder {
+    fn from(mov: Move) -> Self {
+        MoveBuilder(mov)
+    }
+}
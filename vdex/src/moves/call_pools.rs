@@ -0,0 +1,79 @@
+//! The moves that Metronome, Assist, and Sleep Talk can call, with
+//! Generation V rules.
+//!
+//! Each pool is a set of move IDs in order, so a calling move picks the move
+//! at a random index below the pool's length.
+
+use std::collections::BTreeSet;
+use crate::pokedex;
+use crate::versions::Generation;
+use super::{Flags, Move, MoveId};
+
+/// The moves Metronome cannot call, by Veekun identifier.
+pub const METRONOME_EXCLUDED: &[&str] = &[
+    "after-you", "assist", "bestow", "chatter", "copycat", "counter", "covet",
+    "destiny-bond", "detect", "endure", "feint", "focus-punch", "follow-me",
+    "freeze-shock", "helping-hand", "ice-burn", "me-first", "metronome",
+    "mimic", "mirror-coat", "mirror-move", "nature-power", "protect", "quash",
+    "quick-guard", "rage-powder", "relic-song", "secret-sword", "sketch",
+    "sleep-talk", "snarl", "snatch", "snore", "struggle", "switcheroo",
+    "techno-blast", "thief", "transform", "trick", "v-create", "wide-guard",
+];
+
+/// The moves Assist cannot call, by Veekun identifier.
+pub const ASSIST_EXCLUDED: &[&str] = &[
+    "assist", "bestow", "chatter", "circle-throw", "copycat", "counter",
+    "covet", "destiny-bond", "detect", "dragon-tail", "endure", "feint",
+    "focus-punch", "follow-me", "helping-hand", "me-first", "metronome",
+    "mimic", "mirror-coat", "mirror-move", "nature-power", "protect",
+    "rage-powder", "sketch", "sleep-talk", "snatch", "struggle", "switcheroo",
+    "thief", "transform", "trick",
+];
+
+/// The moves Sleep Talk cannot call, by Veekun identifier, besides moves
+/// with `Flags::CHARGE`.
+pub const SLEEP_TALK_EXCLUDED: &[&str] = &[
+    "assist", "bide", "chatter", "copycat", "focus-punch", "me-first",
+    "metronome", "mimic", "mirror-move", "nature-power", "sketch",
+    "sleep-talk", "uproar",
+];
+
+fn excluded(mov: &Move, list: &[&str]) -> bool {
+    list.contains(&mov.identifier.as_str())
+}
+
+/// The moves Metronome can call in a generation: every move introduced by
+/// then, except those in `METRONOME_EXCLUDED`.
+pub fn metronome_pool(generation: Generation) -> BTreeSet<MoveId> {
+    pokedex().moves.0.iter()
+        .filter(|m| m.generation <= generation)
+        .filter(|m| !excluded(m, METRONOME_EXCLUDED))
+        .map(|m| m.id)
+        .collect()
+}
+
+/// The moves Assist can call, given the moves known by the user's allies in
+/// its party (not the user itself), except those in `ASSIST_EXCLUDED`.
+pub fn assist_pool(
+    ally_moves: impl IntoIterator<Item = MoveId>
+) -> BTreeSet<MoveId> {
+    let moves = &pokedex().moves;
+    ally_moves.into_iter()
+        .filter(|&id| !excluded(&moves[id], ASSIST_EXCLUDED))
+        .collect()
+}
+
+/// The moves Sleep Talk can call, given the moves known by the user: those
+/// without `Flags::CHARGE` and not in `SLEEP_TALK_EXCLUDED`.
+pub fn sleep_talk_pool(
+    known_moves: impl IntoIterator<Item = MoveId>
+) -> BTreeSet<MoveId> {
+    let moves = &pokedex().moves;
+    known_moves.into_iter()
+        .filter(|&id| {
+            let mov = &moves[id];
+            !mov.meta.flags.contains(Flags::CHARGE)
+                && !excluded(mov, SLEEP_TALK_EXCLUDED)
+        })
+        .collect()
+}
@@ -0,0 +1,163 @@
+use crate::Stat;
+use super::{Ailment, Category, DamageClass, Flags, Move, PowerKind, Target};
+
+/// The verb for inflicting an ailment, and its third-person form.
+fn ailment_verb(ailment: Ailment) -> Option<(&'static str, &'static str)> {
+    Some(match ailment {
+        Ailment::Paralysis => ("paralyze", "Paralyzes"),
+        Ailment::Sleep => ("put to sleep", "Puts to sleep"),
+        Ailment::Freeze => ("freeze", "Freezes"),
+        Ailment::Burn => ("burn", "Burns"),
+        Ailment::Poison => ("poison", "Poisons"),
+        Ailment::Confusion => ("confuse", "Confuses"),
+        Ailment::Infatuation => ("infatuate", "Infatuates"),
+        Ailment::Trap => ("trap", "Traps"),
+        _ => return None,
+    })
+}
+
+/// The name of a stat in words, e.g. "Special Attack".
+fn stat_name(stat: Stat) -> String {
+    let mut name = String::new();
+    for c in format!("{:?}", stat).chars() {
+        if c.is_uppercase() && !name.is_empty() && stat != Stat::HP {
+            name.push(' ');
+        }
+        name.push(c);
+    }
+    name
+}
+
+fn stages(change: i8) -> String {
+    match change.unsigned_abs() {
+        1 => "one stage".to_string(),
+        2 => "two stages".to_string(),
+        3 => "three stages".to_string(),
+        n => format!("{} stages", n),
+    }
+}
+
+fn chance(percent: u8) -> Option<String> {
+    if percent == 0 || percent == 100 {
+        None
+    } else {
+        Some(format!("Has a {}% chance to", percent))
+    }
+}
+
+impl Move {
+    /// Whether the move's stat changes apply to the user rather than the
+    /// target.
+    fn changes_user_stats(&self) -> bool {
+        match self.meta.category {
+            Category::DamageRaise => true,
+            Category::DamageLower | Category::Swagger => false,
+            _ => matches!(self.target, Target::User | Target::UsersField),
+        }
+    }
+
+    /// Generate an English description of the move from its data: its power
+    /// and damage class, ailment, stat changes, drain, recoil, healing, and
+    /// flags, with the chance of each, e.g. "Inflicts special damage with 90
+    /// power. Has a 10% chance to paralyze the target."
+    ///
+    /// Effects that the structured data does not cover are not described, so
+    /// prose (see `prose`) is preferable when available.
+    pub fn describe(&self) -> String {
+        let meta = &self.meta;
+        let mut sentences = Vec::new();
+        match (self.damage_class, meta.category) {
+            (_, Category::OneHitKO) => {
+                sentences.push("Causes a one-hit KO.".to_string())
+            },
+            (DamageClass::NonDamaging, _) => (),
+            (class, _) => {
+                let class = format!("{:?}", class).to_lowercase();
+                sentences.push(match self.power_kind() {
+                    PowerKind::Fixed(power) => format!(
+                        "Inflicts {} damage with {} power.", class, power),
+                    _ => format!("Inflicts {} damage.", class),
+                });
+            },
+        }
+        if let Some((min, max)) = meta.hits {
+            sentences.push(if min == max {
+                format!("Hits {} times.", min)
+            } else {
+                format!("Hits {} to {} times.", min, max)
+            });
+        }
+        if let Some((verb, verbs)) = ailment_verb(meta.ailment) {
+            sentences.push(match chance(meta.ailment_chance) {
+                Some(chance) => format!("{} {} the target.", chance, verb),
+                None => format!("{} the target.", verbs),
+            });
+        }
+        let whose = if self.changes_user_stats() { "user" } else { "target" };
+        let mut changes: Vec<(i8, Vec<String>)> = Vec::new();
+        for (stat, change) in meta.stat_changes.iter() {
+            let name = stat_name(stat.stat());
+            match changes.iter_mut().find(|(c, _)| *c == change) {
+                Some((_, names)) => names.push(name),
+                None => changes.push((change, vec![name])),
+            }
+        }
+        for (change, names) in changes {
+            let verb = if change > 0 { "raise" } else { "lower" };
+            let stats = names.join(" and ");
+            let stages = stages(change);
+            sentences.push(match chance(meta.stat_chance) {
+                Some(chance) => format!(
+                    "{} {} the {}'s {} by {}.", chance, verb, whose, stats,
+                    stages),
+                None => format!(
+                    "{}s the {}'s {} by {}.",
+                    if change > 0 { "Raise" } else { "Lower" }, whose, stats,
+                    stages),
+            });
+        }
+        if let Some(chance) = chance(meta.flinch_chance) {
+            sentences.push(format!("{} make the target flinch.", chance));
+        }
+        if meta.recoil > 0 {
+            sentences.push(format!(
+                "Drains {}% of the damage inflicted.", meta.recoil));
+        } else if meta.recoil < 0 {
+            sentences.push(format!(
+                "The user takes {}% of the damage inflicted as recoil.",
+                -(meta.recoil as i16)));
+        }
+        if meta.healing > 0 {
+            sentences.push(format!(
+                "Heals the user by {}% of its maximum HP.", meta.healing));
+        } else if meta.healing < 0 {
+            sentences.push(format!(
+                "The user loses {}% of its maximum HP.",
+                -(meta.healing as i16)));
+        }
+        if meta.critical_rate > 0 {
+            sentences.push(
+                "Has an increased chance for a critical hit.".to_string());
+        }
+        if meta.flags.contains(Flags::CHARGE) {
+            sentences.push("Requires a turn to charge first.".to_string());
+        }
+        if meta.flags.contains(Flags::RECHARGE) {
+            sentences.push(
+                "The user must recharge on the next turn.".to_string());
+        }
+        if self.priority != 0 {
+            sentences.push(format!("Has {:+} priority.", self.priority));
+        }
+        let targets_pokemon = matches!(self.target,
+            Target::SelectedPokemon | Target::RandomOpponent
+            | Target::AllOtherPokemon | Target::AllOpponents);
+        if self.accuracy.is_none() && targets_pokemon {
+            sentences.push("Never misses.".to_string());
+        }
+        if sentences.is_empty() {
+            sentences.push("Has no effect described by its data.".to_string());
+        }
+        sentences.join(" ")
+    }
+}
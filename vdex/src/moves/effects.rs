@@ -5,7 +5,11 @@ use crate::FromVeekun;
 ///
 /// Some effects are shared among several moves, whereas others are unique to a
 /// single move.
-#[EnumRepr(type = "u16")]
+///
+/// New effects may be added with new data, so matches on effects outside vdex
+/// need a wildcard arm.
+#[EnumRepr(type = "u16", serde = true)]
+#[non_exhaustive]
 pub enum Effect {
     // Generic
     RegularDamage = 1,
@@ -0,0 +1,130 @@
+use crate::enums::*;
+use crate::Stat;
+use crate::Type;
+use super::{Ailment, Effect, MoveId, Weather, WeatherModifier};
+use self::Terrain::*;
+use self::EnvironmentVariant as Variant;
+
+/// The terrain a battle takes place on, as of Generation V.
+#[EnumRepr(type = "u8")]
+pub enum Terrain {
+    /// Indoors, and in link battles.
+    Building = 0,
+    /// Plains, roads, and other bare ground.
+    Plain,
+    /// Grass and tall grass.
+    Grass,
+    Sand,
+    Cave,
+    /// Rocky ground and mountains.
+    Rock,
+    /// On water, while surfing.
+    Water,
+    Snow,
+    Ice,
+    /// Marshes and puddles.
+    Marsh,
+}
+
+/// The environment of a battle that can change a move's behavior.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Environment {
+    Weather(Weather),
+    Terrain(Terrain),
+}
+
+/// A change to a move's behavior in some environment.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum EnvironmentVariant {
+    /// A change in some weather, as in `Effect::weather_modifiers`.
+    Weather(WeatherModifier),
+    /// The move is replaced by another move, like Nature Power.
+    CallsMove(MoveId),
+    /// The move has a chance in percent to inflict an ailment on the target.
+    Ailment(Ailment, u8),
+    /// The move has a chance in percent to change a stat of the target by a
+    /// number of stages.
+    StatChange(Stat, i8, u8),
+    /// The move has a chance in percent to make the target flinch.
+    Flinch(u8),
+    /// The user's type changes, like with Camouflage.
+    UserType(Type),
+}
+
+const NATURE_POWER: &[(Terrain, Variant)] = &[
+    (Building, Variant::CallsMove(MoveId(88))),
+    (Plain, Variant::CallsMove(MoveId(88))),
+    (Grass, Variant::CallsMove(MoveId(401))),
+    (Sand, Variant::CallsMove(MoveId(88))),
+    (Cave, Variant::CallsMove(MoveId(156))),
+    (Rock, Variant::CallsMove(MoveId(156))),
+    (Water, Variant::CallsMove(MoveId(55))),
+    (Snow, Variant::CallsMove(MoveId(58))),
+    (Ice, Variant::CallsMove(MoveId(58))),
+    (Marsh, Variant::CallsMove(MoveId(425))),
+];
+
+const SECRET_POWER: &[(Terrain, Variant)] = &[
+    (Building, Variant::Ailment(Ailment::Paralysis, 30)),
+    (Plain, Variant::Ailment(Ailment::Paralysis, 30)),
+    (Grass, Variant::Ailment(Ailment::Sleep, 30)),
+    (Sand, Variant::StatChange(Stat::Accuracy, -1, 30)),
+    (Cave, Variant::Flinch(30)),
+    (Rock, Variant::Flinch(30)),
+    (Water, Variant::StatChange(Stat::Attack, -1, 30)),
+    (Snow, Variant::Ailment(Ailment::Freeze, 30)),
+    (Ice, Variant::Ailment(Ailment::Freeze, 30)),
+    (Marsh, Variant::StatChange(Stat::Speed, -1, 30)),
+];
+
+const CAMOUFLAGE: &[(Terrain, Variant)] = &[
+    (Building, Variant::UserType(Type::Normal)),
+    (Plain, Variant::UserType(Type::Ground)),
+    (Grass, Variant::UserType(Type::Grass)),
+    (Sand, Variant::UserType(Type::Ground)),
+    (Cave, Variant::UserType(Type::Rock)),
+    (Rock, Variant::UserType(Type::Rock)),
+    (Water, Variant::UserType(Type::Water)),
+    (Snow, Variant::UserType(Type::Ice)),
+    (Ice, Variant::UserType(Type::Ice)),
+    (Marsh, Variant::UserType(Type::Ground)),
+];
+
+impl Effect {
+    /// How terrain changes the behavior of moves with this effect, as of
+    /// Generation V, as pairs of a terrain and a variant that applies on it.
+    ///
+    /// This covers Nature Power, Secret Power, and Camouflage. Other effects
+    /// are not affected by terrain, and return an empty slice.
+    pub fn terrain_variants(self) -> &'static [(Terrain, EnvironmentVariant)] {
+        match self {
+            Effect::NaturePower => NATURE_POWER,
+            Effect::SecretPower => SECRET_POWER,
+            Effect::Camouflage => CAMOUFLAGE,
+            _ => &[],
+        }
+    }
+
+    /// How the environment changes the behavior of moves with this effect:
+    /// the weather modifiers of `weather_modifiers`, such as the type and
+    /// power of Weather Ball, followed by the variants of `terrain_variants`.
+    pub fn environment_variants(
+        self
+    ) -> impl Iterator<Item = (Environment, EnvironmentVariant)> {
+        let weather = self.weather_modifiers().iter().map(|&(w, modifier)| {
+            (Environment::Weather(w), Variant::Weather(modifier))
+        });
+        let terrain = self.terrain_variants().iter()
+            .map(|&(t, variant)| (Environment::Terrain(t), variant));
+        weather.chain(terrain)
+    }
+
+    /// The variants of this effect that apply in an environment.
+    pub fn variants_in(
+        self, environment: Environment
+    ) -> impl Iterator<Item = EnvironmentVariant> {
+        self.environment_variants()
+            .filter(move |&(e, _)| e == environment)
+            .map(|(_, variant)| variant)
+    }
+}
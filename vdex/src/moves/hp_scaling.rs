@@ -0,0 +1,70 @@
+use super::Effect;
+
+/// The brackets of Flail and Reversal: the highest HP of each, in 48ths of
+/// the user's maximum HP, and the power up to it.
+const FLAIL_BRACKETS: &[(u16, u16)] = &[
+    (1, 200), (4, 150), (9, 100), (16, 80), (32, 40), (48, 20),
+];
+
+/// How the power of a move depends on the HP of its user or target, as of
+/// Generation V.
+///
+/// HP is given as the current and maximum HP.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HpScaling {
+    /// The power is that of the first bracket the user's HP, in 48ths of its
+    /// maximum HP and rounded down, is at most, as in Flail and Reversal.
+    UserBrackets(&'static [(u16, u16)]),
+    /// The power is the move's power in proportion to the user's HP, but at
+    /// least 1, as in Eruption and Water Spout.
+    UserProportional,
+    /// The move's power doubles if the target's HP is at most a fraction of
+    /// its maximum HP, as in Brine at half.
+    DoubledAtTargetFraction((u16, u16)),
+    /// The power is a maximum power in proportion to the target's HP, but at
+    /// least 1, as in Wring Out and Crush Grip.
+    TargetProportional(u16),
+}
+
+impl HpScaling {
+    /// Calculate the power of a move with a Veekun power, like 150 for
+    /// Eruption, from the HP of its user and target.
+    pub fn power(self, power: u8, user: (u16, u16), target: (u16, u16)) -> u16 {
+        let proportion = |max: u16, (hp, max_hp): (u16, u16)| {
+            (max as u32 * hp as u32 / max_hp.max(1) as u32).max(1) as u16
+        };
+        match self {
+            HpScaling::UserBrackets(brackets) => {
+                let (hp, max_hp) = user;
+                let scaled = 48 * hp as u32 / max_hp.max(1) as u32;
+                brackets.iter()
+                    .find(|&&(highest, _)| scaled <= highest as u32)
+                    .or(brackets.last())
+                    .map_or(power.into(), |&(_, power)| power)
+            },
+            HpScaling::UserProportional => proportion(power.into(), user),
+            HpScaling::DoubledAtTargetFraction((numerator, denominator)) => {
+                let (hp, max_hp) = target;
+                let at_most = hp as u32 * denominator as u32
+                    <= max_hp as u32 * numerator as u32;
+                power as u16 * if at_most { 2 } else { 1 }
+            },
+            HpScaling::TargetProportional(max) => proportion(max, target),
+        }
+    }
+}
+
+impl Effect {
+    /// How the power of moves with this effect depends on HP, if it does.
+    pub fn hp_scaling(self) -> Option<HpScaling> {
+        Some(match self {
+            Effect::MoreDamageWhenLessUserHP
+                => HpScaling::UserBrackets(FLAIL_BRACKETS),
+            Effect::MoreDamageWhenMoreUserHP => HpScaling::UserProportional,
+            Effect::Brine => HpScaling::DoubledAtTargetFraction((1, 2)),
+            Effect::MoreDamageWhenMoreTargetHP
+                => HpScaling::TargetProportional(120),
+            _ => return None,
+        })
+    }
+}
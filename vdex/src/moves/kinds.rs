@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter};
+use super::{Category, DamageClass, Effect, Move};
+
+/// The Veekun power of moves whose power is determined in battle.
+pub const VARIABLE_POWER: u8 = 1;
+
+/// How a move's power is determined.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PowerKind {
+    /// The move has a fixed power.
+    Fixed(u8),
+    /// The move's power, or its damage, is determined in battle by its
+    /// effect, like Seismic Toss or Flail.
+    Variable(Effect),
+    /// The move does not deal damage with a power, like status moves.
+    None,
+}
+
+impl Display for PowerKind {
+    /// Format the power for display: the number, "varies", or "—".
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            PowerKind::Fixed(power) => write!(f, "{}", power),
+            PowerKind::Variable(_) => f.write_str("varies"),
+            PowerKind::None => f.write_str("—"),
+        }
+    }
+}
+
+/// How a move's accuracy is determined.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AccuracyKind {
+    /// The move has a fixed accuracy in percent, before stat stages.
+    Fixed(u8),
+    /// The move's accuracy is determined in battle by its effect, like the
+    /// one-hit KO moves, which depend on the levels of the Pokémon.
+    Variable(Effect),
+    /// The move never misses.
+    None,
+}
+
+impl Display for AccuracyKind {
+    /// Format the accuracy for display: the percentage, "varies", or "—".
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            AccuracyKind::Fixed(accuracy) => write!(f, "{}%", accuracy),
+            AccuracyKind::Variable(_) => f.write_str("varies"),
+            AccuracyKind::None => f.write_str("—"),
+        }
+    }
+}
+
+impl Move {
+    /// How the move's power is determined, from its Veekun power: 0 for
+    /// moves without power and `VARIABLE_POWER` for moves whose power varies.
+    pub fn power_kind(&self) -> PowerKind {
+        match self.power {
+            0 => PowerKind::None,
+            _ if self.damage_class == DamageClass::NonDamaging
+                => PowerKind::None,
+            VARIABLE_POWER => PowerKind::Variable(self.effect),
+            power => PowerKind::Fixed(power),
+        }
+    }
+
+    /// How the move's accuracy is determined.
+    pub fn accuracy_kind(&self) -> AccuracyKind {
+        match self.accuracy {
+            None => AccuracyKind::None,
+            Some(_) if self.meta.category == Category::OneHitKO
+                => AccuracyKind::Variable(self.effect),
+            Some(accuracy) => AccuracyKind::Fixed(accuracy),
+        }
+    }
+}
@@ -10,7 +10,7 @@ use crate::FromVeekun;
 /// > battle. There are three kinds of status. The first are non-volatile, the
 /// > second are volatile, and the third lasts while a Pokémon is in battle. The
 /// > Pokérus is a similar but unrelated concept.
-#[EnumRepr(type = "i8")]
+#[EnumRepr(type = "i8", bitset = true, serde = true)]
 pub enum Ailment {
     /// Some special ailment: used by Tri Attack, Telekinesis, and Smack Down.
     Unknown = -1,
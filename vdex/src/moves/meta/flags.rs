@@ -1,3 +1,4 @@
+use bitflags::bitflags;
 use std::collections::HashMap;
 use crate::FromVeekun;
 use crate::vcsv;
@@ -58,8 +59,8 @@ impl FromVeekun for Flags {
 pub struct FlagTable(pub HashMap<MoveId, Flags>);
 
 impl FlagTable {
-    pub fn new() -> Self {
-        FlagTable::from_csv_data(vdata::MOVE_FLAGS).unwrap()
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        FlagTable::from_csv_source(source, vdata::files::MOVE_FLAGS)
     }
 }
 
@@ -67,7 +68,7 @@ impl vcsv::FromCsvIncremental for FlagTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id: MoveId = vcsv::from_field(&record, 0)?;
         if id.0 >= 10000 {
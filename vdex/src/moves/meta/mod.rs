@@ -2,6 +2,7 @@ pub(self) mod ailments;
 pub(self) mod flags;
 
 pub use self::ailments::Ailment;
+pub use self::ailments::AilmentSet;
 pub use self::flags::Flags;
 pub use self::flags::FlagTable;
 
@@ -17,7 +18,7 @@ use crate::VeekunOption;
 use super::MoveId;
 
 /// Broad move category.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Category {
     /// Moves that inflict damage, potentially with some other minor effect.
     Damage = 0,
@@ -65,12 +66,79 @@ impl FromVeekun for Category {
 /// The number of stats directly changeable by moves (all but HP).
 pub const CHANGEABLE_STATS: usize = 7;
 
+/// A stat that can be directly changed by moves, i.e., any stat but HP.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct ChangeableStat(Stat);
+
+impl ChangeableStat {
+    /// Wrap a stat, or `None` if it is HP.
+    pub fn new(stat: Stat) -> Option<Self> {
+        match stat {
+            Stat::HP => None,
+            _ => Some(ChangeableStat(stat)),
+        }
+    }
+
+    /// The wrapped stat.
+    pub fn stat(self) -> Stat {
+        self.0
+    }
+
+    /// The index of the stat in `StatChanges`.
+    pub fn index(self) -> usize {
+        self.0.repr() as usize
+    }
+}
+
+impl From<ChangeableStat> for Stat {
+    fn from(stat: ChangeableStat) -> Self {
+        stat.0
+    }
+}
+
+impl FromVeekun for ChangeableStat {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Stat::from_veekun(value).and_then(ChangeableStat::new)
+    }
+}
+
+/// Wrapper of an array of stage changes to each changeable stat.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StatChanges(pub [i8; CHANGEABLE_STATS]);
+
+impl StatChanges {
+    /// Iterate over the stats with a nonzero change, along with the change.
+    pub fn iter(&self) -> impl Iterator<Item = (ChangeableStat, i8)> + '_ {
+        Stat::VALUES.iter()
+            .filter_map(|&stat| ChangeableStat::new(stat))
+            .map(move |stat| (stat, self[stat]))
+            .filter(|&(_, change)| change != 0)
+    }
+}
+
+impl std::ops::Index<ChangeableStat> for StatChanges {
+    type Output = i8;
+
+    fn index(&self, index: ChangeableStat) -> &i8 {
+        self.0.index(index.index())
+    }
+}
+
+impl std::ops::IndexMut<ChangeableStat> for StatChanges {
+    fn index_mut(&mut self, index: ChangeableStat) -> &mut i8 {
+        self.0.index_mut(index.index())
+    }
+}
+
 #[derive(Default)]
-pub struct StatChangeTable(pub HashMap<MoveId, [i8; CHANGEABLE_STATS]>);
+pub struct StatChangeTable(pub HashMap<MoveId, StatChanges>);
 
 impl StatChangeTable {
-    pub fn new() -> Self {
-        StatChangeTable::from_csv_data(vdata::MOVE_STAT_CHANGES).unwrap()
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        StatChangeTable::from_csv_source(
+            source, vdata::files::MOVE_STAT_CHANGES)
     }
 }
 
@@ -78,18 +146,15 @@ impl vcsv::FromCsvIncremental for StatChangeTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id: MoveId = vcsv::from_field(&record, 0)?;
         if id.0 >= 10000 {
             return Ok(())
         }
-        let stat: Stat = vcsv::from_field(&record, 1)?;
+        let stat: ChangeableStat = vcsv::from_field(&record, 1)?;
         let change = vcsv::from_field(&record, 2)?;
-        let mut stat_changes = self.0.get(&id)
-            .map_or([0; CHANGEABLE_STATS], |v| *v);
-        stat_changes[stat.repr() as usize] = change;
-        self.0.insert(id, stat_changes);
+        self.0.entry(id).or_default()[stat] = change;
         Ok(())
     }
 }
@@ -120,7 +185,7 @@ pub struct Meta {
     /// The chance the move has of changing stats.
     pub stat_chance: u8,
     /// The changes the move can make to stats.
-    pub stat_changes: [i8; CHANGEABLE_STATS],
+    pub stat_changes: StatChanges,
     /// Move bitflags.
     pub flags: Flags,
 }
@@ -128,11 +193,12 @@ pub struct Meta {
 pub struct MetaTable(pub [Meta; MOVE_COUNT]);
 
 impl MetaTable {
-    pub fn new() -> Self {
-        let mut table = MetaTable::from_csv_data(vdata::MOVE_META).unwrap();
-        table.set_flags(&FlagTable::new());
-        table.set_stat_changes(&StatChangeTable::new());
-        table
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut table
+            = MetaTable::from_csv_source(source, vdata::files::MOVE_META)?;
+        table.set_flags(&FlagTable::load(source)?);
+        table.set_stat_changes(&StatChangeTable::load(source)?);
+        Ok(table)
     }
 
     fn set_flags(&mut self, flags_table: &FlagTable) {
@@ -158,7 +224,7 @@ impl vcsv::FromCsvIncremental for MetaTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id: MoveId = vcsv::from_field(&record, 0)?;
         if id.0 >= 10000 {
@@ -193,7 +259,7 @@ impl vcsv::FromCsvIncremental for MetaTable {
             ailment_chance: vcsv::from_field(&record, 10)?,
             flinch_chance: vcsv::from_field(&record, 11)?,
             stat_chance: vcsv::from_field(&record, 12)?,
-            stat_changes: [0; CHANGEABLE_STATS],
+            stat_changes: Default::default(),
             flags: Flags::empty(),
         };
         Ok(())
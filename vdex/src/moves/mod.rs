@@ -1,18 +1,45 @@
 //! Moves and related data.
 
+pub(self) mod audit;
+pub(self) mod builder;
+pub mod call_pools;
+pub(self) mod describe;
 pub(self) mod effects;
+pub(self) mod environment;
+pub(self) mod hp_scaling;
+pub(self) mod kinds;
 pub(self) mod meta;
+pub(self) mod presentation;
+pub(self) mod weather;
 
+pub use self::audit::{audit, AuditWarning, Inconsistency};
+pub use self::builder::{MoveBuilder, MAX_POWER, MAX_PP};
 pub use self::effects::Effect;
+pub use self::environment::Environment;
+pub use self::environment::EnvironmentVariant;
+pub use self::environment::Terrain;
+pub use self::hp_scaling::HpScaling;
+pub use self::kinds::{AccuracyKind, PowerKind, VARIABLE_POWER};
 pub use self::meta::Ailment;
+pub use self::meta::AilmentSet;
 pub use self::meta::Category;
+pub use self::meta::ChangeableStat;
 pub use self::meta::Flags;
 pub use self::meta::Meta;
+pub use self::meta::StatChanges;
 pub use self::meta::CHANGEABLE_STATS;
-
+pub use self::presentation::Presentation;
+pub use self::presentation::PresentationTable;
+pub use self::weather::Weather;
+pub use self::weather::WeatherModifier;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::iter::repeat;
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::IdentifierMap;
 use crate::to_pascal_case;
 use crate::Type;
 use crate::vcsv;
@@ -22,7 +49,7 @@ use crate::VeekunOption;
 use crate::versions::Generation;
 
 /// The Battle Palace style of a move.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum BattleStyle {
     Attack = 0,
     Defense,
@@ -42,13 +69,29 @@ impl FromVeekun for BattleStyle {
 }
 
 /// The damage class (status, physical, or special) of a move.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum DamageClass {
     NonDamaging = 0,
     Physical,
     Special,
 }
 
+impl DamageClass {
+    /// The damage class shared by all damaging moves of a type before
+    /// Generation IV, when the class was determined by type rather than by
+    /// move.
+    pub fn of_type(typ: Type) -> Self {
+        match typ {
+            Type::Normal | Type::Fighting | Type::Flying | Type::Poison
+                | Type::Ground | Type::Rock | Type::Bug | Type::Ghost
+                | Type::Steel => DamageClass::Physical,
+            Type::Fire | Type::Water | Type::Grass | Type::Electric
+                | Type::Psychic | Type::Ice | Type::Dragon | Type::Dark
+                => DamageClass::Special,
+        }
+    }
+}
+
 impl Default for DamageClass {
     fn default() -> Self { DamageClass::NonDamaging }
 }
@@ -62,7 +105,7 @@ impl FromVeekun for DamageClass {
 }
 
 /// The method by which a Pokémon learns a move.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum LearnMethod {
     /// Learned at a certain level.
     LevelUp = 0,
@@ -99,7 +142,7 @@ impl FromVeekun for LearnMethod {
 }
 
 /// The target selection mechanism of a move.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Target {
     /// Target depends on some battle state (Counter, Curse, Mirror Coat, and
     /// Metal Burst).
@@ -140,14 +183,23 @@ impl FromVeekun for Target {
     }
 }
 
-/// The total number of moves in pbirch.
-pub const MOVE_COUNT: usize = 559;
+include!(concat!(env!("OUT_DIR"), "/move_count.rs"));
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct MoveId(pub u16);
 
 impl Default for MoveId {
-    fn default() -> Self { MoveId(std::u16::MAX) }
+    fn default() -> Self { MoveId(u16::MAX) }
+}
+
+impl MoveId {
+    /// True if the ID is that of a stock move, rather than a placeholder like
+    /// the default. Custom moves (see `MoveTable::insert_custom`) have IDs
+    /// beyond the stock moves; check those with `MoveTable::contains`.
+    pub fn is_valid(self) -> bool {
+        (self.0 as usize) < MOVE_COUNT
+    }
 }
 
 impl FromVeekun for MoveId {
@@ -176,11 +228,14 @@ pub struct Move {
     pub id: MoveId,
     /// The pbirch name for the move.
     pub name: String,
+    /// The Veekun identifier for the move, e.g. `"karate-chop"`.
+    pub identifier: String,
     /// The generation the move was introduced.
     pub generation: Generation,
     /// The move's type.
     pub typ: Type,
-    /// The move's power.
+    /// The move's power as in the Veekun data, which is 0 for moves without
+    /// power and 1 for moves whose power varies; see `power_kind`.
     pub power: u8,
     /// The move's power points.
     pub pp: u8,
@@ -200,19 +255,97 @@ pub struct Move {
     pub meta: meta::Meta,
 }
 
+impl Move {
+    /// The move's damage class in the given generation.
+    ///
+    /// In Generations I–III, damaging moves use the class of their type (see
+    /// `DamageClass::of_type`) rather than their own.
+    pub fn damage_class_in(&self, generation: Generation) -> DamageClass {
+        match self.damage_class {
+            DamageClass::NonDamaging => DamageClass::NonDamaging,
+            _ if generation < Generation::IV => DamageClass::of_type(self.typ),
+            class => class,
+        }
+    }
+
+    /// True if the move exists in a generation.
+    pub fn exists_in(&self, generation: Generation) -> bool {
+        self.generation <= generation
+    }
+}
+
 /// Wrapper of a `Vec` for all moves.
 ///
 /// A move's index is its Veekun ID minus 1.
 ///
 /// Use `table.0` to access `Vec` members.
-pub struct MoveTable(pub Vec<Move>);
+///
+/// Moves are also indexed by effect when the table is loaded; the index does
+/// not follow later changes to the moves themselves.
+pub struct MoveTable(
+    pub Vec<Move>, HashMap<Effect, Vec<MoveId>>, IdentifierMap<MoveId>);
 
 impl MoveTable {
     /// Create a move table from the included Veekun CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
     pub fn new() -> Self {
-        let mut table = MoveTable::from_csv_data(vdata::MOVES).unwrap();
-        table.set_meta(&meta::MetaTable::new());
-        table
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Create a move table from the CSV data in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut table = MoveTable::from_csv_source(source, vdata::files::MOVES)?;
+        table.set_meta(&meta::MetaTable::load(source)?);
+        table.index_effects();
+        table.2 = table.0.iter()
+            .map(|m| (m.identifier.as_str(), m.id))
+            .collect();
+        Ok(table)
+    }
+
+    /// Get the map between the moves' Veekun identifiers and IDs.
+    pub fn identifiers(&self) -> &IdentifierMap<MoveId> {
+        &self.2
+    }
+
+    /// Create a table with no moves, for `insert_custom`.
+    #[cfg(feature = "sandbox")]
+    pub(crate) fn empty() -> Self {
+        MoveTable(Vec::new(), HashMap::new(), Default::default())
+    }
+
+    /// True if the table has a move with an ID, stock or custom.
+    pub fn contains(&self, id: MoveId) -> bool {
+        (id.0 as usize) < self.0.len()
+    }
+
+    /// Add a move of a program's own and get its ID, which follows every
+    /// existing move, or an error if the move is invalid.
+    ///
+    /// The identifier and effect indices are updated. The move's identifier
+    /// should be unique, as the identifier map only keeps the newest move
+    /// with an identifier.
+    pub fn insert_custom(
+        &mut self, builder: MoveBuilder
+    ) -> crate::builder::Result<MoveId> {
+        let id = MoveId(self.0.len() as u16);
+        let mov = builder.build(id)?;
+        self.1.entry(mov.effect).or_default().push(id);
+        self.2.insert(&mov.identifier, id);
+        self.0.push(mov);
+        Ok(id)
+    }
+
+    /// The moves introduced in a generation, in order of ID.
+    pub fn introduced_in(
+        &self, generation: Generation
+    ) -> impl Iterator<Item = &Move> {
+        self.0.iter().filter(move |m| m.generation == generation)
+    }
+
+    /// Get the moves with an effect, in order of id.
+    pub fn with_effect(&self, effect: Effect) -> &[MoveId] {
+        self.1.get(&effect).map_or(&[], |ids| &ids[..])
     }
 
     fn set_meta(&mut self, meta_table: &meta::MetaTable) {
@@ -220,11 +353,20 @@ impl MoveTable {
             self.0[i].meta = meta_table.0[i];
         }
     }
+
+    fn index_effects(&mut self) {
+        self.1.clear();
+        for m in &self.0 {
+            self.1.entry(m.effect).or_default().push(m.id);
+        }
+    }
 }
 
 impl Default for MoveTable {
     fn default() -> Self {
-        MoveTable(repeat(Default::default()).take(MOVE_COUNT).collect::<Vec<_>>())
+        MoveTable(
+            repeat(Default::default()).take(MOVE_COUNT).collect::<Vec<_>>(),
+            HashMap::new(), Default::default())
     }
 }
 
@@ -232,7 +374,7 @@ impl vcsv::FromCsvIncremental for MoveTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let id: MoveId = vcsv::from_field(&record, 0)?;
         if id.0 >= 10000 {
@@ -240,9 +382,11 @@ impl vcsv::FromCsvIncremental for MoveTable {
         }
         let accuracy: VeekunOption<_> = vcsv::from_field(&record, 6)?;
         let effect_chance: VeekunOption<_> = vcsv::from_field(&record, 11)?;
+        let identifier = vcsv::get_field(&record, 1)?;
         self[id] = Move {
             id,
-            name: to_pascal_case(vcsv::get_field(&record, 1)?),
+            name: to_pascal_case(identifier),
+            identifier: identifier.to_string(),
             generation: vcsv::from_field(&record, 2)?,
             typ: vcsv::from_field(&record, 3)?,
             power: vcsv::from_field(&record, 4)?,
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use veekun::repr::VeekunString;
+use super::MoveId;
+
+/// Stable keys for binding a move to client-side animation and sound assets.
+///
+/// The included data uses the move's Veekun identifier for both keys.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Presentation {
+    /// Key of the move's animation.
+    pub animation: String,
+    /// Key of the move's sound effect.
+    pub sound: String,
+}
+
+/// Presentation keys for moves, from the auxiliary `move_presentation.csv`.
+///
+/// To use different keys, provide that file through an overriding data source
+/// (see `veekun::data::Overlay`).
+#[derive(Default)]
+pub struct PresentationTable(pub HashMap<MoveId, Presentation>);
+
+impl PresentationTable {
+    /// Create a presentation table from the included CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
+    pub fn new() -> Self {
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Create a presentation table from the CSV data in a source. The table is
+    /// empty if the source does not have the file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        match source.csv(vdata::files::MOVE_PRESENTATION) {
+            Some(data) => Self::from_csv_data(data),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Get the presentation keys of a move, if any.
+    pub fn get(&self, id: MoveId) -> Option<&Presentation> {
+        self.0.get(&id)
+    }
+}
+
+impl vcsv::FromCsvIncremental for PresentationTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: MoveId = vcsv::from_field(&record, 0)?;
+        let animation: VeekunString = vcsv::from_field(&record, 1)?;
+        let sound: VeekunString = vcsv::from_field(&record, 2)?;
+        self.0.insert(id, Presentation {
+            animation: animation.into(),
+            sound: sound.into(),
+        });
+        Ok(())
+    }
+}
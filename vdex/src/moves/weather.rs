@@ -0,0 +1,87 @@
+use crate::enums::*;
+use crate::Type;
+use super::Effect;
+use self::Weather::*;
+use self::WeatherModifier as Modifier;
+
+/// A weather condition, as set by Sunny Day, Rain Dance, Sandstorm, or Hail.
+#[EnumRepr(type = "u8", serde = true)]
+pub enum Weather {
+    Sun = 0,
+    Rain,
+    Sandstorm,
+    Hail,
+}
+
+/// A change to a move's behavior in some weather.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WeatherModifier {
+    /// The move skips its charging turn, like SolarBeam in sun.
+    SkipCharge,
+    /// The move's power is multiplied by a fraction (numerator, denominator).
+    Power(u8, u8),
+    /// The move's accuracy in percent, or `None` if it never misses.
+    Accuracy(Option<u8>),
+    /// The move heals a fraction (numerator, denominator) of the user's max HP
+    /// instead of half.
+    Healing(u8, u8),
+    /// The move's type changes.
+    Type(Type),
+}
+
+const SOLARBEAM: &[(Weather, Modifier)] = &[
+    (Sun, Modifier::SkipCharge),
+    (Rain, Modifier::Power(1, 2)),
+    (Sandstorm, Modifier::Power(1, 2)),
+    (Hail, Modifier::Power(1, 2)),
+];
+
+const THUNDER: &[(Weather, Modifier)] = &[
+    (Rain, Modifier::Accuracy(None)),
+    (Sun, Modifier::Accuracy(Some(50))),
+];
+
+const HEAL_BY_WEATHER: &[(Weather, Modifier)] = &[
+    (Sun, Modifier::Healing(2, 3)),
+    (Rain, Modifier::Healing(1, 4)),
+    (Sandstorm, Modifier::Healing(1, 4)),
+    (Hail, Modifier::Healing(1, 4)),
+];
+
+const WEATHER_BALL: &[(Weather, Modifier)] = &[
+    (Sun, Modifier::Type(Type::Fire)),
+    (Sun, Modifier::Power(2, 1)),
+    (Rain, Modifier::Type(Type::Water)),
+    (Rain, Modifier::Power(2, 1)),
+    (Sandstorm, Modifier::Type(Type::Rock)),
+    (Sandstorm, Modifier::Power(2, 1)),
+    (Hail, Modifier::Type(Type::Ice)),
+    (Hail, Modifier::Power(2, 1)),
+];
+
+impl Effect {
+    /// How weather changes the behavior of moves with this effect, as of
+    /// Generation V, as pairs of a weather and a modifier that applies in it.
+    ///
+    /// This covers SolarBeam, Thunder and Hurricane, Moonlight, Synthesis, and
+    /// Morning Sun, and Weather Ball. Other effects are not affected by
+    /// weather, and return an empty slice.
+    pub fn weather_modifiers(self) -> &'static [(Weather, WeatherModifier)] {
+        match self {
+            Effect::Solarbeam => SOLARBEAM,
+            Effect::Thunder | Effect::Hurricane => THUNDER,
+            Effect::HealUserByHalfMaxHPWeather => HEAL_BY_WEATHER,
+            Effect::WeatherBall => WEATHER_BALL,
+            _ => &[],
+        }
+    }
+
+    /// The modifiers of this effect that apply in a weather.
+    pub fn modifiers_in(
+        self, weather: Weather
+    ) -> impl Iterator<Item = WeatherModifier> {
+        self.weather_modifiers().iter()
+            .filter(move |&&(w, _)| w == weather)
+            .map(|&(_, modifier)| modifier)
+    }
+}
@@ -1,3 +1,5 @@
+//! Natures, flavors, and the stats they affect.
+
 use crate::enums::*;
 use crate::moves::BattleStyle;
 use crate::items::Flavor;
@@ -32,7 +34,7 @@ use crate::FromVeekun;
 /// > increase and decrease; thus, there are five Natures that have no effect on
 /// > the Pokémon's stat growth as they technically increase and decrease the
 /// > same stat (Bashful, Docile, Hardy, Quirky, and Serious).
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", bitset = true, serde = true)]
 pub enum Nature {
     Hardy = 0,
     Lonely,
@@ -62,18 +64,29 @@ pub enum Nature {
 }
 
 impl Nature {
+    /// Get which flavor is liked, if any.
+    pub fn liked(self) -> Option<Flavor> {
+        Flavor::VALUES.iter().cloned()
+            .find(|&flavor| self.preference(flavor) == FlavorPreference::Liked)
+    }
+
     /// Get which flavor is disliked, if any.
     pub fn disliked(self) -> Option<Flavor> {
         let x = self.repr();
         if x % 6 == 0 {
             return None;
         }
-        Flavor::from_repr(x / 5).or_else(|| unreachable!())
+        Flavor::from_repr(x % 5).or_else(|| unreachable!())
+    }
+
+    /// Get how much a Pokémon with this nature likes a flavor.
+    pub fn preference(self, flavor: Flavor) -> FlavorPreference {
+        FLAVOR_MATRIX[self.repr() as usize][flavor.repr() as usize]
     }
 
     /// Get which stat is increased, if any.
     pub fn increased(self) -> Option<Stat> {
-        self.disliked().and_then(|x|
+        self.liked().and_then(|x|
             Stat::from_repr(x.repr() as i8).or_else(|| unreachable!()))
     }
 
@@ -126,6 +139,43 @@ impl FromVeekun for Nature {
     }
 }
 
+/// How much a Pokémon likes a flavor, depending on its nature.
+#[EnumRepr(type = "u8", serde = true)]
+pub enum FlavorPreference {
+    Liked = 0,
+    Neutral,
+    Disliked,
+}
+
+static FLAVOR_MATRIX: [[FlavorPreference; Flavor::COUNT]; Nature::COUNT]
+    = build_flavor_matrix();
+
+const fn build_flavor_matrix()
+    -> [[FlavorPreference; Flavor::COUNT]; Nature::COUNT] {
+    let mut matrix
+        = [[FlavorPreference::Neutral; Flavor::COUNT]; Nature::COUNT];
+    let mut nature = 0;
+    while nature < Nature::COUNT {
+        // Natures are ordered by increased stat, then by decreased stat, and
+        // flavors are ordered by their stats.
+        let liked = nature / Flavor::COUNT;
+        let disliked = nature % Flavor::COUNT;
+        if liked != disliked {
+            matrix[nature][liked] = FlavorPreference::Liked;
+            matrix[nature][disliked] = FlavorPreference::Disliked;
+        }
+        nature += 1;
+    }
+    matrix
+}
+
+/// Get the flavor preferences of every nature, indexed by nature and then
+/// flavor representation.
+pub fn flavor_matrix()
+    -> &'static [[FlavorPreference; Flavor::COUNT]; Nature::COUNT] {
+    &FLAVOR_MATRIX
+}
+
 /// Stats are numbers associated with Pokémon affecting battle mechanics.
 ///
 /// Some stats are only available in battle, whereas others are properties of
@@ -136,7 +186,7 @@ impl FromVeekun for Nature {
 /// > which determines certain aspects of battles in the games. Stats may also
 /// > refer to the numerical values of each field in regards to individual
 /// > Pokémon.
-#[EnumRepr(type = "i8")]
+#[EnumRepr(type = "i8", serde = true)]
 pub enum Stat {
     HP = -1,
     Attack,
@@ -199,8 +249,14 @@ pub struct PalaceTable {
 
 impl PalaceTable {
     /// Create a palace table from the included Veekun CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
     pub fn new() -> Self {
-        Self::from_csv_data(vdata::PALACE).unwrap()
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Create a palace table from the CSV data in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        Self::from_csv_source(source, vdata::files::PALACE)
     }
 }
 
@@ -208,7 +264,7 @@ impl vcsv::FromCsvIncremental for PalaceTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let nature: Nature = vcsv::from_field(&record, 0)?;
         let nature_id = nature.repr() as usize;
@@ -229,22 +285,23 @@ impl vcsv::FromCsvIncremental for PalaceTable {
                 let high_attack = self.high.attack[nature_id];
                 let low_defense = self.low.defense[nature_id];
                 let high_defense = self.high.defense[nature_id];
-                let line = match record.position() {
-                    Some(p) => p.line(),
-                    None => 0,
-                };
+                let line = record.line().unwrap_or(0);
                 let error = vcsv::MiscError::from("Preferences must sum to 100.");
                 if low_attack + low_defense + low != 100 {
                     return Err(vcsv::Error::Veekun {
+                        file: None,
                         line: Some(line),
                         field: 2,
+                        record: Some(record.to_string()),
                         error: Box::new(error),
                     });
                 }
                 if high_attack + high_defense + high != 100 {
                     return Err(vcsv::Error::Veekun {
+                        file: None,
                         line: Some(line),
                         field: 3,
+                        record: Some(record.to_string()),
                         error: Box::new(error),
                     });
                 }
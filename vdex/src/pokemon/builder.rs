@@ -0,0 +1,72 @@
+use crate::Ability;
+use crate::builder::{check, Result};
+use crate::Type;
+use crate::versions::VersionGroup;
+use super::{BaseStats, OneOrTwo, Pokemon, PokemonId, PokemonMove};
+
+/// A Pokémon of a program's own, to add with `SpeciesTable::insert_custom` or
+/// to use as a fixture.
+///
+/// Fields not set keep the defaults of `Pokemon`. A builder can also start
+/// from an existing Pokémon, to tweak it.
+#[derive(Clone, Debug)]
+pub struct PokemonBuilder(Pokemon);
+
+impl PokemonBuilder {
+    /// Start a Pokémon with its types and base stats.
+    pub fn new(types: OneOrTwo<Type>, stats: BaseStats) -> Self {
+        PokemonBuilder(Pokemon { types, stats, .. Default::default() })
+    }
+
+    pub fn types(mut self, types: OneOrTwo<Type>) -> Self {
+        self.0.types = types;
+        self
+    }
+
+    pub fn stats(mut self, stats: BaseStats) -> Self {
+        self.0.stats = stats;
+        self
+    }
+
+    pub fn abilities(mut self, abilities: OneOrTwo<Ability>) -> Self {
+        self.0.abilities = abilities;
+        self
+    }
+
+    pub fn hidden_ability(mut self, ability: Option<Ability>) -> Self {
+        self.0.hidden_ability = ability;
+        self
+    }
+
+    pub fn base_exp(mut self, base_exp: u16) -> Self {
+        self.0.base_exp = base_exp;
+        self
+    }
+
+    /// The moves the Pokémon learns in a version group.
+    pub fn learnset(
+        mut self, version_group: VersionGroup, moves: Vec<PokemonMove>
+    ) -> Self {
+        self.0.moves.insert(version_group, moves.into());
+        self
+    }
+
+    /// Check the Pokémon and finish it with an ID.
+    ///
+    /// Two types must differ, and every base stat must be positive.
+    pub fn build(self, id: PokemonId) -> Result<Pokemon> {
+        let pokemon = &self.0;
+        let types = pokemon.types;
+        check(types.second().is_none_or(|second| second != types.first()),
+              "types", "the same type twice")?;
+        check(pokemon.stats.iter().all(|(_, base)| base > 0), "stats",
+              "zero base stat")?;
+        Ok(Pokemon { id, .. self.0 })
+    }
+}
+
+impl From<Pokemon> for PokemonBuilder {
+    fn from(pokemon: Pokemon) -> Self {
+        PokemonBuilder(pokemon)
+    }
+}
@@ -0,0 +1,143 @@
+//! What triggers the form changes of Pokémon, in battle or with items.
+//!
+//! Only changes with a trigger the rest of vdex can act on are listed: a
+//! Pokémon whose form is chosen once, like Unown or Burmy, has no rules.
+
+use bitflags::bitflags;
+use crate::Ability;
+use crate::items::{plate_type, Item, ItemId};
+use crate::moves::{MoveId, Weather};
+use crate::Type;
+use super::{Form, FormId};
+
+/// What changes a Pokémon into a form.
+///
+/// A Pokémon in a form with a battle-only trigger returns to its default form
+/// when the trigger no longer applies, and at the end of the battle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ChangeTrigger {
+    /// Holding an item.
+    HeldItem(ItemId),
+    /// Having an item used on the Pokémon outside of battle.
+    UsedItem(ItemId),
+    /// Having an ability during a weather, in battle.
+    Weather(Ability, Weather),
+    /// Having an ability with at most a fraction (numerator, denominator) of
+    /// the maximum HP, in battle.
+    LowHp(Ability, (u8, u8)),
+    /// Using a move, in battle.
+    UsedMove(MoveId),
+    /// Knowing a move.
+    KnowsMove(MoveId),
+    /// Entering an appliance outside of battle, which replaces the move of
+    /// the previous form with this one.
+    Appliance(MoveId),
+}
+
+impl ChangeTrigger {
+    /// True if the change only happens in battle.
+    pub fn battle_only(self) -> bool {
+        matches!(
+            self,
+            ChangeTrigger::Weather(..) | ChangeTrigger::LowHp(..)
+                | ChangeTrigger::UsedMove(_))
+    }
+}
+
+bitflags! {
+    /// How a form may be taken, for formats to exclude forms by.
+    pub struct FormFlags: u8 {
+        /// The Pokémon only takes the form in battle, like Meloetta's
+        /// pirouette form.
+        const BATTLE_ONLY = 0x01;
+        /// The Pokémon only changes into the form outside of battle, like
+        /// Shaymin's sky form or Rotom's appliance forms.
+        const OUT_OF_BATTLE_ONLY = 0x02;
+        /// The Pokémon only keeps the form while holding an item, like
+        /// Giratina's origin form or the Arceus forms.
+        const REQUIRES_ITEM = 0x04;
+    }
+}
+
+/// A trigger that changes a Pokémon into a form.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ChangeRule {
+    /// The ID of the form, as in `Form::id`.
+    pub form: FormId,
+    pub trigger: ChangeTrigger,
+}
+
+const fn rule(form: FormId, trigger: ChangeTrigger) -> ChangeRule {
+    ChangeRule { form, trigger }
+}
+
+/// The form change rules, in order of form.
+pub static CHANGE_RULES: [ChangeRule; 35] = [
+    // Meloetta: aria, changing back from pirouette with Relic Song.
+    rule(648, ChangeTrigger::UsedMove(MoveId(546))),
+    // Castform: sunny, rainy, and snowy.
+    rule(677, ChangeTrigger::Weather(Ability::Forecast, Weather::Sun)),
+    rule(678, ChangeTrigger::Weather(Ability::Forecast, Weather::Rain)),
+    rule(679, ChangeTrigger::Weather(Ability::Forecast, Weather::Hail)),
+    // Cherrim: sunshine.
+    rule(687, ChangeTrigger::Weather(Ability::FlowerGift, Weather::Sun)),
+    // Arceus: bug, dark, dragon, electric, fighting, fire, flying, ghost,
+    // grass, ground, ice, poison, psychic, rock, steel, and water (the
+    // plates).
+    rule(690, ChangeTrigger::HeldItem(ItemId(285))),
+    rule(691, ChangeTrigger::HeldItem(ItemId(289))),
+    rule(692, ChangeTrigger::HeldItem(ItemId(288))),
+    rule(693, ChangeTrigger::HeldItem(ItemId(277))),
+    rule(694, ChangeTrigger::HeldItem(ItemId(280))),
+    rule(695, ChangeTrigger::HeldItem(ItemId(275))),
+    rule(696, ChangeTrigger::HeldItem(ItemId(283))),
+    rule(697, ChangeTrigger::HeldItem(ItemId(287))),
+    rule(698, ChangeTrigger::HeldItem(ItemId(278))),
+    rule(699, ChangeTrigger::HeldItem(ItemId(282))),
+    rule(700, ChangeTrigger::HeldItem(ItemId(279))),
+    rule(701, ChangeTrigger::HeldItem(ItemId(281))),
+    rule(702, ChangeTrigger::HeldItem(ItemId(284))),
+    rule(703, ChangeTrigger::HeldItem(ItemId(286))),
+    rule(704, ChangeTrigger::HeldItem(ItemId(290))),
+    rule(705, ChangeTrigger::HeldItem(ItemId(276))),
+    // Rotom: heat (Overheat), wash (Hydro Pump), frost (Blizzard), fan (Air
+    // Slash), and mow (Leaf Storm).
+    rule(707, ChangeTrigger::Appliance(MoveId(314))),
+    rule(708, ChangeTrigger::Appliance(MoveId(55))),
+    rule(709, ChangeTrigger::Appliance(MoveId(58))),
+    rule(710, ChangeTrigger::Appliance(MoveId(402))),
+    rule(711, ChangeTrigger::Appliance(MoveId(436))),
+    // Giratina: origin (Griseous Orb).
+    rule(712, ChangeTrigger::HeldItem(ItemId(442))),
+    // Shaymin: sky (Gracidea).
+    rule(713, ChangeTrigger::UsedItem(ItemId(444))),
+    // Darmanitan: zen.
+    rule(716, ChangeTrigger::LowHp(Ability::ZenMode, (1, 2))),
+    // Meloetta: pirouette (Relic Song).
+    rule(723, ChangeTrigger::UsedMove(MoveId(546))),
+    // Genesect: douse, shock, burn, and chill (the drives).
+    rule(724, ChangeTrigger::HeldItem(ItemId(563))),
+    rule(725, ChangeTrigger::HeldItem(ItemId(564))),
+    rule(726, ChangeTrigger::HeldItem(ItemId(565))),
+    rule(727, ChangeTrigger::HeldItem(ItemId(566))),
+    // Keldeo: resolute (Secret Sword).
+    rule(733, ChangeTrigger::KnowsMove(MoveId(547))),
+];
+
+/// Get the rules that change a Pokémon into a form, by Veekun form ID.
+pub fn change_rules(form: FormId) -> impl Iterator<Item = &'static ChangeRule> {
+    CHANGE_RULES.iter().filter(move |rule| rule.form == form)
+}
+
+/// Get the form a held item changes a Pokémon into, among its forms, such as
+/// Giratina's origin form for the Griseous Orb or an Arceus form for a plate.
+pub fn held_item_form(forms: &[Form], item: ItemId) -> Option<&Form> {
+    forms.iter().find(|form| form.change_rules()
+        .any(|rule| rule.trigger == ChangeTrigger::HeldItem(item)))
+}
+
+/// Get the type of Arceus with Multitype, and of Judgment, from the held
+/// item: the type of a plate, or Normal otherwise.
+pub fn multitype_type(item: Option<&Item>) -> Type {
+    item.and_then(plate_type).unwrap_or(Type::Normal)
+}
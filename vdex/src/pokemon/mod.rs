@@ -0,0 +1,966 @@
+//! Pokemon and related data.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use crate::Ability;
+use crate::builder::check;
+use crate::enums::*;
+use crate::FromVeekun;
+use crate::moves::{LearnMethod, MoveId};
+use crate::IdentifierMap;
+use crate::items::ItemId;
+use crate::Stat;
+use crate::to_pascal_case;
+use crate::Type;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::VeekunOption;
+use veekun::repr::VeekunString;
+use crate::versions::{Generation, VersionGroup};
+use crate::world::TimeOfDay;
+
+pub(self) mod builder;
+pub mod forms;
+#[cfg(feature = "raw-tables")]
+pub mod raw;
+pub(self) mod stat_query;
+
+pub use self::builder::PokemonBuilder;
+pub use self::stat_query::StatQuery;
+#[cfg(not(feature = "raw-tables"))]
+mod raw;
+
+/// The groups of Pokémon which can interbreed.
+///
+/// > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Egg_Group)
+/// > Egg Groups (Japanese: タマゴグループ Egg Group) are categories which
+/// > determine which Pokémon are able to interbreed. The concept was introduced
+/// > in Generation II, along with breeding. Similar to types, a Pokémon may
+/// > belong to either one or two Egg Groups.
+#[EnumRepr(type = "u8", bitset = true, serde = true)]
+pub enum EggGroup {
+    Monster = 1,
+    Water1,
+    Bug,
+    Flying,
+    Ground,
+    Fairy,
+    Plant,
+    Humanshape,
+    Water3,
+    Mineral,
+    Indeterminate,
+    Water2,
+    Ditto,
+    Dragon,
+    NoEggs,
+}
+
+impl Default for EggGroup {
+    fn default() -> Self { EggGroup::NoEggs }
+}
+
+impl FromVeekun for EggGroup {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The color of a species in the Pokédex.
+#[EnumRepr(type = "u8")]
+pub enum Color {
+    Black = 1,
+    Blue,
+    Brown,
+    Gray,
+    Green,
+    Pink,
+    Purple,
+    Red,
+    White,
+    Yellow,
+}
+
+impl Default for Color {
+    fn default() -> Self { Color::Black }
+}
+
+impl FromVeekun for Color {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The body shape of a species in the Pokédex.
+#[EnumRepr(type = "u8")]
+pub enum Shape {
+    /// A head only.
+    Ball = 1,
+    /// A serpentine body.
+    Squiggle,
+    /// Fins.
+    Fish,
+    /// A head and arms.
+    Arms,
+    /// A head and a base.
+    Blob,
+    /// A bipedal body with a tail.
+    Upright,
+    /// A head and legs.
+    Legs,
+    /// A quadruped body.
+    Quadruped,
+    /// A single pair of wings.
+    Wings,
+    /// Tentacles or a multiped body.
+    Tentacles,
+    /// Multiple bodies.
+    Heads,
+    /// A bipedal body without a tail.
+    Humanoid,
+    /// Two or more pairs of wings.
+    BugWings,
+    /// An insectoid body.
+    Armor,
+}
+
+impl Default for Shape {
+    fn default() -> Self { Shape::Ball }
+}
+
+impl FromVeekun for Shape {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// Where a species lives, in the Pokédex of FireRed and LeafGreen; species
+/// introduced after Generation III have no habitat.
+#[EnumRepr(type = "u8")]
+pub enum Habitat {
+    Cave = 1,
+    Forest,
+    Grassland,
+    Mountain,
+    Rare,
+    RoughTerrain,
+    Sea,
+    Urban,
+    WatersEdge,
+}
+
+impl FromVeekun for Habitat {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// How much experience a species needs to reach each level.
+#[EnumRepr(type = "u8")]
+pub enum GrowthRate {
+    Slow = 1,
+    /// Also known as Medium Fast.
+    Medium,
+    Fast,
+    MediumSlow,
+    /// Slow to begin with, then very fast; also known as Erratic.
+    SlowThenVeryFast,
+    /// Fast to begin with, then very slow; also known as Fluctuating.
+    FastThenVerySlow,
+}
+
+impl Default for GrowthRate {
+    fn default() -> Self { GrowthRate::Medium }
+}
+
+impl FromVeekun for GrowthRate {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The method by which a Pokémon evolves.
+#[EnumRepr(type = "u8", serde = true)]
+pub enum EvolutionTrigger {
+    LevelUp = 1,
+    Trade,
+    UseItem,
+    Shed,
+}
+
+impl Default for EvolutionTrigger {
+    fn default() -> Self { EvolutionTrigger::Trade }
+}
+
+impl FromVeekun for EvolutionTrigger {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// Gender of a Pokémon.
+///
+/// > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Gender) The
+/// > gender (Japanese: 性別 sex) of a Pokémon is a concept introduced in
+/// > Generation II, though touched upon in Generation I. In Gold and Silver
+/// > Versions, most species of Pokémon were assigned a gender, male (Japanese:
+/// > オス male) or female (Japanese: メス female); however, the genders of some
+/// > species of Pokémon were left unknown (Japanese: 不明 unknown). This
+/// > feature allowed for Pokémon breeding, as well as introducing the concept
+/// > of a Pokémon Egg to the series. Gender makes no difference in the stats of
+/// > a Pokémon after Generation II, unless the two Pokémon are a different
+/// > species entirely, such as Nidoran.
+#[EnumRepr(type = "u8", serde = true)]
+pub enum Gender {
+    Female = 1,
+    Male,
+    Genderless,
+}
+
+impl Default for Gender {
+    fn default() -> Self { Gender::Genderless }
+}
+
+impl FromVeekun for Gender {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The ratio of genders among wild Pokémon of a species.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum GenderRatio {
+    Genderless,
+    /// The number of eighths that are female (0–8).
+    FemaleEighths(u8),
+}
+
+impl GenderRatio {
+    /// Convert Veekun's gender rate: eighths female, or -1 for genderless.
+    pub fn from_rate(rate: i8) -> Option<Self> {
+        match rate {
+            -1 => Some(GenderRatio::Genderless),
+            0..=8 => Some(GenderRatio::FemaleEighths(rate as u8)),
+            _ => None,
+        }
+    }
+
+    /// The percentage that are female, or `None` if genderless.
+    pub fn female_percent(self) -> Option<f32> {
+        match self {
+            GenderRatio::Genderless => None,
+            GenderRatio::FemaleEighths(eighths) => Some(eighths as f32 * 12.5),
+        }
+    }
+
+    /// The percentage that are male, or `None` if genderless.
+    pub fn male_percent(self) -> Option<f32> {
+        self.female_percent().map(|female| 100.0 - female)
+    }
+}
+
+/// Formats as, e.g., "87.5% male / 12.5% female", "100% male", or
+/// "Genderless".
+impl std::fmt::Display for GenderRatio {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            GenderRatio::Genderless => write!(f, "Genderless"),
+            GenderRatio::FemaleEighths(0) => write!(f, "100% male"),
+            GenderRatio::FemaleEighths(8) => write!(f, "100% female"),
+            ratio => write!(
+                f, "{}% male / {}% female", ratio.male_percent().unwrap(),
+                ratio.female_percent().unwrap()),
+        }
+    }
+}
+
+/// Either one or two elements.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum OneOrTwo<T: Copy> {
+    One(T),
+    Two(T, T),
+}
+
+impl<T: Copy> OneOrTwo<T> {
+    pub fn from_options(array: [Option<T>; 2]) -> Option<Self> {
+        array[0].and_then(|first| Some(match array[1] {
+            Some(second) => OneOrTwo::Two(first, second),
+            None => OneOrTwo::One(first),
+        })).or_else(|| array[1].and_then(|second| Some(OneOrTwo::One(second))))
+    }
+
+    pub fn first(self) -> T {
+        match self {
+            OneOrTwo::One(t) => t,
+            OneOrTwo::Two(t, _) => t,
+        }
+    }
+
+    pub fn second(self) -> Option<T> {
+        match self {
+            OneOrTwo::One(_) => None,
+            OneOrTwo::Two(_, t) => Some(t),
+        }
+    }
+
+    pub fn contains(self, x: T) -> bool where T: PartialEq<T> {
+        self.first() == x || self.second().map_or(false, |y| y == x)
+    }
+}
+
+impl<T: Copy + Default> Default for OneOrTwo<T> {
+    fn default() -> Self { OneOrTwo::One(Default::default()) }
+}
+
+include!(concat!(env!("OUT_DIR"), "/pokemon_count.rs"));
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PokemonId(pub u16);
+
+impl Default for PokemonId {
+    fn default() -> Self { PokemonId(u16::MAX) }
+}
+
+/// The Veekun ID after which upstream Veekun data numbers the alternate
+/// Pokémon, such as 10001 for Deoxys' attack form. pbirch numbers them after
+/// the last species instead, so its 650 is upstream's 10001.
+pub const UPSTREAM_ALTERNATE_OFFSET: u16 = 10000;
+
+impl PokemonId {
+    /// True if the ID is that of a Pokémon, rather than a placeholder like the
+    /// default.
+    pub fn is_valid(self) -> bool {
+        (self.0 as usize) < POKEMON_COUNT
+    }
+
+    /// True if the Pokémon is an alternate Pokémon of its species, like
+    /// Deoxys' attack form, rather than the default one.
+    pub fn is_alternate(self) -> bool {
+        self.is_valid() && (self.0 as usize) >= SPECIES_COUNT
+    }
+
+    /// Get the ID of a Pokémon from its Veekun ID, numbered either like
+    /// pbirch, from 1 to `POKEMON_COUNT`, or like upstream Veekun, with the
+    /// alternate Pokémon from `UPSTREAM_ALTERNATE_OFFSET` + 1 on.
+    pub fn from_veekun_id(id: u16) -> Option<Self> {
+        let alternates = (POKEMON_COUNT - SPECIES_COUNT) as u16;
+        let id = match id.checked_sub(UPSTREAM_ALTERNATE_OFFSET + 1) {
+            Some(alternate) if alternate < alternates
+                => SPECIES_COUNT as u16 + alternate,
+            Some(_) => return None,
+            None => id.checked_sub(1)?,
+        };
+        Some(PokemonId(id)).filter(|id| id.is_valid())
+    }
+
+    /// The pbirch Veekun ID of the Pokémon, or `None` if it is not a stock
+    /// Pokémon.
+    pub fn veekun_id(self) -> Option<u16> {
+        Some(self).filter(|id| id.is_valid()).map(|id| id.0 + 1)
+    }
+
+    /// The upstream Veekun ID of the Pokémon, which differs from the pbirch
+    /// ID for alternate Pokémon, or `None` if it is not a stock Pokémon.
+    pub fn upstream_veekun_id(self) -> Option<u16> {
+        if self.is_alternate() {
+            Some(UPSTREAM_ALTERNATE_OFFSET + 1 + self.0 - SPECIES_COUNT as u16)
+        } else {
+            self.veekun_id()
+        }
+    }
+}
+
+impl FromVeekun for PokemonId {
+    type Intermediate = u16;
+
+    fn from_veekun(value: u16) -> Option<Self> {
+        PokemonId::from_veekun_id(value)
+    }
+}
+
+/// The Veekun ID of a form, from `pokemon_forms.csv`.
+///
+/// Like `PokemonId`, the default forms of the species come first, numbered
+/// like the species, followed by the other forms in order of species. Unlike
+/// `PokemonId`, it is the Veekun ID itself, so forms from upstream Veekun
+/// data keep their IDs from `UPSTREAM_ALTERNATE_OFFSET` + 1 on.
+pub type FormId = u16;
+
+#[derive(Clone, Debug, Default)]
+pub struct Form {
+    pub id: FormId,
+    pub name: Option<String>,
+    /// Whether the Pokémon only takes the form in battle. See `change_rules`
+    /// for what triggers the change.
+    pub battle_only: bool,
+}
+
+impl Form {
+    /// Get the rules that change a Pokémon into this form.
+    pub fn change_rules(&self) -> impl Iterator<Item = &'static forms::ChangeRule> {
+        forms::change_rules(self.id)
+    }
+
+    /// Get the flags for how the form may be taken, from whether it is
+    /// battle-only and its change rules.
+    pub fn flags(&self) -> forms::FormFlags {
+        use self::forms::{ChangeTrigger, FormFlags};
+        let mut flags = FormFlags::empty();
+        flags.set(FormFlags::BATTLE_ONLY, self.battle_only);
+        for rule in self.change_rules() {
+            match rule.trigger {
+                ChangeTrigger::UsedItem(_) | ChangeTrigger::Appliance(_)
+                    => flags.insert(FormFlags::OUT_OF_BATTLE_ONLY),
+                ChangeTrigger::HeldItem(_)
+                    => flags.insert(FormFlags::REQUIRES_ITEM),
+                _ => (),
+            }
+        }
+        flags
+    }
+
+    /// Get the item the Pokémon must hold to keep the form, if any.
+    pub fn required_item(&self) -> Option<ItemId> {
+        self.change_rules().find_map(|rule| match rule.trigger {
+            forms::ChangeTrigger::HeldItem(item) => Some(item),
+            _ => None,
+        })
+    }
+}
+
+/// The forms of a Pokémon. With the `smallvec` feature, the first form is
+/// stored inline, which saves an allocation for most Pokémon.
+#[cfg(feature = "smallvec")]
+pub type Forms = smallvec::SmallVec<[Form; 1]>;
+/// The forms of a Pokémon. With the `smallvec` feature, the first form is
+/// stored inline, which saves an allocation for most Pokémon.
+#[cfg(not(feature = "smallvec"))]
+pub type Forms = Vec<Form>;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct PokemonMove {
+    pub move_id: MoveId,
+    pub learn_method: LearnMethod,
+    pub level: u8,
+}
+
+/// The moves a Pokémon learns in a version group.
+///
+/// Learnsets are hash-consed as the Pokedex is loaded: identical learnsets,
+/// such as a Pokémon's in two version groups of a generation, share storage.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Learnset(Arc<[PokemonMove]>);
+
+impl Learnset {
+    /// True if two learnsets share storage.
+    pub fn shares_storage(&self, other: &Learnset) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Default for Learnset {
+    fn default() -> Self {
+        Learnset(Arc::from(Vec::new()))
+    }
+}
+
+impl From<Vec<PokemonMove>> for Learnset {
+    fn from(moves: Vec<PokemonMove>) -> Self {
+        Learnset(Arc::from(moves))
+    }
+}
+
+impl std::ops::Deref for Learnset {
+    type Target = [PokemonMove];
+
+    fn deref(&self) -> &[PokemonMove] {
+        &self.0
+    }
+}
+
+impl Borrow<[PokemonMove]> for Learnset {
+    fn borrow(&self) -> &[PokemonMove] {
+        &self.0
+    }
+}
+
+/// The number of stats that exist out of battle (all but accuracy and evasion).
+pub const PERMANENT_STATS: usize = 6;
+
+/// A Pokémon's base permanent stats.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BaseStats(pub [u8; PERMANENT_STATS]);
+
+impl BaseStats {
+    /// Create base stats in the conventional display order.
+    ///
+    /// Note that this is not the order of the wrapped array, which follows
+    /// `Stat` and so has Speed before the special stats.
+    pub fn new(
+        hp: u8, attack: u8, defense: u8, special_attack: u8,
+        special_defense: u8, speed: u8
+    ) -> Self {
+        let mut stats = BaseStats::default();
+        stats[Stat::HP] = hp;
+        stats[Stat::Attack] = attack;
+        stats[Stat::Defense] = defense;
+        stats[Stat::SpecialAttack] = special_attack;
+        stats[Stat::SpecialDefense] = special_defense;
+        stats[Stat::Speed] = speed;
+        stats
+    }
+
+    pub fn hp(&self) -> u8 { self[Stat::HP] }
+    pub fn attack(&self) -> u8 { self[Stat::Attack] }
+    pub fn defense(&self) -> u8 { self[Stat::Defense] }
+    pub fn special_attack(&self) -> u8 { self[Stat::SpecialAttack] }
+    pub fn special_defense(&self) -> u8 { self[Stat::SpecialDefense] }
+    pub fn speed(&self) -> u8 { self[Stat::Speed] }
+
+    /// Iterate over the permanent stats and their base values, in the order
+    /// of `Stat`.
+    pub fn iter(&self) -> impl Iterator<Item = (Stat, u8)> + '_ {
+        Stat::VALUES[..PERMANENT_STATS].iter().map(move |&s| (s, self[s]))
+    }
+
+    /// The base stat total.
+    pub fn total(&self) -> u16 {
+        self.0.iter().map(|&b| b as u16).sum()
+    }
+
+    /// The stat with the highest base value; ties go to the earlier stat in
+    /// the order of `Stat`.
+    pub fn highest(&self) -> Stat {
+        self.ranked()[0]
+    }
+
+    /// The stat with the lowest base value; ties go to the earlier stat in
+    /// the order of `Stat`.
+    pub fn lowest(&self) -> Stat {
+        let mut lowest = Stat::HP;
+        for (stat, base) in self.iter() {
+            if base < self[lowest] {
+                lowest = stat;
+            }
+        }
+        lowest
+    }
+
+    /// The permanent stats ordered from highest to lowest base value, with
+    /// ties kept in the order of `Stat`.
+    pub fn ranked(&self) -> [Stat; PERMANENT_STATS] {
+        let mut stats = [Stat::HP; PERMANENT_STATS];
+        stats.copy_from_slice(&Stat::VALUES[..PERMANENT_STATS]);
+        stats.sort_by(|&a, &b| self[b].cmp(&self[a]));
+        stats
+    }
+}
+
+impl std::ops::Index<Stat> for BaseStats {
+    type Output = u8;
+
+    fn index<'a>(&'a self, index: Stat) -> &'a u8 {
+        &self.0[(index.repr() + 1) as usize]
+    }
+}
+
+impl std::ops::IndexMut<Stat> for BaseStats {
+    fn index_mut<'a>(&'a mut self, index: Stat) -> &'a mut u8 {
+        &mut self.0[(index.repr() + 1) as usize]
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/species_count.rs"));
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SpeciesId(pub u16);
+
+impl Default for SpeciesId {
+    fn default() -> Self { SpeciesId(u16::MAX) }
+}
+
+impl SpeciesId {
+    /// True if the ID is that of a stock species, rather than a placeholder
+    /// like the default. Custom species (see `SpeciesTable::insert_custom`)
+    /// have IDs beyond the stock species; check those with
+    /// `SpeciesTable::contains`.
+    pub fn is_valid(self) -> bool {
+        (self.0 as usize) < SPECIES_COUNT
+    }
+}
+
+impl FromVeekun for SpeciesId {
+    type Intermediate = u16;
+
+    fn from_veekun(value: u16) -> Option<Self> {
+        let id = value.checked_sub(1).unwrap_or_default();
+        if id < (SPECIES_COUNT as u16) {
+            Some(SpeciesId(id))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Pokemon {
+    pub id: PokemonId,
+    pub abilities: OneOrTwo<Ability>,
+    pub hidden_ability: Option<Ability>,
+    pub forms: Forms,
+    pub moves: HashMap<VersionGroup, Learnset>,
+    pub stats: BaseStats,
+    pub types: OneOrTwo<Type>,
+    /// The base experience yielded when the Pokémon is defeated.
+    pub base_exp: u16,
+}
+
+impl Pokemon {
+    /// Get the moves the Pokémon learns in a version group, which are none if
+    /// it is not in the version group.
+    pub fn learnset(&self, version_group: VersionGroup) -> &[PokemonMove] {
+        self.moves.get(&version_group).map_or(&[], |learnset| learnset)
+    }
+
+    /// The pbirch Veekun ID of the Pokémon, as in `PokemonId::veekun_id`.
+    pub fn veekun_id(&self) -> Option<u16> {
+        self.id.veekun_id()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EvolvesFrom {
+    pub from_id: SpeciesId,
+    pub trigger: EvolutionTrigger,
+    /// The item used on the Pokémon, like a Fire Stone, if any.
+    pub trigger_item: Option<ItemId>,
+    /// The minimum level, if any.
+    pub level: Option<u8>,
+    /// The gender that evolves, if only one does.
+    pub gender: Option<Gender>,
+    /// The time of day the Pokémon must level up at, if any.
+    pub time_of_day: Option<TimeOfDay>,
+    /// The move the Pokémon must know, if any.
+    pub move_id: Option<MoveId>,
+    /// The minimum happiness, if any.
+    pub happiness: Option<u8>,
+    pub relative_physical_stats: Option<i8>,
+}
+
+impl EvolvesFrom {
+    /// True if the evolution's time of day condition, if any, is met at a
+    /// time of day.
+    pub fn allowed_at(&self, time_of_day: TimeOfDay) -> bool {
+        self.time_of_day.is_none_or(|required| time_of_day.satisfies(required))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Species {
+    pub id: SpeciesId,
+    pub name: String,
+    /// The Veekun identifier for the species, e.g. `"mr-mime"`.
+    pub identifier: String,
+    pub generation: Generation,
+    pub gender_rate: i8,
+    /// The range of the species' Pokémon in `SpeciesTable::all_pokemon`.
+    pokemon: Range<usize>,
+    pub egg_groups: OneOrTwo<EggGroup>,
+    pub evolves_from: Option<EvolvesFrom>,
+    pub color: Color,
+    pub shape: Shape,
+    /// The species' habitat, or `None` if it was introduced after
+    /// Generation III.
+    pub habitat: Option<Habitat>,
+    /// The happiness of the species' Pokémon when caught or hatched.
+    pub base_happiness: u8,
+    /// The number of egg cycles for the species' eggs to hatch.
+    pub hatch_counter: u8,
+    pub growth_rate: GrowthRate,
+}
+
+impl Species {
+    /// The species' gender ratio, from the raw `gender_rate`.
+    pub fn gender_ratio(&self) -> GenderRatio {
+        GenderRatio::from_rate(self.gender_rate)
+            .unwrap_or(GenderRatio::Genderless)
+    }
+}
+
+/// The species of each color, shape, and habitat, in order of ID.
+#[derive(Clone, Debug, Default)]
+struct Filters {
+    colors: HashMap<Color, Vec<SpeciesId>>,
+    shapes: HashMap<Shape, Vec<SpeciesId>>,
+    habitats: HashMap<Habitat, Vec<SpeciesId>>,
+}
+
+/// The species, with the Pokémon of every species stored together in order
+/// of species.
+pub struct SpeciesTable(
+    Vec<Species>, Vec<Pokemon>, IdentifierMap<SpeciesId>,
+    Vec<Vec<SpeciesId>>, Filters);
+
+impl Default for SpeciesTable {
+    fn default() -> Self {
+        let species = vec![Species::default(); SPECIES_COUNT];
+        SpeciesTable(species, Vec::new(), Default::default(),
+            vec![Vec::new(); SPECIES_COUNT], Default::default())
+    }
+}
+
+impl vcsv::FromCsvIncremental for SpeciesTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: SpeciesId = vcsv::from_field(&record, 0)?;
+        let identifier: VeekunString = vcsv::from_field(&record, 1)?;
+        let generation = vcsv::from_field(&record, 2)?;
+        let habitat: VeekunOption<_> = vcsv::from_field(&record, 7)?;
+        let gender_rate = vcsv::from_field(&record, 8)?;
+        self[id].id = id;
+        self[id].color = vcsv::from_field(&record, 5)?;
+        self[id].shape = vcsv::from_field(&record, 6)?;
+        self[id].habitat = habitat.into();
+        self[id].base_happiness = vcsv::from_field(&record, 10)?;
+        self[id].hatch_counter = vcsv::from_field(&record, 12)?;
+        self[id].growth_rate = vcsv::from_field(&record, 14)?;
+        self[id].name = to_pascal_case(identifier.as_str());
+        self[id].identifier = identifier.as_str().to_string();
+        self[id].generation = generation;
+        self[id].gender_rate = gender_rate;
+        if let VeekunOption(Some(from_id)) = vcsv::from_field(&record, 3)? {
+            self[id].evolves_from = Some(EvolvesFrom {
+                from_id,
+                .. Default::default()
+            });
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Index<SpeciesId> for SpeciesTable {
+    type Output = Species;
+
+    fn index(&self, index: SpeciesId) -> &Species {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<SpeciesId> for SpeciesTable {
+    fn index_mut(&mut self, index: SpeciesId) -> &mut Species {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+impl SpeciesTable {
+    /// Create a table with no species, for `insert_custom`.
+    #[cfg(feature = "sandbox")]
+    pub(crate) fn empty() -> Self {
+        SpeciesTable(Vec::new(), Vec::new(), Default::default(), Vec::new(),
+            Default::default())
+    }
+
+    /// Create a species table from the included Veekun CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
+    pub fn new() -> Self {
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Create a species table from the CSV data in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut table
+            = SpeciesTable::from_csv_source(source, vdata::files::SPECIES)?;
+        table.set_pokemon(raw::PokemonTable::load(source)?);
+        table.set_egg_groups(raw::EggGroupTable::load(source)?);
+        table.set_evolutions(raw::EvolutionTable::load(source)?);
+        table.2 = table.0.iter()
+            .map(|s| (s.identifier.as_str(), s.id))
+            .collect();
+        table.index_evolutions();
+        table.index_filters();
+        Ok(table)
+    }
+
+    /// Get the map between the species' Veekun identifiers and IDs.
+    pub fn identifiers(&self) -> &IdentifierMap<SpeciesId> {
+        &self.2
+    }
+
+    /// The number of species, including custom species.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// True if the table has a species with an ID, stock or custom.
+    pub fn contains(&self, id: SpeciesId) -> bool {
+        (id.0 as usize) < self.0.len()
+    }
+
+    /// Add a species of a program's own, with its Pokémon, the first being
+    /// the default, and get its ID, which follows every existing species, or
+    /// an error if the species or a Pokémon is invalid.
+    ///
+    /// The IDs of the species and its Pokémon are allocated and overwritten,
+    /// and the identifiers, evolutions, and filters are updated. The species'
+    /// identifier should be unique, as the identifier map only keeps the
+    /// newest species with an identifier. Items that evolve the species are
+    /// related to it by `ItemTable::relate_species`.
+    pub fn insert_custom(
+        &mut self, mut species: Species, pokemon: Vec<PokemonBuilder>
+    ) -> crate::builder::Result<SpeciesId> {
+        check(!species.identifier.is_empty(), "identifier", "empty")?;
+        check(!pokemon.is_empty(), "pokemon", "none")?;
+        check(species.evolves_from.is_none_or(|from| {
+            self.contains(from.from_id)
+        }), "evolves_from", "unknown species")?;
+        let id = SpeciesId(self.0.len() as u16);
+        let next_pokemon = self.1.iter().map(|p| p.id.0 + 1).max()
+            .unwrap_or_default();
+        let pokemon = pokemon.into_iter().enumerate()
+            .map(|(i, p)| p.build(PokemonId(next_pokemon + i as u16)))
+            .collect::<crate::builder::Result<Vec<_>>>()?;
+        species.id = id;
+        species.pokemon = self.1.len()..self.1.len() + pokemon.len();
+        self.1.extend(pokemon);
+        self.2.insert(&species.identifier, id);
+        self.3.push(Vec::new());
+        if let Some(from) = species.evolves_from {
+            self.3[from.from_id.0 as usize].push(id);
+        }
+        let filters = &mut self.4;
+        filters.colors.entry(species.color).or_default().push(id);
+        filters.shapes.entry(species.shape).or_default().push(id);
+        if let Some(habitat) = species.habitat {
+            filters.habitats.entry(habitat).or_default().push(id);
+        }
+        self.0.push(species);
+        Ok(id)
+    }
+
+    /// Get the Pokémon of a species; the first is the default.
+    pub fn pokemon(&self, id: SpeciesId) -> &[Pokemon] {
+        &self.1[self[id].pokemon.clone()]
+    }
+
+    /// Get a Pokémon by ID, stock or custom.
+    pub fn pokemon_by_id(&self, id: PokemonId) -> Option<&Pokemon> {
+        self.1.iter().find(|p| p.id == id)
+    }
+
+    /// Get the Pokémon of every species, including alternate Pokémon such as
+    /// Deoxys' forms, in order of species.
+    pub fn all_pokemon(&self) -> &[Pokemon] {
+        &self.1
+    }
+
+    /// Get the species that a species evolves into, in order of id.
+    pub fn evolves_into(&self, id: SpeciesId) -> &[SpeciesId] {
+        &self.3[id.0 as usize]
+    }
+
+    /// True if a species does not evolve into any other species.
+    pub fn is_fully_evolved(&self, id: SpeciesId) -> bool {
+        self.evolves_into(id).is_empty()
+    }
+
+    /// Get the species of a color, in order of ID.
+    pub fn by_color(&self, color: Color) -> &[SpeciesId] {
+        self.4.colors.get(&color).map_or(&[], |ids| &ids[..])
+    }
+
+    /// Get the species of a shape, in order of ID.
+    pub fn by_shape(&self, shape: Shape) -> &[SpeciesId] {
+        self.4.shapes.get(&shape).map_or(&[], |ids| &ids[..])
+    }
+
+    /// Get the species of a habitat, in order of ID.
+    pub fn by_habitat(&self, habitat: Habitat) -> &[SpeciesId] {
+        self.4.habitats.get(&habitat).map_or(&[], |ids| &ids[..])
+    }
+
+    fn index_filters(&mut self) {
+        let mut filters = Filters::default();
+        for species in self.0.iter() {
+            filters.colors.entry(species.color).or_default().push(species.id);
+            filters.shapes.entry(species.shape).or_default().push(species.id);
+            if let Some(habitat) = species.habitat {
+                filters.habitats.entry(habitat).or_default().push(species.id);
+            }
+        }
+        self.4 = filters;
+    }
+
+    fn index_evolutions(&mut self) {
+        self.3 = vec![Vec::new(); SPECIES_COUNT];
+        for species in self.0.iter() {
+            if let Some(from) = species.evolves_from {
+                self.3[from.from_id.0 as usize].push(species.id);
+            }
+        }
+    }
+
+    fn set_pokemon(&mut self, pokemon_table: raw::PokemonTable) {
+        let mut pokemon = pokemon_table.0;
+        pokemon.sort_by_key(|&(species, _)| species);
+        let mut start = 0;
+        for (i, species) in self.0.iter_mut().enumerate() {
+            let count = pokemon[start..].iter()
+                .take_while(|(s, _)| s.0 as usize == i)
+                .count();
+            species.pokemon = start..start + count;
+            start += count;
+        }
+        self.1 = pokemon.into_iter().map(|(_, pokemon)| pokemon).collect();
+    }
+
+    fn set_egg_groups(&mut self, egg_group_table: raw::EggGroupTable) {
+        for i in 0..SPECIES_COUNT {
+            let id = SpeciesId(i as u16);
+            let options = [
+                egg_group_table[id].get(0).map(|g| *g),
+                egg_group_table[id].get(1).map(|g| *g),
+            ];
+            self[id].egg_groups = OneOrTwo::from_options(options).unwrap();
+        }
+    }
+
+    fn set_evolutions(&mut self, evolution_table: raw::EvolutionTable) {
+        for i in 0..SPECIES_COUNT {
+            let id = SpeciesId(i as u16);
+            self[id].evolves_from
+                = self[id].evolves_from.map(|e| EvolvesFrom {
+                    from_id: e.from_id,
+                    .. evolution_table[id]
+                });
+        }
+    }
+}
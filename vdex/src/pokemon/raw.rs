@@ -0,0 +1,440 @@
+//! The Veekun CSV tables that `SpeciesTable` is assembled from, indexed by
+//! Pokémon or species ID.
+//!
+//! These are public with the `raw-tables` feature, for tools that want the
+//! tables as Veekun lays them out.
+
+use std::collections::{HashMap, HashSet};
+use std::iter::repeat;
+use std::mem::take;
+use crate::Ability;
+use crate::Type;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::VeekunOption;
+use veekun::repr::VeekunString;
+use crate::versions::VersionGroup;
+use super::{
+    BaseStats, EggGroup, EvolvesFrom, Form, Forms, Learnset, Pokemon,
+    PokemonId, PokemonMove, SpeciesId, OneOrTwo, POKEMON_COUNT, SPECIES_COUNT,
+};
+
+/// The abilities of each Pokémon, by slot; the third slot is the hidden
+/// ability.
+pub struct AbilityTable(pub [[Option<Ability>; 3]; POKEMON_COUNT]);
+
+impl AbilityTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        AbilityTable::from_csv_source(source, vdata::files::ABILITIES)
+    }
+}
+
+impl Default for AbilityTable {
+    fn default() -> Self {
+        AbilityTable([Default::default(); POKEMON_COUNT])
+    }
+}
+
+impl vcsv::FromCsvIncremental for AbilityTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: PokemonId = vcsv::from_field(&record, 0)?;
+        let ability = vcsv::from_field(&record, 1)?;
+        let slot: usize = vcsv::from_field(&record, 3)?;
+        if slot < 1 && slot > 3 {
+            return Err(vcsv::Error::Veekun {
+                file: None,
+                line: vcsv::get_line(&record),
+                field: 3,
+                record: Some(record.to_string()),
+                error: Box::new(vcsv::MiscError("Invalid slot number")),
+            });
+        }
+        self[id][slot - 1] = Some(ability);
+        Ok(())
+    }
+}
+
+impl std::ops::Index<PokemonId> for AbilityTable {
+    type Output = [Option<Ability>; 3];
+
+    fn index(&self, index: PokemonId) -> &[Option<Ability>; 3] {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<PokemonId> for AbilityTable {
+    fn index_mut(&mut self, index: PokemonId) -> &mut [Option<Ability>; 3] {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+/// The forms of each Pokémon.
+pub struct FormTable(pub Vec<Forms>);
+
+impl FormTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        FormTable::from_csv_source(source, vdata::files::FORMS)
+    }
+}
+
+impl Default for FormTable {
+    fn default() -> Self {
+        FormTable(repeat(Forms::new()).take(POKEMON_COUNT).collect::<Vec<_>>())
+    }
+}
+
+impl vcsv::FromCsvIncremental for FormTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let form_id = vcsv::from_field(&record, 0)?;
+        let name: VeekunOption<VeekunString> = vcsv::from_field(&record, 1)?;
+        let pokemon_id: PokemonId = vcsv::from_field(&record, 2)?;
+        let battle_only: u8 = vcsv::from_field(&record, 5)?;
+        self[pokemon_id].push(Form {
+            id: form_id,
+            name: name.into(),
+            battle_only: battle_only != 0,
+        });
+        Ok(())
+    }
+}
+
+impl std::ops::Index<PokemonId> for FormTable {
+    type Output = Forms;
+
+    fn index(&self, index: PokemonId) -> &Forms {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<PokemonId> for FormTable {
+    fn index_mut(&mut self, index: PokemonId) -> &mut Forms {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+/// The moves each Pokémon learns in each version group.
+pub struct PokemonMoveTable(pub Vec<HashMap<VersionGroup, Vec<PokemonMove>>>);
+
+impl PokemonMoveTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        PokemonMoveTable::from_csv_source(source, vdata::files::POKEMON_MOVES)
+    }
+}
+
+impl Default for PokemonMoveTable {
+    fn default() -> Self {
+        PokemonMoveTable(repeat(HashMap::new())
+                .take(POKEMON_COUNT).collect::<Vec<_>>())
+    }
+}
+
+impl vcsv::FromCsvIncremental for PokemonMoveTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let pokemon_id: PokemonId = vcsv::from_field(&record, 0)?;
+        let version_group = vcsv::from_field(&record, 1)?;
+        let move_id = vcsv::from_field(&record, 2)?;
+        let learn_method = vcsv::from_field(&record, 3)?;
+        let level = vcsv::from_field(&record, 4)?;
+        let pokemon_move = PokemonMove { move_id, learn_method, level };
+        self[pokemon_id].entry(version_group)
+            .or_insert(Vec::new()).push(pokemon_move);
+        Ok(())
+    }
+}
+
+impl std::ops::Index<PokemonId> for PokemonMoveTable {
+    type Output = HashMap<VersionGroup, Vec<PokemonMove>>;
+
+    fn index(&self, index: PokemonId) -> &Self::Output {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<PokemonId> for PokemonMoveTable {
+    fn index_mut(&mut self, index: PokemonId) -> &mut Self::Output {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+/// The base stats of each Pokémon.
+pub struct StatTable(pub [BaseStats; POKEMON_COUNT]);
+
+impl StatTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        StatTable::from_csv_source(source, vdata::files::STATS)
+    }
+}
+
+impl Default for StatTable {
+    fn default() -> Self {
+        StatTable([Default::default(); POKEMON_COUNT])
+    }
+}
+
+impl vcsv::FromCsvIncremental for StatTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: PokemonId = vcsv::from_field(&record, 0)?;
+        let stat = vcsv::from_field(&record, 1)?;
+        let base = vcsv::from_field(&record, 2)?;
+        self[id][stat] = base;
+        Ok(())
+    }
+}
+
+impl std::ops::Index<PokemonId> for StatTable {
+    type Output = BaseStats;
+
+    fn index(&self, index: PokemonId) -> &BaseStats {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<PokemonId> for StatTable {
+    fn index_mut(&mut self, index: PokemonId) -> &mut BaseStats {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+/// The types of each Pokémon.
+pub struct TypeTable(pub [[Option<Type>; 2]; POKEMON_COUNT]);
+
+impl TypeTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        TypeTable::from_csv_source(source, vdata::files::TYPES)
+    }
+}
+
+impl Default for TypeTable {
+    fn default() -> Self {
+        TypeTable([Default::default(); POKEMON_COUNT])
+    }
+}
+
+impl vcsv::FromCsvIncremental for TypeTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: PokemonId = vcsv::from_field(&record, 0)?;
+        let typ = vcsv::from_field(&record, 1)?;
+        let slot: usize = vcsv::from_field(&record, 2)?;
+        if slot < 1 && slot > 2 {
+            return Err(vcsv::Error::Veekun {
+                file: None,
+                line: vcsv::get_line(&record),
+                field: 2,
+                record: Some(record.to_string()),
+                error: Box::new(vcsv::MiscError("Invalid slot number")),
+            });
+        }
+        self[id][slot - 1] = Some(typ);
+        Ok(())
+    }
+}
+
+impl std::ops::Index<PokemonId> for TypeTable {
+    type Output = [Option<Type>; 2];
+
+    fn index(&self, index: PokemonId) -> &[Option<Type>; 2] {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<PokemonId> for TypeTable {
+    fn index_mut(&mut self, index: PokemonId) -> &mut [Option<Type>; 2] {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+/// Pokémon and their species, in the order of the CSV file.
+#[derive(Default)]
+pub struct PokemonTable(pub Vec<(SpeciesId, Pokemon)>);
+
+impl vcsv::FromCsvIncremental for PokemonTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let pokemon_id: PokemonId = vcsv::from_field(&record, 0)?;
+        let species_id: SpeciesId = vcsv::from_field(&record, 1)?;
+        let base_exp = vcsv::from_field(&record, 4)?;
+        self.0.push((species_id, Pokemon {
+            id: pokemon_id,
+            base_exp,
+            .. Default::default()
+        }));
+        Ok(())
+    }
+}
+
+impl PokemonTable {
+    /// Load the Pokémon and fill them in from the other Pokémon tables, which
+    /// are consumed as they are loaded.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let mut table
+            = PokemonTable::from_csv_source(source, vdata::files::POKEMON)?;
+        table.set_abilities(AbilityTable::load(source)?);
+        table.set_forms(FormTable::load(source)?);
+        table.set_moves(PokemonMoveTable::load(source)?);
+        table.set_types(TypeTable::load(source)?);
+        table.set_stats(StatTable::load(source)?);
+        Ok(table)
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut Pokemon> {
+        self.0.iter_mut().map(|(_, pokemon)| pokemon)
+    }
+
+    fn set_abilities(&mut self, ability_table: AbilityTable) {
+        for pokemon in self.iter_mut() {
+            let id = pokemon.id;
+            let options = [ability_table[id][0], ability_table[id][1]];
+            pokemon.abilities = OneOrTwo::from_options(options).unwrap();
+            pokemon.hidden_ability = ability_table[id][2];
+        }
+    }
+
+    fn set_forms(&mut self, mut form_table: FormTable) {
+        for pokemon in self.iter_mut() {
+            pokemon.forms = take(&mut form_table[pokemon.id]);
+        }
+    }
+
+    /// Set the Pokémon's learnsets, sharing storage between identical ones.
+    fn set_moves(&mut self, mut move_table: PokemonMoveTable) {
+        let mut learnsets: HashSet<Learnset> = HashSet::new();
+        for pokemon in self.iter_mut() {
+            pokemon.moves = take(&mut move_table[pokemon.id]).into_iter()
+                .map(|(version_group, moves)| {
+                    let learnset = learnsets.get(&moves[..]).cloned()
+                        .unwrap_or_else(|| Learnset::from(moves));
+                    learnsets.insert(learnset.clone());
+                    (version_group, learnset)
+                })
+                .collect();
+        }
+    }
+
+    fn set_types(&mut self, type_table: TypeTable) {
+        for pokemon in self.iter_mut() {
+            let options = type_table[pokemon.id];
+            pokemon.types = OneOrTwo::from_options(options).unwrap();
+        }
+    }
+
+    fn set_stats(&mut self, stat_table: StatTable) {
+        for pokemon in self.iter_mut() {
+            pokemon.stats = stat_table[pokemon.id];
+        }
+    }
+}
+
+/// The egg groups of each species.
+pub struct EggGroupTable(pub Vec<Vec<EggGroup>>);
+
+impl EggGroupTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        EggGroupTable::from_csv_source(source, vdata::files::EGG_GROUPS)
+    }
+}
+
+impl Default for EggGroupTable {
+    fn default() -> Self {
+        EggGroupTable(repeat(Vec::new()).take(SPECIES_COUNT).collect::<Vec<_>>())
+    }
+}
+
+impl vcsv::FromCsvIncremental for EggGroupTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let id: SpeciesId = vcsv::from_field(&record, 0)?;
+        let egg_group = vcsv::from_field(&record, 1)?;
+        self[id].push(egg_group);
+        Ok(())
+    }
+}
+
+impl std::ops::Index<SpeciesId> for EggGroupTable {
+    type Output = Vec<EggGroup>;
+
+    fn index(&self, index: SpeciesId) -> &Vec<EggGroup> {
+        self.0.index(index.0 as usize)
+    }
+}
+
+impl std::ops::IndexMut<SpeciesId> for EggGroupTable {
+    fn index_mut(&mut self, index: SpeciesId) -> &mut Vec<EggGroup> {
+        self.0.index_mut(index.0 as usize)
+    }
+}
+
+/// How each species that evolves from another evolves, except for the
+/// species it evolves from, which comes from the species file.
+#[derive(Default)]
+pub struct EvolutionTable(pub HashMap<SpeciesId, EvolvesFrom>);
+
+impl EvolutionTable {
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        EvolutionTable::from_csv_source(source, vdata::files::EVOLUTION)
+    }
+}
+
+impl vcsv::FromCsvIncremental for EvolutionTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let species_id = vcsv::from_field(&record, 1)?;
+        let trigger = vcsv::from_field(&record, 2)?;
+        let trigger_item: VeekunOption<_> = vcsv::from_field(&record, 3)?;
+        let level: VeekunOption<_> = vcsv::from_field(&record, 4)?;
+        let gender: VeekunOption<_> = vcsv::from_field(&record, 5)?;
+        let time_of_day: VeekunOption<_> = vcsv::from_field(&record, 8)?;
+        let move_id: VeekunOption<_> = vcsv::from_field(&record, 9)?;
+        let happiness: VeekunOption<_> = vcsv::from_field(&record, 10)?;
+        let rps: VeekunOption<_> = vcsv::from_field(&record, 12)?;
+        self.0.insert(species_id, EvolvesFrom {
+            from_id: Default::default(),
+            trigger,
+            trigger_item: trigger_item.into(),
+            level: level.into(),
+            gender: gender.into(),
+            time_of_day: time_of_day.into(),
+            move_id: move_id.into(),
+            happiness: happiness.into(),
+            relative_physical_stats: rps.into(),
+        });
+        Ok(())
+    }
+}
+
+impl std::ops::Index<SpeciesId> for EvolutionTable {
+    type Output = EvolvesFrom;
+
+    fn index(&self, index: SpeciesId) -> &EvolvesFrom {
+        self.0.index(&index)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::Stat;
+use crate::Type;
+use super::{BaseStats, Pokemon, SpeciesTable, PERMANENT_STATS};
+
+/// What the results of a `StatQuery` are sorted by.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum SortKey {
+    Stat(Stat),
+    Total,
+}
+
+/// A search of the Pokémon by base stats, started with
+/// `SpeciesTable::query_stats`.
+///
+/// Every bound is inclusive, and every filter must match. Alternate Pokémon
+/// like Deoxys' forms are searched along with the default ones.
+#[derive(Clone)]
+pub struct StatQuery<'a> {
+    table: &'a SpeciesTable,
+    min: BaseStats,
+    max: BaseStats,
+    total: (u16, u16),
+    typ: Option<Type>,
+    sort: Option<SortKey>,
+}
+
+impl<'a> StatQuery<'a> {
+    pub(super) fn new(table: &'a SpeciesTable) -> Self {
+        StatQuery {
+            table,
+            min: BaseStats([u8::MIN; PERMANENT_STATS]),
+            max: BaseStats([u8::MAX; PERMANENT_STATS]),
+            total: (u16::MIN, u16::MAX),
+            typ: None,
+            sort: None,
+        }
+    }
+
+    /// Only Pokémon with at least a base value of a permanent stat.
+    pub fn min(mut self, stat: Stat, base: u8) -> Self {
+        self.min[stat] = base;
+        self
+    }
+
+    /// Only Pokémon with at most a base value of a permanent stat.
+    pub fn max(mut self, stat: Stat, base: u8) -> Self {
+        self.max[stat] = base;
+        self
+    }
+
+    /// Only Pokémon with a base stat total between a minimum and a maximum.
+    pub fn total_between(mut self, min: u16, max: u16) -> Self {
+        self.total = (min, max);
+        self
+    }
+
+    /// Only Pokémon with a type.
+    pub fn with_type(mut self, typ: Type) -> Self {
+        self.typ = Some(typ);
+        self
+    }
+
+    /// Sort the results from the highest base value of a stat to the lowest.
+    pub fn sorted_by(mut self, stat: Stat) -> Self {
+        self.sort = Some(SortKey::Stat(stat));
+        self
+    }
+
+    /// Sort the results from the highest base stat total to the lowest.
+    pub fn sorted_by_total(mut self) -> Self {
+        self.sort = Some(SortKey::Total);
+        self
+    }
+
+    /// True if a Pokémon matches every filter.
+    pub fn matches(&self, pokemon: &Pokemon) -> bool {
+        let stats = &pokemon.stats;
+        let total = stats.total();
+        stats.iter()
+            .all(|(s, base)| self.min[s] <= base && base <= self.max[s])
+            && self.total.0 <= total && total <= self.total.1
+            && self.typ.is_none_or(|typ| pokemon.types.contains(typ))
+    }
+
+    /// Run the query, getting the matching Pokémon in order of species, or
+    /// sorted with ties in order of species.
+    pub fn run(&self) -> Vec<&'a Pokemon> {
+        let mut results: Vec<_> = self.table.all_pokemon().iter()
+            .filter(|pokemon| self.matches(pokemon))
+            .collect();
+        match self.sort {
+            Some(SortKey::Stat(stat)) => {
+                results.sort_by_key(|p| std::cmp::Reverse(p.stats[stat]))
+            },
+            Some(SortKey::Total) => {
+                results.sort_by_key(|p| std::cmp::Reverse(p.stats.total()))
+            },
+            None => (),
+        }
+        results
+    }
+}
+
+impl SpeciesTable {
+    /// Start a search of the Pokémon by base stats, e.g. for the fastest
+    /// Electric types.
+    pub fn query_stats(&self) -> StatQuery<'_> {
+        StatQuery::new(self)
+    }
+}
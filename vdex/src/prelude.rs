@@ -0,0 +1,28 @@
+//! The items most programs combine, to import with `use vdex::prelude::*`.
+//!
+//! The prelude is the stable way in to vdex: modules may be reorganized, but
+//! these names keep working, and only gain company, within a minor version.
+//!
+//! ```
+//! use vdex::prelude::*;
+//!
+//! # #[cfg(not(feature = "no-embedded-data"))] {
+//! let dex = pokedex();
+//! let pikachu = dex.species.identifiers().id("pikachu").unwrap();
+//! let pokemon = &dex.species.pokemon(pikachu)[0];
+//! assert_eq!(pokemon.types.first(), Type::Electric);
+//! assert_eq!(Nature::Modest.increased(), Some(Stat::SpecialAttack));
+//! # }
+//! ```
+
+pub use crate::{install, pokedex, Pokedex};
+pub use crate::abilities::Ability;
+pub use crate::enums::Enum;
+pub use crate::identifiers::IdentifierMap;
+pub use crate::items::{Item, ItemBuilder, ItemId};
+pub use crate::moves::{Move, MoveBuilder, MoveId};
+pub use crate::natures::{Nature, Stat};
+pub use crate::pokemon::{OneOrTwo, Pokemon, PokemonBuilder, PokemonId};
+pub use crate::pokemon::{Species, SpeciesId};
+pub use crate::types::{Efficacy, Type};
+pub use crate::versions::{Generation, Version, VersionGroup};
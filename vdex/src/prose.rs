@@ -0,0 +1,166 @@
+//! English prose describing abilities and move effects, and keyword search
+//! over it.
+//!
+//! The included data has no prose; it is read from Veekun's
+//! `ability_prose.csv` and `move_effect_prose.csv` when a data source provides
+//! them (see `veekun::data::Overlay` and `veekun::data::Directory`).
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::OnceLock;
+use crate::Ability;
+use crate::moves::{Effect, MoveId};
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use veekun::repr::VeekunString;
+
+/// Veekun's language id for English.
+const ENGLISH: u8 = 9;
+
+/// Something whose prose matched a search query.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum SearchHit {
+    Ability(Ability),
+    Move(MoveId),
+}
+
+/// A subject of prose, as indexed for search.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Subject {
+    Ability(Ability),
+    Effect(Effect),
+}
+
+/// The short and full descriptions of an ability or move effect.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Prose {
+    pub short_effect: String,
+    pub effect: String,
+}
+
+/// Inverted index from lowercase words to the subjects whose prose has them.
+type SearchIndex = HashMap<String, BTreeSet<Subject>>;
+
+/// English prose for abilities and move effects.
+#[derive(Default)]
+pub struct ProseTable {
+    pub abilities: HashMap<Ability, Prose>,
+    pub effects: HashMap<Effect, Prose>,
+    index: OnceLock<SearchIndex>,
+}
+
+impl ProseTable {
+    /// Create a prose table from the CSV data in a source. Either kind of
+    /// prose is empty if the source does not have its file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        let abilities = match source.csv(vdata::files::ABILITY_PROSE) {
+            Some(data) => AbilityProseTable::from_csv_data(data)?.0,
+            None => HashMap::new(),
+        };
+        let effects = match source.csv(vdata::files::MOVE_EFFECT_PROSE) {
+            Some(data) => EffectProseTable::from_csv_data(data)?.0,
+            None => HashMap::new(),
+        };
+        Ok(ProseTable { abilities, effects, index: OnceLock::new() })
+    }
+
+    /// Get the subjects whose prose contains every word of the query.
+    ///
+    /// Matching is by whole words, ignoring case. The index is built on the
+    /// first search.
+    pub fn search(&self, query: &str) -> BTreeSet<Subject> {
+        let index = self.index.get_or_init(|| self.build_index());
+        let mut result: Option<BTreeSet<Subject>> = None;
+        for word in words(query) {
+            let subjects = match index.get(&word) {
+                Some(subjects) => subjects,
+                None => return BTreeSet::new(),
+            };
+            result = Some(match result {
+                Some(r) => r.intersection(subjects).cloned().collect(),
+                None => subjects.clone(),
+            });
+        }
+        result.unwrap_or_default()
+    }
+
+    fn build_index(&self) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        let subjects = self.abilities.iter()
+            .map(|(&a, p)| (Subject::Ability(a), p))
+            .chain(self.effects.iter().map(|(&e, p)| (Subject::Effect(e), p)));
+        for (subject, prose) in subjects {
+            let text = words(&prose.short_effect).chain(words(&prose.effect));
+            for word in text {
+                index.entry(word).or_default().insert(subject);
+            }
+        }
+        index
+    }
+}
+
+/// Split text into lowercase words, skipping Veekun markup targets such as
+/// the `{mechanic:flinch}` in `[flinch]{mechanic:flinch}`.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    let mut depth = 0;
+    text.split(move |c: char| {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => return depth > 0 || !c.is_alphanumeric(),
+        }
+        true
+    })
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+}
+
+/// Read the prose of a record, or `None` for non-English prose and subjects
+/// outside the main series (ids of 10000 and up).
+fn english_prose(record: &vcsv::Record) -> vcsv::Result<Option<Prose>> {
+    let id: u16 = vcsv::from_field(record, 0)?;
+    let language: u8 = vcsv::from_field(record, 1)?;
+    if id >= 10000 || language != ENGLISH {
+        return Ok(None)
+    }
+    let short_effect: VeekunString = vcsv::from_field(record, 2)?;
+    let effect: VeekunString = vcsv::from_field(record, 3)?;
+    Ok(Some(Prose {
+        short_effect: short_effect.into(),
+        effect: effect.into(),
+    }))
+}
+
+#[derive(Default)]
+struct AbilityProseTable(HashMap<Ability, Prose>);
+
+impl vcsv::FromCsvIncremental for AbilityProseTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        if let Some(prose) = english_prose(&record)? {
+            let ability: Ability = vcsv::from_field(&record, 0)?;
+            self.0.insert(ability, prose);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct EffectProseTable(HashMap<Effect, Prose>);
+
+impl vcsv::FromCsvIncremental for EffectProseTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        if let Some(prose) = english_prose(&record)? {
+            let effect: Effect = vcsv::from_field(&record, 0)?;
+            self.0.insert(effect, prose);
+        }
+        Ok(())
+    }
+}
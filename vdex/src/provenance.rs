@@ -0,0 +1,102 @@
+//! Where the data comes from, for attribution and for telling datasets apart.
+
+use std::ops::RangeInclusive;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata::{files, Source};
+use crate::versions::Generation;
+
+/// A CSV file of a data source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableInfo {
+    pub file: &'static str,
+    /// The number of records, excluding the header.
+    pub records: usize,
+}
+
+/// Information about a set of Veekun CSV data. See `about`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataInfo {
+    /// The commit of the Veekun pokedex repository the data was dumped from,
+    /// if known.
+    pub revision: Option<&'static str>,
+    /// The generations species are introduced in.
+    pub generations: RangeInclusive<Generation>,
+    /// The standard files the source has (see `veekun::data::files::ALL`).
+    pub tables: Vec<TableInfo>,
+    /// The license the data is under, if known.
+    pub license: Option<&'static str>,
+}
+
+impl DataInfo {
+    /// Count the records of the standard files in a source. The revision and
+    /// license of a source are unknown.
+    pub fn load(source: &dyn Source) -> vcsv::Result<Self> {
+        let mut tables = Vec::new();
+        for &file in files::ALL.iter() {
+            if let Some(data) = source.csv(file) {
+                let RecordCount(records) = RecordCount::from_csv_data(data)?;
+                tables.push(TableInfo { file, records });
+            }
+        }
+        let GenerationRange(range)
+            = GenerationRange::from_csv_source(source, files::SPECIES)?;
+        let (first, last) = range.unwrap_or((Generation::I, Generation::I));
+        Ok(DataInfo {
+            revision: None,
+            generations: first..=last,
+            tables,
+            license: None,
+        })
+    }
+
+    /// Get the number of records of a file, if the source has it.
+    pub fn records(&self, file: &str) -> Option<usize> {
+        self.tables.iter().find(|t| t.file == file).map(|t| t.records)
+    }
+}
+
+struct RecordCount(usize);
+
+impl vcsv::FromCsvIncremental for RecordCount {
+    fn from_empty_csv() -> Self { RecordCount(0) }
+
+    fn load_csv_record(
+        &mut self, _record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        self.0 += 1;
+        Ok(())
+    }
+}
+
+/// The range of generations in the species file.
+struct GenerationRange(Option<(Generation, Generation)>);
+
+impl vcsv::FromCsvIncremental for GenerationRange {
+    fn from_empty_csv() -> Self { GenerationRange(None) }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let generation: Generation = vcsv::from_field(&record, 2)?;
+        self.0 = Some(match self.0 {
+            Some((first, last))
+                => (first.min(generation), last.max(generation)),
+            None => (generation, generation),
+        });
+        Ok(())
+    }
+}
+
+/// Describe the data included in the binary: its Veekun revision, the
+/// generations it covers, its files and their record counts, and its license.
+///
+/// Applications displaying the data should show the license for attribution.
+#[cfg(not(feature = "no-embedded-data"))]
+pub fn about() -> DataInfo {
+    DataInfo {
+        revision: Some(crate::vdata::COMMIT.trim()),
+        license: Some(crate::vdata::LICENSE),
+        .. DataInfo::load(&crate::vdata::Embedded).unwrap()
+    }
+}
@@ -0,0 +1,271 @@
+//! Python bindings with the `python` feature: lookups, species queries, and
+//! the stat and damage calculators over the global pokedex, as a module named
+//! `vdex`.
+//!
+//! Build the extension module with
+//! `maturin build --features python,pyo3/extension-module`, then:
+//!
+//! ```python
+//! import vdex
+//! vdex.species("pikachu").stats       # [35, 55, 30, 90, 50, 40]
+//! [s.identifier for s in vdex.query_species(type="Water", learns="surf")]
+//! vdex.ko_chance("garchomp", "blissey", "earthquake", hits=3).chance
+//! ```
+//!
+//! Enums are named as in their `Debug` output, like `"Water"` or
+//! `"BlackWhite"`; species, moves, and items by Veekun identifier, like
+//! `"mr-mime"`.
+
+use std::fmt::Debug;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use crate::analysis;
+use crate::battle::{Monster, MoveSlot};
+use crate::Enum;
+use crate::moves::{Move, MoveId, PowerKind};
+use crate::Nature;
+use crate::pokedex;
+use crate::pokemon::{Pokemon, Species, SpeciesId};
+use crate::query::{Filter, Select};
+use crate::stored::{AbilitySlot, Error, StoredPokemon, MAX_IV};
+use crate::Type;
+use crate::versions::VersionGroup;
+
+/// Parse an enum by its variant name.
+fn enum_by_name<T: Enum + Debug>(name: &str, kind: &str) -> PyResult<T> {
+    T::VALUES.iter()
+        .find(|value| format!("{:?}", value) == name)
+        .cloned()
+        .ok_or_else(|| PyValueError::new_err(
+            format!("unknown {} {:?}", kind, name)))
+}
+
+fn species_id(identifier: &str) -> PyResult<SpeciesId> {
+    pokedex().species.identifiers().id(identifier).ok_or_else(
+        || PyValueError::new_err(format!("unknown species {:?}", identifier)))
+}
+
+fn move_id(identifier: &str) -> PyResult<MoveId> {
+    pokedex().moves.identifiers().id(identifier).ok_or_else(
+        || PyValueError::new_err(format!("unknown move {:?}", identifier)))
+}
+
+/// A species, with the fields of its default Pokémon.
+#[pyclass(name = "Species", frozen, get_all)]
+#[derive(Clone, Debug)]
+pub struct PySpecies {
+    pub id: u16,
+    pub name: String,
+    pub identifier: String,
+    pub types: Vec<String>,
+    pub abilities: Vec<String>,
+    /// The base stats, in the order of `BaseStats`.
+    pub stats: Vec<u16>,
+}
+
+impl PySpecies {
+    fn new(species: &Species, pokemon: &Pokemon) -> Self {
+        let types = pokemon.types;
+        let abilities = pokemon.abilities;
+        PySpecies {
+            id: species.id.0,
+            name: species.name.clone(),
+            identifier: species.identifier.clone(),
+            types: [Some(types.first()), types.second()].iter().flatten()
+                .map(|t| format!("{:?}", t))
+                .collect(),
+            abilities: [Some(abilities.first()), abilities.second(),
+                        pokemon.hidden_ability].iter().flatten()
+                .map(|a| format!("{:?}", a))
+                .collect(),
+            stats: pokemon.stats.0.iter().map(|&s| s.into()).collect(),
+        }
+    }
+}
+
+/// A move.
+#[pyclass(name = "Move", frozen, get_all)]
+#[derive(Clone, Debug)]
+pub struct PyMove {
+    pub id: u16,
+    pub name: String,
+    pub identifier: String,
+    #[pyo3(name = "type")]
+    pub typ: String,
+    /// The power, or `None` for moves without a fixed power.
+    pub power: Option<u8>,
+    /// The accuracy in percent, or `None` for moves that cannot miss.
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub priority: i8,
+    pub damage_class: String,
+}
+
+impl PyMove {
+    fn new(mov: &Move) -> Self {
+        PyMove {
+            id: mov.id.0,
+            name: mov.name.clone(),
+            identifier: mov.identifier.clone(),
+            typ: format!("{:?}", mov.typ),
+            power: match mov.power_kind() {
+                PowerKind::Fixed(power) => Some(power),
+                _ => None,
+            },
+            accuracy: mov.accuracy,
+            pp: mov.pp,
+            priority: mov.priority,
+            damage_class: format!("{:?}", mov.damage_class),
+        }
+    }
+}
+
+/// The damage of a move and the chance it knocks out, as in
+/// `analysis::KoReport`.
+#[pyclass(name = "KoReport", frozen, get_all)]
+#[derive(Clone, Debug)]
+pub struct PyKoReport {
+    /// The damage of each roll, lowest first.
+    pub rolls: Vec<u16>,
+    pub hits: u8,
+    pub chance: f64,
+    /// The defender's HP.
+    pub hp: u16,
+}
+
+/// Get a species by identifier.
+#[pyfunction]
+pub fn species(identifier: &str) -> PyResult<PySpecies> {
+    let id = species_id(identifier)?;
+    let table = &pokedex().species;
+    Ok(PySpecies::new(&table[id], &table.pokemon(id)[0]))
+}
+
+/// Get a move by identifier.
+#[pyfunction(name = "move")]
+pub fn get_move(identifier: &str) -> PyResult<PyMove> {
+    Ok(PyMove::new(&pokedex().moves[move_id(identifier)?]))
+}
+
+/// Get the species matching every filter given, as in `query::Select`.
+#[pyfunction]
+#[pyo3(signature = (
+    r#type=None, ability=None, learns=None, version_group="BlackWhite"
+))]
+pub fn query_species(
+    r#type: Option<&str>, ability: Option<&str>, learns: Option<&str>,
+    version_group: &str,
+) -> PyResult<Vec<PySpecies>> {
+    let mut query = Select::species();
+    if let Some(typ) = r#type {
+        query = query.filter(Filter::TypeIs(enum_by_name(typ, "type")?));
+    }
+    if let Some(ability) = ability {
+        query = query.filter(
+            Filter::HasAbility(enum_by_name(ability, "ability")?));
+    }
+    if let Some(learns) = learns {
+        let version_group: VersionGroup =
+            enum_by_name(version_group, "version group")?;
+        query = query.filter(Filter::Learns(move_id(learns)?, version_group));
+    }
+    let table = &pokedex().species;
+    Ok(query.run()
+        .map(|s| PySpecies::new(&table[s.id], &table.pokemon(s.id)[0]))
+        .collect())
+}
+
+/// A stored Pokémon of a species' default form and first ability.
+fn stored(
+    identifier: &str, level: u8, nature: Nature, ivs: [u8; 6], evs: [u8; 6]
+) -> PyResult<StoredPokemon> {
+    let species = species_id(identifier)?;
+    let pokemon = StoredPokemon {
+        species,
+        form: pokedex().species.pokemon(species)[0].forms[0].id,
+        ability: AbilitySlot::First,
+        nature,
+        level,
+        ivs,
+        evs,
+        ..Default::default()
+    };
+    match pokemon.validate(pokedex()) {
+        // The calculators do not need the Pokémon's moves.
+        Ok(()) | Err(Error::Invalid { field: "moves", .. }) => Ok(pokemon),
+        Err(error) => Err(PyValueError::new_err(error.to_string())),
+    }
+}
+
+/// Calculate the permanent stats of a Pokémon, in the order of `BaseStats`,
+/// as in `StoredPokemon::stats`.
+#[pyfunction]
+#[pyo3(signature = (
+    species, level, nature="Hardy", ivs=[MAX_IV; 6], evs=[0; 6]
+))]
+pub fn stats(
+    species: &str, level: u8, nature: &str, ivs: [u8; 6], evs: [u8; 6]
+) -> PyResult<Vec<u16>> {
+    let nature = enum_by_name(nature, "nature")?;
+    let pokemon = stored(species, level, nature, ivs, evs)?;
+    Ok(pokemon.stats(pokedex()).unwrap_or_default().to_vec())
+}
+
+/// A Pokémon in battle of a species with perfect IVs, no EVs, and a neutral
+/// nature, as in `stored`.
+fn monster(identifier: &str, level: u8) -> PyResult<Monster> {
+    let dex = pokedex();
+    let pokemon =
+        stored(identifier, level, Nature::Hardy, [MAX_IV; 6], [0; 6])?;
+    let stats = pokemon.stats(dex).unwrap_or_default();
+    let types = pokemon.pokemon(dex).map(|p| p.types).unwrap_or_default();
+    let ability = pokemon.ability(dex).unwrap_or_default();
+    Ok(Monster::new(pokemon.species, level, types, ability, stats))
+}
+
+/// Calculate the chance that a move knocks a Pokémon out within a number of
+/// hits, as in `analysis::ko_chance`, with both Pokémon as in `stats` with
+/// the defaults.
+#[pyfunction]
+#[pyo3(signature = (attacker, defender, mov, level=50, hits=1))]
+pub fn ko_chance(
+    attacker: &str, defender: &str, mov: &str, level: u8, hits: u8
+) -> PyResult<PyKoReport> {
+    let id = move_id(mov)?;
+    let mut attacker = monster(attacker, level)?;
+    attacker.moves = vec![MoveSlot { id, pp: pokedex().moves[id].pp }];
+    let defender = monster(defender, level)?;
+    let report = analysis::ko_chance(
+        &attacker, &defender, &pokedex().moves[id], hits);
+    Ok(PyKoReport {
+        rolls: report.rolls.to_vec(),
+        hits: report.hits,
+        chance: report.chance,
+        hp: report.hp,
+    })
+}
+
+/// Get the damage modifier of an attacking type against one or two types.
+#[pyfunction]
+pub fn type_modifier(attacking: &str, types: Vec<String>) -> PyResult<f64> {
+    let attacking: Type = enum_by_name(attacking, "type")?;
+    types.iter().try_fold(1.0, |modifier, typ| {
+        let typ = enum_by_name(typ, "type")?;
+        Ok(modifier * pokedex().efficacy[(attacking, typ)].modifier())
+    })
+}
+
+/// The `vdex` Python module.
+#[pymodule]
+fn vdex(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PySpecies>()?;
+    module.add_class::<PyMove>()?;
+    module.add_class::<PyKoReport>()?;
+    module.add_function(wrap_pyfunction!(species, module)?)?;
+    module.add_function(wrap_pyfunction!(get_move, module)?)?;
+    module.add_function(wrap_pyfunction!(query_species, module)?)?;
+    module.add_function(wrap_pyfunction!(stats, module)?)?;
+    module.add_function(wrap_pyfunction!(ko_chance, module)?)?;
+    module.add_function(wrap_pyfunction!(type_modifier, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! A typed query interface over the global pokedex, for tools that would
+//! otherwise chain many table lookups by hand.
+//!
+//! A query starts from `Select`, is narrowed with `Filter`s, and chooses the
+//! extra fields its results carry, like `with_moves` and `with_stats`:
+//!
+//! ```
+//! use vdex::query::{type_is, Select};
+//! use vdex::versions::VersionGroup;
+//! use vdex::Type;
+//!
+//! # #[cfg(not(feature = "no-embedded-data"))] {
+//! let query = Select::species()
+//!     .filter(type_is(Type::Water))
+//!     .with_moves(VersionGroup::BlackWhite)
+//!     .with_stats();
+//! let squirtle = query.run().find(|s| s.identifier == "squirtle").unwrap();
+//! assert!(squirtle.moves.is_some() && squirtle.stats.is_some());
+//! # }
+//! ```
+//!
+//! Results are computed lazily as `run` is iterated, starting from the
+//! color, shape, or habitat index of `SpeciesTable` when a filter has one.
+
+use crate::Ability;
+use crate::moves::MoveId;
+use crate::pokedex;
+use crate::pokemon::{BaseStats, Color, Habitat, OneOrTwo, Pokemon, Shape};
+use crate::pokemon::{Species, SpeciesId};
+use crate::SpeciesRef;
+use crate::Type;
+use crate::versions::{Generation, VersionGroup};
+
+/// A condition on the species a query selects, checked against the
+/// species and its default Pokémon.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Filter {
+    /// The Pokémon has a type.
+    TypeIs(Type),
+    /// The Pokémon may have an ability, including its hidden ability.
+    HasAbility(Ability),
+    /// The species was introduced in a generation.
+    Generation(Generation),
+    Color(Color),
+    Shape(Shape),
+    Habitat(Habitat),
+    /// The Pokémon learns a move in a version group, by any method.
+    Learns(MoveId, VersionGroup),
+    /// The species does not evolve into any other species.
+    FullyEvolved,
+}
+
+/// Only species of a type.
+pub fn type_is(typ: Type) -> Filter {
+    Filter::TypeIs(typ)
+}
+
+/// Only species that may have an ability.
+pub fn has_ability(ability: Ability) -> Filter {
+    Filter::HasAbility(ability)
+}
+
+/// Only species that learn a move in a version group.
+pub fn learns(move_id: MoveId, version_group: VersionGroup) -> Filter {
+    Filter::Learns(move_id, version_group)
+}
+
+impl Filter {
+    /// True if a species and its default Pokémon match the filter.
+    pub fn matches(&self, species: &Species, pokemon: &Pokemon) -> bool {
+        match *self {
+            Filter::TypeIs(typ) => pokemon.types.contains(typ),
+            Filter::HasAbility(ability) => {
+                pokemon.abilities.contains(ability)
+                    || pokemon.hidden_ability == Some(ability)
+            },
+            Filter::Generation(generation) => species.generation == generation,
+            Filter::Color(color) => species.color == color,
+            Filter::Shape(shape) => species.shape == shape,
+            Filter::Habitat(habitat) => species.habitat == Some(habitat),
+            Filter::Learns(move_id, version_group) => {
+                pokemon.learnset(version_group).iter()
+                    .any(|m| m.move_id == move_id)
+            },
+            Filter::FullyEvolved => {
+                pokedex().species.is_fully_evolved(species.id)
+            },
+        }
+    }
+
+    /// The species the filter is indexed by, if any.
+    fn index(&self) -> Option<&'static [SpeciesId]> {
+        let species = &pokedex().species;
+        match *self {
+            Filter::Color(color) => Some(species.by_color(color)),
+            Filter::Shape(shape) => Some(species.by_shape(shape)),
+            Filter::Habitat(habitat) => Some(species.by_habitat(habitat)),
+            _ => None,
+        }
+    }
+}
+
+/// The starting points of queries.
+pub struct Select;
+
+impl Select {
+    /// Select species, with the fields of their default Pokémon.
+    pub fn species() -> SpeciesQuery {
+        SpeciesQuery::default()
+    }
+}
+
+/// A query for species, started with `Select::species`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SpeciesQuery {
+    filters: Vec<Filter>,
+    moves: Option<VersionGroup>,
+    stats: bool,
+}
+
+/// A species selected by a `SpeciesQuery`, with the fields it asked for.
+#[derive(Clone, Debug)]
+pub struct SpeciesResult {
+    pub id: SpeciesId,
+    pub name: &'static str,
+    /// The Veekun identifier of the species, e.g. `"mr-mime"`.
+    pub identifier: &'static str,
+    pub types: OneOrTwo<Type>,
+    /// The moves learned in the version group of `with_moves`, by any
+    /// method, in order of ID.
+    pub moves: Option<Vec<MoveId>>,
+    /// The base stats, with `with_stats`.
+    pub stats: Option<BaseStats>,
+}
+
+impl SpeciesResult {
+    /// Get a handle to the species in the global pokedex.
+    pub fn species(&self) -> SpeciesRef {
+        SpeciesRef::new(self.id).expect("query results are in the pokedex")
+    }
+}
+
+impl SpeciesQuery {
+    /// Only species matching a filter, as well as any earlier ones.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Include the moves learned in a version group in the results.
+    pub fn with_moves(mut self, version_group: VersionGroup) -> Self {
+        self.moves = Some(version_group);
+        self
+    }
+
+    /// Include the base stats in the results.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = true;
+        self
+    }
+
+    /// Run the query, getting the matching species lazily in order of ID.
+    pub fn run(&self) -> impl Iterator<Item = SpeciesResult> + '_ {
+        let table = &pokedex().species;
+        let ids: Box<dyn Iterator<Item = SpeciesId>> =
+            match self.filters.iter().find_map(Filter::index) {
+                Some(ids) => Box::new(ids.iter().copied()),
+                None => Box::new((0..table.len() as u16).map(SpeciesId)),
+            };
+        ids.filter_map(move |id| {
+            let species = &table[id];
+            let pokemon = table.pokemon(id).first()?;
+            if !self.filters.iter().all(|f| f.matches(species, pokemon)) {
+                return None;
+            }
+            Some(self.project(species, pokemon))
+        })
+    }
+
+    fn project(
+        &self, species: &'static Species, pokemon: &'static Pokemon
+    ) -> SpeciesResult {
+        let moves = self.moves.map(|version_group| {
+            let mut moves: Vec<_> = pokemon.learnset(version_group).iter()
+                .map(|m| m.move_id)
+                .collect();
+            moves.sort();
+            moves.dedup();
+            moves
+        });
+        SpeciesResult {
+            id: species.id,
+            name: &species.name,
+            identifier: &species.identifier,
+            types: pokemon.types,
+            moves,
+            stats: self.stats.then_some(pokemon.stats),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Seeded, constrained shuffles of species, moves, and items, as used by ROM
+//! randomizers.
+//!
+//! Each shuffle is a bijection: the IDs are split into groups that share a
+//! property (such as evolution stage), each group is sorted by a measure
+//! (such as base stat total) and cut into windows of `WINDOW` consecutive
+//! IDs, and each window is shuffled. So every ID maps to a distinct ID with
+//! the same property and a similar measure.
+//!
+//! Mappings are deterministic for a seed and version of vdex.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokedex;
+use crate::pokemon::{SpeciesId, SPECIES_COUNT};
+
+/// The number of IDs with similar measures that are shuffled together.
+pub const WINDOW: usize = 8;
+
+/// A bijective mapping of IDs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Mapping<Id: Copy + Eq + Hash>(HashMap<Id, Id>);
+
+impl<Id: Copy + Eq + Hash> Mapping<Id> {
+    /// Get the ID an ID maps to; IDs outside the mapping map to themselves.
+    pub fn get(&self, id: Id) -> Id {
+        self.0.get(&id).cloned().unwrap_or(id)
+    }
+
+    /// Get the mapping in the other direction.
+    pub fn inverse(&self) -> Self {
+        Mapping(self.0.iter().map(|(&from, &to)| (to, from)).collect())
+    }
+
+    /// Iterate over the pairs of IDs, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, Id)> + '_ {
+        self.0.iter().map(|(&from, &to)| (from, to))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Shuffle IDs within groups of the same key, in windows of similar measure.
+fn shuffle<Id, K, M>(
+    ids: impl Iterator<Item = Id>, key: impl Fn(Id) -> K,
+    measure: impl Fn(Id) -> M, rng: &mut impl Rng
+) -> Mapping<Id>
+where Id: Copy + Eq + Hash + Ord, K: Ord, M: Ord {
+    let mut groups: BTreeMap<K, Vec<Id>> = BTreeMap::new();
+    for id in ids {
+        groups.entry(key(id)).or_default().push(id);
+    }
+    let mut mapping = HashMap::new();
+    for group in groups.values_mut() {
+        group.sort_by_key(|&id| (measure(id), id));
+        for window in group.chunks(WINDOW) {
+            let mut shuffled = window.to_vec();
+            for i in (1..shuffled.len()).rev() {
+                shuffled.swap(i, rng.gen_range(0, i + 1));
+            }
+            mapping.extend(window.iter().cloned().zip(shuffled));
+        }
+    }
+    Mapping(mapping)
+}
+
+/// The number of times a species has evolved from its basic form.
+pub fn evolution_stage(id: SpeciesId) -> u8 {
+    let species = &pokedex().species;
+    let mut stage = 0;
+    let mut current = id;
+    while let Some(from) = species[current].evolves_from {
+        stage += 1;
+        current = from.from_id;
+    }
+    stage
+}
+
+/// Map each species to one with the same evolution stage and a similar base
+/// stat total.
+pub fn shuffle_species(seed: u64) -> Mapping<SpeciesId> {
+    let species = &pokedex().species;
+    let stat_total = |id: SpeciesId| species.pokemon(id).first()
+        .map_or(0, |p| p.stats.total());
+    let ids = (0..SPECIES_COUNT).map(|i| SpeciesId(i as u16));
+    shuffle(ids, evolution_stage, stat_total, &mut StdRng::seed_from_u64(seed))
+}
+
+/// Map each move to one with the same damage class and a similar power.
+pub fn shuffle_moves(seed: u64) -> Mapping<MoveId> {
+    let moves = &pokedex().moves;
+    shuffle(
+        moves.0.iter().map(|m| m.id), |id| moves[id].damage_class.repr(),
+        |id| moves[id].power, &mut StdRng::seed_from_u64(seed))
+}
+
+/// Map each item to one in the same category with a similar cost.
+pub fn shuffle_items(seed: u64) -> Mapping<ItemId> {
+    let items = &pokedex().items;
+    shuffle(
+        items.0.keys().cloned(), |id| items[id].category.repr(),
+        |id| items[id].cost, &mut StdRng::seed_from_u64(seed))
+}
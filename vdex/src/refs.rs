@@ -0,0 +1,191 @@
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use crate::moves::{Move, MoveId};
+use crate::pokedex;
+use crate::pokemon::{Pokemon, Species, SpeciesId};
+use crate::Pokedex;
+use crate::versions::VersionGroup;
+
+/// A cheap, copyable handle to a move in a pokedex that lives for the rest
+/// of the program, like the global one. It dereferences to the `Move`.
+///
+/// Handles are equal if they are of the same ID in the same pokedex.
+#[derive(Copy, Clone)]
+pub struct MoveRef {
+    id: MoveId,
+    dex: &'static Pokedex,
+}
+
+impl MoveRef {
+    /// Get a handle to a move in the global pokedex, if it has the move.
+    pub fn new(id: MoveId) -> Option<Self> {
+        Self::in_dex(pokedex(), id)
+    }
+
+    /// Get a handle to a move in a pokedex, if it has the move.
+    pub fn in_dex(dex: &'static Pokedex, id: MoveId) -> Option<Self> {
+        dex.moves.contains(id).then_some(MoveRef { id, dex })
+    }
+
+    /// Get a handle to a move in the global pokedex by Veekun identifier,
+    /// like `"karate-chop"`.
+    pub fn by_identifier(identifier: &str) -> Option<Self> {
+        Self::new(pokedex().moves.identifiers().id(identifier)?)
+    }
+
+    pub fn id(self) -> MoveId {
+        self.id
+    }
+
+    /// The pokedex the move is in.
+    pub fn pokedex(self) -> &'static Pokedex {
+        self.dex
+    }
+
+    /// Get the move itself, for as long as the pokedex lives.
+    pub fn get(self) -> &'static Move {
+        &self.dex.moves[self.id]
+    }
+
+    /// The other moves with the same effect, in order of ID.
+    pub fn same_effect(self) -> impl Iterator<Item = MoveRef> {
+        let MoveRef { id, dex } = self;
+        dex.moves.with_effect(self.get().effect).iter()
+            .filter(move |&&other| other != id)
+            .map(move |&other| MoveRef { id: other, dex })
+    }
+}
+
+impl Deref for MoveRef {
+    type Target = Move;
+
+    fn deref(&self) -> &Move {
+        self.get()
+    }
+}
+
+impl PartialEq for MoveRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && std::ptr::eq(self.dex, other.dex)
+    }
+}
+
+impl Eq for MoveRef { }
+
+impl Hash for MoveRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Debug for MoveRef {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "MoveRef({}, {:?})", self.id.0, self.identifier)
+    }
+}
+
+/// A cheap, copyable handle to a species in a pokedex that lives for the
+/// rest of the program, like the global one. It dereferences to the
+/// `Species`.
+///
+/// Handles are equal if they are of the same ID in the same pokedex.
+#[derive(Copy, Clone)]
+pub struct SpeciesRef {
+    id: SpeciesId,
+    dex: &'static Pokedex,
+}
+
+impl SpeciesRef {
+    /// Get a handle to a species in the global pokedex, if it has the
+    /// species.
+    pub fn new(id: SpeciesId) -> Option<Self> {
+        Self::in_dex(pokedex(), id)
+    }
+
+    /// Get a handle to a species in a pokedex, if it has the species.
+    pub fn in_dex(dex: &'static Pokedex, id: SpeciesId) -> Option<Self> {
+        dex.species.contains(id).then_some(SpeciesRef { id, dex })
+    }
+
+    /// Get a handle to a species in the global pokedex by Veekun identifier,
+    /// like `"mr-mime"`.
+    pub fn by_identifier(identifier: &str) -> Option<Self> {
+        Self::new(pokedex().species.identifiers().id(identifier)?)
+    }
+
+    pub fn id(self) -> SpeciesId {
+        self.id
+    }
+
+    /// The pokedex the species is in.
+    pub fn pokedex(self) -> &'static Pokedex {
+        self.dex
+    }
+
+    /// Get the species itself, for as long as the pokedex lives.
+    pub fn get(self) -> &'static Species {
+        &self.dex.species[self.id]
+    }
+
+    /// The Pokémon of the species; the first is the default.
+    pub fn pokemon(self) -> &'static [Pokemon] {
+        self.dex.species.pokemon(self.id)
+    }
+
+    /// The default Pokémon of the species.
+    pub fn default_pokemon(self) -> &'static Pokemon {
+        &self.pokemon()[0]
+    }
+
+    /// The species this species evolves from, if any.
+    pub fn evolves_from(self) -> Option<SpeciesRef> {
+        let from = self.get().evolves_from?;
+        Some(SpeciesRef { id: from.from_id, dex: self.dex })
+    }
+
+    /// The species this species evolves into, in order of ID.
+    pub fn evolves_into(self) -> impl Iterator<Item = SpeciesRef> {
+        let dex = self.dex;
+        dex.species.evolves_into(self.id).iter()
+            .map(move |&id| SpeciesRef { id, dex })
+    }
+
+    /// The moves the default Pokémon learns in a version group, once for
+    /// each way it learns them, in the order of its learnset.
+    pub fn learnable_moves(
+        self, version_group: VersionGroup
+    ) -> impl Iterator<Item = MoveRef> {
+        let dex = self.dex;
+        self.default_pokemon().learnset(version_group).iter()
+            .map(move |m| MoveRef { id: m.move_id, dex })
+    }
+}
+
+impl Deref for SpeciesRef {
+    type Target = Species;
+
+    fn deref(&self) -> &Species {
+        self.get()
+    }
+}
+
+impl PartialEq for SpeciesRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && std::ptr::eq(self.dex, other.dex)
+    }
+}
+
+impl Eq for SpeciesRef { }
+
+impl Hash for SpeciesRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Debug for SpeciesRef {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "SpeciesRef({}, {:?})", self.id.0, self.identifier)
+    }
+}
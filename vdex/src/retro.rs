@@ -0,0 +1,156 @@
+//! Stat mechanics of Generations I and II.
+//!
+//! Before Generation III, stats were calculated from determinant values (DVs,
+//! 0–15) and Stat Experience (0–65535) instead of IVs and EVs, and Generation
+//! I had a single Special stat in place of Special Attack and Special Defense.
+//!
+//! vdex only contains Generation V base stats. For Generation I, the Special
+//! base stat is taken to be the Special Attack base stat.
+
+use crate::pokemon::{BaseStats, PERMANENT_STATS};
+use crate::Enum;
+use crate::Stat;
+use crate::versions::Generation;
+
+/// Determinant values, the Generation I and II equivalent of IVs.
+///
+/// There is no HP DV; it is derived from the lowest bits of the others. The
+/// Special DV applies to both Special Attack and Special Defense.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DVs {
+    pub attack: u8,
+    pub defense: u8,
+    pub speed: u8,
+    pub special: u8,
+}
+
+impl DVs {
+    /// The maximum value of a DV.
+    pub const MAX: u8 = 15;
+
+    /// The HP DV, built from the lowest bit of each of the other DVs.
+    pub fn hp(self) -> u8 {
+        ((self.attack & 1) << 3) | ((self.defense & 1) << 2)
+            | ((self.speed & 1) << 1) | (self.special & 1)
+    }
+
+    /// Get the DV for a permanent stat.
+    pub fn get(self, stat: Stat) -> u8 {
+        match stat {
+            Stat::HP => self.hp(),
+            Stat::Attack => self.attack,
+            Stat::Defense => self.defense,
+            Stat::SpecialAttack | Stat::SpecialDefense => self.special,
+            Stat::Speed => self.speed,
+            Stat::Accuracy | Stat::Evasion => 0,
+        }
+    }
+}
+
+/// Stat Experience, the Generation I and II equivalent of EVs.
+///
+/// As with DVs, there is a single Special value.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StatExp {
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub speed: u16,
+    pub special: u16,
+}
+
+impl StatExp {
+    /// Get the Stat Experience for a permanent stat.
+    pub fn get(self, stat: Stat) -> u16 {
+        match stat {
+            Stat::HP => self.hp,
+            Stat::Attack => self.attack,
+            Stat::Defense => self.defense,
+            Stat::SpecialAttack | Stat::SpecialDefense => self.special,
+            Stat::Speed => self.speed,
+            Stat::Accuracy | Stat::Evasion => 0,
+        }
+    }
+}
+
+/// True if the generation calculates stats from DVs and Stat Experience.
+pub fn uses_retro_stats(generation: Generation) -> bool {
+    generation <= Generation::II
+}
+
+/// The stat bonus from Stat Experience: ⌊min(255, ⌊√(*exp* − 1)⌋ + 1) / 4⌋.
+fn stat_exp_bonus(exp: u16) -> u32 {
+    let root = (exp.saturating_sub(1) as f64).sqrt() as u32;
+    (root + 1).min(255) / 4
+}
+
+/// Calculate a single permanent stat with the Generation I and II formula.
+pub fn calc_stat(
+    stat: Stat, base: u8, dv: u8, stat_exp: u16, level: u8
+) -> u16 {
+    let core = (base as u32 + dv as u32) * 2 + stat_exp_bonus(stat_exp);
+    let value = core * level as u32 / 100;
+    match stat {
+        Stat::HP => (value + level as u32 + 10) as u16,
+        _ => (value + 5) as u16,
+    }
+}
+
+/// Calculate all permanent stats for a Pokémon in Generation I or II.
+///
+/// The result is ordered like `BaseStats`. In Generation I, Special Defense
+/// is the merged Special stat and therefore always equals Special Attack.
+/// Returns `None` for generations that do not use this stat system.
+pub fn calc_stats(
+    generation: Generation, base: &BaseStats, dvs: DVs, stat_exp: StatExp,
+    level: u8
+) -> Option<[u16; PERMANENT_STATS]> {
+    if !uses_retro_stats(generation) {
+        return None;
+    }
+    let mut stats = [0; PERMANENT_STATS];
+    for (i, &stat) in Stat::VALUES[..PERMANENT_STATS].iter().enumerate() {
+        let base_stat = match (generation, stat) {
+            (Generation::I, Stat::SpecialDefense)
+                => base[Stat::SpecialAttack],
+            _ => base[stat],
+        };
+        stats[i] = calc_stat(
+            stat, base_stat, dvs.get(stat), stat_exp.get(stat), level);
+    }
+    Some(stats)
+}
+
+/// The Generation I critical hit threshold: a hit is critical if a random
+/// byte is less than the threshold, so the chance is *threshold* / 256.
+///
+/// The threshold is based on the attacker's base Speed. High critical hit
+/// ratio moves multiply it by 8 (capped at 255), and, due to a bug, Focus
+/// Energy divides it by 4 instead of multiplying it.
+pub fn critical_threshold(
+    base_speed: u8, high_critical: bool, focus_energy: bool
+) -> u8 {
+    let mut threshold = (base_speed / 2) as u16;
+    if focus_energy {
+        threshold /= 2;
+    } else {
+        threshold = (threshold * 2).min(255);
+    }
+    if high_critical {
+        threshold = (threshold * 2).min(255);
+        threshold = (threshold * 2).min(255);
+    } else {
+        threshold /= 2;
+    }
+    threshold as u8
+}
+
+/// The level used in the Generation I damage formula, which is doubled on a
+/// critical hit.
+pub fn critical_level(level: u8, critical: bool) -> u16 {
+    if critical {
+        level as u16 * 2
+    } else {
+        level as u16
+    }
+}
@@ -0,0 +1,139 @@
+//! The linear congruential RNGs of Generations III to V.
+//!
+//! Each call advances the RNG by one frame. Tools search the frames after a
+//! seed for one that generates a wanted Pokémon, then hit that frame in game.
+
+use crate::pokemon::PERMANENT_STATS;
+use crate::{Enum, Nature};
+
+macro_rules! lcrng {
+    (
+        $(#[$attr:meta])* $name:ident($seed:ty) -> $output:ty,
+        $mult:expr, $add:expr
+    ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+        pub struct $name {
+            pub seed: $seed,
+            /// The number of frames advanced since the initial seed.
+            pub frame: u64,
+        }
+
+        impl $name {
+            pub const MULTIPLIER: $seed = $mult;
+            pub const INCREMENT: $seed = $add;
+
+            pub fn new(seed: $seed) -> Self {
+                $name { seed, frame: 0 }
+            }
+
+            /// Advance one frame and get the upper half of the new seed.
+            #[allow(clippy::should_implement_trait)]
+            pub fn next(&mut self) -> $output {
+                self.seed = self.seed.wrapping_mul(Self::MULTIPLIER)
+                    .wrapping_add(Self::INCREMENT);
+                self.frame += 1;
+                (self.seed >> (<$output>::BITS)) as $output
+            }
+
+            /// Advance a number of frames at once, in time logarithmic in the
+            /// number of frames.
+            pub fn advance(&mut self, frames: u64) {
+                let (mut mult, mut add) = (Self::MULTIPLIER, Self::INCREMENT);
+                let (mut total_mult, mut total_add): ($seed, $seed) = (1, 0);
+                let mut remaining = frames;
+                while remaining > 0 {
+                    if remaining & 1 == 1 {
+                        total_mult = total_mult.wrapping_mul(mult);
+                        total_add = total_add.wrapping_mul(mult)
+                            .wrapping_add(add);
+                    }
+                    add = add.wrapping_mul(mult.wrapping_add(1));
+                    mult = mult.wrapping_mul(mult);
+                    remaining >>= 1;
+                }
+                self.seed = self.seed.wrapping_mul(total_mult)
+                    .wrapping_add(total_add);
+                self.frame += frames;
+            }
+        }
+    };
+}
+
+lcrng! {
+    /// The RNG of Generations III and IV, which generates wild and
+    /// stationary Pokémon.
+    Lcrng32(u32) -> u16, 0x41c6_4e6d, 0x6073
+}
+
+lcrng! {
+    /// The RNG of Generation V, which generates natures, PIDs, and most
+    /// other random values but IVs.
+    Lcrng64(u64) -> u32, 0x5d58_8b65_6c07_8965, 0x0026_9ec3
+}
+
+impl Lcrng32 {
+    /// Go back one frame.
+    pub fn previous(&mut self) {
+        self.seed = self.seed.wrapping_mul(0xeeb9_eb65)
+            .wrapping_add(0x0a35_61a1);
+        self.frame = self.frame.wrapping_sub(1);
+    }
+}
+
+impl Lcrng64 {
+    /// Advance one frame and get a number below `bound`, as the games scale
+    /// the upper half of the seed.
+    pub fn next_below(&mut self, bound: u32) -> u32 {
+        ((self.next() as u64 * bound as u64) >> 32) as u32
+    }
+
+    /// Advance one frame and get the nature it determines.
+    pub fn next_nature(&mut self) -> Nature {
+        Nature::VALUES[self.next_below(Nature::COUNT as u32) as usize]
+    }
+}
+
+/// The Pokémon a frame generates.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Spread {
+    /// The frame of the RNG before the Pokémon is generated.
+    pub frame: u64,
+    pub pid: u32,
+    pub nature: Nature,
+    /// IVs, in the order of `BaseStats`.
+    pub ivs: [u8; PERMANENT_STATS],
+}
+
+/// Unpack three IVs from the lowest 15 bits of an RNG output.
+fn unpack_ivs(output: u16) -> [u8; 3] {
+    [0, 5, 10].map(|shift| ((output >> shift) & 0x1f) as u8)
+}
+
+/// Generate a Pokémon by Method 1, used for stationary Pokémon in
+/// Generations III and IV: the lower then upper half of the PID, then two
+/// outputs with three IVs each. The RNG advances four frames.
+pub fn method1(rng: &mut Lcrng32) -> Spread {
+    let frame = rng.frame;
+    let pid = rng.next() as u32 | (rng.next() as u32) << 16;
+    let [hp, attack, defense] = unpack_ivs(rng.next());
+    let [speed, special_attack, special_defense] = unpack_ivs(rng.next());
+    Spread {
+        frame,
+        pid,
+        nature: Nature::VALUES[(pid % Nature::COUNT as u32) as usize],
+        ivs: [hp, attack, defense, speed, special_attack, special_defense],
+    }
+}
+
+/// The Method 1 spreads of consecutive frames from a seed, starting at
+/// frame `start`.
+pub fn method1_frames(seed: u32, start: u64) -> impl Iterator<Item = Spread> {
+    let mut rng = Lcrng32::new(seed);
+    rng.advance(start);
+    std::iter::from_fn(move || {
+        let spread = method1(&mut rng.clone());
+        rng.next();
+        Some(spread)
+    })
+}
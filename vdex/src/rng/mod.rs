@@ -0,0 +1,3 @@
+//! The random number generators of the games, for RNG manipulation tools.
+
+pub mod lcrng;
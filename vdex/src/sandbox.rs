@@ -0,0 +1,195 @@
+//! A small synthetic Pokedex for fast tests, with the `sandbox` feature.
+
+use std::collections::HashMap;
+use crate::Ability;
+use crate::Efficacy;
+use crate::EfficacyTable;
+use crate::Enum;
+use crate::items::{self, ItemBuilder, ItemTable};
+use crate::moves::{Ailment, Category, DamageClass, Effect, Flags, LearnMethod};
+use crate::moves::{Meta, MoveBuilder, MoveTable, StatChanges, Target};
+use crate::Pokedex;
+use crate::pokemon::{BaseStats, Color, GrowthRate, OneOrTwo, PokemonBuilder};
+use crate::pokemon::{PokemonMove, Shape, Species, SpeciesTable};
+use crate::Type;
+use crate::versions::{Generation, VersionGroup};
+
+use self::Type::*;
+
+/// An attacking type, with the types it is super effective against, not very
+/// effective against, and has no effect on.
+type ChartRow = (Type, &'static [Type], &'static [Type], &'static [Type]);
+
+/// The Generation V type chart.
+const TYPE_CHART: [ChartRow; Type::COUNT] = [
+    (Normal, &[], &[Rock, Steel], &[Ghost]),
+    (Fighting, &[Normal, Rock, Steel, Ice, Dark],
+     &[Flying, Poison, Bug, Psychic], &[Ghost]),
+    (Flying, &[Fighting, Bug, Grass], &[Rock, Steel, Electric], &[]),
+    (Poison, &[Grass], &[Poison, Ground, Rock, Ghost], &[Steel]),
+    (Ground, &[Poison, Rock, Steel, Fire, Electric], &[Bug, Grass],
+     &[Flying]),
+    (Rock, &[Flying, Bug, Fire, Ice], &[Fighting, Ground, Steel], &[]),
+    (Bug, &[Grass, Psychic, Dark],
+     &[Fighting, Flying, Poison, Ghost, Steel, Fire], &[]),
+    (Ghost, &[Ghost, Psychic], &[Steel, Dark], &[Normal]),
+    (Steel, &[Rock, Ice], &[Steel, Fire, Water, Electric], &[]),
+    (Fire, &[Bug, Steel, Grass, Ice], &[Rock, Fire, Water, Dragon], &[]),
+    (Water, &[Ground, Rock, Fire], &[Water, Grass, Dragon], &[]),
+    (Grass, &[Ground, Rock, Water],
+     &[Flying, Poison, Bug, Steel, Fire, Grass, Dragon], &[]),
+    (Electric, &[Flying, Water], &[Grass, Electric, Dragon], &[Ground]),
+    (Psychic, &[Fighting, Poison], &[Steel, Psychic], &[Dark]),
+    (Ice, &[Flying, Ground, Grass, Dragon], &[Steel, Fire, Water, Ice], &[]),
+    (Dragon, &[Dragon], &[Steel], &[]),
+    (Dark, &[Ghost, Psychic], &[Fighting, Steel, Dark], &[]),
+];
+
+fn efficacy() -> EfficacyTable {
+    let mut table = EfficacyTable::default();
+    for &(attack, sup, not_very, not) in TYPE_CHART.iter() {
+        let rows = [(sup, Efficacy::Super), (not_very, Efficacy::NotVery),
+                    (not, Efficacy::Not)];
+        for &(targets, efficacy) in rows.iter() {
+            for &target in targets {
+                table[(attack, target)] = efficacy;
+            }
+        }
+    }
+    table
+}
+
+fn moves() -> MoveTable {
+    let damage = |identifier, typ, power, class| MoveBuilder::new(identifier)
+        .typ(typ)
+        .power(power)
+        .pp(25)
+        .accuracy(Some(100))
+        .target(Target::SelectedPokemon)
+        .damage_class(class)
+        .effect(Effect::RegularDamage, None)
+        .flags(Flags::PROTECT | Flags::MIRROR);
+    let paralysis = Meta {
+        category: Category::DamageAilment,
+        ailment: Ailment::Paralysis,
+        ailment_chance: 10,
+        .. Default::default()
+    };
+    let growl = Meta {
+        category: Category::NetGoodStats,
+        stat_changes: StatChanges([-1, 0, 0, 0, 0, 0, 0]),
+        .. Default::default()
+    };
+    let mut table = MoveTable::empty();
+    for builder in [
+        damage("tackle", Normal, 50, DamageClass::Physical).pp(35)
+            .flags(Flags::CONTACT | Flags::PROTECT | Flags::MIRROR),
+        damage("quick-attack", Normal, 40, DamageClass::Physical).pp(30)
+            .priority(1)
+            .flags(Flags::CONTACT | Flags::PROTECT | Flags::MIRROR),
+        damage("thunderbolt", Electric, 95, DamageClass::Special).pp(15)
+            .effect(Effect::ChanceParalyzeTarget, Some(10))
+            .meta(paralysis),
+        damage("water-gun", Water, 40, DamageClass::Special),
+        damage("ember", Fire, 40, DamageClass::Special),
+        damage("vine-whip", Grass, 35, DamageClass::Physical).pp(15)
+            .flags(Flags::CONTACT | Flags::PROTECT | Flags::MIRROR),
+        MoveBuilder::new("growl").typ(Normal).pp(40).accuracy(Some(100))
+            .target(Target::AllOpponents)
+            .effect(Effect::LowerTargetAttack, None)
+            .meta(growl)
+            .flags(Flags::PROTECT | Flags::REFLECTABLE | Flags::MIRROR
+                   | Flags::SOUND),
+    ] {
+        table.insert_custom(builder).expect("sandbox moves are valid");
+    }
+    table
+}
+
+fn species(moves: &MoveTable) -> SpeciesTable {
+    let learnset = |identifiers: &[&str]| identifiers.iter()
+        .map(|&identifier| PokemonMove {
+            move_id: moves.identifiers().id(identifier)
+                .unwrap_or_default(),
+            learn_method: LearnMethod::LevelUp,
+            level: 1,
+        })
+        .collect();
+    let entries = [
+        ("pikachu", Color::Yellow, OneOrTwo::One(Electric),
+         [35, 55, 30, 90, 50, 40], Ability::Static,
+         ["thunderbolt", "quick-attack", "growl"]),
+        ("bulbasaur", Color::Green, OneOrTwo::Two(Grass, Poison),
+         [45, 49, 49, 45, 65, 65], Ability::Overgrow,
+         ["tackle", "vine-whip", "growl"]),
+        ("charmander", Color::Red, OneOrTwo::One(Fire),
+         [39, 52, 43, 65, 60, 50], Ability::Blaze,
+         ["tackle", "ember", "growl"]),
+        ("squirtle", Color::Blue, OneOrTwo::One(Water),
+         [44, 48, 65, 43, 50, 64], Ability::Torrent,
+         ["tackle", "water-gun", "growl"]),
+    ];
+    let mut table = SpeciesTable::empty();
+    for (identifier, color, types, stats, ability, known) in entries {
+        let mut species = Species::default();
+        species.name = crate::to_pascal_case(identifier);
+        species.identifier = identifier.to_string();
+        species.generation = Generation::I;
+        species.gender_rate = 1;
+        species.color = color;
+        species.shape = Shape::Upright;
+        species.base_happiness = 70;
+        species.hatch_counter = 20;
+        species.growth_rate = GrowthRate::MediumSlow;
+        let pokemon = PokemonBuilder::new(types, BaseStats(stats))
+            .abilities(OneOrTwo::One(ability))
+            .base_exp(64)
+            .learnset(VersionGroup::BlackWhite, learnset(&known));
+        table.insert_custom(species, vec![pokemon])
+            .expect("sandbox species are valid");
+    }
+    table
+}
+
+fn items() -> ItemTable {
+    let mut table = ItemTable::default();
+    table.insert_custom(ItemBuilder::new("leftovers")
+            .category(items::Category::HeldItems)
+            .cost(200)
+            .flags(items::Flags::HOLDABLE | items::Flags::HOLDABLE_ACTIVE))
+        .expect("sandbox items are valid");
+    table
+}
+
+impl Pokedex {
+    /// Create a small, synthetic Pokedex for tests, which is fast to create
+    /// and can be modified freely before it is used or installed.
+    ///
+    /// It has the Generation V type chart; a handful of moves, like
+    /// `"tackle"`, `"thunderbolt"`, and `"growl"`; the species `"pikachu"`,
+    /// `"bulbasaur"`, `"charmander"`, and `"squirtle"`, with one Pokémon each
+    /// that learns three of the moves in Black and White; and `"leftovers"`.
+    /// IDs are numbered from the start, not as in the Veekun data, so look
+    /// entities up by identifier. The other tables are empty.
+    pub fn sandbox() -> Self {
+        let moves = moves();
+        let species = species(&moves);
+        let mut dex = Pokedex {
+            assets: Default::default(),
+            efficacy: efficacy(),
+            facilities: Default::default(),
+            items: items(),
+            locations: Default::default(),
+            moves,
+            palace: Default::default(),
+            presentation: Default::default(),
+            prose: Default::default(),
+            species,
+            trainers: Default::default(),
+            fingerprint: 0,
+            extensions: HashMap::new(),
+        };
+        dex.items.relate_species(&dex.species);
+        dex
+    }
+}
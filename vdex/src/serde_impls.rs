@@ -0,0 +1,23 @@
+//! `Serialize` and `Deserialize` for sets of enums. The enums themselves
+//! are (de)serialized as their variant names, e.g. `"InnerFocus"`, by
+//! `#[EnumRepr(serde = true)]`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Sets of ailments are (de)serialized as lists of their names.
+impl Serialize for crate::moves::AilmentSet {
+    fn serialize<S: Serializer>(
+        &self, serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for crate::moves::AilmentSet {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        let ailments = Vec::<crate::moves::Ailment>::deserialize(deserializer)?;
+        Ok(ailments.into_iter().collect())
+    }
+}
@@ -0,0 +1,357 @@
+//! A compact, versioned binary format for a stored Pokémon, for exchanging
+//! Pokémon between tools built on vdex independently of any game's save data.
+//!
+//! The format is little-endian:
+//!
+//! | Bytes | Field                                                     |
+//! |-------|-----------------------------------------------------------|
+//! | 4     | Magic number, `b"vdxp"`                                   |
+//! | 1     | Format version, `FORMAT_VERSION`                          |
+//! | 2     | Species ID                                                |
+//! | 2     | Veekun form ID                                            |
+//! | 1     | Ability slot: 0, 1, or 2 for the hidden ability           |
+//! | 1     | Nature representation                                     |
+//! | 1     | Level                                                     |
+//! | 6     | IVs, in the order of `BaseStats`                          |
+//! | 6     | EVs, in the same order                                    |
+//! | 2     | Item ID, or 0 for no item                                 |
+//! | 1     | Number of moves, *n*                                      |
+//! | 3*n*  | Move ID (2 bytes) and PP Ups (1 byte) of each move        |
+//! | 1     | Length of the nickname in bytes, or 0 for no nickname     |
+//! | ...   | Nickname in UTF-8                                         |
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::Ability;
+use crate::enums::*;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::Nature;
+use crate::pokemon::{Form, OneOrTwo, Pokemon, SpeciesId, PERMANENT_STATS};
+use crate::Pokedex;
+use crate::Stat;
+
+/// The magic number at the start of a stored Pokémon.
+pub const MAGIC: &[u8; 4] = b"vdxp";
+
+/// The current version of the format, which `to_bytes` writes.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// The highest IV of a stat.
+pub const MAX_IV: u8 = 31;
+
+/// The highest total of a Pokémon's EVs.
+pub const MAX_TOTAL_EVS: u16 = 510;
+
+/// The most moves a Pokémon can know.
+pub const MAX_MOVES: usize = 4;
+
+/// The most PP Ups that can be applied to a move.
+pub const MAX_PP_UPS: u8 = 3;
+
+/// The longest nickname, in characters, as of Generation V.
+pub const MAX_NICKNAME_CHARS: usize = 10;
+
+/// Error decoding or validating a stored Pokémon.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The data ends before the end of the Pokémon.
+    Truncated,
+    /// The data does not start with `MAGIC`.
+    BadMagic,
+    /// The data is in a format version this library cannot read.
+    UnsupportedVersion(u8),
+    /// There is data after the end of the Pokémon.
+    TrailingData,
+    /// A field is out of range or inconsistent with the Pokedex.
+    Invalid {
+        /// The name of the field.
+        field: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Error::Truncated => write!(f, "Stored Pokémon is truncated."),
+            Error::BadMagic => write!(f, "Not a stored Pokémon."),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "Unsupported stored Pokémon version {}.", version)
+            },
+            Error::TrailingData => {
+                write!(f, "Unexpected data after stored Pokémon.")
+            },
+            Error::Invalid { field, reason } => {
+                write!(f, "Invalid {}: {}", field, reason)
+            },
+        }
+    }
+}
+
+impl StdError for Error { }
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+fn invalid<T>(field: &'static str, reason: &'static str) -> Result<T> {
+    Err(Error::Invalid { field, reason })
+}
+
+/// Which of a Pokémon's abilities it has.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum AbilitySlot {
+    #[default]
+    First,
+    Second,
+    Hidden,
+}
+
+/// A move known by a stored Pokémon.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StoredMove {
+    pub id: MoveId,
+    pub pp_ups: u8,
+}
+
+/// A Pokémon as stored outside of battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StoredPokemon {
+    pub species: SpeciesId,
+    /// The Veekun ID of the Pokémon's form.
+    pub form: u16,
+    pub ability: AbilitySlot,
+    pub nature: Nature,
+    pub level: u8,
+    /// IVs, in the order of `BaseStats`.
+    pub ivs: [u8; 6],
+    /// EVs, in the order of `BaseStats`.
+    pub evs: [u8; 6],
+    pub item: Option<ItemId>,
+    pub moves: Vec<StoredMove>,
+    pub nickname: Option<String>,
+}
+
+impl StoredPokemon {
+    /// Encode the Pokémon in the current format version.
+    ///
+    /// Moves after the first `MAX_MOVES` and nicknames longer than 255 bytes
+    /// are truncated; `validate` rejects such Pokémon.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&self.species.0.to_le_bytes());
+        bytes.extend_from_slice(&self.form.to_le_bytes());
+        bytes.push(match self.ability {
+            AbilitySlot::First => 0,
+            AbilitySlot::Second => 1,
+            AbilitySlot::Hidden => 2,
+        });
+        bytes.push(self.nature.repr());
+        bytes.push(self.level);
+        bytes.extend_from_slice(&self.ivs);
+        bytes.extend_from_slice(&self.evs);
+        let item = self.item.map_or(0, |item| item.0);
+        bytes.extend_from_slice(&item.to_le_bytes());
+        let moves = &self.moves[..self.moves.len().min(MAX_MOVES)];
+        bytes.push(moves.len() as u8);
+        for mov in moves {
+            bytes.extend_from_slice(&mov.id.0.to_le_bytes());
+            bytes.push(mov.pp_ups);
+        }
+        let nickname = self.nickname.as_deref().unwrap_or("");
+        let mut length = nickname.len().min(u8::MAX as usize);
+        while !nickname.is_char_boundary(length) {
+            length -= 1;
+        }
+        bytes.push(length as u8);
+        bytes.extend_from_slice(&nickname.as_bytes()[..length]);
+        bytes
+    }
+
+    /// Decode a Pokémon, checking its structure but not validating it against
+    /// the Pokedex (see `validate`).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(Error::BadMagic);
+        }
+        match reader.u8()? {
+            FORMAT_VERSION => (),
+            version => return Err(Error::UnsupportedVersion(version)),
+        }
+        let species = SpeciesId(reader.u16()?);
+        let form = reader.u16()?;
+        let ability = match reader.u8()? {
+            0 => AbilitySlot::First,
+            1 => AbilitySlot::Second,
+            2 => AbilitySlot::Hidden,
+            _ => return invalid("ability", "unknown slot"),
+        };
+        let nature = match Nature::from_repr(reader.u8()?) {
+            Some(nature) => nature,
+            None => return invalid("nature", "unknown nature"),
+        };
+        let level = reader.u8()?;
+        let mut ivs = [0; 6];
+        ivs.copy_from_slice(reader.take(6)?);
+        let mut evs = [0; 6];
+        evs.copy_from_slice(reader.take(6)?);
+        let item = match reader.u16()? {
+            0 => None,
+            id => Some(ItemId(id)),
+        };
+        let move_count = reader.u8()? as usize;
+        if move_count > MAX_MOVES {
+            return invalid("moves", "too many moves");
+        }
+        let mut moves = Vec::with_capacity(move_count);
+        for _ in 0..move_count {
+            let id = MoveId(reader.u16()?);
+            moves.push(StoredMove { id, pp_ups: reader.u8()? });
+        }
+        let nickname = match reader.u8()? as usize {
+            0 => None,
+            length => match std::str::from_utf8(reader.take(length)?) {
+                Ok(nickname) => Some(nickname.to_string()),
+                Err(_) => return invalid("nickname", "not UTF-8"),
+            },
+        };
+        if !reader.0.is_empty() {
+            return Err(Error::TrailingData);
+        }
+        Ok(StoredPokemon {
+            species, form, ability, nature, level, ivs, evs, item, moves,
+            nickname,
+        })
+    }
+
+    /// Get the Pokémon (as opposed to species) of the stored Pokémon's form.
+    pub fn pokemon<'a>(&self, dex: &'a Pokedex) -> Option<&'a Pokemon> {
+        if !dex.species.contains(self.species) {
+            return None;
+        }
+        dex.species.pokemon(self.species).iter()
+            .find(|p| p.forms.iter().any(|f| f.id == self.form))
+    }
+
+    /// Get the stored Pokémon's form.
+    pub fn form<'a>(&self, dex: &'a Pokedex) -> Option<&'a Form> {
+        self.pokemon(dex)?.forms.iter().find(|f| f.id == self.form)
+    }
+
+    /// Get the Pokémon's ability.
+    pub fn ability(&self, dex: &Pokedex) -> Option<Ability> {
+        let pokemon = self.pokemon(dex)?;
+        match (self.ability, pokemon.abilities) {
+            (AbilitySlot::First, abilities) => Some(abilities.first()),
+            (AbilitySlot::Second, OneOrTwo::Two(_, second)) => Some(second),
+            (AbilitySlot::Second, OneOrTwo::One(_)) => None,
+            (AbilitySlot::Hidden, _) => pokemon.hidden_ability,
+        }
+    }
+
+    /// Calculate the Pokémon's permanent stats with the formula of
+    /// Generation III on, ordered like `BaseStats`.
+    ///
+    /// The nature raises one stat by 10% and lowers another, and a Pokémon
+    /// with a base HP of 1, like Shedinja, always has 1 HP.
+    pub fn stats(&self, dex: &Pokedex) -> Option<[u16; PERMANENT_STATS]> {
+        let base = &self.pokemon(dex)?.stats;
+        let level = self.level as u32;
+        let mut stats = [0; PERMANENT_STATS];
+        for (i, &stat) in Stat::VALUES[..PERMANENT_STATS].iter().enumerate() {
+            let core = (2 * base[stat] as u32 + self.ivs[i] as u32
+                        + self.evs[i] as u32 / 4) * level / 100;
+            stats[i] = match stat {
+                Stat::HP if base[stat] == 1 => 1,
+                Stat::HP => (core + level + 10) as u16,
+                _ if self.nature.increased() == Some(stat)
+                    => ((core + 5) * 11 / 10) as u16,
+                _ if self.nature.decreased() == Some(stat)
+                    => ((core + 5) * 9 / 10) as u16,
+                _ => (core + 5) as u16,
+            };
+        }
+        Some(stats)
+    }
+
+    /// Check that the Pokémon is consistent with the Pokedex and the limits
+    /// of Generation V.
+    ///
+    /// This does not check whether the Pokémon can learn its moves.
+    pub fn validate(&self, dex: &Pokedex) -> Result<()> {
+        if !dex.species.contains(self.species) {
+            return invalid("species", "unknown species");
+        }
+        if self.pokemon(dex).is_none() {
+            return invalid("form", "not a form of the species");
+        }
+        if self.ability(dex).is_none() {
+            return invalid("ability", "the Pokémon has no such ability");
+        }
+        if !(1..=100).contains(&self.level) {
+            return invalid("level", "not between 1 and 100");
+        }
+        if self.ivs.iter().any(|&iv| iv > MAX_IV) {
+            return invalid("ivs", "IV above 31");
+        }
+        if self.evs.iter().map(|&ev| ev as u16).sum::<u16>() > MAX_TOTAL_EVS {
+            return invalid("evs", "total above 510");
+        }
+        if let Some(item) = self.item {
+            if !dex.items.0.contains_key(&item) {
+                return invalid("item", "unknown item");
+            }
+        }
+        if self.moves.is_empty() || self.moves.len() > MAX_MOVES {
+            return invalid("moves", "not between 1 and 4 moves");
+        }
+        for (i, mov) in self.moves.iter().enumerate() {
+            if !dex.moves.contains(mov.id) {
+                return invalid("moves", "unknown move");
+            }
+            if mov.pp_ups > MAX_PP_UPS {
+                return invalid("moves", "more than 3 PP Ups");
+            }
+            if self.moves[..i].iter().any(|m| m.id == mov.id) {
+                return invalid("moves", "duplicate move");
+            }
+        }
+        if let Some(ref nickname) = self.nickname {
+            let length = nickname.chars().count();
+            if length == 0 || length > MAX_NICKNAME_CHARS {
+                return invalid("nickname", "not between 1 and 10 characters");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads fields from the front of a byte slice.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8]> {
+        if self.0.len() < count {
+            return Err(Error::Truncated);
+        }
+        let (taken, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+}
@@ -0,0 +1,109 @@
+//! Summary statistics of a Pokedex, for cross-checking loaded data and for
+//! dashboards.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use crate::enums::*;
+use crate::moves::{DamageClass, PowerKind};
+use crate::pokemon::{SpeciesId, SPECIES_COUNT};
+use crate::Pokedex;
+use crate::Type;
+
+/// The width of the buckets of `DexStats::move_power_histogram`.
+pub const POWER_BUCKET: u8 = 20;
+
+/// Summary statistics of a Pokedex. See `Pokedex::stats`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DexStats {
+    pub species: usize,
+    /// Pokémon, including alternate Pokémon of a species such as Deoxys'
+    /// forms.
+    pub pokemon: usize,
+    pub forms: usize,
+    pub moves: usize,
+    pub items: usize,
+    /// Items that are berries.
+    pub berries: usize,
+    /// Species whose default Pokémon has each type, indexed by type
+    /// representation. Dual-type species count toward both types.
+    pub species_per_type: [usize; Type::COUNT],
+    /// The average base stat total of the species counted in
+    /// `species_per_type`, or 0 for types without species.
+    pub average_stat_total_per_type: [f64; Type::COUNT],
+    /// Moves of each damage class, indexed by representation.
+    pub moves_per_damage_class: [usize; DamageClass::COUNT],
+    /// Moves with a fixed power, by power rounded down to a multiple of
+    /// `POWER_BUCKET`.
+    pub move_power_histogram: BTreeMap<u8, usize>,
+}
+
+impl DexStats {
+    /// Compute the statistics of a Pokedex.
+    pub fn new(dex: &Pokedex) -> Self {
+        let mut stats = DexStats {
+            moves: dex.moves.0.len(),
+            items: dex.items.0.len(),
+            berries: dex.items.0.values().filter(|i| i.berry.is_some()).count(),
+            .. Default::default()
+        };
+        let mut stat_totals = [0u32; Type::COUNT];
+        for i in 0..SPECIES_COUNT {
+            let pokemon = dex.species.pokemon(SpeciesId(i as u16));
+            if pokemon.is_empty() {
+                continue;
+            }
+            stats.species += 1;
+            stats.pokemon += pokemon.len();
+            stats.forms += pokemon.iter()
+                .map(|p| p.forms.len())
+                .sum::<usize>();
+            let default = &pokemon[0];
+            let types = [Some(default.types.first()), default.types.second()];
+            for typ in types.iter().flatten() {
+                stats.species_per_type[typ.repr() as usize] += 1;
+                stat_totals[typ.repr() as usize] += default.stats.total() as u32;
+            }
+        }
+        for (i, &total) in stat_totals.iter().enumerate() {
+            let count = stats.species_per_type[i];
+            if count > 0 {
+                stats.average_stat_total_per_type[i] = total as f64 / count as f64;
+            }
+        }
+        for mov in &dex.moves.0 {
+            stats.moves_per_damage_class[mov.damage_class.repr() as usize] += 1;
+            if let PowerKind::Fixed(power) = mov.power_kind() {
+                let bucket = power / POWER_BUCKET * POWER_BUCKET;
+                *stats.move_power_histogram.entry(bucket).or_default() += 1;
+            }
+        }
+        stats
+    }
+}
+
+impl Display for DexStats {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "Species: {}", self.species)?;
+        writeln!(f, "Pokémon: {}", self.pokemon)?;
+        writeln!(f, "Forms: {}", self.forms)?;
+        writeln!(f, "Moves: {}", self.moves)?;
+        writeln!(f, "Items: {} ({} berries)", self.items, self.berries)?;
+        writeln!(f, "Species by type (average base stat total):")?;
+        for &typ in Type::VALUES.iter() {
+            let i = typ.repr() as usize;
+            writeln!(f, "  {:?}: {} ({:.1})", typ, self.species_per_type[i],
+                     self.average_stat_total_per_type[i])?;
+        }
+        writeln!(f, "Moves by damage class:")?;
+        for &class in DamageClass::VALUES.iter() {
+            writeln!(f, "  {:?}: {}",
+                     class, self.moves_per_damage_class[class.repr() as usize])?;
+        }
+        writeln!(f, "Moves by power:")?;
+        for (&bucket, &count) in &self.move_power_histogram {
+            writeln!(f, "  {}-{}: {}", bucket,
+                     bucket.saturating_add(POWER_BUCKET - 1), count)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,420 @@
+//! Teams of stored Pokémon, with rulesets to validate them against and the
+//! Pokémon Showdown text format for interop.
+//!
+//! With the `serde` feature, a `Team` serializes as a map with a `pokemon`
+//! list, so it works with formats that require a top-level table, like TOML,
+//! as well as JSON.
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Write};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use veekun::identifiers::to_display_name;
+use crate::Ability;
+use crate::enums::*;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::{Nature, NatureSet};
+use crate::pokedex;
+use crate::pokemon::{Form, SpeciesId, SPECIES_COUNT};
+use crate::pokemon::forms::FormFlags;
+use crate::stored::{self, AbilitySlot, StoredMove, StoredPokemon, MAX_IV};
+
+/// Showdown's names for the permanent stats, in the order of `BaseStats`.
+const STAT_NAMES: [&str; 6] = ["HP", "Atk", "Def", "Spe", "SpA", "SpD"];
+
+/// The order Showdown lists the stats in, as indices into `STAT_NAMES`.
+const SHOWDOWN_STAT_ORDER: [usize; 6] = [0, 1, 2, 4, 5, 3];
+
+/// Error validating or parsing a team.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A Pokémon of the team is invalid by itself.
+    Pokemon {
+        /// The index of the Pokémon in the team.
+        index: usize,
+        error: stored::Error,
+    },
+    /// The team breaks a rule of the ruleset.
+    Rule {
+        /// The index of the offending Pokémon, if a single Pokémon breaks the
+        /// rule.
+        index: Option<usize>,
+        rule: &'static str,
+    },
+    /// A line of Showdown text could not be parsed.
+    Parse {
+        /// The line number, starting at 1.
+        line: usize,
+        reason: &'static str,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Error::Pokemon { index, error } => {
+                write!(f, "Pokémon {}: {}", index + 1, error)
+            },
+            Error::Rule { index: Some(index), rule } => {
+                write!(f, "Pokémon {} breaks rule: {}", index + 1, rule)
+            },
+            Error::Rule { index: None, rule } => {
+                write!(f, "Team breaks rule: {}", rule)
+            },
+            Error::Parse { line, reason } => {
+                write!(f, "Error on line {}: {}", line, reason)
+            },
+        }
+    }
+}
+
+impl StdError for Error { }
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Rules a team must follow, beyond each Pokémon being valid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ruleset {
+    /// The most Pokémon a team can have.
+    pub max_pokemon: usize,
+    /// The highest level a Pokémon can have.
+    pub max_level: u8,
+    /// Whether each Pokémon must be of a different species.
+    pub species_clause: bool,
+    /// Whether each Pokémon must hold a different item.
+    pub item_clause: bool,
+    pub banned_species: Vec<SpeciesId>,
+    pub banned_abilities: Vec<Ability>,
+    pub banned_items: Vec<ItemId>,
+    pub banned_moves: Vec<MoveId>,
+    pub banned_natures: NatureSet,
+    /// The form flags a Pokémon's chosen form must not have. Only the form
+    /// the team is built with is checked, so a Pokémon may still change into
+    /// an excluded form in battle.
+    pub excluded_forms: FormFlags,
+}
+
+impl Default for Ruleset {
+    /// Up to six Pokémon up to level 100, with the species clause, and no
+    /// battle-only forms.
+    fn default() -> Self {
+        Ruleset {
+            max_pokemon: 6,
+            max_level: 100,
+            species_clause: true,
+            item_clause: false,
+            banned_species: Vec::new(),
+            banned_abilities: Vec::new(),
+            banned_items: Vec::new(),
+            banned_moves: Vec::new(),
+            banned_natures: NatureSet::new(),
+            excluded_forms: FormFlags::BATTLE_ONLY,
+        }
+    }
+}
+
+/// A team of Pokémon.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Team {
+    pub pokemon: Vec<StoredPokemon>,
+}
+
+impl Team {
+    /// Check that every Pokémon is valid against the global pokedex, and that
+    /// the team follows a ruleset.
+    pub fn validate(&self, rules: &Ruleset) -> Result<()> {
+        let dex = pokedex();
+        let rule = |index, rule| Err(Error::Rule { index, rule });
+        if self.pokemon.is_empty() {
+            return rule(None, "a team needs at least one Pokémon");
+        }
+        if self.pokemon.len() > rules.max_pokemon {
+            return rule(None, "too many Pokémon");
+        }
+        for (index, pokemon) in self.pokemon.iter().enumerate() {
+            pokemon.validate(dex)
+                .map_err(|error| Error::Pokemon { index, error })?;
+            let earlier = &self.pokemon[..index];
+            if pokemon.level > rules.max_level {
+                return rule(Some(index), "level too high");
+            }
+            if rules.species_clause
+                    && earlier.iter().any(|p| p.species == pokemon.species) {
+                return rule(Some(index), "species clause");
+            }
+            if rules.item_clause && pokemon.item.is_some()
+                    && earlier.iter().any(|p| p.item == pokemon.item) {
+                return rule(Some(index), "item clause");
+            }
+            if rules.banned_species.contains(&pokemon.species) {
+                return rule(Some(index), "banned species");
+            }
+            if pokemon.ability(dex)
+                    .is_some_and(|a| rules.banned_abilities.contains(&a)) {
+                return rule(Some(index), "banned ability");
+            }
+            if pokemon.item.is_some_and(|i| rules.banned_items.contains(&i)) {
+                return rule(Some(index), "banned item");
+            }
+            if pokemon.moves.iter()
+                    .any(|m| rules.banned_moves.contains(&m.id)) {
+                return rule(Some(index), "banned move");
+            }
+            if rules.banned_natures.contains(pokemon.nature) {
+                return rule(Some(index), "banned nature");
+            }
+            let form = pokemon.form(dex);
+            if form.is_some_and(
+                    |f| f.flags().intersects(rules.excluded_forms)) {
+                return rule(Some(index), "excluded form");
+            }
+            if form.and_then(Form::required_item)
+                    .is_some_and(|item| pokemon.item != Some(item)) {
+                return rule(Some(index), "form requires its held item");
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the team in the Pokémon Showdown text format, using names from
+    /// the global pokedex.
+    ///
+    /// The format has no PP Ups; `from_showdown` gives every move three.
+    /// Pokémon of unknown species and unknown moves, which a team that
+    /// passes `validate` does not have, are left out.
+    pub fn to_showdown(&self) -> String {
+        let mut text = String::new();
+        let known = self.pokemon.iter()
+            .filter(|pokemon| pokedex().species.contains(pokemon.species));
+        for pokemon in known {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            write_showdown(&mut text, pokemon).unwrap();
+        }
+        text
+    }
+
+    /// Parse a team in the Pokémon Showdown text format, using names from the
+    /// global pokedex. Names are matched ignoring case, spaces, and
+    /// punctuation.
+    ///
+    /// Lines the format allows but a stored Pokémon has no field for, such as
+    /// "Shiny: Yes", are ignored.
+    pub fn from_showdown(text: &str) -> Result<Self> {
+        let mut team = Team::default();
+        let mut current: Option<StoredPokemon> = None;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let parse_error = |reason| Error::Parse { line: i + 1, reason };
+            if line.is_empty() {
+                team.pokemon.extend(current.take());
+                continue;
+            }
+            let pokemon = match current {
+                Some(ref mut pokemon) => pokemon,
+                None => {
+                    current = Some(parse_header(line).ok_or_else(
+                        || parse_error("unknown species or item"))?);
+                    continue;
+                },
+            };
+            if let Some(name) = line.strip_prefix("- ") {
+                let id = find_move(name)
+                    .ok_or_else(|| parse_error("unknown move"))?;
+                pokemon.moves.push(StoredMove { id, pp_ups: 3 });
+            } else if let Some(name) = line.strip_prefix("Ability:") {
+                pokemon.ability = find_ability_slot(pokemon, name)
+                    .ok_or_else(|| parse_error("unknown ability"))?;
+            } else if let Some(level) = line.strip_prefix("Level:") {
+                pokemon.level = level.trim().parse()
+                    .map_err(|_| parse_error("invalid level"))?;
+            } else if let Some(spread) = line.strip_prefix("EVs:") {
+                pokemon.evs = parse_spread(spread, 0)
+                    .ok_or_else(|| parse_error("invalid EVs"))?;
+            } else if let Some(spread) = line.strip_prefix("IVs:") {
+                pokemon.ivs = parse_spread(spread, MAX_IV)
+                    .ok_or_else(|| parse_error("invalid IVs"))?;
+            } else if let Some(name) = line.strip_suffix(" Nature") {
+                pokemon.nature = Nature::VALUES.iter().cloned()
+                    .find(|n| squash(&format!("{:?}", n)) == squash(name))
+                    .ok_or_else(|| parse_error("unknown nature"))?;
+            }
+        }
+        team.pokemon.extend(current);
+        Ok(team)
+    }
+}
+
+/// Lowercase alphanumerics only, as in Showdown IDs, with gender symbols
+/// spelled out.
+pub(crate) fn squash(s: &str) -> String {
+    s.chars()
+        .flat_map(|c| match c {
+            '♀' => 'f'.to_lowercase(),
+            '♂' => 'm'.to_lowercase(),
+            c => c.to_lowercase(),
+        })
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+fn species_display_name(species: SpeciesId, form: u16) -> String {
+    let species = &pokedex().species[species];
+    let name = to_display_name(&species.name);
+    let form = pokedex().species.pokemon(species.id).iter()
+        .flat_map(|p| p.forms.iter())
+        .enumerate()
+        .find(|(_, f)| f.id == form);
+    match form {
+        Some((i, Form { name: Some(form_name), .. })) if i > 0
+            => format!("{}-{}", name, to_display_name(
+                &veekun::to_pascal_case(form_name))),
+        _ => name,
+    }
+}
+
+fn write_showdown(
+    text: &mut String, pokemon: &StoredPokemon
+) -> std::fmt::Result {
+    let dex = pokedex();
+    let species = species_display_name(pokemon.species, pokemon.form);
+    match pokemon.nickname {
+        Some(ref nickname) => write!(text, "{} ({})", nickname, species)?,
+        None => write!(text, "{}", species)?,
+    }
+    if let Some(item) = pokemon.item.and_then(|id| dex.items.0.get(&id)) {
+        write!(text, " @ {}", to_display_name(&item.name))?;
+    }
+    writeln!(text)?;
+    if let Some(ability) = pokemon.ability(dex) {
+        writeln!(text, "Ability: {}",
+                 to_display_name(&format!("{:?}", ability)))?;
+    }
+    if pokemon.level != 100 {
+        writeln!(text, "Level: {}", pokemon.level)?;
+    }
+    write_spread(text, "EVs", &pokemon.evs, 0)?;
+    writeln!(text, "{:?} Nature", pokemon.nature)?;
+    write_spread(text, "IVs", &pokemon.ivs, MAX_IV)?;
+    let moves = pokemon.moves.iter()
+        .filter_map(|mov| dex.moves.0.get(mov.id.0 as usize));
+    for mov in moves {
+        writeln!(text, "- {}", to_display_name(&mov.name))?;
+    }
+    Ok(())
+}
+
+fn write_spread(
+    text: &mut String, label: &str, values: &[u8; 6], default: u8
+) -> std::fmt::Result {
+    let parts: Vec<_> = SHOWDOWN_STAT_ORDER.iter()
+        .filter(|&&i| values[i] != default)
+        .map(|&i| format!("{} {}", values[i], STAT_NAMES[i]))
+        .collect();
+    if !parts.is_empty() {
+        writeln!(text, "{}: {}", label, parts.join(" / "))?;
+    }
+    Ok(())
+}
+
+/// Parse a spread like "4 HP / 252 SpA / 252 Spe"; unlisted stats get the
+/// default.
+fn parse_spread(spread: &str, default: u8) -> Option<[u8; 6]> {
+    let mut values = [default; 6];
+    for part in spread.split('/') {
+        let mut words = part.split_whitespace();
+        let value = words.next()?.parse().ok()?;
+        let stat = words.next()?;
+        let index = STAT_NAMES.iter().position(|&s| s == stat)?;
+        values[index] = value;
+    }
+    Some(values)
+}
+
+/// Parse a line like "Sparky (Pikachu) (F) @ Light Ball" into a Pokémon with
+/// Showdown's defaults: level 100, a Serious nature, and perfect IVs.
+fn parse_header(line: &str) -> Option<StoredPokemon> {
+    let (names, item) = match line.rfind(" @ ") {
+        Some(at) => (&line[..at], Some(find_item(&line[at + 3..])?)),
+        None => (line, None),
+    };
+    let names = names.trim_end_matches(" (M)").trim_end_matches(" (F)");
+    let (nickname, species) = match (names.rfind(" ("), names.ends_with(')')) {
+        (Some(open), true)
+            => (Some(&names[..open]), &names[open + 2..names.len() - 1]),
+        _ => (None, names),
+    };
+    let (species, form) = find_species(species)?;
+    Some(StoredPokemon {
+        species,
+        form,
+        nature: Nature::Serious,
+        level: 100,
+        ivs: [MAX_IV; 6],
+        item,
+        nickname: nickname.map(|n| n.to_string()),
+        .. Default::default()
+    })
+}
+
+/// Find a species and form by name, e.g. "Rotom-Wash".
+pub(crate) fn find_species(name: &str) -> Option<(SpeciesId, u16)> {
+    let table = &pokedex().species;
+    let all_species = (0..SPECIES_COUNT).map(|i| &table[SpeciesId(i as u16)]);
+    let key = squash(name);
+    for species in all_species {
+        let mut forms = table.pokemon(species.id).iter()
+            .flat_map(|p| p.forms.iter());
+        if squash(&species.name) == key {
+            return forms.next().map(|f| (species.id, f.id));
+        }
+        let form_key = match key.strip_prefix(&squash(&species.name)) {
+            Some(form_key) => form_key,
+            None => continue,
+        };
+        let form = forms.find(|f| f.name.as_deref()
+            .is_some_and(|form_name| squash(form_name) == form_key));
+        if let Some(form) = form {
+            return Some((species.id, form.id));
+        }
+    }
+    None
+}
+
+pub(crate) fn find_item(name: &str) -> Option<ItemId> {
+    let key = squash(name);
+    pokedex().items.0.values()
+        .find(|item| squash(&item.name) == key)
+        .map(|item| item.id)
+}
+
+/// Find a move by name, ignoring a Hidden Power type like "[Fire]".
+pub(crate) fn find_move(name: &str) -> Option<MoveId> {
+    let name = name.split(" [").next().unwrap_or(name);
+    let key = squash(name);
+    pokedex().moves.0.iter()
+        .find(|mov| squash(&mov.name) == key)
+        .map(|mov| mov.id)
+}
+
+pub(crate) fn find_ability(name: &str) -> Option<Ability> {
+    let key = squash(name);
+    Ability::VALUES.iter().cloned()
+        .find(|a| squash(&format!("{:?}", a)) == key)
+}
+
+fn find_ability_slot(
+    pokemon: &StoredPokemon, name: &str
+) -> Option<AbilitySlot> {
+    let key = squash(name);
+    [AbilitySlot::First, AbilitySlot::Second, AbilitySlot::Hidden].iter()
+        .cloned()
+        .find(|&slot| {
+            let candidate = StoredPokemon { ability: slot, ..pokemon.clone() };
+            candidate.ability(pokedex())
+                .is_some_and(|a| squash(&format!("{:?}", a)) == key)
+        })
+}
@@ -8,10 +8,40 @@ use crate::moves;
 use crate::pokemon;
 use crate::versions;
 
+#[cfg(not(feature = "no-embedded-data"))]
 use crate::Enum;
+#[cfg(not(feature = "no-embedded-data"))]
 use crate::pokedex;
 use crate::to_pascal_case;
 
+/// Always picks the highest number: no secondary effects or critical hits,
+/// maximum damage rolls, and 100% accurate moves always hit.
+#[cfg(not(feature = "no-embedded-data"))]
+struct Highest;
+
+#[cfg(not(feature = "no-embedded-data"))]
+impl crate::battle::BattleRng for Highest {
+    fn below(&mut self, bound: u32) -> u32 { bound - 1 }
+}
+
+/// Always picks 0: every chance of a secondary effect or critical hit
+/// succeeds, and damage rolls are the lowest.
+#[cfg(not(feature = "no-embedded-data"))]
+struct Lowest;
+
+#[cfg(not(feature = "no-embedded-data"))]
+impl crate::battle::BattleRng for Lowest {
+    fn below(&mut self, _: u32) -> u32 { 0 }
+}
+
+/// A level 50 Pokémon in battle with 100 in every stat, and no moves or item.
+fn monster(
+    species: u16, types: pokemon::OneOrTwo<Type>, ability: Ability
+) -> crate::battle::Monster {
+    crate::battle::Monster::new(
+        pokemon::SpeciesId(species), 50, types, ability, [100; 6])
+}
+
 #[test]
 fn assert_sanity() {
     assert_eq!(Ability::Teravolt.repr(), 164);
@@ -59,7 +89,2665 @@ fn check_pascal_case() {
     assert_eq!(to_pascal_case("master-ball"), "MasterBall");
 }
 
+#[test]
+fn identifier_conversions() {
+    use veekun::identifiers::*;
+    assert_eq!(to_kebab_case("MasterBall"), "master-ball");
+    assert_eq!(to_kebab_case("DataCard01"), "data-card-01");
+    assert_eq!(to_kebab_case("Tm01"), "tm01");
+    assert_eq!(to_display_name("MasterBall"), "Master Ball");
+    assert_eq!(to_display_name("MrMime"), "Mr. Mime");
+    assert_eq!(to_display_name("HoOh"), "Ho-Oh");
+    assert_eq!(to_display_name("Porygon2"), "Porygon2");
+    assert_eq!(to_display_name("Tm01"), "TM01");
+    for name in &["Mr. Mime", "MrMime", " mr  mime ", "mr_mime"] {
+        assert_eq!(normalize_identifier(name), "mr-mime");
+    }
+    assert_eq!(normalize_identifier("Nidoran ♀"), "nidoran-f");
+    assert_eq!(normalize_identifier("Farfetch'd"), "farfetchd");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn identifier_round_trips() {
+    use veekun::identifiers::{normalize_identifier, to_kebab_case};
+    let dex = pokedex();
+    let identifiers = dex.items.identifiers().iter().map(|(s, _)| s)
+        .chain(dex.moves.identifiers().iter().map(|(s, _)| s))
+        .chain(dex.species.identifiers().iter().map(|(s, _)| s));
+    for identifier in identifiers {
+        let name = to_pascal_case(identifier);
+        assert_eq!(to_kebab_case(&name), identifier);
+        assert_eq!(normalize_identifier(&name), identifier);
+        assert_eq!(normalize_identifier(identifier), identifier);
+    }
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
 #[test]
 fn load_pokedex() {
     pokedex();
 }
+
+#[cfg(feature = "external-data")]
+#[test]
+fn open_data_directory() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("veekun/data");
+    let dex = crate::Pokedex::open(&path).unwrap();
+    assert_eq!(dex.moves[moves::MoveId(0)].name, "Pound");
+    assert_eq!(dex.species[pokemon::SpeciesId(0)].name, "Bulbasaur");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn damage_class_by_generation() {
+    use crate::versions::Generation;
+    let crunch = &pokedex().moves[moves::MoveId(241)];
+    assert_eq!(crunch.name, "Crunch");
+    assert_eq!(crunch.damage_class_in(Generation::III),
+               moves::DamageClass::Special);
+    assert_eq!(crunch.damage_class_in(Generation::IV),
+               moves::DamageClass::Physical);
+}
+
+#[test]
+fn retro_stats() {
+    use crate::pokemon::BaseStats;
+    use crate::retro;
+    use crate::versions::Generation;
+    let dvs = retro::DVs { attack: 15, defense: 15, speed: 15, special: 15 };
+    let exp = retro::StatExp {
+        hp: 65535, attack: 65535, defense: 65535, speed: 65535,
+        special: 65535,
+    };
+    // Mewtwo
+    let base = BaseStats::new(106, 110, 90, 154, 90, 130);
+    let stats = retro::calc_stats(Generation::I, &base, dvs, exp, 100);
+    assert_eq!(stats, Some([415, 318, 278, 358, 406, 406]));
+    let stats = retro::calc_stats(Generation::II, &base, dvs, exp, 100);
+    assert_eq!(stats.unwrap()[5], 278);
+    assert!(retro::calc_stats(Generation::III, &base, dvs, exp, 100).is_none());
+    assert_eq!(retro::critical_threshold(130, false, false), 65);
+    assert_eq!(retro::critical_threshold(130, false, true), 16);
+    assert_eq!(retro::critical_threshold(130, true, false), 255);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn override_presentation() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    let pound = moves::MoveId(0);
+    assert_eq!(pokedex().presentation.get(pound).unwrap().sound, "pound");
+    let mut files = HashMap::new();
+    files.insert(String::from("move_presentation.csv"),
+                 b"move_id,animation,sound\n1,hit-small,thud\n".to_vec());
+    let source = Overlay { top: files, base: Embedded };
+    let table = moves::PresentationTable::load(&source).unwrap();
+    assert_eq!(table.get(pound).unwrap().animation, "hit-small");
+    assert!(table.get(moves::MoveId(1)).is_none());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn asset_keys() {
+    use crate::assets;
+    let rotom = &pokedex().species[pokemon::SpeciesId(478)];
+    let rotom_pokemon = pokedex().species.pokemon(rotom.id);
+    let wash = rotom_pokemon.iter()
+        .flat_map(|p| p.forms.iter())
+        .find(|f| f.name.as_deref() == Some("wash"))
+        .unwrap();
+    assert_eq!(assets::form_sprite_key(rotom, wash), "479-wash");
+    assert_eq!(assets::pokemon_sprite_key(rotom, &rotom_pokemon[0]), "479");
+    let master_ball = &pokedex().items[items::ItemId(1)];
+    assert_eq!(assets::item_icon_key(master_ball), "master-ball");
+    let mr_mime = assets::species_assets(&pokedex().species[pokemon::SpeciesId(121)]);
+    assert_eq!(mr_mime.footprint.as_deref(), Some("122"));
+    assert_eq!(mr_mime.cry, "mr-mime");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn override_species_assets() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    use crate::assets::SpeciesAssetTable;
+    assert!(SpeciesAssetTable::load(&Embedded).unwrap().0.is_empty());
+    let mut files = HashMap::new();
+    files.insert(String::from("species_assets.csv"),
+                 b"species_id,footprint,cry\n1,,bulba\n".to_vec());
+    let source = Overlay { top: files, base: Embedded };
+    let table = SpeciesAssetTable::load(&source).unwrap();
+    let bulbasaur = table.get(pokemon::SpeciesId(0)).unwrap();
+    assert_eq!(bulbasaur.footprint, None);
+    assert_eq!(bulbasaur.cry, "bulba");
+    assert!(table.get(pokemon::SpeciesId(1)).is_none());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn install_after_first_use() {
+    pokedex();
+    let dex = crate::Pokedex::new();
+    let returned = crate::install(dex).unwrap_err();
+    assert_eq!(returned.moves.0.len(), pokedex().moves.0.len());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn identifier_maps() {
+    let species = pokedex().species.identifiers();
+    assert_eq!(species.id("mr-mime"), Some(pokemon::SpeciesId(121)));
+    assert_eq!(species.identifier(pokemon::SpeciesId(121)), Some("mr-mime"));
+    assert_eq!(species.len(), pokemon::SPECIES_COUNT);
+    let items = pokedex().items.identifiers();
+    let data_card = items.id("data-card-01").unwrap();
+    assert_eq!(pokedex().items[data_card].name, "DataCard01");
+    assert_eq!(crate::assets::item_icon_key(&pokedex().items[data_card]),
+               "data-card-01");
+    let moves = pokedex().moves.identifiers();
+    assert_eq!(moves.id("karate-chop"), Some(moves::MoveId(1)));
+    assert!(moves.iter().all(|(s, id)| pokedex().moves[id].identifier == s));
+
+    let mut renamed: crate::IdentifierMap<_> =
+        [("pound", 0), ("tackle", 1), ("", 2)].iter().cloned().collect();
+    renamed.insert("pound-2", 0);
+    assert_eq!(renamed.id("pound"), None);
+    assert_eq!(renamed.identifier(0), Some("pound-2"));
+    renamed.insert("pound-2", 1);
+    assert_eq!(renamed.identifier(0), None);
+    assert_eq!(renamed.id("tackle"), None);
+    assert_eq!(renamed.len(), 1);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn moves_with_effect() {
+    let table = &pokedex().moves;
+    let ids = table.with_effect(moves::Effect::RegularDamage);
+    assert!(ids.contains(&moves::MoveId(0)));
+    assert!(ids.iter().all(|&id| table[id].effect == moves::Effect::RegularDamage));
+    let total: usize = moves::Effect::VALUES.iter()
+        .map(|&e| table.with_effect(e).len())
+        .sum();
+    assert_eq!(total, moves::MOVE_COUNT);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn berry_lists() {
+    use items::{BerryTable, Category, Flavor, ItemId};
+    let resisting = pokedex().items.berries_in_category(Category::TypeProtection);
+    assert_eq!(resisting.len(), 17);
+    // Occa Berry weakens a super-effective Fire move.
+    assert!(resisting.iter().any(|&id| pokedex().items[id].name == "OccaBerry"));
+    let berries = BerryTable::new();
+    // Cheri Berry is spicy.
+    assert_eq!(berries.by_flavor(Flavor::Spicy).first(), Some(&ItemId(126)));
+    assert!(Flavor::VALUES.iter().all(|&f| !berries.by_flavor(f).is_empty()));
+}
+
+#[test]
+fn flavor_preferences() {
+    use crate::FlavorPreference;
+    use items::Flavor;
+    // Lonely raises Attack and lowers Defense.
+    assert_eq!(Nature::Lonely.liked(), Some(Flavor::Spicy));
+    assert_eq!(Nature::Lonely.disliked(), Some(Flavor::Sour));
+    assert_eq!(Nature::Lonely.preference(Flavor::Spicy), FlavorPreference::Liked);
+    assert_eq!(Nature::Lonely.preference(Flavor::Sour), FlavorPreference::Disliked);
+    assert_eq!(Nature::Lonely.preference(Flavor::Dry), FlavorPreference::Neutral);
+    assert_eq!(Nature::Modest.increased(), Some(Stat::SpecialAttack));
+    assert_eq!(Nature::Modest.decreased(), Some(Stat::Attack));
+    let neutral = crate::flavor_matrix()[Nature::Quirky.repr() as usize];
+    assert!(neutral.iter().all(|&p| p == FlavorPreference::Neutral));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn item_groups() {
+    use items::{ItemGroup, ItemId};
+    let names = |group: ItemGroup| group.members().iter()
+        .map(|&id| pokedex().items[id].name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names(ItemGroup::Choice),
+               vec!["ChoiceBand", "ChoiceScarf", "ChoiceSpecs"]);
+    assert_eq!(names(ItemGroup::StatusOrbs), vec!["ToxicOrb", "FlameOrb"]);
+    assert_eq!(ItemGroup::Plates.members().len(), 16);
+    assert_eq!(ItemGroup::TypeGems.members().len(), 17);
+    assert_eq!(ItemGroup::Incenses.members().len(), 9);
+    assert_eq!(ItemGroup::EvolutionStones.members().len(), 9);
+    assert!(ItemGroup::EvolutionStones.members().contains(&ItemId(83)));
+    assert_eq!(ItemGroup::EvBerries.members().len(), 6);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn item_relations() {
+    use items::{ItemGroup, Machine, MachineKind};
+    use pokemon::SpeciesId;
+    let items = &pokedex().items;
+    let item = |identifier| &items[items.identifiers().id(identifier).unwrap()];
+    assert_eq!(item("tm01").as_machine(),
+               Some(Machine { kind: MachineKind::Technical, number: 1 }));
+    assert_eq!(item("hm06").as_machine(),
+               Some(Machine { kind: MachineKind::Hidden, number: 6 }));
+    assert_eq!(item("fire-stone").as_machine(), None);
+    for identifier in ["x", "éx", "tmx"] {
+        let odd = items::Item {
+            identifier: identifier.to_string(),
+            category: items::Category::Machines,
+            .. Default::default()
+        };
+        assert_eq!(odd.as_machine(), None);
+        assert_eq!(odd.sell_price(), 0);
+    }
+    assert!(item("cheri-berry").as_berry().is_some());
+    assert_eq!(item("fire-gem").as_jewel_type(), Some(Type::Fire));
+    assert!(ItemGroup::TypeGems.members().iter()
+        .all(|&id| items[id].as_jewel_type().is_some()));
+    assert_eq!(item("fire-stone").evolves_species(),
+               &[SpeciesId(37), SpeciesId(58), SpeciesId(135), SpeciesId(513)]);
+    assert!(ItemGroup::EvolutionStones.members().iter()
+        .all(|&id| !items[id].evolves_species().is_empty()));
+    assert!(item("leftovers").evolves_species().is_empty());
+}
+
+#[test]
+fn weather_modifiers() {
+    use moves::{Effect, Weather, WeatherModifier};
+    let in_sun: Vec<_> = Effect::WeatherBall.modifiers_in(Weather::Sun).collect();
+    assert_eq!(in_sun, vec![
+        WeatherModifier::Type(Type::Fire), WeatherModifier::Power(2, 1),
+    ]);
+    assert_eq!(Effect::Hurricane.modifiers_in(Weather::Rain).next(),
+               Some(WeatherModifier::Accuracy(None)));
+    assert!(Effect::RegularDamage.weather_modifiers().is_empty());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn environment_variants() {
+    use moves::{Environment, EnvironmentVariant, Effect, Terrain, Weather};
+    let moves = &pokedex().moves;
+    for &(_, variant) in Effect::NaturePower.terrain_variants() {
+        match variant {
+            EnvironmentVariant::CallsMove(id) => {
+                assert_eq!(moves[id].effect.terrain_variants().len(), 0)
+            },
+            _ => panic!("Nature Power should call a move"),
+        }
+    }
+    let in_marsh: Vec<_> = Effect::NaturePower
+        .variants_in(Environment::Terrain(Terrain::Marsh)).collect();
+    match in_marsh[..] {
+        [EnvironmentVariant::CallsMove(id)] => {
+            assert_eq!(moves[id].identifier, "mud-bomb")
+        },
+        _ => panic!("Nature Power should call one move in a marsh"),
+    }
+    assert_eq!(Effect::SecretPower
+                   .variants_in(Environment::Terrain(Terrain::Cave)).next(),
+               Some(EnvironmentVariant::Flinch(30)));
+    assert_eq!(Effect::WeatherBall
+                   .variants_in(Environment::Weather(Weather::Hail)).count(), 2);
+    assert_eq!(Effect::RegularDamage.environment_variants().count(), 0);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn call_pools() {
+    use moves::call_pools::*;
+    let ids = pokedex().moves.identifiers();
+    for list in [METRONOME_EXCLUDED, ASSIST_EXCLUDED, SLEEP_TALK_EXCLUDED] {
+        for identifier in list {
+            assert!(ids.id(identifier).is_some(), "{}", identifier);
+        }
+    }
+    let id = |identifier| ids.id(identifier).unwrap();
+    let pool = metronome_pool(versions::Generation::V);
+    assert!(pool.contains(&id("tackle")));
+    assert!(!pool.contains(&id("metronome")));
+    assert!(!metronome_pool(versions::Generation::IV).contains(&id("scald")));
+    let known = [id("tackle"), id("solarbeam"), id("sleep-talk"), id("snore")];
+    let pool: Vec<_> = sleep_talk_pool(known.iter().cloned()).into_iter()
+        .collect();
+    assert_eq!(pool, vec![id("tackle"), id("snore")]);
+    assert!(assist_pool(known.iter().cloned()).contains(&id("solarbeam")));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn changeable_stats() {
+    use moves::ChangeableStat;
+    assert!(ChangeableStat::new(Stat::HP).is_none());
+    let attack = ChangeableStat::new(Stat::Attack).unwrap();
+    let swords_dance = &pokedex().moves[moves::MoveId(13)];
+    let changes = swords_dance.meta.stat_changes;
+    assert_eq!(changes[attack], 2);
+    assert_eq!(changes.iter().collect::<Vec<_>>(), vec![(attack, 2)]);
+}
+
+#[test]
+fn base_stat_helpers() {
+    let base = pokemon::BaseStats::new(106, 110, 90, 154, 90, 130);
+    assert_eq!(base.0, [106, 110, 90, 130, 154, 90]);
+    assert_eq!(base.special_attack(), 154);
+    assert_eq!(base.speed(), 130);
+    assert_eq!(base.total(), 680);
+    assert_eq!(base.highest(), Stat::SpecialAttack);
+    assert_eq!(base.lowest(), Stat::Defense);
+    assert_eq!(base.ranked(), [
+        Stat::SpecialAttack, Stat::Speed, Stat::Attack, Stat::HP,
+        Stat::Defense, Stat::SpecialDefense,
+    ]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn gender_ratio_display() {
+    let species = &pokedex().species;
+    let bulbasaur = species[pokemon::SpeciesId(0)].gender_ratio();
+    assert_eq!(bulbasaur.to_string(), "87.5% male / 12.5% female");
+    assert_eq!(species[pokemon::SpeciesId(80)].gender_ratio().to_string(),
+               "Genderless");
+    assert_eq!(pokemon::GenderRatio::FemaleEighths(4).to_string(),
+               "50% male / 50% female");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn search_prose() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    let bite = moves::MoveId(43);
+    let effect = pokedex().moves[bite].effect;
+    let mut files = HashMap::new();
+    files.insert(String::from("ability_prose.csv"), b"\
+ability_id,local_language_id,short_effect,effect
+39,9,Prevents [flinching]{mechanic:flinching}.,Cannot flinch.
+".to_vec());
+    files.insert(String::from("move_effect_prose.csv"), format!("\
+move_effect_id,local_language_id,short_effect,effect
+{},9,Has a $effect_chance% chance to make the target flinch.,Flinch.
+{},5,Peut apeurer la cible.,Apeure.
+", effect.repr(), effect.repr()).into_bytes());
+    let dex = crate::Pokedex::load(&Overlay { top: files, base: Embedded })
+        .unwrap();
+    let hits = dex.search_text("Flinch");
+    assert_eq!(hits[0], crate::prose::SearchHit::Ability(Ability::InnerFocus));
+    assert!(hits.contains(&crate::prose::SearchHit::Move(bite)));
+    assert_eq!(dex.search_text("target flinch").len(), hits.len() - 1);
+    assert!(dex.search_text("mechanic").is_empty());
+    assert!(dex.search_text("apeurer").is_empty());
+    assert!(pokedex().search_text("flinch").is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_events() {
+    use crate::battle::{Event, Position};
+    let target = Position { side: 1, slot: 0 };
+    let events = vec![
+        Event::StatStageChanged { target, stat: Stat::Attack, change: -1, stage: -1 },
+        Event::AilmentInflicted { target, ailment: moves::Ailment::Burn },
+        Event::ItemConsumed { holder: target, item: items::ItemId(0) },
+        Event::Fainted { target },
+    ];
+    let json = serde_json::to_string(&events).unwrap();
+    assert!(json.ends_with(r#"{"event":"Fainted","target":{"side":1,"slot":0}}]"#));
+    assert!(json.contains(r#""stat":"Attack""#));
+    let parsed: Vec<Event> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, events);
+    assert!(serde_json::from_str::<Stat>(r#""Charisma""#).is_err());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn species_filters() {
+    use pokemon::{Color, Habitat, Shape, SpeciesId};
+    let species = &pokedex().species;
+    let pikachu = &species[SpeciesId(24)];
+    assert_eq!((pikachu.color, pikachu.shape, pikachu.habitat),
+               (Color::Yellow, Shape::Quadruped, Some(Habitat::Forest)));
+    assert_eq!(species[SpeciesId(493)].habitat, None);
+    assert!(species.by_color(Color::Yellow).contains(&SpeciesId(24)));
+    assert!(species.by_shape(Shape::Quadruped).contains(&SpeciesId(24)));
+    assert!(species.by_habitat(Habitat::Forest).contains(&SpeciesId(24)));
+    let total: usize = Color::VALUES.iter()
+        .map(|&color| species.by_color(color).len()).sum();
+    assert_eq!(total, pokemon::SPECIES_COUNT);
+    let habitats: usize = Habitat::VALUES.iter()
+        .map(|&habitat| species.by_habitat(habitat).len()).sum();
+    assert_eq!(habitats, 386);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn evolution_conditions() {
+    use pokemon::{Gender, SpeciesId};
+    let species = &pokedex().species;
+    let raichu = species[SpeciesId(25)].evolves_from.unwrap();
+    assert_eq!(raichu.from_id, SpeciesId(24));
+    assert_eq!((raichu.level, raichu.gender, raichu.move_id), (None, None, None));
+    let mamoswine = species[SpeciesId(472)].evolves_from.unwrap();
+    assert_eq!(mamoswine.move_id, Some(moves::MoveId(245)));
+    assert!(mamoswine.move_id.unwrap().is_valid());
+    let froslass = species[SpeciesId(477)].evolves_from.unwrap();
+    assert_eq!(froslass.gender, Some(Gender::Female));
+    assert_eq!(species[SpeciesId(2)].evolves_from.unwrap().level, Some(32));
+    assert!(!moves::MoveId::default().is_valid());
+    assert!(!SpeciesId::default().is_valid());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn stored_pokemon() {
+    use crate::stored::*;
+    let pikachu = StoredPokemon {
+        species: pokemon::SpeciesId(24),
+        form: 25,
+        ability: AbilitySlot::Hidden,
+        nature: Nature::Timid,
+        level: 50,
+        ivs: [31; 6],
+        evs: [4, 0, 0, 252, 252, 0],
+        item: Some(items::ItemId(213)),
+        moves: vec![
+            StoredMove { id: moves::MoveId(84), pp_ups: 3 },
+            StoredMove { id: moves::MoveId(97), pp_ups: 0 },
+        ],
+        nickname: Some("Sparky".to_string()),
+    };
+    assert_eq!(pikachu.validate(pokedex()), Ok(()));
+    assert_eq!(pikachu.ability(pokedex()), Some(Ability::Lightningrod));
+    let bytes = pikachu.to_bytes();
+    assert_eq!(&bytes[..5], b"vdxp\x01");
+    assert_eq!(StoredPokemon::from_bytes(&bytes), Ok(pikachu.clone()));
+    assert_eq!(StoredPokemon::from_bytes(&bytes[..bytes.len() - 1]),
+               Err(Error::Truncated));
+    let mut invalid = pikachu;
+    invalid.evs = [255; 6];
+    assert!(matches!(invalid.validate(pokedex()),
+                     Err(Error::Invalid { field: "evs", .. })));
+    invalid.form = 1;
+    assert!(matches!(invalid.validate(pokedex()),
+                     Err(Error::Invalid { field: "form", .. })));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn showdown_teams() {
+    use crate::team::*;
+    let text = "\
+Sparky (Pikachu) @ Light Ball
+Ability: Lightningrod
+Level: 50
+EVs: 4 HP / 252 SpA / 252 Spe
+Timid Nature
+IVs: 0 Atk
+- Thunderbolt
+- Hidden Power [Ice]
+
+Rotom-Wash @ Leftovers
+Ability: Levitate
+Bold Nature
+- Hydro Pump
+";
+    let team = Team::from_showdown(text).unwrap();
+    assert_eq!(team.pokemon.len(), 2);
+    assert_eq!(team.pokemon[0].evs, [4, 0, 0, 252, 252, 0]);
+    assert_eq!(team.pokemon[0].ivs, [31, 0, 31, 31, 31, 31]);
+    assert_eq!(team.pokemon[1].species, pokemon::SpeciesId(478));
+    assert_eq!(team.to_showdown(), text.replace(" [Ice]", ""));
+    assert_eq!(team.validate(&Ruleset::default()), Ok(()));
+    let rules = Ruleset { max_level: 5, .. Default::default() };
+    assert_eq!(team.validate(&rules),
+               Err(Error::Rule { index: Some(0), rule: "level too high" }));
+    let rules = Ruleset {
+        banned_natures: [Nature::Bold].iter().cloned().collect(),
+        .. Default::default()
+    };
+    assert_eq!(team.validate(&rules),
+               Err(Error::Rule { index: Some(1), rule: "banned nature" }));
+    assert!(matches!(Team::from_showdown("Pikachu\n- Not A Move"),
+                     Err(Error::Parse { line: 2, .. })));
+
+    let mut unknown = team.clone();
+    unknown.pokemon.push(Default::default());
+    unknown.pokemon[1].moves.push(crate::stored::StoredMove {
+        id: moves::MoveId(u16::MAX), pp_ups: 0
+    });
+    assert_eq!(unknown.to_showdown(), team.to_showdown());
+}
+
+#[cfg(all(feature = "serde", not(feature = "no-embedded-data")))]
+#[test]
+fn serialize_teams() {
+    use crate::team::Team;
+    let team = Team::from_showdown("Pikachu @ Light Ball\n- Thunderbolt").unwrap();
+    let json = serde_json::to_string(&team).unwrap();
+    assert_eq!(serde_json::from_str::<Team>(&json).unwrap(), team);
+    let toml = toml::to_string(&team).unwrap();
+    assert_eq!(toml::from_str::<Team>(&toml).unwrap(), team);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn dex_stats() {
+    let stats = pokedex().stats();
+    assert_eq!(stats.species, pokemon::SPECIES_COUNT);
+    assert!(stats.pokemon > stats.species && stats.forms >= stats.pokemon);
+    assert_eq!(stats.moves, moves::MOVE_COUNT);
+    assert_eq!(stats.moves_per_damage_class.iter().sum::<usize>(), stats.moves);
+    assert_eq!(stats.berries, items::BERRY_COUNT);
+    assert!(stats.species_per_type.iter().all(|&n| n > 0));
+    assert!(stats.average_stat_total_per_type[Type::Dragon.repr() as usize]
+            > stats.average_stat_total_per_type[Type::Bug.repr() as usize]);
+    assert!(stats.to_string().starts_with("Species: 649\n"));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn random_species() {
+    use crate::gen::*;
+    let dragonite = pokemon::SpeciesId(148);
+    let constraints = Constraints {
+        generation: Some(versions::Generation::I),
+        typ: Some(Type::Dragon),
+        fully_evolved: true,
+        .. Default::default()
+    };
+    assert_eq!(candidates(&constraints), vec![dragonite]);
+    let mut rng = rand::thread_rng();
+    assert_eq!(random_pokemon(&mut rng, &constraints), Some(dragonite));
+    assert_eq!(pokedex().species.evolves_into(pokemon::SpeciesId(146)),
+               &[pokemon::SpeciesId(147)]);
+    let impossible = Constraints { stat_total: Some(0..=100), .. Default::default() };
+    assert_eq!(random_pokemon(&mut rng, &impossible), None);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn smogon_usage_text() {
+    use crate::interop::smogon_usage::UsageTable;
+    let usage = " Total battles: 1000
+ + ---- + ------------------ + --------- + ------ + ------- +
+ | Rank | Pokemon            | Usage %   | Raw    | %       |
+ + ---- + ------------------ + --------- + ------ + ------- +
+ | 1    | Garchomp           | 25.12345% | 2500   | 20.123% |
+ | 2    | Rotom-Wash         | 10.00000% | 1000   | 9.000%  |
+ | 3    | Fakemon            |  1.00000% | 10     | 1.000%  |
+ + ---- + ------------------ + --------- + ------ + ------- +
+";
+    let moveset = " +----------------------------------------+
+ | Garchomp                               |
+ +----------------------------------------+
+ | Raw count: 2500                        |
+ | Avg. weight: 0.5                       |
+ +----------------------------------------+
+ | Abilities                              |
+ | Sand Veil 19.877%                      |
+ | Rough Skin 80.123%                     |
+ +----------------------------------------+
+ | Items                                  |
+ | Choice Scarf 40.000%                   |
+ | Other 60.000%                          |
+ +----------------------------------------+
+ | Moves                                  |
+ | Earthquake 99.000%                     |
+ | Outrage 80.000%                        |
+ +----------------------------------------+
+ | Teammates                              |
+ | Tyranitar +5.123%                      |
+ +----------------------------------------+
+";
+    let mut table = UsageTable::default();
+    table.add_usage_text(usage).unwrap();
+    table.add_moveset_text(moveset).unwrap();
+    let garchomp = pokemon::SpeciesId(444);
+    let rotom = pokemon::SpeciesId(478);
+    assert_eq!(table.ranked(), vec![garchomp, rotom]);
+    let stats = table.get(garchomp).unwrap();
+    assert_eq!(stats.raw_count, 2500);
+    assert_eq!(stats.abilities[0], (Ability::RoughSkin, 80.123));
+    assert_eq!(stats.items.len(), 1);
+    assert_eq!(stats.moves[0], (moves::MoveId(88), 99.0));
+    assert_eq!(table.get(rotom).unwrap().form, 708);
+    let missing_percent = " +--+\n | Garchomp |\n +--+\n | Moves |\n | Earthquake |";
+    assert!(table.add_moveset_text(missing_percent).is_err());
+}
+
+#[cfg(all(feature = "smogon-json", not(feature = "no-embedded-data")))]
+#[test]
+fn smogon_usage_json() {
+    use crate::interop::smogon_usage::{Error, UsageTable};
+    let json = r#"{"info": {"number of battles": 1000}, "data": {
+        "Garchomp": {"usage": 0.25, "Raw count": 2500,
+            "Abilities": {"roughskin": 75.0, "sandveil": 25.0},
+            "Items": {"choicescarf": 40.0, "nothing": 1.0},
+            "Moves": {"earthquake": 99.0, "": 10.0}}}}"#;
+    let table = UsageTable::from_chaos_json(json).unwrap();
+    let stats = table.get(pokemon::SpeciesId(444)).unwrap();
+    assert_eq!(stats.usage, 25.0);
+    assert_eq!(stats.abilities,
+               vec![(Ability::RoughSkin, 75.0), (Ability::SandVeil, 25.0)]);
+    assert_eq!(stats.items, vec![(items::ItemId(264), 40.0)]);
+    assert_eq!(stats.moves, vec![(moves::MoveId(88), 99.0)]);
+    assert!(matches!(UsageTable::from_chaos_json("{}"),
+                     Err(Error::MissingField("data"))));
+    assert!(matches!(UsageTable::from_chaos_json("{"), Err(Error::Json(_))));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn pokedex_extensions() {
+    let mut dex = crate::Pokedex::new();
+    let tiers = vec![("Garchomp", "OU")];
+    assert_eq!(dex.register_extension("tiers", tiers.clone()), None);
+    assert_eq!(dex.extension::<Vec<(&str, &str)>>("tiers"), Some(&tiers));
+    assert_eq!(dex.extension::<Vec<(&str, &str)>>("usage"), None);
+    assert_eq!(dex.extension::<u32>("tiers"), None);
+    assert_eq!(dex.register_extension("tiers", Vec::new()), Some(tiers));
+}
+
+#[test]
+fn csv_error_context() {
+    use std::collections::HashMap;
+    let file = veekun::data::files::EFFICACY;
+    let data = "damage_type_id,target_type_id,damage_factor\n\
+                1,1,100\n\
+                1,2,300\n";
+    let mut source = HashMap::new();
+    source.insert(file.to_string(), data.as_bytes().to_vec());
+    let error = match crate::EfficacyTable::load(&source) {
+        Err(error) => error,
+        Ok(_) => panic!("loaded an invalid damage factor"),
+    };
+    assert_eq!(error.line(), Some(3));
+    let message = format!("{}", error);
+    assert!(message.starts_with("type_efficacy.csv line 3, field 2: "));
+    assert!(format!("{:#}", error).ends_with("\n    1,2,300"));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn shared_learnsets() {
+    use crate::versions::VersionGroup;
+    let all_pokemon = pokedex().species.all_pokemon();
+    let learnsets: Vec<_> = all_pokemon.iter()
+        .flat_map(|p| p.moves.values())
+        .collect();
+    let storage: std::collections::HashSet<_> = learnsets.iter()
+        .map(|l| l.as_ptr())
+        .collect();
+    assert!(storage.len() < learnsets.len());
+    for pokemon in all_pokemon {
+        for a in pokemon.moves.values() {
+            for b in pokemon.moves.values() {
+                assert_eq!(a == b, a.shares_storage(b));
+            }
+        }
+    }
+    let bulbasaur = &all_pokemon[0];
+    let moves = bulbasaur.learnset(VersionGroup::BlackWhite);
+    assert_eq!(moves, &bulbasaur.moves[&VersionGroup::BlackWhite][..]);
+    let victini = &pokedex().species.pokemon(pokemon::SpeciesId(493))[0];
+    assert!(victini.learnset(VersionGroup::RedBlue).is_empty());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn data_info() {
+    let info = crate::about();
+    assert_eq!(info.revision, Some("d667024937d9bd61e4377af56f98f186ea64c44b"));
+    assert_eq!(info.generations, versions::Generation::I..=versions::Generation::V);
+    assert_eq!(info.tables.len(), veekun::data::files::ALL.len());
+    assert_eq!(info.records(veekun::data::files::SPECIES), Some(pokemon::SPECIES_COUNT));
+    assert!(info.license.unwrap().contains("Permission is hereby granted"));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn randomizer_mappings() {
+    use crate::randomizer::*;
+    use std::collections::HashSet;
+    let species = shuffle_species(42);
+    assert_eq!(species, shuffle_species(42));
+    assert_eq!(species.len(), pokemon::SPECIES_COUNT);
+    let targets: HashSet<_> = species.iter().map(|(_, to)| to).collect();
+    assert_eq!(targets.len(), pokemon::SPECIES_COUNT);
+    assert!(species.iter().all(|(from, to)| evolution_stage(from) == evolution_stage(to)));
+    let moves = shuffle_moves(7);
+    let table = &pokedex().moves;
+    assert!(moves.iter().all(|(from, to)| table[from].damage_class == table[to].damage_class));
+    let items = shuffle_items(7);
+    let id = items::ItemId(1);
+    assert_eq!(items.inverse().get(items.get(id)), id);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn whole_dex_analysis() {
+    use crate::analysis;
+    let profiles = analysis::defensive_profiles();
+    assert_eq!(profiles.len(), analysis::all_pokemon().len());
+    // Bulbasaur: Grass/Poison.
+    assert_eq!(profiles[0].modifier(Type::Psychic), 2.0);
+    assert_eq!(profiles[0].modifier(Type::Grass), 0.25);
+    let weaknesses = profiles[0].weaknesses();
+    assert_eq!(weaknesses.len(), 4);
+    assert!(weaknesses.contains(Type::Psychic));
+    assert!(weaknesses.intersection(profiles[0].resistances()).is_empty());
+    let matrix = analysis::coverage_matrix();
+    assert_eq!(matrix[(Type::Ice, Type::Ground)], matrix[(Type::Ground, Type::Ice)]);
+    assert!(matrix[(Type::Ice, Type::Ground)] >= matrix[(Type::Ice, Type::Ice)]);
+    let tiers = analysis::speed_tiers();
+    // Shuckle and Munchlax have the lowest base Speed, 5.
+    assert_eq!(tiers.keys().next(), Some(&5));
+    #[cfg(feature = "rayon")]
+    {
+        assert_eq!(analysis::par_coverage_matrix(), matrix);
+        assert_eq!(analysis::par_defensive_profiles().len(), profiles.len());
+    }
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn resolve_single_turn() {
+    use crate::battle::*;
+    use pokemon::OneOrTwo;
+
+    let electric = monster(24, OneOrTwo::One(Type::Electric), Ability::Static);
+    let mut normal = monster(18, OneOrTwo::One(Type::Normal), Ability::Guts);
+    normal.stats[3] = 50;
+    normal.item = Some(items::ItemId(132));
+    let sides = [
+        Side::new(vec![electric]),
+        Side::new(vec![normal]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let actions = [
+        Action::Move { user: b, move_id: moves::MoveId(13), target: a },
+        Action::Move { user: a, move_id: moves::MoveId(84), target: b },
+    ];
+    let events = resolve_turn(&mut state, &actions, &mut Highest);
+    assert_eq!(events, vec![
+        Event::MoveUsed { user: a, move_id: moves::MoveId(84), target: Some(b) },
+        Event::DamageDealt { target: b, amount: 64, hp: 36, critical: false },
+        Event::ItemConsumed { holder: b, item: items::ItemId(132) },
+        Event::Healed { target: b, amount: 10, hp: 46 },
+        Event::MoveUsed { user: b, move_id: moves::MoveId(13), target: Some(b) },
+        Event::StatStageChanged {
+            target: b, stat: Stat::Attack, change: 2, stage: 2,
+        },
+    ]);
+    assert_eq!(state.monster(b).staged_stat(Stat::Attack), 200);
+    assert_eq!(state.turn, 1);
+
+    let evasion = moves::ChangeableStat::new(Stat::Evasion).unwrap();
+    assert_eq!(state.monster(b).stat(Stat::Evasion), 0);
+    assert_eq!(state.monster(b).staged_stat(Stat::Evasion), 100);
+    state.monster_mut(b).stages[evasion] = 1;
+    assert_eq!(state.monster(b).staged_stat(Stat::Evasion), 133);
+    assert_eq!(state.monster(b).staged_stat(Stat::HP), 100);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn residual_damage_in_turns() {
+    use crate::analysis::residual_damage;
+    use crate::battle::*;
+    use moves::Ailment;
+    use pokemon::OneOrTwo;
+
+    let normal = |ability| {
+        let mut normal = monster(0, OneOrTwo::One(Type::Normal), ability);
+        normal.ailment = Ailment::Burn;
+        normal
+    };
+    let sides = [
+        Side::new(vec![normal(Ability::MagicGuard)]),
+        Side::new(vec![normal(Ability::Guts)]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let events = resolve_turn(&mut state, &[], &mut Highest);
+    assert_eq!(events, [
+        Event::DamageDealt { target: b, amount: 12, hp: 88, critical: false },
+    ]);
+    assert_eq!(state.monster(a).hp, 100);
+    assert_eq!(residual_damage(state.monster(a), None), 0);
+
+    for side in 0..2 {
+        let monster = state.monster_mut(state.active(side));
+        monster.ailment = Ailment::None;
+        monster.volatile.insert(Ailment::Trap);
+        monster.bound_turns = 1;
+    }
+    let events = resolve_turn(&mut state, &[], &mut Highest);
+    assert_eq!(events, [
+        Event::AilmentCured { target: a, ailment: Ailment::Trap },
+        Event::DamageDealt { target: b, amount: 6, hp: 82, critical: false },
+        Event::AilmentCured { target: b, ailment: Ailment::Trap },
+    ]);
+    assert_eq!(state.monster(a).hp, 100);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn state_dependent_damage() {
+    use crate::battle::*;
+    use pokemon::OneOrTwo;
+
+    let guts =
+        |species, typ| monster(species, OneOrTwo::One(typ), Ability::Guts);
+    let sides = [
+        Side::new(vec![guts(0, Type::Normal)]),
+        Side::new(vec![guts(3, Type::Fighting), guts(6, Type::Water)]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let (tackle, counter) = (moves::MoveId(32), moves::MoveId(67));
+    let actions = [
+        Action::Move { user: b, move_id: counter, target: a },
+        Action::Move { user: a, move_id: tackle, target: b },
+    ];
+    let events = resolve_turn(&mut state, &actions, &mut Highest);
+    assert_eq!(events[1], Event::DamageDealt {
+        target: b, amount: 36, hp: 64, critical: false,
+    });
+    assert_eq!(events[3], Event::DamageDealt {
+        target: a, amount: 72, hp: 28, critical: false,
+    });
+    // Nothing to counter on the next turn.
+    let events = resolve_turn(&mut state, &actions[..1], &mut Highest);
+    assert_eq!(events[1], Event::MoveFailed { user: b, move_id: counter });
+    // Charmander (52 base Attack) and Squirtle (48).
+    assert_eq!(effects::beat_up_powers(&state, b), vec![10, 9]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn trapping() {
+    use crate::battle::*;
+    use crate::battle::trapping::{SwitchVerdict, TrapReason};
+    use pokemon::OneOrTwo;
+
+    let normal = |ability| monster(0, OneOrTwo::One(Type::Normal), ability);
+    let sides = [
+        Side::new(vec![normal(Ability::Guts), normal(Ability::Guts)]),
+        Side::new(vec![normal(Ability::ShadowTag)]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    assert_eq!(state.can_switch(0),
+               SwitchVerdict::Trapped(TrapReason::Ability(Ability::ShadowTag)));
+    let switch = [Action::Switch { side: 0, slot: 1 }];
+    assert!(resolve_turn(&mut state, &switch, &mut Highest).is_empty());
+    // Mean Look still traps the opponent's Shadow Tag Pokémon.
+    let (a, b) = (state.active(0), state.active(1));
+    let mean_look = [Action::Move { user: a, move_id: moves::MoveId(211), target: b }];
+    resolve_turn(&mut state, &mean_look, &mut Highest);
+    assert_eq!(state.can_switch(1), SwitchVerdict::Trapped(TrapReason::CannotEscape));
+    state.monster_mut(a).item = Some(items::ItemId(272));
+    assert_eq!(state.can_switch(0), SwitchVerdict::Allowed);
+    let events = resolve_turn(&mut state, &switch, &mut Highest);
+    assert_eq!(events, vec![Event::SwitchedIn { target: Position { side: 0, slot: 1 } }]);
+    // Leaving the field ends the trapping of Mean Look.
+    assert!(state.can_switch(1).is_allowed());
+}
+
+#[test]
+fn apply_ailments() {
+    use crate::battle::ailments::{try_apply, ApplyResult, FieldCtx};
+    use moves::{Ailment, Flags};
+    use pokemon::OneOrTwo;
+
+    let mut monster = monster(
+        0, OneOrTwo::Two(Type::Grass, Type::Poison), Ability::Limber);
+    let ctx = FieldCtx::default();
+    assert_eq!(try_apply(&mut monster, Ailment::Poison, &ctx),
+               ApplyResult::TypeImmune(Type::Poison));
+    assert_eq!(try_apply(&mut monster, Ailment::Paralysis, &ctx),
+               ApplyResult::AbilityImmune(Ability::Limber));
+    assert_eq!(try_apply(&mut monster, Ailment::Nightmare, &ctx),
+               ApplyResult::Failed);
+    let safeguard = FieldCtx { safeguard: true, .. ctx };
+    assert_eq!(try_apply(&mut monster, Ailment::Sleep, &safeguard),
+               ApplyResult::Safeguard);
+    let rest = FieldCtx { self_inflicted: true, .. safeguard };
+    assert!(try_apply(&mut monster, Ailment::Sleep, &rest).is_applied());
+    assert_eq!(try_apply(&mut monster, Ailment::Burn, &ctx),
+               ApplyResult::AlreadyAffected(Ailment::Sleep));
+    assert!(try_apply(&mut monster, Ailment::Nightmare, &ctx).is_applied());
+    let substitute = FieldCtx { substitute: true, .. ctx };
+    assert_eq!(try_apply(&mut monster, Ailment::Confusion, &substitute),
+               ApplyResult::Substitute);
+    let authentic = FieldCtx { flags: Flags::AUTHENTIC, .. substitute };
+    assert!(try_apply(&mut monster, Ailment::Confusion, &authentic).is_applied());
+    assert_eq!(try_apply(&mut monster, Ailment::Confusion, &ctx),
+               ApplyResult::AlreadyAffected(Ailment::Confusion));
+    assert_eq!(monster.volatile.len(), 2);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn move_restrictions() {
+    use crate::battle::*;
+    use crate::battle::restrictions::*;
+    use moves::{Ailment, MoveId};
+    use pokemon::OneOrTwo;
+
+    let (tackle, growl, taunt) = (MoveId(32), MoveId(44), MoveId(268));
+    let knowing = |moves: &[MoveId]| {
+        let mut knowing =
+            monster(0, OneOrTwo::One(Type::Normal), Ability::Guts);
+        knowing.moves =
+            moves.iter().map(|&id| MoveSlot { id, pp: 10 }).collect();
+        knowing
+    };
+    let mut user = knowing(&[tackle, growl]);
+    assert_eq!(allowed_moves(&user, &[]), MoveMask(0b11));
+    assert!(!impose(&mut user, Restriction::Disable));
+    record_move(&mut user, tackle);
+    assert!(impose(&mut user, Restriction::Disable));
+    assert!(user.volatile.contains(Ailment::Disable));
+    assert_eq!(allowed_moves(&user, &[]), MoveMask(0b10));
+    assert!(impose(&mut user, Restriction::Taunt));
+    assert!(allowed_moves(&user, &[]).is_empty());
+    assert!(end_turn(&mut user).is_empty());
+    assert!(end_turn(&mut user).is_empty());
+    assert_eq!(end_turn(&mut user), [Restriction::Taunt]);
+    assert_eq!(end_turn(&mut user), [Restriction::Disable]);
+    assert!(user.volatile.is_empty());
+    let mut imprisoner = knowing(&[growl]);
+    imprisoner.restrictions.imprisoning = true;
+    assert_eq!(allowed_moves(&user, &[&imprisoner]), MoveMask(0b01));
+
+    let sides = [
+        Side::new(vec![knowing(&[tackle, growl])]),
+        Side::new(vec![knowing(&[taunt])]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    resolve_turn(&mut state, &[Action::Move { user: b, move_id: taunt, target: a }],
+                 &mut Highest);
+    let events = resolve_turn(
+        &mut state, &[Action::Move { user: a, move_id: growl, target: b }],
+        &mut Highest);
+    assert_eq!(events, [Event::MoveFailed { user: a, move_id: growl }]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn substitutes_and_screens() {
+    use crate::battle::*;
+    use crate::battle::barriers::*;
+    use crate::battle::damage::calc_damage;
+    use moves::MoveId;
+    use pokemon::OneOrTwo;
+
+    let (tackle, reflect, substitute) = (MoveId(32), MoveId(114), MoveId(163));
+    let knowing = |moves: &[MoveId]| {
+        let mut knowing =
+            monster(0, OneOrTwo::One(Type::Normal), Ability::Guts);
+        knowing.moves =
+            moves.iter().map(|&id| MoveSlot { id, pp: 10 }).collect();
+        knowing
+    };
+    let sides = [
+        Side::new(vec![knowing(&[tackle])]),
+        Side::new(vec![knowing(&[reflect, substitute])]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let use_move = |user, move_id, target| [Action::Move { user, move_id, target }];
+    let unscreened = calc_damage(&state, a, b, &pokedex().moves[tackle], false, 100);
+    resolve_turn(&mut state, &use_move(b, reflect, a), &mut Highest);
+    assert_eq!(state.sides[1].screens.turns(Screen::Reflect), SCREEN_TURNS - 1);
+    let screened = calc_damage(&state, a, b, &pokedex().moves[tackle], false, 100);
+    assert_eq!(screened, unscreened / 2);
+    assert_eq!(calc_damage(&state, a, b, &pokedex().moves[tackle], true, 100),
+               unscreened * 2);
+
+    let events = resolve_turn(&mut state, &use_move(b, substitute, b), &mut Highest);
+    assert!(events.contains(&Event::SubstituteMade { target: b, hp: 25 }));
+    assert_eq!(state.monster(b).hp, 75);
+    let events = resolve_turn(&mut state, &use_move(a, tackle, b), &mut Highest);
+    assert!(events.iter().any(|e| matches!(e, Event::SubstituteDamaged { .. })));
+    assert_eq!(state.monster(b).hp, 75);
+    assert!(state.monster(b).substitute_hp < 25);
+
+    state.monster_mut(b).item = Some(items::ItemId(246));
+    assert_eq!(screen_turns(state.monster(b)), LIGHT_CLAY_TURNS);
+    let mut screens = Screens { reflect: 1, light_screen: 2 };
+    assert_eq!(screens.end_turn(), [Screen::Reflect]);
+    assert!(screens.weakens(moves::DamageClass::Special));
+    assert!(!screens.start(Screen::LightScreen, SCREEN_TURNS));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn entry_hazards() {
+    use crate::battle::*;
+    use crate::battle::hazards::*;
+    use moves::MoveId;
+    use pokemon::OneOrTwo;
+
+    let guts = |types| monster(0, types, Ability::Guts);
+    let charizard = guts(OneOrTwo::Two(Type::Fire, Type::Flying));
+    let normal = guts(OneOrTwo::One(Type::Normal));
+    let mut hazards = HazardState::default();
+    assert!(hazards.add(Hazard::StealthRock));
+    assert!(!hazards.add(Hazard::StealthRock));
+    assert_eq!(entry_damage(&charizard, &hazards), 50);
+    for _ in 0..MAX_SPIKES {
+        assert!(hazards.add(Hazard::Spikes));
+    }
+    assert!(!hazards.add(Hazard::Spikes));
+    assert_eq!(entry_damage(&charizard, &hazards), 50);
+    assert_eq!(entry_damage(&normal, &hazards), 12 + 25);
+
+    hazards.add(Hazard::ToxicSpikes);
+    hazards.add(Hazard::ToxicSpikes);
+    let mut steel = guts(OneOrTwo::One(Type::Steel));
+    assert_eq!(toxic_spikes(&mut steel, &mut hazards), ToxicSpikesEffect::None);
+    let mut normal = normal.clone();
+    assert_eq!(toxic_spikes(&mut normal, &mut hazards),
+               ToxicSpikesEffect::Poisoned { badly: true });
+    let mut poison = guts(OneOrTwo::One(Type::Poison));
+    assert_eq!(toxic_spikes(&mut poison, &mut hazards), ToxicSpikesEffect::Absorbed);
+    assert_eq!(hazards.toxic_spikes, 0);
+
+    let sides = [
+        Side::new(vec![guts(OneOrTwo::One(Type::Rock))]),
+        Side::new(vec![guts(OneOrTwo::One(Type::Normal)), charizard]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let stealth_rock = MoveId(445);
+    resolve_turn(&mut state, &[Action::Move { user: a, move_id: stealth_rock, target: b }],
+                 &mut Highest);
+    assert_eq!(state.sides[1].hazards.layers(Hazard::StealthRock), 1);
+    let events = resolve_turn(&mut state, &[Action::Switch { side: 1, slot: 1 }],
+                              &mut Highest);
+    let target = Position { side: 1, slot: 1 };
+    assert_eq!(events[1], Event::DamageDealt { target, amount: 50, hp: 50, critical: false });
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn form_change_rules() {
+    use pokemon::forms::{ChangeTrigger, CHANGE_RULES};
+    let dex = pokedex();
+    let forms: Vec<_> = dex.species.all_pokemon().iter()
+        .flat_map(|p| p.forms.iter())
+        .collect();
+    assert!(CHANGE_RULES.windows(2).all(|w| w[0].form < w[1].form));
+    for rule in CHANGE_RULES.iter() {
+        let form = forms.iter().find(|f| f.id == rule.form).unwrap();
+        if form.battle_only {
+            assert!(rule.trigger.battle_only(), "{:?}", form);
+        }
+    }
+    let rotom_wash = forms.iter().find(|f| f.id == 708).unwrap();
+    assert_eq!(rotom_wash.name.as_deref(), Some("wash"));
+    let triggers: Vec<_> = rotom_wash.change_rules().map(|r| r.trigger).collect();
+    assert_eq!(triggers, [ChangeTrigger::Appliance(moves::MoveId(55))]);
+    assert_eq!(dex.moves[moves::MoveId(55)].identifier, "hydro-pump");
+    let zen = forms.iter().find(|f| f.name.as_deref() == Some("zen")).unwrap();
+    assert!(zen.battle_only);
+    assert_eq!(zen.change_rules().next().unwrap().trigger,
+               ChangeTrigger::LowHp(Ability::ZenMode, (1, 2)));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn arceus_plates() {
+    use crate::battle::Monster;
+    use crate::battle::damage::move_type;
+    use pokemon::forms::{held_item_form, multitype_type};
+    let dex = pokedex();
+    let plates: Vec<_> = dex.items.0.values()
+        .filter(|item| item.category == items::Category::Plates)
+        .collect();
+    assert_eq!(plates.len(), 16);
+    assert!(plates.iter().all(|plate| items::plate_type(plate).is_some()));
+    let flame = &dex.items[items::ItemId(275)];
+    assert_eq!(items::plate_type(flame), Some(Type::Fire));
+    assert_eq!(items::plate_type(&dex.items[items::ItemId(264)]), None);
+    assert_eq!(multitype_type(Some(flame)), Type::Fire);
+    assert_eq!(multitype_type(None), Type::Normal);
+    let arceus = &dex.species.pokemon(pokemon::SpeciesId(492))[0];
+    for plate in plates {
+        let form = held_item_form(&arceus.forms, plate.id).unwrap();
+        let typ = items::plate_type(plate).unwrap();
+        let name = format!("{:?}", typ).to_lowercase();
+        assert_eq!(form.name.as_deref(), Some(&name[..]), "{}", plate.name);
+    }
+    let mut user = Monster::new(
+        pokemon::SpeciesId(492), 100, arceus.types, Ability::Multitype, [100; 6]);
+    let judgment = &dex.moves[moves::MoveId(448)];
+    assert_eq!(move_type(&user, judgment), Type::Normal);
+    user.item = Some(flame.id);
+    assert_eq!(move_type(&user, judgment), Type::Fire);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn transform_copies() {
+    use crate::battle::*;
+    use moves::{ChangeableStat, MoveId};
+    use pokemon::OneOrTwo;
+    let (tackle, sketch) = (MoveId(32), MoveId(165));
+    let mut user = Monster::new(
+        pokemon::SpeciesId(131), 50, OneOrTwo::One(Type::Normal),
+        Ability::Imposter, [150, 60, 60, 60, 60, 60]);
+    let mut target = Monster::new(
+        pokemon::SpeciesId(5), 50, OneOrTwo::Two(Type::Fire, Type::Flying),
+        Ability::Blaze, [120, 90, 80, 110, 85, 100]);
+    target.moves = vec![MoveSlot { id: tackle, pp: 35 },
+                        MoveSlot { id: sketch, pp: 1 }];
+    let attack = ChangeableStat::new(Stat::Attack).unwrap();
+    target.stages.change(attack, 2);
+    user.hp = 100;
+    let snapshot = transform(&target, versions::Generation::V);
+    assert_eq!(snapshot.ability, Some(Ability::Blaze));
+    assert!(transform(&target, versions::Generation::II).ability.is_none());
+    snapshot.apply(&mut user);
+    assert_eq!(user.species, target.species);
+    assert_eq!(user.ability, Ability::Blaze);
+    assert!(user.types.contains(Type::Flying));
+    assert_eq!(user.stats, [150, 90, 80, 110, 85, 100]);
+    assert_eq!(user.hp, 100);
+    assert_eq!(user.stages[attack], 2);
+    assert_eq!(user.moves, vec![MoveSlot { id: tackle, pp: TRANSFORM_PP },
+                                MoveSlot { id: sketch, pp: 1 }]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn experience() {
+    use crate::experience::*;
+    use pokemon::GrowthRate;
+    let bulbasaur = &pokedex().species[pokemon::SpeciesId(0)];
+    assert_eq!(bulbasaur.growth_rate, GrowthRate::MediumSlow);
+    assert_eq!(exp_for_level(GrowthRate::MediumSlow, 1), 0);
+    assert_eq!(exp_for_level(GrowthRate::MediumSlow, 100), 1_059_860);
+    assert_eq!(exp_for_level(GrowthRate::SlowThenVeryFast, 100), 600_000);
+    assert_eq!(exp_for_level(GrowthRate::FastThenVerySlow, 100), 1_640_000);
+    assert_eq!(level_for_exp(GrowthRate::Medium, 999), 9);
+    assert_eq!(level_for_exp(GrowthRate::Medium, 1000), 10);
+    let pokemon = &pokedex().species.pokemon(bulbasaur.id)[0];
+    assert_eq!(pokemon.base_exp, 64);
+    assert_eq!(exp_gain(pokemon, 5, 5, 1, false, false), 65);
+    assert_eq!(exp_gain(pokemon, 5, 5, 1, true, true), 145);
+    assert!(exp_gain(pokemon, 5, 50, 1, false, false) < 65);
+    assert!(boosts_exp(Some(lucky_egg())));
+    assert!(!boosts_exp(None));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn happiness() {
+    use crate::friendship::*;
+    use pokemon::SpeciesId;
+    let (pichu, pikachu) = (SpeciesId(171), SpeciesId(24));
+    assert_eq!(base_happiness(pikachu), 70);
+    assert_eq!(evolves_by_happiness(pichu), Some(EVOLUTION_HAPPINESS));
+    assert_eq!(evolves_by_happiness(pikachu), None);
+    assert_eq!(
+        pokedex().species[pikachu].evolves_from.unwrap().happiness,
+        Some(EVOLUTION_HAPPINESS));
+    let bell = Some(soothe_bell());
+    assert_eq!(HappinessEvent::LevelUp.apply(70, None), 75);
+    assert_eq!(HappinessEvent::LevelUp.apply(70, bell), 77);
+    assert_eq!(HappinessEvent::Vitamin.apply(150, bell), 154);
+    assert_eq!(HappinessEvent::Walk.apply(MAX_HAPPINESS, bell), MAX_HAPPINESS);
+    assert_eq!(HappinessEvent::Faint.apply(0, bell), 0);
+    assert_eq!(HappinessEvent::Faint.apply(220, bell), 219);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn breeding_offspring() {
+    use rand::SeedableRng;
+    use crate::breeding::*;
+    use crate::stored::StoredPokemon;
+    use pokemon::SpeciesId;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    let dex = pokedex();
+    let pikachu = StoredPokemon {
+        species: SpeciesId(24), nature: Nature::Timid, ivs: [31; 6],
+        .. Default::default()
+    };
+    let ditto = StoredPokemon {
+        species: SpeciesId(131), ivs: [30; 6], .. Default::default()
+    };
+    let bulbasaur = StoredPokemon { species: SpeciesId(0), .. Default::default() };
+    assert!(offspring(&mut rng, &pikachu, &bulbasaur, (None, None), false)
+        .is_none());
+    assert!(offspring(&mut rng, &ditto, &ditto, (None, None), false).is_none());
+    let power = dex.items.identifiers().id("power-anklet").unwrap();
+    assert_eq!(power_item_stat(power), Some(Stat::Speed));
+    for _ in 0..20 {
+        let egg = offspring(
+            &mut rng, &ditto, &pikachu, (Some(Stat::HP), None), true).unwrap();
+        assert_eq!(egg.species, SpeciesId(171));
+        assert_eq!(egg.inherited.iter().filter(|&&i| i).count(), INHERITED_IVS);
+        assert!(egg.inherited[0]);
+        assert_eq!(egg.ivs[0], 30);
+    }
+    let marill = dex.species.identifiers().id("marill").unwrap();
+    let mut mother = StoredPokemon { species: marill, .. pikachu };
+    let egg = offspring(&mut rng, &mother, &ditto, (None, None), false);
+    assert_eq!(egg.unwrap().species, marill);
+    mother.item = dex.items.identifiers().id("sea-incense");
+    let egg = offspring(&mut rng, &mother, &ditto, (None, None), false);
+    assert_eq!(egg.unwrap().species, base_species(marill));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn hatch_steps() {
+    use crate::breeding::*;
+    use pokemon::SpeciesId;
+    let (pichu, magikarp) = (SpeciesId(171), SpeciesId(128));
+    assert_eq!(pokedex().species[pichu].hatch_counter, 10);
+    assert_eq!(hatch_steps(pichu, false), 2570);
+    assert_eq!(egg_cycles(magikarp, true), 3);
+    assert_eq!(hatch_steps(magikarp, true), 3 * STEPS_PER_CYCLE);
+    assert_eq!(cycles_remaining(10, 2569), 1);
+    assert_eq!(cycles_remaining(10, 10_000), 0);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn pid_attributes() {
+    use crate::legality::pid::*;
+    use crate::stored::{AbilitySlot, StoredPokemon};
+    use pokemon::{Gender, GenderRatio, SpeciesId};
+    use versions::Generation;
+    let pid = 0x5a3b_7f81;
+    let trainer = TrainerId { tid: 12345, sid: 54321 };
+    assert_eq!(nature(pid, Generation::IV), Some(Nature::Bold));
+    assert_eq!(nature(pid, Generation::V), None);
+    assert_eq!(gender(pid, GenderRatio::FemaleEighths(4)), Gender::Male);
+    assert_eq!(gender(pid, GenderRatio::FemaleEighths(6)), Gender::Female);
+    assert_eq!(gender(pid, GenderRatio::Genderless), Gender::Genderless);
+    assert_eq!(ability_slot(0x0000_0001, Generation::IV), AbilitySlot::Second);
+    assert_eq!(ability_slot(0x0000_0001, Generation::V), AbilitySlot::First);
+    assert!(is_shiny(0xf63c_1234, trainer));
+    assert!(!is_shiny(pid, trainer));
+    let pikachu = StoredPokemon {
+        species: SpeciesId(24), nature: Nature::Bold, .. Default::default()
+    };
+    assert!(check(pid, trainer, &pikachu, Gender::Male, false, Generation::IV)
+        .is_ok());
+    assert!(check(pid, trainer, &pikachu, Gender::Female, false, Generation::IV)
+        .is_err());
+    let modest = StoredPokemon { nature: Nature::Modest, .. pikachu.clone() };
+    assert!(check(pid, trainer, &modest, Gender::Male, false, Generation::IV)
+        .is_err());
+    assert!(check(pid, trainer, &modest, Gender::Male, false, Generation::V)
+        .is_ok());
+    let missingno = StoredPokemon { species: SpeciesId(u16::MAX), .. pikachu };
+    assert_eq!(
+        check(pid, trainer, &missingno, Gender::Male, false, Generation::V),
+        Err(crate::stored::Error::Invalid {
+            field: "species", reason: "unknown species"
+        }));
+    assert!(PidAttributes::new(pid, trainer, SpeciesId(u16::MAX),
+                               Generation::V).is_err());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn shiny_chance() {
+    use crate::breeding::*;
+    use versions::Generation;
+    assert_eq!(shiny_chance(false, false, Generation::V), (1, SHINY_ODDS));
+    assert_eq!(shiny_chance(true, true, Generation::V), (8, SHINY_ODDS));
+    assert_eq!(shiny_chance(true, true, Generation::IV), (5, SHINY_ODDS));
+    assert_eq!(shiny_chance(true, false, Generation::III), (1, SHINY_ODDS));
+    assert_eq!(expected_eggs_until_shiny(true, false, Generation::V),
+               Some(8192.0 / 6.0));
+    assert_eq!(expected_eggs_until_shiny(false, false, Generation::I), None);
+}
+
+#[test]
+fn lcrng_frames() {
+    use crate::rng::lcrng::*;
+    let mut rng = Lcrng32::new(0x1234);
+    let spread = method1(&mut rng);
+    assert_eq!(spread.pid, 0xe161_4dcb);
+    assert_eq!(spread.nature, Nature::Relaxed);
+    assert_eq!(spread.ivs, [0, 26, 16, 17, 31, 31]);
+    assert_eq!(rng.frame, 4);
+    let mut jumped = Lcrng32::new(0x1234);
+    jumped.advance(4);
+    assert_eq!(jumped, rng);
+    jumped.previous();
+    assert_eq!(jumped.frame, 3);
+    let spread = method1_frames(0x1234, 0).next().unwrap();
+    assert_eq!(spread.pid, 0xe161_4dcb);
+    let mut stepped = Lcrng64::new(1);
+    let mut jumped = stepped;
+    for _ in 0..1000 {
+        stepped.next();
+    }
+    jumped.advance(1000);
+    assert_eq!(jumped, stepped);
+    assert_eq!(Lcrng64::new(1).next_nature(), Nature::Lax);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn facility_sets() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    use crate::facilities::*;
+    assert!(FacilityTable::load(&Embedded).unwrap().0.is_empty());
+    let mut files = HashMap::new();
+    files.insert(String::from("facility_sets.csv"), b"\
+id,facility,min_battle,max_battle,species_id,nature_id,item_id,move1_id,\
+move2_id,move3_id,move4_id
+1,3,1,7,25,5,,85,,,
+2,3,8,14,445,2,234,89,200,,
+3,1,1,7,1,1,,33,,,
+".to_vec());
+    let source = Overlay { top: files, base: Embedded };
+    let table = FacilityTable::load(&source).unwrap();
+    let opponents: Vec<_>
+        = table.opponents(Facility::BattleSubway, 3).collect();
+    assert_eq!(opponents.len(), 1);
+    assert_eq!(opponents[0].species, pokemon::SpeciesId(24));
+    assert_eq!(opponents[0].nature, Nature::Timid);
+    assert_eq!(opponents[0].item, None);
+    assert_eq!(opponents[0].moves, vec![moves::MoveId(84)]);
+    let streak = table.in_streak(Facility::BattleSubway, 7..=8);
+    assert_eq!(streak.count(), 2);
+    let garchomp = table.opponents(Facility::BattleSubway, 14).next().unwrap();
+    assert_eq!(garchomp.moves.len(), 2);
+    assert!(garchomp.item.is_some());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn ai_policies() {
+    use crate::ai::*;
+    use crate::battle::*;
+    use moves::MoveId;
+    use pokemon::OneOrTwo;
+    let (thunderbolt, thunder_wave, tackle)
+        = (MoveId(84), MoveId(85), MoveId(32));
+    let mut pikachu =
+        monster(24, OneOrTwo::One(Type::Electric), Ability::Static);
+    pikachu.moves = [thunderbolt, thunder_wave, tackle].iter()
+        .map(|&id| MoveSlot { id, pp: 10 }).collect();
+    let foe = |typ| monster(0, OneOrTwo::One(typ), Ability::Overgrow);
+    let mut state = BattleState::new(versions::Generation::V, [
+        Side::new(vec![pikachu.clone(), pikachu]),
+        Side::new(vec![foe(Type::Water)]),
+    ]);
+    let user = state.active(0);
+    assert_eq!(legal_actions(&state, 0).len(), 4);
+    assert_eq!(ScorePolicy.choose(&state, 0, &mut Highest),
+               Action::Move { user, move_id: thunderbolt, target: state.active(1) });
+    state.sides[1].party[0] = foe(Type::Ground);
+    assert_eq!(ScorePolicy::score(&state, user, thunderbolt), 0);
+    assert_eq!(ScorePolicy.choose(&state, 0, &mut Highest),
+               Action::Move { user, move_id: tackle, target: state.active(1) });
+    assert_eq!(RandomPolicy.choose(&state, 0, &mut Highest),
+               Action::Switch { side: 0, slot: 1 });
+    for slot in state.sides[0].party[0].moves.iter_mut() {
+        slot.pp = 0;
+    }
+    assert_eq!(move_actions(&state, 0),
+               vec![Action::Move { user, move_id: struggle(), target: state.active(1) }]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn ko_chance() {
+    use crate::analysis::*;
+    use moves::{Ailment, MoveId, Weather};
+    use pokemon::OneOrTwo;
+    let attacker = monster(24, OneOrTwo::One(Type::Electric), Ability::Static);
+    let mut defender =
+        monster(0, OneOrTwo::One(Type::Normal), Ability::Overgrow);
+    let tackle = &pokedex().moves[MoveId(32)];
+    let report = ko_chance(&attacker, &defender, tackle, 4);
+    assert_eq!(report.rolls[0], 20);
+    assert_eq!(report.rolls[DAMAGE_ROLLS - 1], 24);
+    assert_eq!(report.chance, 0.0);
+    assert_eq!(ko_chance(&attacker, &defender, tackle, 5).chance, 1.0);
+    assert_eq!(report.to_string(), "20-24 (20.0-24.0%): 0.0% chance to 4HKO");
+    defender.ailment = Ailment::Burn;
+    let burned = ko_chance(&attacker, &defender, tackle, 3);
+    assert_eq!(burned.residual, 12);
+    assert!(burned.chance > 0.0 && burned.chance < 1.0);
+    defender.ailment = Ailment::None;
+    let sand = ko_chance_in(
+        &attacker, &defender, tackle, 4, Some(Weather::Sandstorm));
+    assert_eq!(sand.residual, 6);
+    assert!(sand.chance > 0.0);
+
+    let surf = pokedex().moves.identifiers().id("surf").unwrap();
+    let surf = &pokedex().moves[surf];
+    let clear = ko_chance(&attacker, &defender, surf, 1);
+    let rain = ko_chance_in(&attacker, &defender, surf, 1, Some(Weather::Rain));
+    let sun = ko_chance_in(&attacker, &defender, surf, 1, Some(Weather::Sun));
+    assert_eq!(rain.residual, 0);
+    assert!(rain.rolls[0] >= clear.rolls[0] * 3 / 2 - 1);
+    assert!(sun.rolls[0] <= clear.rolls[0] / 2 + 1);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn explain_efficacy() {
+    use pokemon::OneOrTwo;
+    let efficacy = &pokedex().efficacy;
+    let explanation = efficacy.explain(
+        Type::Fire, OneOrTwo::Two(Type::Grass, Type::Steel));
+    assert_eq!(explanation.total(), 4.0);
+    assert_eq!(explanation.to_string(),
+               "Fire vs Grass: 2x; Fire vs Steel: 2x; total 4x");
+    let explanation = efficacy.explain(Type::Ground, OneOrTwo::One(Type::Flying));
+    assert_eq!(explanation.components.first(), (Type::Flying, Efficacy::Not));
+    assert_eq!(explanation.to_string(), "Ground vs Flying: 0x; total 0x");
+    let explanation = efficacy.explain(
+        Type::Water, OneOrTwo::Two(Type::Grass, Type::Dragon));
+    assert_eq!(explanation.to_string(),
+               "Water vs Grass: 0.5x; Water vs Dragon: 0.5x; total 0.25x");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn diff_from_embedded() {
+    use std::collections::HashMap;
+    use veekun::data::{files, Embedded, Overlay, Source};
+    use crate::Pokedex;
+    assert!(Pokedex::diff_from_embedded(&Embedded).unwrap().is_empty());
+    let species = std::str::from_utf8(Embedded.csv(files::SPECIES).unwrap())
+        .unwrap()
+        .replace("\n25,pikachu,", "\n25,pika,")
+        .replace("\n1,bulbasaur,", "\n9001,bulbasaur,");
+    let mut top = HashMap::new();
+    top.insert(files::SPECIES.to_string(), species.into_bytes());
+    let source = Overlay { top, base: Embedded };
+    let diff = Pokedex::diff_from_embedded(&source).unwrap();
+    assert_eq!(diff.tables.len(), 1);
+    let table = diff.table(files::SPECIES).unwrap();
+    assert_eq!(table.added, ["9001"]);
+    assert_eq!(table.removed, ["1"]);
+    assert_eq!(table.changed, ["25"]);
+    assert!(diff.table(files::MOVES).is_none());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn describe_moves() {
+    let moves = &pokedex().moves;
+    let describe = |identifier| {
+        moves[moves.identifiers().id(identifier).unwrap()].describe()
+    };
+    assert_eq!(describe("thunderbolt"),
+               "Inflicts special damage with 95 power. \
+                Has a 10% chance to paralyze the target.");
+    assert_eq!(describe("growl"), "Lowers the target's Attack by one stage.");
+    assert_eq!(describe("swords-dance"),
+               "Raises the user's Attack by two stages.");
+    assert_eq!(describe("close-combat"),
+               "Inflicts physical damage with 120 power. \
+                Lowers the user's Defense and Special Defense by one stage.");
+    assert_eq!(describe("thunder-wave"), "Paralyzes the target.");
+    assert_eq!(describe("giga-drain"),
+               "Inflicts special damage with 75 power. \
+                Drains 50% of the damage inflicted.");
+    assert_eq!(describe("quick-attack"),
+               "Inflicts physical damage with 40 power. Has +1 priority.");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn insert_custom() {
+    use moves::{DamageClass, MoveBuilder, MoveTable, MOVE_COUNT};
+    use pokemon::{EvolvesFrom, SpeciesTable, SPECIES_COUNT};
+    let mut moves = MoveTable::new();
+    let mov = MoveBuilder::new("shadow-rush")
+        .typ(Type::Dark).power(55).pp(20).accuracy(Some(100))
+        .damage_class(DamageClass::Physical);
+    let id = moves.insert_custom(mov).unwrap();
+    assert_eq!(id.0 as usize, MOVE_COUNT);
+    assert!(!id.is_valid() && moves.contains(id));
+    assert_eq!(moves.identifiers().id("shadow-rush"), Some(id));
+    assert_eq!(moves[id].name, "ShadowRush");
+    assert!(moves.with_effect(moves[id].effect).contains(&id));
+
+    let mut species = SpeciesTable::new();
+    let pikachu = species.identifiers().id("pikachu").unwrap();
+    let mut custom = species[pikachu].clone();
+    custom.identifier = "pikablu".to_string();
+    custom.color = pokemon::Color::Blue;
+    custom.evolves_from = Some(EvolvesFrom {
+        from_id: pikachu, .. Default::default()
+    });
+    let pokemon = species.pokemon(pikachu)[0].clone();
+    let pokemon = pokemon::PokemonBuilder::from(pokemon)
+        .types(pokemon::OneOrTwo::One(Type::Water));
+    let id = species.insert_custom(custom, vec![pokemon]).unwrap();
+    assert_eq!(id.0 as usize, SPECIES_COUNT);
+    assert_eq!(species.identifiers().id("pikablu"), Some(id));
+    assert_eq!(species[id].id, id);
+    assert_eq!(species.pokemon(id).len(), 1);
+    let last = species.all_pokemon().iter().map(|p| p.id.0).max();
+    assert_eq!(Some(species.pokemon(id)[0].id.0), last);
+    assert!(species.evolves_into(pikachu).contains(&id));
+    assert!(species.is_fully_evolved(id));
+    assert_eq!(species.by_color(pokemon::Color::Blue).last(), Some(&id));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn builders() {
+    use crate::builder::Error;
+    use items::{Category, ItemBuilder, ItemTable};
+    use moves::{DamageClass, Flags, MoveBuilder, MoveId};
+    use pokemon::{BaseStats, OneOrTwo, PokemonBuilder, PokemonId};
+    let dex = pokedex();
+    for mov in &dex.moves.0 {
+        MoveBuilder::from(mov.clone()).build(mov.id).unwrap();
+    }
+    for item in dex.items.0.values() {
+        ItemBuilder::from(item.clone()).build(item.id).unwrap();
+    }
+    for pokemon in dex.species.all_pokemon() {
+        PokemonBuilder::from(pokemon.clone()).build(pokemon.id).unwrap();
+    }
+
+    let status = MoveBuilder::new("shadow-mist").pp(10)
+        .damage_class(DamageClass::NonDamaging);
+    assert!(status.clone().build(MoveId(0)).is_ok());
+    assert_eq!(status.clone().power(255).build(MoveId(0)).unwrap_err(),
+               Error { field: "power", reason: "above 250" });
+    assert_eq!(status.clone().accuracy(Some(101)).build(MoveId(0))
+                   .unwrap_err().field, "accuracy");
+    assert_eq!(status.flags(Flags::PUNCH).build(MoveId(0)).unwrap_err().field,
+               "flags");
+
+    let mut items = ItemTable::new();
+    let plate = ItemBuilder::new("shadow-plate").category(Category::Plates);
+    assert_eq!(items.insert_custom(plate).unwrap_err().field, "identifier");
+    let orb = ItemBuilder::new("shadow-orb").category(Category::HeldItems);
+    let id = items.insert_custom(orb).unwrap();
+    assert_eq!(items.identifiers().id("shadow-orb"), Some(id));
+    assert!(items.0.keys().all(|&other| other <= id));
+
+    let stats = BaseStats::new(80, 80, 80, 80, 80, 80);
+    let pokemon = PokemonBuilder::new(OneOrTwo::Two(Type::Dark, Type::Dark),
+                                      stats);
+    assert_eq!(pokemon.clone().build(PokemonId(0)).unwrap_err().field,
+               "types");
+    assert!(pokemon.types(OneOrTwo::One(Type::Dark)).build(PokemonId(0))
+                .is_ok());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn introduction_generations() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    use items::{ItemId, ItemTable};
+    use versions::Generation;
+    assert_eq!(Ability::introduced_in(Generation::III).count(), 77);
+    assert_eq!(Ability::introduced_in(Generation::V).count(), 41);
+    assert_eq!(Ability::Levitate.generation(), Generation::III);
+    assert!(!Ability::Multiscale.exists_in(Generation::IV));
+
+    let moves = &pokedex().moves;
+    assert_eq!(moves.introduced_in(Generation::I).count(), 165);
+    let thunderbolt = &moves[moves::MoveId(84)];
+    assert!(thunderbolt.exists_in(Generation::I));
+    let volt_switch = moves.identifiers().id("volt-switch").unwrap();
+    assert!(!moves[volt_switch].exists_in(Generation::IV));
+
+    let items = &pokedex().items;
+    assert!(items.introduced_in(Generation::III).is_empty());
+    assert!(items[ItemId(1)].exists_in(Generation::I));
+    let mut files = HashMap::new();
+    files.insert(String::from("item_game_indices.csv"),
+                 b"item_id,generation_id,game_index\n\
+                   1,1,1\n1,2,1\n4,3,4\n4,4,4\n".to_vec());
+    let items = ItemTable::load(&Overlay { top: files, base: Embedded })
+        .unwrap();
+    let introduced: Vec<_> = items.introduced_in(Generation::III).iter()
+        .map(|item| item.id)
+        .collect();
+    assert_eq!(introduced, [ItemId(4)]);
+    assert!(items[ItemId(1)].exists_in(Generation::I));
+    assert!(!items[ItemId(4)].exists_in(Generation::II));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn egg_group_compatibility() {
+    use pokemon::{EggGroup, Species, SpeciesId};
+    assert!(EggGroup::Monster.compatible(EggGroup::Monster));
+    assert!(!EggGroup::Monster.compatible(EggGroup::Dragon));
+    assert!(EggGroup::Ditto.compatible(EggGroup::Dragon));
+    assert!(!EggGroup::Ditto.compatible(EggGroup::Ditto));
+    assert!(!EggGroup::NoEggs.compatible(EggGroup::Ditto));
+    assert_eq!(EggGroup::Ditto.compatible_groups().len(), EggGroup::COUNT - 2);
+    let water = EggGroup::Water1.compatible_groups();
+    assert_eq!(water.iter().collect::<Vec<_>>(),
+               [EggGroup::Water1, EggGroup::Ditto]);
+
+    let species = &pokedex().species;
+    let bulbasaur = &species[SpeciesId(0)];
+    let charmander = &species[SpeciesId(3)];
+    let ditto = &species[SpeciesId(131)];
+    let pichu = &species[SpeciesId(171)];
+    assert!(Species::shares_egg_group(bulbasaur, charmander));
+    assert!(Species::shares_egg_group(ditto, charmander));
+    assert!(!Species::shares_egg_group(ditto, ditto));
+    assert!(!Species::shares_egg_group(ditto, pichu));
+    assert!(!crate::breeding::compatible(pichu, pichu));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn item_economy() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    use items::{ItemTable, ShoppingList};
+    use versions::VersionGroup;
+    let items = &pokedex().items;
+    let id = |identifier| items.identifiers().id(identifier).unwrap();
+    assert_eq!(items[id("potion")].sell_price(), 150);
+    assert_eq!(items[id("nugget")].sell_price(), 5000);
+    assert_eq!(items[id("hm01")].sell_price(), 0);
+    assert_eq!(items[id("bicycle")].sell_price(), 0);
+
+    let mut list = ShoppingList::new();
+    list.add(id("poke-ball"), 10).add(id("potion"), 3);
+    assert_eq!(list.cost(items), 2000 + 900);
+    assert_eq!(list.change(3000, items), Some(100));
+    assert_eq!(list.change(2000, items), None);
+    assert_eq!(list.premier_balls(), 1);
+    list.add(id("poke-ball"), 9);
+    assert_eq!(list.premier_balls(), 1);
+
+    assert!(items.marts_selling(id("potion")).is_empty());
+    let mut files = HashMap::new();
+    files.insert(String::from("item_marts.csv"),
+                 b"item_id,version_group_id\n17,11\n17,1\n4,11\n".to_vec());
+    let items = ItemTable::load(&Overlay { top: files, base: Embedded })
+        .unwrap();
+    assert_eq!(items.marts_selling(id("potion")),
+               [VersionGroup::RedBlue, VersionGroup::BlackWhite]);
+    assert_eq!(items.sold_in(VersionGroup::BlackWhite),
+               [id("poke-ball"), id("potion")]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn item_restores() {
+    use items::{HpRestore, PpRestore};
+    use moves::Ailment;
+    let items = &pokedex().items;
+    let item = |identifier| &items[items.identifiers().id(identifier).unwrap()];
+    assert!(item("potion").consumed_on_use_in_battle());
+    assert!(item("poke-ball").consumed_on_use_in_battle());
+    assert!(!item("leftovers").consumed_on_use_in_battle());
+    assert!(item("leftovers").restores().is_none());
+
+    let potion = item("super-potion").restores().unwrap();
+    assert_eq!(potion.hp, Some(HpRestore::Amount(50)));
+    assert!(potion.cures.is_empty() && !potion.revives);
+    let sitrus = item("sitrus-berry").restores().unwrap().hp.unwrap();
+    assert_eq!(sitrus.amount(150), 37);
+    let full_restore = item("full-restore").restores().unwrap();
+    assert_eq!(full_restore.hp.unwrap().amount(150), 150);
+    assert!(full_restore.cures(Ailment::Confusion));
+    assert!(!item("antidote").restores().unwrap().cures(Ailment::Burn));
+    assert_eq!(item("elixir").restores().unwrap().pp,
+               Some(PpRestore { amount: Some(10), all_moves: true }));
+    let revive = item("revive").restores().unwrap();
+    assert!(revive.revives && !revive.whole_party);
+    assert_eq!(revive.hp.unwrap().amount(151), 75);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn battle_stat_boosts() {
+    use items::BattleStatBoost;
+    use moves::ChangeableStat;
+    let items = &pokedex().items;
+    let boost = |identifier| {
+        items[items.identifiers().id(identifier).unwrap()].battle_stat_boost()
+    };
+    let special_attack = ChangeableStat::new(Stat::SpecialAttack).unwrap();
+    assert_eq!(boost("x-special"),
+               Some(BattleStatBoost::Stat(special_attack, 1)));
+    assert_eq!(boost("dire-hit"), Some(BattleStatBoost::Critical(1)));
+    assert_eq!(boost("guard-spec"), Some(BattleStatBoost::Mist(5)));
+    assert_eq!(boost("potion"), None);
+    let boosts = items.0.values().filter_map(|item| item.battle_stat_boost());
+    assert_eq!(boosts.count(), 8);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn ball_modifiers() {
+    use items::{ball_modifier, CatchContext, GUARANTEED_CATCH};
+    use pokemon::{Gender, SpeciesId};
+    let items = &pokedex().items;
+    let modifier = |identifier, ctx: &CatchContext| {
+        ball_modifier(&items[items.identifiers().id(identifier).unwrap()], ctx)
+    };
+    let magikarp = CatchContext {
+        species: SpeciesId(128), level: 10, gender: Gender::Female, turns: 3,
+        user_species: SpeciesId(128), user_level: 40, user_gender: Gender::Male,
+        .. Default::default()
+    };
+    assert_eq!(modifier("master-ball", &magikarp), GUARANTEED_CATCH);
+    assert_eq!(modifier("ultra-ball", &magikarp), 2.0);
+    assert_eq!(modifier("net-ball", &magikarp), 3.0);
+    assert_eq!(modifier("dusk-ball", &magikarp), 1.0);
+    assert_eq!(modifier("quick-ball", &magikarp), 1.0);
+    assert_eq!(modifier("quick-ball",
+                        &CatchContext { turns: 0, .. magikarp }), 5.0);
+    assert!((modifier("timer-ball", &magikarp) - 1.9).abs() < 0.01);
+    assert_eq!(modifier("timer-ball",
+                        &CatchContext { turns: 30, .. magikarp }), 4.0);
+    assert_eq!(modifier("nest-ball", &magikarp), 3.1);
+    assert_eq!(modifier("repeat-ball", &magikarp), 1.0);
+    assert_eq!(modifier("level-ball", &magikarp), 8.0);
+    assert_eq!(modifier("love-ball", &magikarp), 8.0);
+    let pikachu = CatchContext { species: SpeciesId(24), .. magikarp };
+    assert_eq!(modifier("net-ball", &pikachu), 1.0);
+    assert_eq!(modifier("love-ball", &pikachu), 1.0);
+    assert_eq!(modifier("potion", &pikachu), 1.0);
+    assert_eq!(modifier("net-ball", &CatchContext::default()), 1.0);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn ability_modifiers() {
+    use crate::abilities::{ModifierContext, ModifierKind, Modifiers};
+    use crate::battle::*;
+    use crate::battle::damage::calc_damage;
+    use pokemon::OneOrTwo;
+    let moves = &pokedex().moves;
+    let mov = |identifier| &moves[moves.identifiers().id(identifier).unwrap()];
+    let fire = |ability| monster(0, OneOrTwo::One(Type::Fire), ability);
+    let huge_power: Vec<_> = Modifiers::of(Ability::HugePower).collect();
+    assert_eq!(huge_power.len(), 1);
+    assert_eq!(huge_power[0].kind, ModifierKind::Stat(Stat::Attack));
+    assert_eq!(huge_power[0].factor, (2, 1));
+
+    let factor = |holder: &Monster, identifier| {
+        let ctx = ModifierContext::new(holder, None).with_move(mov(identifier));
+        Modifiers::factor(ModifierKind::Damage, &ctx)
+    };
+    let levitate = fire(Ability::Levitate);
+    assert_eq!(factor(&levitate, "earthquake"), 0.0);
+    assert_eq!(factor(&levitate, "tackle"), 1.0);
+    let thick_fat = fire(Ability::ThickFat);
+    assert_eq!(factor(&thick_fat, "ember"), 0.5);
+    assert_eq!(factor(&thick_fat, "ice-beam"), 0.5);
+    assert_eq!(factor(&thick_fat, "water-gun"), 1.0);
+
+    let mut blaze = fire(Ability::Blaze);
+    let power = |holder: &Monster| {
+        let ctx = ModifierContext::new(holder, None).with_move(mov("ember"));
+        Modifiers::factor(ModifierKind::Power, &ctx)
+    };
+    assert_eq!(power(&blaze), 1.0);
+    blaze.hp = 34;
+    assert_eq!(power(&blaze), 1.0);
+    blaze.hp = 33;
+    assert_eq!(power(&blaze), 1.5);
+
+    let sides = [
+        Side::new(vec![fire(Ability::HugePower)]),
+        Side::new(vec![fire(Ability::Levitate)]),
+    ];
+    let state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let damage = |mov| calc_damage(&state, a, b, mov, false, 100);
+    assert_eq!(damage(mov("earthquake")), 0);
+    let doubled = damage(mov("tackle"));
+    let mut state = state.clone();
+    state.monster_mut(a).ability = Ability::Blaze;
+    let plain = calc_damage(&state, a, b, mov("tackle"), false, 100);
+    assert!(doubled > plain * 3 / 2);
+
+    /// Rolls 70 out of 100 for accuracy, which misses a move with 60%
+    /// accuracy unless a modifier raises it.
+    struct Seventy;
+    impl BattleRng for Seventy {
+        fn below(&mut self, bound: u32) -> u32 { 70.min(bound - 1) }
+    }
+
+    let hypnosis = mov("hypnosis").id;
+    let missed = |state: &mut BattleState| {
+        let action = Action::Move { user: a, move_id: hypnosis, target: b };
+        resolve_turn(state, &[action], &mut Seventy).iter()
+            .any(|event| matches!(event, Event::MoveMissed { .. }))
+    };
+    assert!(missed(&mut state.clone()));
+    state.monster_mut(a).ability = Ability::Compoundeyes;
+    assert!(!missed(&mut state.clone()));
+    state.monster_mut(b).ability = Ability::SandVeil;
+    state.weather = Some(moves::Weather::Sandstorm);
+    assert!(missed(&mut state.clone()));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn ability_and_effect_scripts() {
+    use crate::battle::*;
+    use crate::battle::scripts::{Op, Primitive, Recipient};
+    use moves::{Ailment, Effect, Weather};
+    use pokemon::OneOrTwo;
+
+    assert_eq!(
+        Ability::Static.script().collect::<Vec<_>>(),
+        [Primitive::OnContact(
+            Op::ChanceAilment(Recipient::Other, Ailment::Paralysis, 30))]);
+    assert!(Ability::Blaze.script()
+        .all(|p| matches!(p, Primitive::ModifyMovePower(..))));
+    assert_eq!(Effect::Facade.script().count(), 1);
+    assert_eq!(Ability::Cacophony.script().count(), 0);
+
+    let moves = &pokedex().moves;
+    let id = |identifier| moves.identifiers().id(identifier).unwrap();
+    let (tackle, rain_dance) = (id("tackle"), id("rain-dance"));
+    let normal = |ability| {
+        let mut normal = monster(0, OneOrTwo::One(Type::Normal), ability);
+        normal.moves = vec![
+            MoveSlot { id: tackle, pp: 10 },
+            MoveSlot { id: rain_dance, pp: 10 },
+        ];
+        normal
+    };
+    let sides = [
+        Side::new(vec![normal(Ability::Guts), normal(Ability::Intimidate)]),
+        Side::new(vec![normal(Ability::Static)]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let events = resolve_turn(
+        &mut state, &[Action::Move { user: a, move_id: tackle, target: b }],
+        &mut Lowest);
+    assert!(events.contains(
+        &Event::AilmentInflicted { target: a, ailment: Ailment::Paralysis }));
+
+    let events = resolve_turn(
+        &mut state, &[Action::Switch { side: 0, slot: 1 }], &mut Lowest);
+    assert!(events.iter().any(|e| matches!(
+        e, Event::StatStageChanged { target, change: -1, .. }
+            if *target == b)));
+
+    let a = state.active(0);
+    let events = resolve_turn(
+        &mut state, &[Action::Move { user: a, move_id: rain_dance, target: a }],
+        &mut Lowest);
+    assert!(events.contains(
+        &Event::WeatherChanged { user: a, weather: Weather::Rain }));
+    assert_eq!(state.weather, Some(Weather::Rain));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn veekun_pokemon_ids() {
+    use pokemon::{PokemonId, POKEMON_COUNT, UPSTREAM_ALTERNATE_OFFSET};
+    let dex = pokedex();
+    for pokemon in dex.species.all_pokemon() {
+        let id = pokemon.veekun_id().unwrap();
+        let upstream = pokemon.id.upstream_veekun_id().unwrap();
+        assert_eq!(dex.by_veekun_pokemon_id(id).unwrap().id, pokemon.id);
+        assert_eq!(dex.by_veekun_pokemon_id(upstream).unwrap().id, pokemon.id);
+        assert_eq!(upstream > UPSTREAM_ALTERNATE_OFFSET,
+                   pokemon.id.is_alternate());
+    }
+    let deoxys_attack = dex.by_veekun_pokemon_id(10001).unwrap();
+    assert_eq!(deoxys_attack.id, PokemonId(649));
+    assert_eq!(deoxys_attack.veekun_id(), Some(650));
+    let deoxys = &dex.species.pokemon(pokemon::SpeciesId(385))[1];
+    assert_eq!(deoxys.id, deoxys_attack.id);
+    let keldeo_resolute = dex.by_veekun_pokemon_id(10024).unwrap();
+    assert_eq!(keldeo_resolute.id, PokemonId(POKEMON_COUNT as u16 - 1));
+    assert!(dex.by_veekun_pokemon_id(0).is_none());
+    assert!(dex.by_veekun_pokemon_id(674).is_none());
+    assert!(dex.by_veekun_pokemon_id(10000).is_none());
+    assert!(dex.by_veekun_pokemon_id(10025).is_none());
+    assert_eq!(PokemonId::default().veekun_id(), None);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn stat_queries() {
+    let species = &pokedex().species;
+    let fastest_electric = species.query_stats()
+        .with_type(Type::Electric)
+        .min(Stat::Speed, 100)
+        .sorted_by(Stat::Speed)
+        .run();
+    assert!(!fastest_electric.is_empty());
+    assert!(fastest_electric.iter().all(|p| {
+        p.types.contains(Type::Electric) && p.stats.speed() >= 100
+    }));
+    assert!(fastest_electric.windows(2)
+        .all(|w| w[0].stats.speed() >= w[1].stats.speed()));
+
+    let shuckle = species.query_stats()
+        .min(Stat::Defense, 230)
+        .max(Stat::HP, 20)
+        .run();
+    assert_eq!(shuckle.len(), 1);
+    assert_eq!(shuckle[0].id, pokemon::PokemonId(212));
+
+    let legendary = species.query_stats().total_between(680, 720).run();
+    assert!(legendary.iter().all(|p| (680..=720).contains(&p.stats.total())));
+    let by_total = species.query_stats().sorted_by_total().run();
+    assert_eq!(by_total.len(), species.all_pokemon().len());
+    assert_eq!(by_total[0].stats.total(), 720);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn power_and_accuracy_kinds() {
+    use moves::{AccuracyKind, Effect, PowerKind};
+    let moves = &pokedex().moves;
+    let mov = |identifier| &moves[moves.identifiers().id(identifier).unwrap()];
+    assert_eq!(mov("tackle").power_kind(), PowerKind::Fixed(50));
+    assert_eq!(mov("seismic-toss").power_kind(),
+               PowerKind::Variable(mov("seismic-toss").effect));
+    assert_eq!(mov("growl").power_kind(), PowerKind::None);
+    assert_eq!(mov("me-first").power_kind(), PowerKind::None);
+    assert_eq!(mov("seismic-toss").power_kind().to_string(), "varies");
+    assert_eq!(mov("growl").power_kind().to_string(), "—");
+    assert!(!mov("seismic-toss").describe().contains("power"));
+    assert_eq!(mov("tackle").accuracy_kind(), AccuracyKind::Fixed(100));
+    assert_eq!(mov("swift").accuracy_kind(), AccuracyKind::None);
+    assert!(matches!(mov("fissure").accuracy_kind(),
+                     AccuracyKind::Variable(Effect::OneHitKO)));
+    assert_eq!(mov("tackle").accuracy_kind().to_string(), "100%");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn generated_counts() {
+    use veekun::data as vdata;
+    let records = |csv: &str| csv.lines().skip(1)
+        .filter(|line| !line.is_empty())
+        .filter(|line| line.split(',').next().unwrap().parse::<u32>().unwrap()
+            < 10000)
+        .count();
+    assert_eq!(records(vdata::MOVES), moves::MOVE_COUNT);
+    assert_eq!(records(vdata::POKEMON), pokemon::POKEMON_COUNT);
+    assert_eq!(records(vdata::SPECIES), pokemon::SPECIES_COUNT);
+    assert_eq!(records(vdata::BERRIES), items::BERRY_COUNT);
+    let dex = pokedex();
+    assert_eq!(dex.moves.0.len(), moves::MOVE_COUNT);
+    assert_eq!(dex.species.all_pokemon().len(), pokemon::POKEMON_COUNT);
+    assert_eq!(dex.species.len(), pokemon::SPECIES_COUNT);
+}
+
+#[cfg(all(feature = "serde", not(feature = "no-embedded-data")))]
+#[test]
+fn battle_state_serialization() {
+    use crate::battle::*;
+    use moves::{Ailment, MoveId};
+    use pokemon::OneOrTwo;
+
+    let tackle = MoveId(32);
+    let electric = |ability| {
+        let mut electric = monster(24, OneOrTwo::One(Type::Electric), ability);
+        electric.moves = vec![MoveSlot { id: tackle, pp: 10 }];
+        electric
+    };
+    let sides = [
+        Side::new(vec![electric(Ability::Guts)]),
+        Side::new(vec![electric(Ability::Static)]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    state.monster_mut(b).volatile.insert(Ailment::Confusion);
+    let mut resumed = state.clone();
+    let action = [Action::Move { user: a, move_id: tackle, target: b }];
+    resolve_turn(&mut state, &action, &mut Lowest);
+
+    let json = serde_json::to_string(&SavedBattle::new(state.clone())).unwrap();
+    let saved: SavedBattle = serde_json::from_str(&json).unwrap();
+    assert_eq!(saved.schema, STATE_SCHEMA_VERSION);
+    let restored = saved.clone().restore().unwrap();
+    assert_eq!(restored, state);
+    assert_eq!(restored.hash(), state.hash());
+    assert!(restored.monster(b).volatile.contains(Ailment::Confusion));
+    assert!(SavedBattle { schema: 0, .. saved.clone() }.restore().is_none());
+    assert!(SavedBattle { fingerprint: 0, .. saved }.restore().is_none());
+
+    assert_ne!(resumed.hash(), state.hash());
+    resolve_turn(&mut resumed, &action, &mut Lowest);
+    assert_eq!(resumed.hash(), state.hash());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn matchup_matrices() {
+    use crate::analysis::matchup_matrix;
+    use crate::battle::MoveSlot;
+    use crate::pokemon::OneOrTwo;
+    use std::cmp::Ordering;
+
+    let ids = pokedex().moves.identifiers();
+    let (tackle, thunder_shock, growl) = (
+        ids.id("tackle").unwrap(), ids.id("thundershock").unwrap(),
+        ids.id("growl").unwrap());
+    let mut pikachu =
+        monster(24, OneOrTwo::One(Type::Electric), Ability::Static);
+    pikachu.moves = [tackle, thunder_shock, growl].iter()
+        .map(|&id| MoveSlot { id, pp: 10 })
+        .collect();
+    let mut squirtle = monster(6, OneOrTwo::One(Type::Water), Ability::Torrent);
+    squirtle.stats[3] = 50;
+    let mut diglett =
+        monster(49, OneOrTwo::One(Type::Ground), Ability::SandVeil);
+    diglett.stats[3] = 150;
+    diglett.moves = vec![MoveSlot { id: growl, pp: 10 }];
+
+    let matrix =
+        matchup_matrix(&[pikachu.clone()], &[squirtle, diglett.clone()]);
+    assert_eq!(matrix.attackers, ["pikachu"]);
+    assert_eq!(matrix.defenders, ["squirtle", "diglett"]);
+    assert_eq!(matrix[(0, 0)].best_move, Some(thunder_shock));
+    assert_eq!(matrix[(0, 0)].efficacy, 2.0);
+    assert_eq!(matrix[(0, 0)].speed(), Ordering::Greater);
+    assert_eq!(matrix[(0, 1)].best_move, Some(tackle));
+    assert_eq!(matrix[(0, 1)].efficacy, 1.0);
+    assert_eq!(matrix[(0, 1)].speed(), Ordering::Less);
+    let reverse = matchup_matrix(&[diglett], &[pikachu]);
+    assert_eq!(reverse[(0, 0)].best_move, None);
+    assert_eq!(reverse[(0, 0)].damage, 0);
+
+    let csv = matrix.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(),
+               Some("attacker,defender,best_move,efficacy,damage,speed"));
+    assert!(lines.next().unwrap()
+        .starts_with("pikachu,squirtle,thundershock,2,"));
+    assert!(lines.next().unwrap().ends_with(",slower"));
+    let json: serde_json::Value =
+        serde_json::from_str(&reverse.to_json()).unwrap();
+    assert_eq!(json[0]["best_move"], serde_json::Value::Null);
+    assert_eq!(json[0]["speed"], "faster");
+    assert_eq!(json[0]["attacker_speed"], 150);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn form_exclusion() {
+    use crate::pokemon::forms::FormFlags;
+    use crate::stored::{AbilitySlot, StoredMove, StoredPokemon};
+    use crate::team::*;
+    let stored = |species, form, item: Option<u16>| StoredPokemon {
+        species: pokemon::SpeciesId(species),
+        form,
+        ability: AbilitySlot::First,
+        nature: Nature::Timid,
+        level: 50,
+        ivs: [31; 6],
+        evs: [0; 6],
+        item: item.map(items::ItemId),
+        moves: vec![StoredMove { id: moves::MoveId(32), pp_ups: 0 }],
+        nickname: None,
+    };
+    let dex = pokedex();
+    let pirouette = stored(647, 723, None);
+    let pirouette_form = pirouette.form(dex).unwrap();
+    assert_eq!(pirouette_form.flags(), FormFlags::BATTLE_ONLY);
+    assert_eq!(stored(647, 648, None).form(dex).unwrap().flags(),
+               FormFlags::empty());
+
+    let team = |pokemon| Team { pokemon: vec![pokemon] };
+    let rules = Ruleset::default();
+    assert_eq!(team(stored(647, 648, None)).validate(&rules), Ok(()));
+    assert_eq!(team(pirouette.clone()).validate(&rules),
+               Err(Error::Rule { index: Some(0), rule: "excluded form" }));
+    let anything = Ruleset { excluded_forms: FormFlags::empty(), .. rules };
+    assert_eq!(team(pirouette).validate(&anything), Ok(()));
+
+    let origin = stored(486, 712, Some(442));
+    assert_eq!(origin.form(dex).unwrap().flags(), FormFlags::REQUIRES_ITEM);
+    assert_eq!(team(origin.clone()).validate(&anything), Ok(()));
+    assert_eq!(team(StoredPokemon { item: None, .. origin })
+                   .validate(&anything),
+               Err(Error::Rule {
+                   index: Some(0), rule: "form requires its held item",
+               }));
+    let wash = stored(478, 708, None);
+    assert_eq!(wash.form(dex).unwrap().flags(), FormFlags::OUT_OF_BATTLE_ONLY);
+    let no_changes = Ruleset {
+        excluded_forms: FormFlags::OUT_OF_BATTLE_ONLY,
+        .. Default::default()
+    };
+    assert!(team(wash).validate(&no_changes).is_err());
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn typed_queries() {
+    use crate::pokemon::Color;
+    use crate::query::{learns, type_is, Filter, Select};
+    use crate::versions::VersionGroup;
+
+    let surf = pokedex().moves.identifiers().id("surf").unwrap();
+    let query = Select::species()
+        .filter(type_is(Type::Water))
+        .filter(Filter::Color(Color::Yellow))
+        .with_stats();
+    let yellow: Vec<_> = query.run().map(|s| s.identifier).collect();
+    assert!(yellow.contains(&"psyduck"));
+    assert!(!yellow.contains(&"pikachu"));
+    let psyduck = query.run().next().unwrap();
+    assert_eq!(psyduck.identifier, "psyduck");
+    assert_eq!(psyduck.stats.unwrap().speed(), 55);
+    assert!(psyduck.moves.is_none());
+
+    let bw = VersionGroup::BlackWhite;
+    let surfers = Select::species().filter(learns(surf, bw)).with_moves(bw);
+    let mut results = surfers.run();
+    let first = results.next().unwrap();
+    assert!(first.moves.unwrap().binary_search(&surf).is_ok());
+    assert!(results.all(|s| s.moves.unwrap().contains(&surf)));
+}
+
+#[cfg(all(feature = "capi", not(feature = "no-embedded-data")))]
+#[test]
+fn c_api() {
+    use crate::capi::*;
+    let pikachu = unsafe { vdex_species_id(c"pikachu".as_ptr()) };
+    assert_eq!(pikachu, 24);
+    assert_eq!(unsafe { vdex_species_id(c"missingno".as_ptr()) }, VDEX_NONE);
+    assert_eq!(unsafe { vdex_species_id(std::ptr::null()) }, VDEX_NONE);
+    let mut name = [0; 5];
+    let len = unsafe {
+        vdex_copy_species_name(pikachu, name.as_mut_ptr(), name.len())
+    };
+    assert_eq!(len, "Pikachu".len());
+    let name = unsafe { std::ffi::CStr::from_ptr(name.as_ptr()) };
+    assert_eq!(name.to_str(), Ok("Pika"));
+
+    let mut stats = VdexBaseStats::default();
+    assert!(unsafe { vdex_base_stats(pikachu, &mut stats) });
+    assert_eq!((stats.hp, stats.speed), (35, 90));
+    let (mut first, mut second) = (0, 0);
+    assert!(unsafe { vdex_species_types(pikachu, &mut first, &mut second) });
+    assert_eq!((first, second), (Type::Electric.repr(), VDEX_NO_TYPE));
+    let mut tackle = VdexMove::default();
+    let id = unsafe { vdex_move_id(c"tackle".as_ptr()) };
+    assert!(unsafe { vdex_move(id, &mut tackle) });
+    assert_eq!((tackle.power, tackle.accuracy, tackle.pp), (50, 100, 35));
+    assert!(!unsafe { vdex_move(VDEX_NONE, &mut tackle) });
+    assert_eq!(
+        vdex_type_modifier(Type::Water.repr(), Type::Fire.repr()), 2.0);
+    assert_eq!(vdex_type_modifier(Type::COUNT as u8, 0), -1.0);
+}
+
+#[cfg(all(feature = "python", not(feature = "no-embedded-data")))]
+#[test]
+fn python_bindings() {
+    use crate::python::*;
+    let pikachu = species("pikachu").unwrap();
+    assert_eq!(pikachu.types, ["Electric"]);
+    assert_eq!(pikachu.stats, [35, 55, 30, 90, 50, 40]);
+    assert!(species("missingno").is_err());
+    assert_eq!(get_move("seismic-toss").unwrap().power, None);
+    let surfers = query_species(
+        Some("Water"), None, Some("surf"), "BlackWhite").unwrap();
+    assert_eq!(surfers[0].identifier, "squirtle");
+    assert!(query_species(Some("Sound"), None, None, "BlackWhite").is_err());
+    assert_eq!(stats("pikachu", 50, "Timid", [31; 6], [0, 0, 0, 252, 252, 4])
+                   .unwrap()[3], 156);
+    assert!(stats("pikachu", 0, "Hardy", [31; 6], [0; 6]).is_err());
+    let report = ko_chance("garchomp", "blissey", "earthquake", 50, 3).unwrap();
+    assert_eq!(report.chance, 1.0);
+    assert_eq!(type_modifier("Ground", vec!["Flying".to_string()]).unwrap(),
+               0.0);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn entity_refs() {
+    use crate::{MoveRef, SpeciesRef};
+    use crate::versions::VersionGroup;
+    use std::collections::HashSet;
+
+    let pikachu = SpeciesRef::by_identifier("pikachu").unwrap();
+    assert_eq!(pikachu.name, "Pikachu");
+    assert_eq!(pikachu.default_pokemon().types.first(), Type::Electric);
+    assert_eq!(pikachu.evolves_from().unwrap().identifier, "pichu");
+    let raichu: Vec<_> = pikachu.evolves_into().collect();
+    assert_eq!(raichu, [SpeciesRef::by_identifier("raichu").unwrap()]);
+    assert!(SpeciesRef::new(pokemon::SpeciesId::default()).is_none());
+    assert_eq!(format!("{:?}", pikachu), r#"SpeciesRef(24, "pikachu")"#);
+
+    let thunderbolt = MoveRef::by_identifier("thunderbolt").unwrap();
+    assert!(pikachu.learnable_moves(VersionGroup::BlackWhite)
+        .any(|m| m == thunderbolt));
+    assert_eq!(thunderbolt.power, 95);
+    assert!(thunderbolt.same_effect().all(|m| {
+        m.effect == thunderbolt.effect && m != thunderbolt
+    }));
+    let set: HashSet<_> = [thunderbolt, thunderbolt].into_iter()
+        .map(MoveRef::id).collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[cfg(feature = "sandbox")]
+#[test]
+fn sandbox_pokedex() {
+    use crate::moves::MoveBuilder;
+    use crate::versions::VersionGroup;
+    use crate::{Pokedex, SpeciesRef};
+
+    let mut dex = Pokedex::sandbox();
+    assert_eq!(dex.efficacy[(Type::Water, Type::Fire)], Efficacy::Super);
+    assert_eq!(dex.efficacy[(Type::Normal, Type::Ghost)], Efficacy::Not);
+    assert_eq!(dex.efficacy[(Type::Fire, Type::Normal)], Efficacy::Regular);
+    assert_eq!(dex.species.len(), 4);
+    assert!(dex.items.identifiers().id("leftovers").is_some());
+    let thunderbolt = dex.moves.identifiers().id("thunderbolt").unwrap();
+    assert_eq!(dex.moves[thunderbolt].power, 95);
+
+    let surf = dex.moves.insert_custom(MoveBuilder::new("surf")
+        .typ(Type::Water).power(95).pp(15)).unwrap();
+    dex.moves.0[thunderbolt.0 as usize].pp = 24;
+    let dex: &'static Pokedex = Box::leak(Box::new(dex));
+    let pikachu = dex.species.identifiers().id("pikachu").unwrap();
+    let pikachu = SpeciesRef::in_dex(dex, pikachu).unwrap();
+    assert!(pikachu.learnable_moves(VersionGroup::BlackWhite)
+        .any(|m| m.id() == thunderbolt && m.pp == 24));
+    assert_eq!(dex.moves[surf].identifier, "surf");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn world_clock() {
+    use crate::world::{Conditions, Season, TimeOfDay};
+
+    assert_eq!(Season::from_month(1), Some(Season::Spring));
+    assert_eq!(Season::from_month(8), Some(Season::Winter));
+    assert_eq!(Season::from_month(13), None);
+    assert_eq!(TimeOfDay::at(20, Season::Summer), Some(TimeOfDay::Evening));
+    assert_eq!(TimeOfDay::at(20, Season::Winter), Some(TimeOfDay::Night));
+    assert_eq!(TimeOfDay::at(4, Season::Summer), Some(TimeOfDay::Morning));
+    assert_eq!(TimeOfDay::at(24, Season::Summer), None);
+
+    let species = &pokedex().species;
+    let umbreon = species.identifiers().id("umbreon").unwrap();
+    let evolution = species[umbreon].evolves_from.unwrap();
+    assert_eq!(evolution.time_of_day, Some(TimeOfDay::Night));
+    assert!(!evolution.allowed_at(TimeOfDay::Evening));
+    let espeon = species.identifiers().id("espeon").unwrap();
+    let evolution = species[espeon].evolves_from.unwrap();
+    assert!(evolution.allowed_at(TimeOfDay::Morning));
+    assert!(!evolution.allowed_at(TimeOfDay::Night));
+
+    let slots = [
+        (Conditions::default(), "patrat"),
+        (Conditions { time_of_day: Some(TimeOfDay::Night), season: None },
+         "hoothoot"),
+        (Conditions { time_of_day: None, season: Some(Season::Winter) },
+         "vanillite"),
+    ];
+    let found: Vec<_> = Conditions::filter(
+        slots, TimeOfDay::Night, Season::Winter).collect();
+    assert_eq!(found, ["patrat", "hoothoot", "vanillite"]);
+    let found: Vec<_> = Conditions::filter(
+        slots, TimeOfDay::Day, Season::Spring).collect();
+    assert_eq!(found, ["patrat"]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn location_routes() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    use crate::locations::*;
+    assert!(pokedex().locations.0.is_empty());
+    let mut files = HashMap::new();
+    files.insert(String::from("location_area_regions.csv"), b"\
+location_area_id,identifier,region_id
+1,nuvema-town-area,5
+2,unova-route-1-area,5
+3,accumula-town-area,5
+4,unova-route-2-area,5
+5,dreamyard-area,5
+6,pallet-town-area,1
+".to_vec());
+    files.insert(String::from("location_area_adjacency.csv"), b"\
+location_area_id,adjacent_area_id
+1,2
+3,2
+3,4
+4,5
+".to_vec());
+    let source = Overlay { top: files, base: Embedded };
+    let mut table = LocationTable::load(&source).unwrap();
+    assert_eq!(table.in_region(Region::Unova).len(), 5);
+    assert_eq!(table.0[&AreaId(6)].region, Some(Region::Kanto));
+    assert_eq!(table.adjacent(AreaId(3)), [AreaId(2), AreaId(4)]);
+
+    let nuvema = table.identifiers().id("nuvema-town-area").unwrap();
+    let catchable = [AreaId(4), AreaId(5)];
+    let route = table.earliest_where(nuvema, |id| catchable.contains(&id));
+    assert_eq!(route.unwrap(), [1, 2, 3, 4].map(AreaId));
+    assert_eq!(table.earliest_where(nuvema, |id| id == AreaId(6)), None);
+    table.connect(AreaId(1), AreaId(5));
+    let route = table.earliest_where(nuvema, |id| catchable.contains(&id));
+    assert_eq!(route.unwrap(), [1, 5].map(AreaId));
+    table.disconnect(AreaId(5), AreaId(1));
+    assert_eq!(table.adjacent(AreaId(1)), [AreaId(2)]);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn trainer_teams() {
+    use std::collections::HashMap;
+    use veekun::data::{Embedded, Overlay};
+    use crate::trainers::*;
+    use crate::versions::Version;
+    assert!(pokedex().trainers.0.is_empty());
+    let mut files = HashMap::new();
+    files.insert(String::from("trainer_pokemon.csv"), b"\
+id,version_id,trainer_identifier,trainer_class_id,species_id,level,item_id,\
+move1_id,move2_id,move3_id,move4_id
+1,17,elesa,1,587,25,,521,98,,
+2,17,elesa,1,587,25,,521,98,,
+3,17,elesa,1,523,27,155,521,209,,
+4,21,elesa,1,587,28,,521,,,
+5,21,elesa,1,180,28,,521,,,
+6,21,elesa,1,523,30,155,521,209,,
+7,21,iris,3,612,57,,,,,
+".to_vec());
+    let source = Overlay { top: files, base: Embedded };
+    let table = TrainerTable::load(&source).unwrap();
+    let elesa = table.get("elesa", Version::Black2).unwrap();
+    assert_eq!(elesa.name, "Elesa");
+    assert_eq!(elesa.class, TrainerClass::GymLeader);
+    assert_eq!(elesa.team.len(), 3);
+    let zebstrika = &elesa.team[2];
+    assert_eq!(zebstrika.species, pokemon::SpeciesId(522));
+    assert_eq!(zebstrika.level, 30);
+    assert_eq!(zebstrika.item, Some(items::ItemId(155)));
+    assert_eq!(zebstrika.moves, [moves::MoveId(520), moves::MoveId(208)]);
+    assert_eq!(table.get("elesa", Version::Black).unwrap().team.len(), 3);
+    assert!(table.get("elesa", Version::White).is_none());
+    let champions: Vec<_> = table
+        .of_class(TrainerClass::Champion, Version::Black2)
+        .map(|t| t.name.as_str())
+        .collect();
+    assert_eq!(champions, ["Iris"]);
+    assert_eq!(table.using(pokemon::SpeciesId(179), Version::Black2).count(),
+               1);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn theme_palette() {
+    use crate::assets::{Palette, Rgb};
+    assert_eq!(Type::Normal.color().to_string(), "#a8a878");
+    assert_eq!(items::Category::PPRecovery.icon_key(), "pp-recovery");
+    let leftovers = pokedex().items.identifiers().id("leftovers").unwrap();
+    let category = pokedex().items[leftovers].category;
+    assert_eq!(category.icon_key(), "held-items");
+    let palette = Palette::default()
+        .with_type_color(Type::Fire, Rgb(255, 0, 0))
+        .with_icon_key(items::Category::HeldItems, "held");
+    assert_eq!(palette.type_color(Type::Fire), Rgb(255, 0, 0));
+    assert_eq!(palette.type_color(Type::Water), Type::Water.color());
+    assert_eq!(palette.icon_key(category), "held");
+    assert_eq!(palette.icon_key(items::Category::Mail), "all-mail");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn move_audit() {
+    use moves::{Ailment, Category, Effect, Inconsistency, Meta, MoveBuilder};
+    let warnings = moves::audit();
+    assert!(warnings.len() < 10);
+    let tackle = pokedex().moves.identifiers().id("tackle").unwrap();
+    assert!(warnings.iter().all(|w| w.move_id != tackle));
+
+    let mut table = moves::MoveTable::new();
+    let id = table.insert_custom(MoveBuilder::new("bad-bolt")
+        .typ(Type::Electric).power(90).pp(15)
+        .damage_class(moves::DamageClass::Special)
+        .effect(Effect::ChanceParalyzeTarget, Some(10))
+        .meta(Meta {
+            category: Category::DamageAilment,
+            ailment: Ailment::None,
+            ailment_chance: 10,
+            ..Default::default()
+        })).unwrap();
+    let found: Vec<_> = table.audit().into_iter()
+        .filter(|w| w.move_id == id)
+        .map(|w| w.inconsistency)
+        .collect();
+    assert_eq!(found, [
+        Inconsistency::AilmentCategoryWithoutAilment,
+        Inconsistency::AilmentChanceWithoutAilment,
+        Inconsistency::EffectAilmentMismatch(Ailment::Paralysis),
+    ]);
+    let warning = table.audit().into_iter().find(|w| w.move_id == id);
+    assert_eq!(warning.unwrap().to_string(),
+               "bad-bolt: ailment category without an ailment");
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn hp_scaled_power() {
+    use crate::battle::*;
+    use crate::battle::damage::{calc_damage, MAX_DAMAGE_ROLL};
+    use moves::{Effect, HpScaling};
+    use pokemon::OneOrTwo;
+
+    let flail = Effect::MoreDamageWhenLessUserHP.hp_scaling().unwrap();
+    assert_eq!(flail.power(1, (1, 300), (1, 1)), 200);
+    assert_eq!(flail.power(1, (150, 300), (1, 1)), 40);
+    assert_eq!(flail.power(1, (300, 300), (1, 1)), 20);
+    let eruption = Effect::MoreDamageWhenMoreUserHP.hp_scaling().unwrap();
+    assert_eq!(eruption, HpScaling::UserProportional);
+    assert_eq!(eruption.power(150, (100, 300), (1, 1)), 50);
+    assert_eq!(eruption.power(150, (1, 300), (1, 1)), 1);
+    let brine = Effect::Brine.hp_scaling().unwrap();
+    assert_eq!(brine.power(65, (1, 1), (150, 300)), 130);
+    assert_eq!(brine.power(65, (1, 1), (151, 300)), 65);
+    let wring_out = Effect::MoreDamageWhenMoreTargetHP.hp_scaling().unwrap();
+    assert_eq!(wring_out.power(1, (1, 1), (300, 300)), 120);
+    assert_eq!(Effect::RegularDamage.hp_scaling(), None);
+
+    let normal = || monster(0, OneOrTwo::One(Type::Normal), Ability::Guts);
+    let sides = [Side::new(vec![normal()]), Side::new(vec![normal()])];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let id = pokedex().moves.identifiers().id("flail").unwrap();
+    let flail = &pokedex().moves[id];
+    let full = calc_damage(&state, a, b, flail, false, MAX_DAMAGE_ROLL);
+    state.monster_mut(a).hp = 1;
+    let low = calc_damage(&state, a, b, flail, false, MAX_DAMAGE_ROLL);
+    assert!(full > 0 && low > full * 5);
+
+    let events = resolve_turn(
+        &mut state, &[Action::Move { user: a, move_id: id, target: b }],
+        &mut Highest);
+    assert!(low > 100);
+    assert!(events.contains(&Event::Fainted { target: b }));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn two_turn_moves() {
+    use crate::battle::two_turn::*;
+
+    let mov = |identifier| {
+        let id = pokedex().moves.identifiers().id(identifier).unwrap();
+        &pokedex().moves[id]
+    };
+    let (fly, thunder, tackle) = (mov("fly"), mov("thunder"), mov("tackle"));
+
+    let mut state = State::default();
+    assert_eq!(state.use_move(fly, false), Step::Charge);
+    assert_eq!(state.locked_move(), Some(fly.id));
+    assert_eq!(state.vanished(), Some(Vanished::Air));
+    assert!(state.can_be_hit_by(thunder));
+    assert!(!state.can_be_hit_by(tackle));
+    assert_eq!(state.use_move(tackle, false), Step::Release(fly.id));
+    assert_eq!(state, State::Ready);
+    assert!(state.can_be_hit_by(tackle));
+
+    assert_eq!(state.use_move(mov("solarbeam"), true), Step::Execute);
+    assert_eq!(state.use_move(mov("dig"), false), Step::Charge);
+    state.interrupt();
+    assert_eq!(state, State::Ready);
+
+    let hyper_beam = mov("hyper-beam");
+    assert_eq!(state.use_move(hyper_beam, false), Step::Execute);
+    assert_eq!(state, State::Recharging);
+    assert_eq!(state.use_move(hyper_beam, false), Step::Recharge);
+    assert_eq!(state.use_move(tackle, false), Step::Execute);
+    assert_eq!(Vanished::Underwater.damage_factor(mov("surf")), (2, 1));
+
+    let mut user =
+        monster(0, pokemon::OneOrTwo::One(Type::Normal), Ability::Guts);
+    assert!(!skips_charge(&user, fly, None));
+    assert!(skips_charge(&user, mov("solarbeam"), Some(moves::Weather::Sun)));
+    user.item = pokedex().items.identifiers().id("power-herb");
+    assert!(skips_charge(&user, fly, None));
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn held_item_restrictions() {
+    use crate::battle::*;
+    use crate::battle::held::*;
+    use crate::battle::restrictions::*;
+    use pokemon::OneOrTwo;
+
+    let item = |identifier| pokedex().items.identifiers().id(identifier);
+    let moves = pokedex().moves.identifiers();
+    let (tackle, growl) =
+        (moves.id("tackle").unwrap(), moves.id("growl").unwrap());
+    let holding = |item| {
+        let mut holding =
+            monster(0, OneOrTwo::One(Type::Normal), Ability::Guts);
+        holding.moves = vec![
+            MoveSlot { id: tackle, pp: 10 }, MoveSlot { id: growl, pp: 10 }];
+        holding.item = item;
+        holding
+    };
+
+    let mut user = holding(item("choice-scarf"));
+    assert_eq!(HeldEffect::of(&user), Some(HeldEffect::Choice(Stat::Speed)));
+    record_move(&mut user, growl);
+    assert_eq!(allowed_moves(&user, &[]), MoveMask(0b10));
+    user.item = None;
+    assert_eq!(allowed_moves(&user, &[]), MoveMask(0b11));
+    assert!(!HeldEffect::DamagingOnly.allows(&pokedex().moves[growl]));
+    assert!(HeldEffect::DamagingOnly.allows(&pokedex().moves[tackle]));
+
+    let fire_gem = holding(item("fire-gem"));
+    assert_eq!(HeldEffect::of(&fire_gem), Some(HeldEffect::Gem(Type::Fire)));
+    assert_eq!(gem_boost(&fire_gem, &pokedex().moves[tackle], None), None);
+    let normal_gem = item("normal-gem").unwrap();
+    let sides = [
+        Side::new(vec![holding(Some(normal_gem))]),
+        Side::new(vec![holding(None)]),
+    ];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    assert_eq!(gem_boost(state.monster(a), &pokedex().moves[tackle], None),
+               Some(Type::Normal));
+    let events = resolve_turn(
+        &mut state, &[Action::Move { user: a, move_id: tackle, target: b }],
+        &mut Highest);
+    assert!(events.contains(
+        &Event::ItemConsumed { holder: a, item: normal_gem }));
+    assert_eq!(state.monster(a).item, None);
+}
+
+#[cfg(not(feature = "no-embedded-data"))]
+#[test]
+fn effective_move_types() {
+    use crate::battle::{BattleState, Side};
+    use crate::battle::damage::{calc_damage, MAX_DAMAGE_ROLL};
+    use crate::battle::damage::{effective_move_type, stab_multiplier};
+    use moves::Weather;
+    use pokemon::OneOrTwo;
+
+    let mov = |identifier| {
+        let id = pokedex().moves.identifiers().id(identifier).unwrap();
+        &pokedex().moves[id]
+    };
+    let mut user = monster(0, OneOrTwo::One(Type::Normal), Ability::Guts);
+    let (weather_ball, judgment) = (mov("weather-ball"), mov("judgment"));
+    assert_eq!(effective_move_type(weather_ball, &user, None), Type::Normal);
+    assert_eq!(effective_move_type(weather_ball, &user, Some(Weather::Rain)),
+               Type::Water);
+    user.item = pokedex().items.identifiers().id("flame-plate");
+    assert_eq!(effective_move_type(judgment, &user, None), Type::Fire);
+    user.ability = Ability::Normalize;
+    assert_eq!(effective_move_type(judgment, &user, None), Type::Normal);
+    assert_eq!(effective_move_type(weather_ball, &user, Some(Weather::Sun)),
+               Type::Normal);
+
+    let types = OneOrTwo::Two(Type::Fire, Type::Flying);
+    assert_eq!(stab_multiplier(types, Type::Flying, false), (3, 2));
+    assert_eq!(stab_multiplier(types, Type::Fire, true), (2, 1));
+    assert_eq!(stab_multiplier(types, Type::Water, true), (1, 1));
+
+    let guts = |typ| monster(0, OneOrTwo::One(typ), Ability::Guts);
+    let sides = [Side::new(vec![guts(Type::Water)]),
+                 Side::new(vec![guts(Type::Normal)])];
+    let mut state = BattleState::new(versions::Generation::V, sides);
+    let (a, b) = (state.active(0), state.active(1));
+    let damage = |state: &BattleState, mov| {
+        calc_damage(state, a, b, mov, false, MAX_DAMAGE_ROLL)
+    };
+    let (plain, solarbeam) = (damage(&state, weather_ball), mov("solarbeam"));
+    let plain_solarbeam = damage(&state, solarbeam);
+    state.weather = Some(Weather::Sun);
+    let sunny = damage(&state, weather_ball);
+    // Double power, and the sun boosts Fire moves by half.
+    assert!(sunny >= 3 * plain - 3 && sunny <= 3 * plain);
+    state.weather = Some(Weather::Rain);
+    let rainy_solarbeam = damage(&state, solarbeam);
+    assert!(rainy_solarbeam <= plain_solarbeam / 2 + 1);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn enum_repr_serde() {
+    #[crate::enums::EnumRepr(type = "i8", serde = true, serde_as = "repr")]
+    enum Code {
+        Low = -1,
+        High = 7,
+    }
+
+    assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), r#""Fire""#);
+    assert_eq!(serde_json::from_str::<Ability>(r#""InnerFocus""#).unwrap(),
+               Ability::InnerFocus);
+    assert!(serde_json::from_str::<Type>(r#""Shadow""#).is_err());
+    assert_eq!(serde_json::to_string(&[Code::Low, Code::High]).unwrap(),
+               "[-1,7]");
+    assert_eq!(serde_json::from_str::<Code>("7").unwrap(), Code::High);
+    assert!(serde_json::from_str::<Code>("0").is_err());
+}
@@ -0,0 +1,140 @@
+//! The teams of notable in-game trainers like gym leaders, from the
+//! auxiliary `trainer_pokemon.csv` (see `TrainerTable`).
+
+use crate::enums::*;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokemon::SpeciesId;
+use crate::to_pascal_case;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+use crate::versions::Version;
+use crate::{FromVeekun, VeekunOption};
+use veekun::repr::VeekunString;
+
+/// The standing of a notable trainer.
+#[EnumRepr(type = "u8")]
+pub enum TrainerClass {
+    GymLeader = 1,
+    EliteFour,
+    Champion,
+}
+
+impl FromVeekun for TrainerClass {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// A Pokémon on a trainer's team.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TrainerPokemon {
+    pub species: SpeciesId,
+    pub level: u8,
+    pub item: Option<ItemId>,
+    pub moves: Vec<MoveId>,
+}
+
+/// A notable trainer's team in a version.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Trainer {
+    pub name: String,
+    /// The identifier of the trainer, e.g. `"elesa"`.
+    pub identifier: String,
+    pub class: TrainerClass,
+    pub version: Version,
+    /// The trainer's Pokémon, in the order they are sent out.
+    pub team: Vec<TrainerPokemon>,
+}
+
+/// The teams of notable trainers, from the auxiliary `trainer_pokemon.csv`,
+/// with a Pokémon on each line and the columns `id`, `version_id`,
+/// `trainer_identifier`, `trainer_class_id`, `species_id`, `level`,
+/// `item_id`, and `move1_id` to `move4_id`, by Veekun ID. Empty items and
+/// moves are omitted, and the lines of a trainer in a version make up their
+/// team in order.
+///
+/// The file is not included in the binary; provide it through a data source
+/// (see `veekun::data::Overlay`).
+#[derive(Clone, Debug, Default)]
+pub struct TrainerTable(pub Vec<Trainer>);
+
+impl TrainerTable {
+    /// Create a trainer table from the CSV data in a source. The table is
+    /// empty if the source does not have the file.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        match source.csv(vdata::files::TRAINER_POKEMON) {
+            Some(data) => Self::from_csv_data(data),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Get a trainer's team in a version by identifier, like `"elesa"`.
+    pub fn get(&self, identifier: &str, version: Version) -> Option<&Trainer> {
+        self.0.iter()
+            .find(|t| t.identifier == identifier && t.version == version)
+    }
+
+    /// The trainers of a class in a version, in the order of the file.
+    pub fn of_class(
+        &self, class: TrainerClass, version: Version
+    ) -> impl Iterator<Item = &Trainer> {
+        self.0.iter()
+            .filter(move |t| t.class == class && t.version == version)
+    }
+
+    /// The trainers in a version who use a species.
+    pub fn using(
+        &self, species: SpeciesId, version: Version
+    ) -> impl Iterator<Item = &Trainer> {
+        self.0.iter().filter(move |t| {
+            t.version == version && t.team.iter().any(|p| p.species == species)
+        })
+    }
+}
+
+impl vcsv::FromCsvIncremental for TrainerTable {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: vcsv::Record
+    ) -> vcsv::Result<()> {
+        let version = vcsv::from_field(&record, 1)?;
+        let identifier: VeekunString = vcsv::from_field(&record, 2)?;
+        let class = vcsv::from_field(&record, 3)?;
+        let item: VeekunOption<_> = vcsv::from_field(&record, 6)?;
+        let mut moves = Vec::new();
+        for i in 7..11 {
+            if let VeekunOption(Some(id)) = vcsv::from_field(&record, i)? {
+                moves.push(id);
+            }
+        }
+        let pokemon = TrainerPokemon {
+            species: vcsv::from_field(&record, 4)?,
+            level: vcsv::from_field(&record, 5)?,
+            item: item.into(),
+            moves,
+        };
+        let identifier = identifier.as_str();
+        let index = match self.0.iter().position(|t| {
+            t.identifier == identifier && t.version == version
+        }) {
+            Some(index) => index,
+            None => {
+                self.0.push(Trainer {
+                    name: to_pascal_case(identifier),
+                    identifier: identifier.to_string(),
+                    class,
+                    version,
+                    team: Vec::new(),
+                });
+                self.0.len() - 1
+            },
+        };
+        self.0[index].team.push(pokemon);
+        Ok(())
+    }
+}
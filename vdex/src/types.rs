@@ -1,11 +1,14 @@
+//! Types and their efficacy against each other.
+
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::pokemon::OneOrTwo;
 use crate::vcsv;
 use crate::vcsv::FromCsv;
 use crate::vdata;
 
 /// Level of efficacy of some type combination.
-#[EnumRepr(type = "i8")]
+#[EnumRepr(type = "i8", serde = true)]
 pub enum Efficacy {
     /// Attacks have no effect.
     Not = -2,
@@ -58,7 +61,7 @@ impl FromVeekun for Efficacy {
 /// > which types of Pokémon it is super effective against, which types of
 /// > Pokémon it is not very effective against, and which types of Pokémon it is
 /// > completely ineffective against.
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", bitset = true, serde = true)]
 pub enum Type {
     Normal = 0,
     Fighting,
@@ -97,8 +100,60 @@ pub struct EfficacyTable([[Efficacy; Type::COUNT]; Type::COUNT]);
 
 impl EfficacyTable {
     /// Creates a type efficacy table from the included Veekun CSV data.
+    #[cfg(not(feature = "no-embedded-data"))]
     pub fn new() -> Self {
-        Self::from_csv_data(vdata::EFFICACY).unwrap()
+        Self::load(&vdata::Embedded).unwrap()
+    }
+
+    /// Creates a type efficacy table from the CSV data in a source.
+    pub fn load(source: &dyn vdata::Source) -> vcsv::Result<Self> {
+        Self::from_csv_source(source, vdata::files::EFFICACY)
+    }
+
+    /// Break down the efficacy of an attacking type against one or two
+    /// defending types into the efficacy against each.
+    pub fn explain(
+        &self, attack: Type, defender: OneOrTwo<Type>
+    ) -> EfficacyExplanation {
+        let component = |typ| (typ, self[(attack, typ)]);
+        let components = match defender {
+            OneOrTwo::One(first) => OneOrTwo::One(component(first)),
+            OneOrTwo::Two(first, second) => {
+                OneOrTwo::Two(component(first), component(second))
+            },
+        };
+        EfficacyExplanation { attack, components }
+    }
+}
+
+/// The efficacy of an attacking type against each of one or two defending
+/// types, from `EfficacyTable::explain`.
+#[derive(Copy, Clone, Debug)]
+pub struct EfficacyExplanation {
+    pub attack: Type,
+    /// Each defending type with the efficacy against it.
+    pub components: OneOrTwo<(Type, Efficacy)>,
+}
+
+impl EfficacyExplanation {
+    /// The combined modifier, the product of the components' modifiers.
+    pub fn total(&self) -> f64 {
+        let modifier = |(_, efficacy): (Type, Efficacy)| efficacy.modifier();
+        let second = self.components.second().map_or(1.0, modifier);
+        modifier(self.components.first()) * second
+    }
+}
+
+/// Formats as, e.g., "Fire vs Grass: 2x; Fire vs Steel: 2x; total 4x".
+impl std::fmt::Display for EfficacyExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let components
+            = [Some(self.components.first()), self.components.second()];
+        for (typ, efficacy) in components.iter().flatten() {
+            write!(f, "{:?} vs {:?}: {}x; ",
+                   self.attack, typ, efficacy.modifier())?;
+        }
+        write!(f, "total {}x", self.total())
     }
 }
 
@@ -106,7 +161,7 @@ impl vcsv::FromCsvIncremental for EfficacyTable {
     fn from_empty_csv() -> Self { Default::default() }
 
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: vcsv::Record
     ) -> vcsv::Result<()> {
         let damage = vcsv::from_field(&record, 0)?;
         let target = vcsv::from_field(&record, 1)?;
@@ -6,7 +6,7 @@ use crate::FromVeekun;
 use self::Version as V;
 use self::VersionGroup as VG;
 
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Generation {
     I = 0,
     II,
@@ -27,7 +27,7 @@ impl FromVeekun for Generation {
     }
 }
 
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum Version {
     Red = 0,
     Blue,
@@ -86,7 +86,7 @@ impl Version {
     }
 }
 
-#[EnumRepr(type = "u8")]
+#[EnumRepr(type = "u8", serde = true)]
 pub enum VersionGroup {
     RedBlue = 0,
     Yellow,
@@ -0,0 +1,156 @@
+//! The in-game clock: times of day and the seasons of Generation V.
+//!
+//! Evolutions may require a time of day, as in `EvolvesFrom::time_of_day`,
+//! and encounters may require a time of day or season, which `Conditions`
+//! filters on.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::enums::*;
+use crate::vcsv;
+
+/// A part of the day on the in-game clock.
+///
+/// > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Time)
+/// > Time is a game mechanic introduced in Generation II which allows the
+/// > games' environments to change in accordance with a real-time clock.
+///
+/// Veekun's evolution conditions only distinguish `Day` and `Night`, where
+/// a `Day` condition is met at any time but night.
+#[EnumRepr(type = "u8", serde = true)]
+#[derive(Default)]
+pub enum TimeOfDay {
+    Morning = 0,
+    #[default]
+    Day,
+    Evening,
+    Night,
+}
+
+impl TimeOfDay {
+    /// Get the time of day at an hour (0–23) in a season, as in Black and
+    /// White, or `None` if the hour is invalid.
+    pub fn at(hour: u8, season: Season) -> Option<Self> {
+        if hour >= 24 {
+            return None;
+        }
+        let [morning, day, evening, night] = season.hours();
+        Some(if hour >= night || hour < morning {
+            TimeOfDay::Night
+        } else if hour >= evening {
+            TimeOfDay::Evening
+        } else if hour >= day {
+            TimeOfDay::Day
+        } else {
+            TimeOfDay::Morning
+        })
+    }
+
+    /// True if a condition requiring a time of day is met at this time.
+    ///
+    /// A `Day` condition is met at any time but night; others only at the
+    /// same time.
+    pub fn satisfies(self, required: TimeOfDay) -> bool {
+        match required {
+            TimeOfDay::Day => self != TimeOfDay::Night,
+            _ => self == required,
+        }
+    }
+}
+
+impl Display for TimeOfDay {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TimeOfDay::Morning => "morning",
+            TimeOfDay::Day => "day",
+            TimeOfDay::Evening => "evening",
+            TimeOfDay::Night => "night",
+        })
+    }
+}
+
+/// Parses Veekun's identifiers, like `"night"`.
+impl FromStr for TimeOfDay {
+    type Err = vcsv::MiscError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TimeOfDay::VALUES.iter()
+            .find(|time| time.to_string() == s)
+            .copied()
+            .ok_or(vcsv::MiscError("Invalid time of day"))
+    }
+}
+
+/// A season of Generation V, which changes every month and affects the
+/// length of the day, some areas, and the forms of Deerling and Sawsbuck.
+#[EnumRepr(type = "u8", serde = true)]
+#[derive(Default)]
+pub enum Season {
+    #[default]
+    Spring = 0,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Get the season of a month (1–12), or `None` if the month is invalid.
+    ///
+    /// The seasons cycle from spring in January, so each season falls in
+    /// three months of the year.
+    pub fn from_month(month: u8) -> Option<Self> {
+        if !(1..=12).contains(&month) {
+            return None;
+        }
+        Season::from_repr((month - 1) % 4)
+    }
+
+    /// The hours that morning, day, evening, and night begin at.
+    fn hours(self) -> [u8; 4] {
+        match self {
+            Season::Spring => [5, 10, 17, 20],
+            Season::Summer => [4, 9, 19, 21],
+            Season::Autumn => [6, 10, 18, 20],
+            Season::Winter => [7, 11, 17, 19],
+        }
+    }
+
+    /// The Veekun form identifier of Deerling and Sawsbuck in the season,
+    /// like `"autumn"`.
+    pub fn form_identifier(self) -> &'static str {
+        match self {
+            Season::Spring => "spring",
+            Season::Summer => "summer",
+            Season::Autumn => "autumn",
+            Season::Winter => "winter",
+        }
+    }
+}
+
+/// Conditions on the time of day and season, like those of an encounter.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Conditions {
+    /// The time of day required, if any, as in `TimeOfDay::satisfies`.
+    pub time_of_day: Option<TimeOfDay>,
+    /// The season required, if any.
+    pub season: Option<Season>,
+}
+
+impl Conditions {
+    /// True if the conditions are met at a time of day in a season.
+    pub fn met(&self, time_of_day: TimeOfDay, season: Season) -> bool {
+        self.time_of_day.is_none_or(|required| time_of_day.satisfies(required))
+            && self.season.is_none_or(|required| season == required)
+    }
+
+    /// Keep the entries whose conditions are met at a time of day in a
+    /// season, like the slots of an encounter table.
+    pub fn filter<T>(
+        entries: impl IntoIterator<Item = (Conditions, T)>,
+        time_of_day: TimeOfDay, season: Season,
+    ) -> impl Iterator<Item = T> {
+        entries.into_iter()
+            .filter(move |(conditions, _)| conditions.met(time_of_day, season))
+            .map(|(_, entry)| entry)
+    }
+}
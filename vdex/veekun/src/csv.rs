@@ -4,6 +4,7 @@ use std::error::Error as StdError;
 use std::fmt::{Display, Formatter};
 use std::io::{Cursor, Read};
 use std::path::Path;
+use crate::data::Source;
 use crate::repr::FromVeekunField;
 
 /// Miscellaneous error intended for `Error::Veekun`. Just wraps a string
@@ -38,12 +39,21 @@ pub enum Error {
     },
     /// Representation error.
     Veekun {
+        /// Name of the file, if known (see `Error::in_file`).
+        file: Option<&'static str>,
         line: Option<u64>,
         /// Field number on the line.
         field: usize,
+        /// Text of the record, if available (see `Error::with_record`).
+        record: Option<String>,
         /// Error object (usually of type `veekun::repr::Error`).
         error: Box<dyn StdError>,
     },
+    /// The data source does not provide a required file.
+    Missing {
+        /// Name of the missing file.
+        file: &'static str,
+    },
 }
 
 impl Error {
@@ -58,7 +68,26 @@ impl Error {
             }.and_then(|p| Some(p.line())),
             Error::RecordLength { line, .. } => *line,
             Error::Veekun { line, .. } => *line,
+            Error::Missing { .. } => None,
+        }
+    }
+
+    /// Name the file a representation error is in, unless it is already
+    /// named.
+    pub fn in_file(mut self, name: &'static str) -> Self {
+        if let Error::Veekun { ref mut file, .. } = self {
+            file.get_or_insert(name);
         }
+        self
+    }
+
+    /// Attach the text of the offending record to a representation error,
+    /// unless it already has it.
+    pub fn with_record(mut self, text: &Record) -> Self {
+        if let Error::Veekun { ref mut record, .. } = self {
+            record.get_or_insert_with(|| text.to_string());
+        }
+        self
     }
 }
 
@@ -68,6 +97,12 @@ impl From<csv::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Csv(csv::Error::from(error))
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
@@ -80,11 +115,24 @@ impl Display for Error {
                 write!(f, "Record on line {} too short for field index {}.",
                        line_str, index)
             },
-            Error::Veekun { line, field, error } => {
+            Error::Veekun { file, line, field, record, error } => {
                 let line_str = line
                     .map_or("?".to_string(), |n| format!("{}", n));
-                write!(f, "Error on line {} field {}: {}",
-                       line_str, field, error)
+                match file {
+                    Some(file) => write!(f, "{} line {}", file, line_str)?,
+                    None => write!(f, "Line {}", line_str)?,
+                }
+                write!(f, ", field {}: {}", field, error)?;
+                // The alternate form echoes the record, e.g. `{:#}`.
+                match record {
+                    Some(record) if f.alternate() => {
+                        write!(f, "\n    {}", record)
+                    },
+                    _ => Ok(()),
+                }
+            },
+            Error::Missing { file } => {
+                write!(f, "Missing data file {}.", file)
             },
         }
     }
@@ -103,14 +151,57 @@ impl StdError for Error {
 /// The type returned by Veekun CSV functions.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A record of a CSV file, independent of the CSV parser in use.
+#[derive(Clone, Debug)]
+pub struct Record(csv::StringRecord);
+
+impl Record {
+    /// Get the line number of the record, if it is available.
+    pub fn line(&self) -> Option<u64> {
+        self.0.position().map(csv::Position::line)
+    }
+
+    /// Get a field, or `None` if the record is too short.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index)
+    }
+
+    /// Get the number of fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the fields.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter()
+    }
+}
+
+/// The fields separated by commas, unquoted.
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for (i, field) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", field)?;
+        }
+        Ok(())
+    }
+}
+
 /// Get the line number of a record, if it is available.
-pub fn get_line(record: &csv::StringRecord) -> Option<u64> {
-    record.position().map(csv::Position::line)
+pub fn get_line(record: &Record) -> Option<u64> {
+    record.line()
 }
 
 /// Get the string for a field, or an Error on out-of-bounds.
 pub fn get_field(
-    record: &csv::StringRecord, index: usize
+    record: &Record, index: usize
 ) -> Result<&str> {
     record.get(index).ok_or_else(|| Error::RecordLength {
         line: get_line(record),
@@ -125,8 +216,10 @@ pub fn from_veekun_field<T: FromVeekunField>(
     where <T as FromVeekunField>::VeekunErr: 'static + StdError
 {
     T::from_veekun_field(field, default).or_else(|e| Err(Error::Veekun {
+        file: None,
         line,
         field: index,
+        record: None,
         error: Box::new(e),
     }))
 }
@@ -135,22 +228,24 @@ pub fn from_veekun_field<T: FromVeekunField>(
 ///
 /// See `veekun::FromVeekunField::from_veekun_field` for details.
 pub fn from_option_field<T: FromVeekunField>(
-    record: &csv::StringRecord, index: usize, default: T
+    record: &Record, index: usize, default: T
 ) -> Result<T>
     where <T as FromVeekunField>::VeekunErr: 'static + StdError
 {
     let field = get_field(record, index)?;
     from_veekun_field(get_line(record), index, field, Some(default))
+        .map_err(|e| e.with_record(record))
 }
 
 /// Read a value from a CSV field. Useful for implementing `FromCsv`.
 pub fn from_field<T: FromVeekunField>(
-    record: &csv::StringRecord, index: usize
+    record: &Record, index: usize
 ) -> Result<T>
     where <T as FromVeekunField>::VeekunErr: 'static + StdError
 {
     let field = get_field(record, index)?;
     from_veekun_field(get_line(record), index, field, None)
+        .map_err(|e| e.with_record(record))
 }
 
 /// Abstracts creating an object by loading a CSV file.
@@ -161,6 +256,17 @@ pub trait FromCsv: Sized {
         Self::from_csv(&mut reader)
     }
 
+    /// Gets the named file from the source and passes it to `from_csv_data`.
+    fn from_csv_source(
+        source: &dyn Source, file: &'static str
+    ) -> Result<Self> {
+        match source.csv(file) {
+            Some(data) => Self::from_csv_data(data)
+                .map_err(|e| e.in_file(file)),
+            None => Err(Error::Missing { file }),
+        }
+    }
+
     /// Creates a `Reader` from the path and passes it to `from_csv`.
     fn from_csv_file(path: &Path) -> Result<Self> {
         let mut reader = csv::Reader::from_path(path)?;
@@ -179,7 +285,7 @@ pub trait FromCsvIncremental: Sized {
 
     /// Update the object from a record.
     fn load_csv_record(
-        &mut self, record: csv::StringRecord
+        &mut self, record: Record
     ) -> Result<()>;
 }
 
@@ -187,8 +293,7 @@ impl<T: FromCsvIncremental> FromCsv for T {
     fn from_csv<R: Read>(reader: &mut csv::Reader<R>) -> Result<T> {
         let mut state = T::from_empty_csv();
         for result in reader.records() {
-            let record = result?;
-            state.load_csv_record(record)?;
+            state.load_csv_record(Record(result?))?;
         }
         Ok(state)
     }
@@ -1,23 +1,214 @@
-//! The Veekun CSV data, included in the binary.
-
-pub const BERRIES: &'static str = include_str!("../data/berries.csv");
-pub const BERRY_FLAVORS: &'static str = include_str!("../data/berry_flavors.csv");
-pub const ITEM_FLAGS: &'static str = include_str!("../data/item_flag_map.csv");
-pub const ITEMS: &'static str = include_str!("../data/items.csv");
-pub const MOVE_FLAGS: &'static str = include_str!("../data/move_flag_map.csv");
-pub const MOVE_META: &'static str = include_str!("../data/move_meta.csv");
-pub const MOVE_STAT_CHANGES: &'static str
+//! The Veekun CSV data, included in the binary, and sources for loading it.
+
+use std::collections::HashMap;
+#[cfg(feature = "external-data")]
+use std::fs::File;
+#[cfg(feature = "external-data")]
+use std::path::Path;
+
+#[cfg(not(feature = "no-embedded-data"))]
+pub const BERRIES: &str = include_str!("../data/berries.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const BERRY_FLAVORS: &str = include_str!("../data/berry_flavors.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const ITEM_FLAGS: &str = include_str!("../data/item_flag_map.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const ITEMS: &str = include_str!("../data/items.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const MOVE_FLAGS: &str = include_str!("../data/move_flag_map.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const MOVE_META: &str = include_str!("../data/move_meta.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const MOVE_STAT_CHANGES: &str
     = include_str!("../data/move_meta_stat_changes.csv");
-pub const MOVES: &'static str = include_str!("../data/moves.csv");
-pub const PALACE: &'static str
+#[cfg(not(feature = "no-embedded-data"))]
+pub const MOVE_PRESENTATION: &str
+    = include_str!("../data/move_presentation.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const MOVES: &str = include_str!("../data/moves.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const PALACE: &str
     = include_str!("../data/nature_battle_style_preferences.csv");
-pub const POKEMON: &'static str = include_str!("../data/pokemon.csv");
-pub const ABILITIES: &'static str = include_str!("../data/pokemon_abilities.csv");
-pub const EGG_GROUPS: &'static str = include_str!("../data/pokemon_egg_groups.csv");
-pub const EVOLUTION: &'static str = include_str!("../data/pokemon_evolution.csv");
-pub const FORMS: &'static str = include_str!("../data/pokemon_forms.csv");
-pub const POKEMON_MOVES: &'static str = include_str!("../data/pokemon_moves.csv");
-pub const SPECIES: &'static str = include_str!("../data/pokemon_species.csv");
-pub const STATS: &'static str = include_str!("../data/pokemon_stats.csv");
-pub const TYPES: &'static str = include_str!("../data/pokemon_types.csv");
-pub const EFFICACY: &'static str = include_str!("../data/type_efficacy.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const POKEMON: &str = include_str!("../data/pokemon.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const ABILITIES: &str = include_str!("../data/pokemon_abilities.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const EGG_GROUPS: &str = include_str!("../data/pokemon_egg_groups.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const EVOLUTION: &str = include_str!("../data/pokemon_evolution.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const FORMS: &str = include_str!("../data/pokemon_forms.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const POKEMON_MOVES: &str = include_str!("../data/pokemon_moves.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const SPECIES: &str = include_str!("../data/pokemon_species.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const STATS: &str = include_str!("../data/pokemon_stats.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const TYPES: &str = include_str!("../data/pokemon_types.csv");
+#[cfg(not(feature = "no-embedded-data"))]
+pub const EFFICACY: &str = include_str!("../data/type_efficacy.csv");
+
+/// The commit of the Veekun pokedex repository the included data was dumped
+/// from, followed by a newline.
+#[cfg(not(feature = "no-embedded-data"))]
+pub const COMMIT: &str = include_str!("../data/COMMIT");
+/// The license of the Veekun pokedex, which covers the included data.
+#[cfg(not(feature = "no-embedded-data"))]
+pub const LICENSE: &str = include_str!("../data/LICENSE");
+
+/// File names of the Veekun CSV files, as requested from a `Source`.
+pub mod files {
+    pub const BERRIES: &str = "berries.csv";
+    pub const BERRY_FLAVORS: &str = "berry_flavors.csv";
+    pub const ITEM_FLAGS: &str = "item_flag_map.csv";
+    pub const ITEMS: &str = "items.csv";
+    pub const MOVE_FLAGS: &str = "move_flag_map.csv";
+    pub const MOVE_META: &str = "move_meta.csv";
+    pub const MOVE_STAT_CHANGES: &str = "move_meta_stat_changes.csv";
+    pub const MOVE_PRESENTATION: &str = "move_presentation.csv";
+    pub const MOVES: &str = "moves.csv";
+    pub const PALACE: &str = "nature_battle_style_preferences.csv";
+    pub const POKEMON: &str = "pokemon.csv";
+    pub const ABILITIES: &str = "pokemon_abilities.csv";
+    pub const EGG_GROUPS: &str = "pokemon_egg_groups.csv";
+    pub const EVOLUTION: &str = "pokemon_evolution.csv";
+    pub const FORMS: &str = "pokemon_forms.csv";
+    pub const POKEMON_MOVES: &str = "pokemon_moves.csv";
+    pub const SPECIES: &str = "pokemon_species.csv";
+    pub const STATS: &str = "pokemon_stats.csv";
+    pub const TYPES: &str = "pokemon_types.csv";
+    pub const EFFICACY: &str = "type_efficacy.csv";
+    /// Every file included in the binary.
+    pub const ALL: [&str; 20] = [
+        BERRIES, BERRY_FLAVORS, ITEM_FLAGS, ITEMS, MOVE_FLAGS, MOVE_META,
+        MOVE_STAT_CHANGES, MOVE_PRESENTATION, MOVES, PALACE, POKEMON,
+        ABILITIES, EGG_GROUPS, EVOLUTION, FORMS, POKEMON_MOVES, SPECIES,
+        STATS, TYPES, EFFICACY,
+    ];
+    /// Not included in the binary.
+    pub const ABILITY_PROSE: &str = "ability_prose.csv";
+    /// Not included in the binary.
+    pub const MOVE_EFFECT_PROSE: &str = "move_effect_prose.csv";
+    /// Not included in the binary.
+    pub const SPECIES_ASSETS: &str = "species_assets.csv";
+    /// Not included in the binary.
+    pub const FACILITY_SETS: &str = "facility_sets.csv";
+    /// Not included in the binary.
+    pub const ITEM_GAME_INDICES: &str = "item_game_indices.csv";
+    /// Not included in the binary.
+    pub const ITEM_MARTS: &str = "item_marts.csv";
+    /// Not included in the binary.
+    pub const LOCATION_AREA_REGIONS: &str
+        = "location_area_regions.csv";
+    /// Not included in the binary.
+    pub const LOCATION_AREA_ADJACENCY: &str
+        = "location_area_adjacency.csv";
+    /// Not included in the binary.
+    pub const TRAINER_POKEMON: &str = "trainer_pokemon.csv";
+}
+
+/// Provides the contents of Veekun CSV files by file name.
+pub trait Source {
+    /// Get the contents of the named CSV file (see `files`), or `None` if the
+    /// source does not have it.
+    fn csv(&self, name: &str) -> Option<&[u8]>;
+}
+
+/// In-memory CSV files keyed by file name.
+impl Source for HashMap<String, Vec<u8>> {
+    fn csv(&self, name: &str) -> Option<&[u8]> {
+        self.get(name).map(|v| &v[..])
+    }
+}
+
+/// A source that takes each file from `top` if it has it, and from `base`
+/// otherwise, so individual tables can be overridden.
+pub struct Overlay<T: Source, B: Source> {
+    pub top: T,
+    pub base: B,
+}
+
+impl<T: Source, B: Source> Source for Overlay<T, B> {
+    fn csv(&self, name: &str) -> Option<&[u8]> {
+        self.top.csv(name).or_else(|| self.base.csv(name))
+    }
+}
+
+/// The CSV data included in the binary.
+#[cfg(not(feature = "no-embedded-data"))]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Embedded;
+
+#[cfg(not(feature = "no-embedded-data"))]
+impl Source for Embedded {
+    fn csv(&self, name: &str) -> Option<&[u8]> {
+        let data = match name {
+            files::BERRIES => BERRIES,
+            files::BERRY_FLAVORS => BERRY_FLAVORS,
+            files::ITEM_FLAGS => ITEM_FLAGS,
+            files::ITEMS => ITEMS,
+            files::MOVE_FLAGS => MOVE_FLAGS,
+            files::MOVE_META => MOVE_META,
+            files::MOVE_STAT_CHANGES => MOVE_STAT_CHANGES,
+            files::MOVE_PRESENTATION => MOVE_PRESENTATION,
+            files::MOVES => MOVES,
+            files::PALACE => PALACE,
+            files::POKEMON => POKEMON,
+            files::ABILITIES => ABILITIES,
+            files::EGG_GROUPS => EGG_GROUPS,
+            files::EVOLUTION => EVOLUTION,
+            files::FORMS => FORMS,
+            files::POKEMON_MOVES => POKEMON_MOVES,
+            files::SPECIES => SPECIES,
+            files::STATS => STATS,
+            files::TYPES => TYPES,
+            files::EFFICACY => EFFICACY,
+            _ => return None,
+        };
+        Some(data.as_bytes())
+    }
+}
+
+/// A directory of CSV files, memory-mapped when opened.
+///
+/// Every `.csv` file directly inside the directory is mapped, so auxiliary
+/// tables can be provided alongside the standard Veekun files.
+#[cfg(feature = "external-data")]
+pub struct Directory {
+    maps: HashMap<String, memmap2::Mmap>,
+}
+
+#[cfg(feature = "external-data")]
+impl Directory {
+    /// Memory-map the CSV files in a directory.
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let mut maps = HashMap::new();
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let file_path = entry.path();
+            if !entry.file_type()?.is_file()
+                    || file_path.extension().map_or(true, |e| e != "csv") {
+                continue;
+            }
+            let name = match file_path.file_name().and_then(|n| n.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+            let file = File::open(&file_path)?;
+            // Safety: the mapping is read-only; as with any mapped file, the
+            // caller must not truncate the files while the `Directory` lives.
+            let map = unsafe { memmap2::Mmap::map(&file)? };
+            maps.insert(name, map);
+        }
+        Ok(Directory { maps })
+    }
+}
+
+#[cfg(feature = "external-data")]
+impl Source for Directory {
+    fn csv(&self, name: &str) -> Option<&[u8]> {
+        self.maps.get(name).map(|m| &m[..])
+    }
+}
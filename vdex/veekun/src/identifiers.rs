@@ -0,0 +1,134 @@
+//! Conversions between Veekun identifiers, PascalCase names, and display
+//! names.
+//!
+//! Veekun identifiers are `kebab-case`, e.g. `"master-ball"`; vdex names are
+//! their `PascalCase` forms, e.g. `"MasterBall"`.
+
+/// Words that are followed by digits without a hyphen in Veekun identifiers,
+/// e.g. `"tm01"` and `"porygon2"`.
+const FUSED_WITH_DIGITS: &[&str] = &["tm", "hm", "porygon"];
+
+/// Display names that do not follow from splitting `PascalCase` into words.
+const DISPLAY_EXCEPTIONS: &[(&str, &str)] = &[
+    ("DoubleEdge", "Double-Edge"),
+    ("Farfetchd", "Farfetch'd"),
+    ("HoOh", "Ho-Oh"),
+    ("KingsRock", "King's Rock"),
+    ("MimeJr", "Mime Jr."),
+    ("MrMime", "Mr. Mime"),
+    ("NidoranF", "Nidoran♀"),
+    ("NidoranM", "Nidoran♂"),
+    ("Porygon2", "Porygon2"),
+    ("PorygonZ", "Porygon-Z"),
+    ("UTurn", "U-turn"),
+    ("VCreate", "V-create"),
+];
+
+/// Convert a Veekun-standard `kebab-case` identifier to `PascalCase`.
+pub fn to_pascal_case(s: &str) -> String {
+    let mut builder = String::new();
+    for word in s.split('-') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            builder.extend(first.to_uppercase());
+            builder.extend(chars);
+        }
+    }
+    builder
+}
+
+/// Convert a `PascalCase` name back to its Veekun `kebab-case` identifier; the
+/// inverse of `to_pascal_case` for Veekun identifiers.
+///
+/// Each uppercase letter starts a word, and so does each run of digits unless
+/// it follows a word like "Tm" or "Porygon".
+pub fn to_kebab_case(s: &str) -> String {
+    let mut builder = String::with_capacity(s.len() + 4);
+    let mut word_start = 0;
+    let mut previous: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        let starts_word = match previous {
+            None => false,
+            Some(_) if c.is_uppercase() => true,
+            Some(p) if c.is_ascii_digit() && !p.is_ascii_digit() => {
+                let word = s[word_start..i].to_lowercase();
+                !FUSED_WITH_DIGITS.contains(&word.as_str())
+            },
+            _ => false,
+        };
+        if starts_word {
+            builder.push('-');
+        }
+        if starts_word || c.is_uppercase() {
+            word_start = i;
+        }
+        builder.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+    builder
+}
+
+/// Convert a `PascalCase` name to an English display name, e.g.
+/// `"MasterBall"` to `"Master Ball"` and `"MrMime"` to `"Mr. Mime"`.
+///
+/// Words are separated by spaces, except for a small table of exceptions.
+/// TMs and HMs are uppercased, e.g. `"TM01"`.
+pub fn to_display_name(s: &str) -> String {
+    if let Some(&(_, display)) = DISPLAY_EXCEPTIONS.iter()
+            .find(|&&(name, _)| name == s) {
+        return display.to_string();
+    }
+    let kebab = to_kebab_case(s);
+    let mut builder = String::with_capacity(s.len() + 4);
+    for word in kebab.split('-') {
+        if !builder.is_empty() {
+            builder.push(' ');
+        }
+        if (word.starts_with("tm") || word.starts_with("hm"))
+                && word[2..].chars().all(|c| c.is_ascii_digit()) {
+            builder.push_str(&word.to_uppercase());
+        } else {
+            builder.push_str(&to_pascal_case(word));
+        }
+    }
+    builder
+}
+
+/// Convert a name in any common form, such as a display name, a `PascalCase`
+/// name, or a loosely typed search query, to a Veekun-style identifier.
+///
+/// For example, `"Mr. Mime"`, `"MrMime"`, and `" mr mime "` all become
+/// `"mr-mime"`, and `"Nidoran♀"` becomes `"nidoran-f"`.
+pub fn normalize_identifier(s: &str) -> String {
+    let s = s.trim();
+    let has_separators = s.chars()
+        .any(|c| c.is_whitespace() || c == '-' || c == '_');
+    let is_pascal_case = s.chars().next().is_some_and(char::is_uppercase);
+    let source = if !has_separators && is_pascal_case {
+        to_kebab_case(s)
+    } else {
+        s.to_lowercase()
+    };
+    let mut builder = String::with_capacity(source.len());
+    let separate = |builder: &mut String| {
+        if !builder.is_empty() && !builder.ends_with('-') {
+            builder.push('-');
+        }
+    };
+    for c in source.chars() {
+        match c {
+            '♀' | '♂' => {
+                separate(&mut builder);
+                builder.push(if c == '♀' { 'f' } else { 'm' });
+            },
+            c if c.is_whitespace() || c == '-' || c == '_'
+                => separate(&mut builder),
+            c if c.is_alphanumeric() => builder.push(c),
+            _ => (),
+        }
+    }
+    while builder.ends_with('-') {
+        builder.pop();
+    }
+    builder
+}